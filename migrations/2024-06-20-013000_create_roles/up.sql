@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE "roles"(
+	"id" SERIAL PRIMARY KEY,
+	"name" VARCHAR NOT NULL UNIQUE,
+	"description" VARCHAR,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE "permissions"(
+	"id" SERIAL PRIMARY KEY,
+	"name" VARCHAR NOT NULL UNIQUE,
+	"description" VARCHAR
+);
+
+CREATE TABLE "role_permissions"(
+	"role_id" INT4 NOT NULL REFERENCES "roles"("id") ON DELETE CASCADE,
+	"permission_id" INT4 NOT NULL REFERENCES "permissions"("id") ON DELETE CASCADE,
+	PRIMARY KEY ("role_id", "permission_id")
+);
+
+CREATE TABLE "user_roles"(
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	"role_id" INT4 NOT NULL REFERENCES "roles"("id") ON DELETE CASCADE,
+	PRIMARY KEY ("user_id", "role_id")
+);
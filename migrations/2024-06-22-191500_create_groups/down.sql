@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "group_roles";
+DROP TABLE IF EXISTS "group_subgroups";
+DROP TABLE IF EXISTS "group_members";
+DROP TABLE IF EXISTS "groups";
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+CREATE TABLE "groups"(
+	"id" SERIAL PRIMARY KEY,
+	"name" VARCHAR NOT NULL UNIQUE,
+	"description" VARCHAR,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE "group_members"(
+	"group_id" INT4 NOT NULL REFERENCES "groups"("id") ON DELETE CASCADE,
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	PRIMARY KEY ("group_id", "user_id")
+);
+
+CREATE TABLE "group_subgroups"(
+	"parent_group_id" INT4 NOT NULL REFERENCES "groups"("id") ON DELETE CASCADE,
+	"child_group_id" INT4 NOT NULL REFERENCES "groups"("id") ON DELETE CASCADE,
+	PRIMARY KEY ("parent_group_id", "child_group_id"),
+	CHECK ("parent_group_id" <> "child_group_id")
+);
+
+CREATE TABLE "group_roles"(
+	"group_id" INT4 NOT NULL REFERENCES "groups"("id") ON DELETE CASCADE,
+	"role_id" INT4 NOT NULL REFERENCES "roles"("id") ON DELETE CASCADE,
+	PRIMARY KEY ("group_id", "role_id")
+);
@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "groups" DROP CONSTRAINT IF EXISTS "groups_organization_id_name_key";
+ALTER TABLE "groups" DROP COLUMN IF EXISTS "organization_id";
+ALTER TABLE "groups" ADD CONSTRAINT "groups_name_key" UNIQUE ("name");
+
+ALTER TABLE "roles" DROP CONSTRAINT IF EXISTS "roles_organization_id_name_key";
+ALTER TABLE "roles" DROP COLUMN IF EXISTS "organization_id";
+ALTER TABLE "roles" ADD CONSTRAINT "roles_name_key" UNIQUE ("name");
+
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS "users_organization_id_username_key";
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS "users_organization_id_email_key";
+ALTER TABLE "users" DROP COLUMN IF EXISTS "organization_id";
+ALTER TABLE "users" ADD CONSTRAINT "users_username_key" UNIQUE ("username");
+ALTER TABLE "users" ADD CONSTRAINT "users_email_key" UNIQUE ("email");
+
+DROP TABLE IF EXISTS "organizations";
@@ -0,0 +1,30 @@
+-- Your SQL goes here
+CREATE TABLE "organizations"(
+	"id" SERIAL PRIMARY KEY,
+	"slug" VARCHAR NOT NULL UNIQUE,
+	"name" VARCHAR NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Existing users, roles and groups move to the default organization
+INSERT INTO "organizations"("slug", "name") VALUES ('default', 'Default');
+
+ALTER TABLE "users" ADD COLUMN "organization_id" INT4 REFERENCES "organizations"("id") ON DELETE CASCADE;
+UPDATE "users" SET "organization_id" = (SELECT "id" FROM "organizations" WHERE "slug" = 'default');
+ALTER TABLE "users" ALTER COLUMN "organization_id" SET NOT NULL;
+ALTER TABLE "users" DROP CONSTRAINT "users_username_key";
+ALTER TABLE "users" DROP CONSTRAINT "users_email_key";
+ALTER TABLE "users" ADD CONSTRAINT "users_organization_id_username_key" UNIQUE ("organization_id", "username");
+ALTER TABLE "users" ADD CONSTRAINT "users_organization_id_email_key" UNIQUE ("organization_id", "email");
+
+ALTER TABLE "roles" ADD COLUMN "organization_id" INT4 REFERENCES "organizations"("id") ON DELETE CASCADE;
+UPDATE "roles" SET "organization_id" = (SELECT "id" FROM "organizations" WHERE "slug" = 'default');
+ALTER TABLE "roles" ALTER COLUMN "organization_id" SET NOT NULL;
+ALTER TABLE "roles" DROP CONSTRAINT "roles_name_key";
+ALTER TABLE "roles" ADD CONSTRAINT "roles_organization_id_name_key" UNIQUE ("organization_id", "name");
+
+ALTER TABLE "groups" ADD COLUMN "organization_id" INT4 REFERENCES "organizations"("id") ON DELETE CASCADE;
+UPDATE "groups" SET "organization_id" = (SELECT "id" FROM "organizations" WHERE "slug" = 'default');
+ALTER TABLE "groups" ALTER COLUMN "organization_id" SET NOT NULL;
+ALTER TABLE "groups" DROP CONSTRAINT "groups_name_key";
+ALTER TABLE "groups" ADD CONSTRAINT "groups_organization_id_name_key" UNIQUE ("organization_id", "name");
@@ -0,0 +1,34 @@
+-- This file should undo anything in `up.sql`
+DROP POLICY IF EXISTS "group_roles_tenant_isolation" ON "group_roles";
+ALTER TABLE "group_roles" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "group_roles" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "group_subgroups_tenant_isolation" ON "group_subgroups";
+ALTER TABLE "group_subgroups" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "group_subgroups" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "group_members_tenant_isolation" ON "group_members";
+ALTER TABLE "group_members" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "group_members" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "role_permissions_tenant_isolation" ON "role_permissions";
+ALTER TABLE "role_permissions" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "role_permissions" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "user_roles_tenant_isolation" ON "user_roles";
+ALTER TABLE "user_roles" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "user_roles" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "groups_tenant_isolation" ON "groups";
+ALTER TABLE "groups" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "groups" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "roles_tenant_isolation" ON "roles";
+ALTER TABLE "roles" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "roles" DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "users_tenant_isolation" ON "users";
+ALTER TABLE "users" NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE "users" DISABLE ROW LEVEL SECURITY;
+
+DROP FUNCTION IF EXISTS "current_tenant"();
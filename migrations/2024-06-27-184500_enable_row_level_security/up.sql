@@ -0,0 +1,62 @@
+-- Your SQL goes here
+-- Tenant isolation enforced by Postgres. The application sets "app.current_tenant" in
+-- every transaction that touches these tables; without it no row is visible.
+-- Superusers and roles with BYPASSRLS ignore these policies, so the service must
+-- connect with a regular role (the owner of the tables is fine, thanks to FORCE).
+CREATE FUNCTION "current_tenant"() RETURNS INT4 AS $$
+	SELECT NULLIF(current_setting('app.current_tenant', true), '')::INT4
+$$ LANGUAGE SQL STABLE;
+
+ALTER TABLE "users" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "users" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "users_tenant_isolation" ON "users"
+	USING ("organization_id" = "current_tenant"());
+
+ALTER TABLE "roles" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "roles" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "roles_tenant_isolation" ON "roles"
+	USING ("organization_id" = "current_tenant"());
+
+ALTER TABLE "groups" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "groups" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "groups_tenant_isolation" ON "groups"
+	USING ("organization_id" = "current_tenant"());
+
+-- Link tables have no organization of their own: a row is visible when the rows it
+-- links are, which the subqueries below check under the policies above.
+ALTER TABLE "user_roles" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "user_roles" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "user_roles_tenant_isolation" ON "user_roles"
+	USING (
+		EXISTS (SELECT 1 FROM "users" WHERE "users"."id" = "user_id")
+		AND EXISTS (SELECT 1 FROM "roles" WHERE "roles"."id" = "role_id")
+	);
+
+ALTER TABLE "role_permissions" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "role_permissions" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "role_permissions_tenant_isolation" ON "role_permissions"
+	USING (EXISTS (SELECT 1 FROM "roles" WHERE "roles"."id" = "role_id"));
+
+ALTER TABLE "group_members" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "group_members" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "group_members_tenant_isolation" ON "group_members"
+	USING (
+		EXISTS (SELECT 1 FROM "groups" WHERE "groups"."id" = "group_id")
+		AND EXISTS (SELECT 1 FROM "users" WHERE "users"."id" = "user_id")
+	);
+
+ALTER TABLE "group_subgroups" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "group_subgroups" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "group_subgroups_tenant_isolation" ON "group_subgroups"
+	USING (
+		EXISTS (SELECT 1 FROM "groups" WHERE "groups"."id" = "parent_group_id")
+		AND EXISTS (SELECT 1 FROM "groups" WHERE "groups"."id" = "child_group_id")
+	);
+
+ALTER TABLE "group_roles" ENABLE ROW LEVEL SECURITY;
+ALTER TABLE "group_roles" FORCE ROW LEVEL SECURITY;
+CREATE POLICY "group_roles_tenant_isolation" ON "group_roles"
+	USING (
+		EXISTS (SELECT 1 FROM "groups" WHERE "groups"."id" = "group_id")
+		AND EXISTS (SELECT 1 FROM "roles" WHERE "roles"."id" = "role_id")
+	);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "authorization_codes";
+DROP TABLE IF EXISTS "oauth_clients";
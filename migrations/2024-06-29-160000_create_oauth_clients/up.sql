@@ -0,0 +1,27 @@
+-- Your SQL goes here
+-- OAuth clients are looked up by "client_id" before any tenant is known, so these
+-- tables are not under row-level security; the client's organization is the tenant.
+CREATE TABLE "oauth_clients"(
+	"id" SERIAL PRIMARY KEY,
+	"client_id" VARCHAR NOT NULL UNIQUE,
+	"client_secret_hash" VARCHAR,
+	"name" VARCHAR NOT NULL,
+	"client_type" VARCHAR NOT NULL CHECK ("client_type" IN ('public', 'confidential')),
+	"redirect_uris" TEXT[] NOT NULL,
+	"allowed_scopes" TEXT[] NOT NULL,
+	"organization_id" INT4 NOT NULL REFERENCES "organizations"("id") ON DELETE CASCADE,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE "authorization_codes"(
+	"code_hash" VARCHAR PRIMARY KEY,
+	"oauth_client_id" INT4 NOT NULL REFERENCES "oauth_clients"("id") ON DELETE CASCADE,
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	"redirect_uri" VARCHAR NOT NULL,
+	"scope" VARCHAR NOT NULL,
+	"code_challenge" VARCHAR,
+	"code_challenge_method" VARCHAR,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"used_at" TIMESTAMPTZ,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
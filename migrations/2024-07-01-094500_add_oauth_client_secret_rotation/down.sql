@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "oauth_clients" DROP COLUMN IF EXISTS "previous_client_secret_hash";
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- The secret replaced by the last rotation stays valid until it is revoked, so
+-- services can roll out the new one without downtime.
+ALTER TABLE "oauth_clients" ADD COLUMN "previous_client_secret_hash" VARCHAR;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "authorization_codes" DROP COLUMN IF EXISTS "auth_time";
+ALTER TABLE "authorization_codes" DROP COLUMN IF EXISTS "nonce";
+ALTER TABLE "users" DROP COLUMN IF EXISTS "email_verified";
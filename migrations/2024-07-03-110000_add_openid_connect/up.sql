@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE "users" ADD COLUMN "email_verified" BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- The ID token issued for a code must carry the nonce sent to /oauth/authorize and
+-- the moment the user actually authenticated.
+ALTER TABLE "authorization_codes" ADD COLUMN "nonce" VARCHAR;
+ALTER TABLE "authorization_codes" ADD COLUMN "auth_time" TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS "authorization_codes_expires_at_idx";
+DROP TABLE IF EXISTS "device_codes";
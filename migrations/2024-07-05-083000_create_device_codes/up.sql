@@ -0,0 +1,18 @@
+-- Your SQL goes here
+-- Device authorization grant (RFC 8628). Like "authorization_codes", rows are looked
+-- up by code before the tenant is known and are not under row-level security.
+CREATE TABLE "device_codes"(
+	"device_code_hash" VARCHAR PRIMARY KEY,
+	"user_code" VARCHAR NOT NULL UNIQUE,
+	"oauth_client_id" INT4 NOT NULL REFERENCES "oauth_clients"("id") ON DELETE CASCADE,
+	"scope" VARCHAR NOT NULL,
+	"status" VARCHAR NOT NULL DEFAULT 'pending' CHECK ("status" IN ('pending', 'approved', 'denied')),
+	"user_id" INT4 REFERENCES "users"("id") ON DELETE CASCADE,
+	"interval" INT4 NOT NULL,
+	"last_polled_at" TIMESTAMPTZ,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "device_codes_expires_at_idx" ON "device_codes"("expires_at");
+CREATE INDEX "authorization_codes_expires_at_idx" ON "authorization_codes"("expires_at");
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+-- Refresh tokens rotate on every use. All tokens descending from the same grant share
+-- a "family_id", so reusing an already rotated token revokes the whole family.
+CREATE TABLE "refresh_tokens"(
+	"token_hash" VARCHAR PRIMARY KEY,
+	"family_id" VARCHAR NOT NULL,
+	"oauth_client_id" INT4 NOT NULL REFERENCES "oauth_clients"("id") ON DELETE CASCADE,
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	"scope" VARCHAR NOT NULL,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"used_at" TIMESTAMPTZ,
+	"revoked_at" TIMESTAMPTZ,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "refresh_tokens_family_id_idx" ON "refresh_tokens"("family_id");
+CREATE INDEX "refresh_tokens_expires_at_idx" ON "refresh_tokens"("expires_at");
+
+-- Access tokens are stateless JWTs; revoked ones are denied by "jti" until they expire.
+CREATE TABLE "revoked_tokens"(
+	"jti" VARCHAR PRIMARY KEY,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "revoked_tokens_expires_at_idx" ON "revoked_tokens"("expires_at");
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "initial_access_tokens";
+ALTER TABLE "oauth_clients" DROP COLUMN IF EXISTS "registration_access_token_hash";
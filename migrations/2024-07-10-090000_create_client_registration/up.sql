@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- Dynamically registered clients manage themselves through the client configuration
+-- endpoint (RFC 7592) with this token; only its hash is stored.
+ALTER TABLE "oauth_clients" ADD COLUMN "registration_access_token_hash" VARCHAR UNIQUE;
+
+CREATE TABLE "initial_access_tokens"(
+	"token_hash" VARCHAR PRIMARY KEY,
+	"organization_id" INT4 NOT NULL REFERENCES "organizations"("id") ON DELETE CASCADE,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX "initial_access_tokens_expires_at_idx" ON "initial_access_tokens"("expires_at");
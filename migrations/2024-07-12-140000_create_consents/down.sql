@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "authorization_codes" DROP COLUMN IF EXISTS "consent_pending";
+DROP TABLE IF EXISTS "consents";
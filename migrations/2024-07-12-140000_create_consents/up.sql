@@ -0,0 +1,15 @@
+-- Your SQL goes here
+-- Scopes a user approved for a client. Authorizations within these scopes skip the
+-- consent screen; withdrawing consent also revokes the client's refresh tokens.
+CREATE TABLE "consents"(
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	"oauth_client_id" INT4 NOT NULL REFERENCES "oauth_clients"("id") ON DELETE CASCADE,
+	"scopes" TEXT[] NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	"updated_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	PRIMARY KEY ("user_id", "oauth_client_id")
+);
+
+-- Codes created after sign-in wait here until the user answers the consent screen and
+-- cannot be exchanged before that.
+ALTER TABLE "authorization_codes" ADD COLUMN "consent_pending" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Audiences a client may request tokens for with the token exchange grant (RFC 8693).
+ALTER TABLE "oauth_clients" ADD COLUMN "allowed_audiences" TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- Failed lookups per subject (e.g. a user code typed from one address), counted until "expires_at".
+CREATE TABLE "failed_attempts"(
+	"subject" VARCHAR NOT NULL PRIMARY KEY,
+	"failures" INT4 NOT NULL,
+	"expires_at" TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX "failed_attempts_expires_at_idx" ON "failed_attempts"("expires_at");
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- Grants each client may use at /oauth/token. Existing clients keep every grant their type
+-- supports; dynamically registered ones keep the registration defaults.
+ALTER TABLE "oauth_clients" ADD COLUMN "grant_types" TEXT[] NOT NULL DEFAULT '{}';
+
+UPDATE "oauth_clients" SET "grant_types" = CASE
+	WHEN "registration_access_token_hash" IS NOT NULL THEN ARRAY['authorization_code', 'refresh_token']
+	WHEN "client_type" = 'confidential' THEN ARRAY[
+		'authorization_code',
+		'refresh_token',
+		'urn:ietf:params:oauth:grant-type:device_code',
+		'client_credentials',
+		'urn:ietf:params:oauth:grant-type:token-exchange'
+	]
+	ELSE ARRAY['authorization_code', 'refresh_token', 'urn:ietf:params:oauth:grant-type:device_code']
+END;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- DPoP proofs (RFC 9449) already seen, kept until they are too old to be accepted again.
+CREATE TABLE "dpop_proofs"(
+	"proof_hash" VARCHAR NOT NULL PRIMARY KEY,
+	"expires_at" TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX "dpop_proofs_expires_at_idx" ON "dpop_proofs"("expires_at");
+
+-- Thumbprint of the key refresh tokens are bound to; rotation keeps the binding.
+ALTER TABLE "refresh_tokens" ADD COLUMN "dpop_jkt" VARCHAR;
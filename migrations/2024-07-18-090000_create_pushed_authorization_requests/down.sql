@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "oauth_clients" DROP COLUMN IF EXISTS "require_pushed_authorization_requests";
+DROP TABLE IF EXISTS "pushed_authorization_requests";
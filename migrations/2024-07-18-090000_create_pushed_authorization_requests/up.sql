@@ -0,0 +1,20 @@
+-- Your SQL goes here
+-- Pushed authorization requests (RFC 9126). Like "authorization_codes", rows are looked up by
+-- request_uri before the tenant is known and are not under row-level security.
+CREATE TABLE "pushed_authorization_requests"(
+	"request_uri_hash" VARCHAR PRIMARY KEY,
+	"oauth_client_id" INT4 NOT NULL REFERENCES "oauth_clients"("id") ON DELETE CASCADE,
+	"response_type" VARCHAR NOT NULL,
+	"redirect_uri" VARCHAR,
+	"scope" VARCHAR,
+	"state" VARCHAR,
+	"code_challenge" VARCHAR,
+	"code_challenge_method" VARCHAR,
+	"nonce" VARCHAR,
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "pushed_authorization_requests_expires_at_idx" ON "pushed_authorization_requests"("expires_at");
+
+ALTER TABLE "oauth_clients" ADD COLUMN "require_pushed_authorization_requests" BOOL NOT NULL DEFAULT FALSE;
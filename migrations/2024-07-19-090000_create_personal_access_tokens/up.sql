@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- Personal access tokens for scripts and CI. Only the SHA-256 of the token is stored; like
+-- refresh tokens, rows are looked up by hash before the tenant is known and are not under
+-- row-level security. "token_hint" keeps the recognizable prefix and the first characters
+-- so users can tell their tokens apart in listings.
+CREATE TABLE "personal_access_tokens"(
+	"id" SERIAL PRIMARY KEY,
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	"organization_id" INT4 NOT NULL REFERENCES "organizations"("id") ON DELETE CASCADE,
+	"name" VARCHAR NOT NULL,
+	"token_hash" VARCHAR NOT NULL UNIQUE,
+	"token_hint" VARCHAR NOT NULL,
+	"scope" VARCHAR NOT NULL,
+	"expires_at" TIMESTAMPTZ,
+	"last_used_at" TIMESTAMPTZ,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "personal_access_tokens_user_id_idx" ON "personal_access_tokens"("user_id");
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM "users" WHERE "principal_type" = 'service';
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS "users_principal_credentials_check";
+ALTER TABLE "users" DROP COLUMN IF EXISTS "owner_user_id";
+ALTER TABLE "users" DROP COLUMN IF EXISTS "principal_type";
+ALTER TABLE "users" ALTER COLUMN "email" SET NOT NULL;
+ALTER TABLE "users" ALTER COLUMN "password" SET NOT NULL;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- Service accounts are non-human principals stored alongside users, so roles, groups and
+-- personal access tokens work for them unchanged. They have no password (and so cannot log
+-- in) and usually no email; "owner_user_id" is the user responsible for the account, or NULL
+-- when it belongs to the organization.
+ALTER TABLE "users" ALTER COLUMN "password" DROP NOT NULL;
+ALTER TABLE "users" ALTER COLUMN "email" DROP NOT NULL;
+ALTER TABLE "users" ADD COLUMN "principal_type" VARCHAR NOT NULL DEFAULT 'user'
+	CHECK ("principal_type" IN ('user', 'service'));
+ALTER TABLE "users" ADD COLUMN "owner_user_id" INT4 REFERENCES "users"("id") ON DELETE CASCADE;
+ALTER TABLE "users" ADD CONSTRAINT "users_principal_credentials_check" CHECK (
+	CASE "principal_type"
+		WHEN 'user' THEN "password" IS NOT NULL AND "email" IS NOT NULL AND "owner_user_id" IS NULL
+		ELSE "password" IS NULL
+	END
+);
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- One row per login. The session id travels in the "sid" claim of the access token, so
+-- revoking the row invalidates the token before it expires. Rows are looked up by id on
+-- every request, before the tenant is known, and are not under row-level security.
+CREATE TABLE "sessions"(
+	"id" VARCHAR PRIMARY KEY,
+	"user_id" INT4 NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+	"organization_id" INT4 NOT NULL REFERENCES "organizations"("id") ON DELETE CASCADE,
+	"user_agent" VARCHAR,
+	"ip_address" VARCHAR,
+	"device_name" VARCHAR,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	"last_seen_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	"expires_at" TIMESTAMPTZ NOT NULL,
+	"revoked_at" TIMESTAMPTZ
+);
+
+CREATE INDEX "sessions_user_id_idx" ON "sessions"("user_id");
+CREATE INDEX "sessions_expires_at_idx" ON "sessions"("expires_at");
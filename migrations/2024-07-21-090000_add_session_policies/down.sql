@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "sessions" DROP COLUMN IF EXISTS "idle_timeout_seconds";
+ALTER TABLE "roles" DROP COLUMN IF EXISTS "max_concurrent_sessions";
+ALTER TABLE "roles" DROP COLUMN IF EXISTS "session_max_lifetime_seconds";
+ALTER TABLE "roles" DROP COLUMN IF EXISTS "session_idle_timeout_seconds";
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- Per-role overrides of the session policy. NULL keeps the value configured for the service;
+-- when a user has several roles, the strictest value wins.
+ALTER TABLE "roles" ADD COLUMN "session_idle_timeout_seconds" INT4
+	CHECK ("session_idle_timeout_seconds" > 0);
+ALTER TABLE "roles" ADD COLUMN "session_max_lifetime_seconds" INT4
+	CHECK ("session_max_lifetime_seconds" > 0);
+ALTER TABLE "roles" ADD COLUMN "max_concurrent_sessions" INT4
+	CHECK ("max_concurrent_sessions" > 0);
+
+-- The idle timeout is fixed when the session is created, so changing a role's policy only
+-- affects new logins. A session ends once it goes unused for this long.
+ALTER TABLE "sessions" ADD COLUMN "idle_timeout_seconds" INT4 NOT NULL DEFAULT 3600;
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- Append-only record of authentication and administration events. Failed logins happen
+-- before the tenant is known, so rows are not under row-level security; queries filter by
+-- "organization_id" instead. There are no foreign keys so events outlive the users,
+-- clients and organizations they mention.
+CREATE TABLE "audit_events"(
+	"id" BIGSERIAL PRIMARY KEY,
+	"organization_id" INT4,
+	"action" VARCHAR NOT NULL,
+	"outcome" VARCHAR NOT NULL CHECK ("outcome" IN ('success', 'failure')),
+	"actor_type" VARCHAR,
+	"actor_id" VARCHAR,
+	"target_type" VARCHAR,
+	"target_id" VARCHAR,
+	"ip_address" VARCHAR,
+	"user_agent" VARCHAR,
+	"details" JSONB NOT NULL DEFAULT '{}',
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "audit_events_organization_id_created_at_idx" ON "audit_events"("organization_id", "created_at");
+CREATE INDEX "audit_events_actor_id_idx" ON "audit_events"("actor_id");
+CREATE INDEX "audit_events_target_id_idx" ON "audit_events"("target_id");
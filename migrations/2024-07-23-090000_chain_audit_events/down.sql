@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS "audit_events_append_only" ON "audit_events";
+DROP TABLE IF EXISTS "audit_checkpoints";
+DROP FUNCTION IF EXISTS "reject_audit_changes"();
+ALTER TABLE "audit_events" DROP COLUMN IF EXISTS "hash";
+ALTER TABLE "audit_events" DROP COLUMN IF EXISTS "prev_hash";
@@ -0,0 +1,30 @@
+-- Your SQL goes here
+-- Each entry carries the SHA-256 of the previous entry of the same UTC day ("prev_hash") and
+-- its own ("hash"), computed by the service over "prev_hash" and the entry contents. Entries
+-- recorded before this migration keep empty hashes and are reported as unchained.
+ALTER TABLE "audit_events" ADD COLUMN "prev_hash" VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE "audit_events" ADD COLUMN "hash" VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE "audit_events" ALTER COLUMN "prev_hash" DROP DEFAULT;
+ALTER TABLE "audit_events" ALTER COLUMN "hash" DROP DEFAULT;
+
+-- Signed summary of a finished day: how many entries it had and the hash of the last one, so
+-- entries removed from the end of a day are detected too.
+CREATE TABLE "audit_checkpoints"(
+	"day" DATE PRIMARY KEY,
+	"event_count" INT8 NOT NULL,
+	"last_event_id" INT8 NOT NULL,
+	"last_hash" VARCHAR NOT NULL,
+	"signature" TEXT NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE FUNCTION "reject_audit_changes"() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit records are append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER "audit_events_append_only" BEFORE UPDATE OR DELETE ON "audit_events"
+    FOR EACH ROW EXECUTE FUNCTION "reject_audit_changes"();
+CREATE TRIGGER "audit_checkpoints_append_only" BEFORE UPDATE OR DELETE ON "audit_checkpoints"
+    FOR EACH ROW EXECUTE FUNCTION "reject_audit_changes"();
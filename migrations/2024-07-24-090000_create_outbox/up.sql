@@ -0,0 +1,48 @@
+-- Your SQL goes here
+-- User lifecycle events, written in the same transaction as the change to "users" so an
+-- event exists if and only if the change was committed. The dispatcher reads every tenant,
+-- so these tables are not under row-level security; queries filter by "organization_id".
+-- There is no foreign key to "users" so "user.deleted" events outlive the user.
+CREATE TABLE "outbox"(
+	"id" BIGSERIAL PRIMARY KEY,
+	"organization_id" INT4 NOT NULL REFERENCES "organizations"("id") ON DELETE CASCADE,
+	"event_type" VARCHAR NOT NULL,
+	"aggregate_type" VARCHAR NOT NULL,
+	"aggregate_id" VARCHAR NOT NULL,
+	"payload" JSONB NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	-- set once a delivery was queued for every webhook subscribed to the event
+	"dispatched_at" TIMESTAMPTZ
+);
+
+CREATE INDEX "outbox_pending_idx" ON "outbox"("id") WHERE "dispatched_at" IS NULL;
+
+-- The secret is kept in clear because it signs every delivery (HMAC-SHA256).
+CREATE TABLE "webhooks"(
+	"id" SERIAL PRIMARY KEY,
+	"organization_id" INT4 NOT NULL REFERENCES "organizations"("id") ON DELETE CASCADE,
+	"url" VARCHAR NOT NULL,
+	"secret" VARCHAR NOT NULL,
+	"event_types" TEXT[] NOT NULL,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "webhooks_organization_id_idx" ON "webhooks"("organization_id");
+
+-- One row per event and webhook. Failed deliveries are retried at "next_attempt_at" until
+-- they run out of attempts and are left as "dead".
+CREATE TABLE "webhook_deliveries"(
+	"id" BIGSERIAL PRIMARY KEY,
+	"webhook_id" INT4 NOT NULL REFERENCES "webhooks"("id") ON DELETE CASCADE,
+	"outbox_event_id" INT8 NOT NULL REFERENCES "outbox"("id") ON DELETE CASCADE,
+	"status" VARCHAR NOT NULL DEFAULT 'pending' CHECK ("status" IN ('pending', 'delivered', 'dead')),
+	"attempts" INT4 NOT NULL DEFAULT 0,
+	"next_attempt_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	"last_error" VARCHAR,
+	"delivered_at" TIMESTAMPTZ,
+	"created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+	UNIQUE ("webhook_id", "outbox_event_id")
+);
+
+CREATE INDEX "webhook_deliveries_due_idx" ON "webhook_deliveries"("next_attempt_at") WHERE "status" = 'pending';
+CREATE INDEX "webhook_deliveries_outbox_event_id_idx" ON "webhook_deliveries"("outbox_event_id");
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- A disabled user can't log in and its tokens stop being accepted; enabling clears the column.
+ALTER TABLE "users" ADD COLUMN "disabled_at" TIMESTAMPTZ;
+-- Set by an admin; the next login must go through a password change.
+ALTER TABLE "users" ADD COLUMN "password_reset_required" BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Consecutive failed logins. Reaching the limit locks the account until "locked_until" and
+-- starts the count again; a successful login or an admin unlock resets both.
+ALTER TABLE "users" ADD COLUMN "failed_login_attempts" INT4 NOT NULL DEFAULT 0;
+ALTER TABLE "users" ADD COLUMN "locked_until" TIMESTAMPTZ;
+
+CREATE INDEX "users_organization_id_created_at_idx" ON "users" ("organization_id", "created_at");
@@ -0,0 +1,46 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpMessage, HttpRequest};
+
+use crate::api::controllers::user_handler::session_client;
+use crate::domain::models::audit::{AuditAction, AuditOutcome, CreateAuditEvent};
+use crate::domain::models::token::Claim;
+use crate::domain::models::user::PrincipalType;
+use crate::domain::services::audit::AuditService;
+use crate::services::audit::record_or_warn;
+
+/// Marca a requisição cujo handler já registrou um evento, para que o middleware `AuditTrail`
+/// não registre outro `admin.action` para ela.
+#[derive(Clone, Copy)]
+pub struct Audited;
+
+/// Evento com o IP e o user agent da requisição.
+pub fn request_event(req: &HttpRequest, action: AuditAction, outcome: AuditOutcome) -> CreateAuditEvent {
+    let client = session_client(req, None);
+    CreateAuditEvent::new(action, outcome).client(client.ip_address, client.user_agent)
+}
+
+/// Resultado de uma resposta: erros do cliente e do servidor contam como falha.
+pub fn outcome_of(status: StatusCode) -> AuditOutcome {
+    if status.is_client_error() || status.is_server_error() {
+        AuditOutcome::Failure
+    } else {
+        AuditOutcome::Success
+    }
+}
+
+/// Quem fez a ação: o usuário ou conta de serviço do `sub` ou, nos tokens de `client_credentials`,
+/// cujo `sub` é o `client_id`, o cliente OAuth.
+pub fn claim_actor(event: CreateAuditEvent, claim: &Claim) -> CreateAuditEvent {
+    let actor_type = match (claim.sub.parse::<i32>(), claim.principal_type) {
+        (Ok(_), Some(principal_type)) => principal_type.as_str(),
+        (Ok(_), None) => PrincipalType::User.as_str(),
+        (Err(_), _) => "client",
+    };
+    event.organization(claim.tenant).actor(actor_type, &claim.sub)
+}
+
+/// Registra o evento de um handler e marca a requisição como auditada.
+pub async fn record(audit_service: &dyn AuditService, req: &HttpRequest, event: CreateAuditEvent) {
+    req.extensions_mut().insert(Audited);
+    record_or_warn(audit_service, event).await;
+}
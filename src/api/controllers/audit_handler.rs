@@ -0,0 +1,21 @@
+use actix_web::{web, Result};
+
+use crate::api::dto::audit::{AuditEventDTO, AuditEventQueryDTO};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::repositories::repository::ResultPaging;
+use crate::domain::services::audit::AuditService;
+
+/// Eventos da organização do token, dos mais recentes para os mais antigos.
+pub async fn list_audit_events_handler(
+    audit_service: web::Data<dyn AuditService>,
+    user: AuthenticatedUser,
+    query: web::Query<AuditEventQueryDTO>,
+) -> Result<web::Json<ResultPaging<AuditEventDTO>>, ApiError> {
+    let (filter, params) = query.into_inner().into_parts();
+    let page = audit_service.list(user.tenant()?, &filter, &params).await?;
+    Ok(web::Json(ResultPaging {
+        total: page.total,
+        items: page.items.into_iter().map(AuditEventDTO::from).collect(),
+    }))
+}
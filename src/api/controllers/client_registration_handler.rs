@@ -0,0 +1,97 @@
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+
+use crate::api::dto::client_registration::{ClientInformationDTO, ClientMetadataDTO, InitialAccessTokenDTO};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::{ApiError, OAuthError};
+use crate::domain::models::client_registration::ClientInformation;
+use crate::domain::services::client_registration::ClientRegistrationService;
+
+pub async fn create_initial_access_token_handler(
+    registration_service: web::Data<dyn ClientRegistrationService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<InitialAccessTokenDTO>, ApiError> {
+    let token = registration_service
+        .create_initial_access_token(user.tenant()?)
+        .await?;
+    Ok(web::Json(token.into()))
+}
+
+pub async fn register_handler(
+    registration_service: web::Data<dyn ClientRegistrationService>,
+    req: HttpRequest,
+    post_data: web::Json<ClientMetadataDTO>,
+) -> Result<HttpResponse, OAuthError> {
+    let information = registration_service
+        .register_client(bearer_token(&req)?, post_data.into_inner().into())
+        .await?;
+    Ok(client_information_response(HttpResponse::Created(), &req, information))
+}
+
+pub async fn get_registration_handler(
+    registration_service: web::Data<dyn ClientRegistrationService>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, OAuthError> {
+    let information = registration_service
+        .get_client(&path.into_inner(), bearer_token(&req)?)
+        .await?;
+    Ok(client_information_response(HttpResponse::Ok(), &req, information))
+}
+
+pub async fn update_registration_handler(
+    registration_service: web::Data<dyn ClientRegistrationService>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    post_data: web::Json<ClientMetadataDTO>,
+) -> Result<HttpResponse, OAuthError> {
+    let client_id = path.into_inner();
+    let metadata = post_data.into_inner();
+    if metadata.client_id.as_deref().is_some_and(|id| id != client_id) {
+        return Err(OAuthError::new(
+            "invalid_client_metadata",
+            "client_id does not match the registration",
+        ));
+    }
+    let information = registration_service
+        .update_client(&client_id, bearer_token(&req)?, metadata.into())
+        .await?;
+    Ok(client_information_response(HttpResponse::Ok(), &req, information))
+}
+
+pub async fn delete_registration_handler(
+    registration_service: web::Data<dyn ClientRegistrationService>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, OAuthError> {
+    registration_service
+        .delete_client(&path.into_inner(), bearer_token(&req)?)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn bearer_token(req: &HttpRequest) -> Result<&str, OAuthError> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or_else(|| OAuthError::new("invalid_token", "A bearer token is required"))
+}
+
+fn client_information_response(
+    mut response: actix_web::HttpResponseBuilder,
+    req: &HttpRequest,
+    information: ClientInformation,
+) -> HttpResponse {
+    let connection = req.connection_info();
+    let registration_client_uri = format!(
+        "{}://{}/oauth/register/{}",
+        connection.scheme(),
+        connection.host(),
+        information.client.client_id
+    );
+    response
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(ClientInformationDTO::new(information, registration_client_uri))
+}
@@ -0,0 +1,128 @@
+use actix_web::{web, HttpResponse, Result};
+
+use crate::api::dto::group::{
+    CreateGroupDTO, GroupDTO, PermissionExplanationDTO, PermissionGrantDTO,
+};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::services::authorization::AuthorizationService;
+use crate::domain::services::group::GroupService;
+
+pub async fn create_group_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<CreateGroupDTO>,
+) -> Result<web::Json<GroupDTO>, ApiError> {
+    let group = group_service
+        .create_group(user.tenant()?, post_data.into_inner().into())
+        .await?;
+    Ok(web::Json(group.into()))
+}
+
+pub async fn list_groups_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<GroupDTO>>, ApiError> {
+    let groups = group_service.list_groups(user.tenant()?).await?;
+    Ok(web::Json(groups.into_iter().map(GroupDTO::from).collect()))
+}
+
+pub async fn delete_group_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    group_service
+        .delete_group(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn add_member_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (group_id, user_id) = path.into_inner();
+    group_service
+        .add_member(user.tenant()?, group_id, user_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn remove_member_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (group_id, user_id) = path.into_inner();
+    group_service
+        .remove_member(user.tenant()?, group_id, user_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn add_subgroup_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (group_id, subgroup_id) = path.into_inner();
+    group_service
+        .add_subgroup(user.tenant()?, group_id, subgroup_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn remove_subgroup_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (group_id, subgroup_id) = path.into_inner();
+    group_service
+        .remove_subgroup(user.tenant()?, group_id, subgroup_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn assign_group_role_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (group_id, role_id) = path.into_inner();
+    group_service
+        .assign_role(user.tenant()?, group_id, role_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn unassign_group_role_handler(
+    group_service: web::Data<dyn GroupService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (group_id, role_id) = path.into_inner();
+    group_service
+        .unassign_role(user.tenant()?, group_id, role_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn explain_permission_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, String)>,
+) -> Result<web::Json<PermissionExplanationDTO>, ApiError> {
+    let (user_id, permission) = path.into_inner();
+    let grants = authorization_service
+        .explain_permission(user.tenant()?, user_id, permission.clone())
+        .await?;
+    Ok(web::Json(PermissionExplanationDTO {
+        user_id,
+        permission,
+        granted: !grants.is_empty(),
+        grants: grants.into_iter().map(PermissionGrantDTO::from).collect(),
+    }))
+}
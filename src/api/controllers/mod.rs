@@ -1 +1,14 @@
+pub mod audit_handler;
+pub mod client_registration_handler;
+pub mod group_handler;
+pub mod oauth_handler;
+pub mod oidc_handler;
+pub mod organization_handler;
+pub mod personal_access_token_handler;
+pub mod role_handler;
+pub mod service_account_handler;
+pub mod service_context_handlers;
+pub mod session_handler;
+pub mod user_admin_handler;
 pub mod user_handler;
+pub mod webhook_handler;
@@ -0,0 +1,671 @@
+use actix_web::http::header::{self, ContentType};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use url::Url;
+
+use crate::api::dto::oauth::{
+    AuthorizeFormDTO, ConsentDTO, CreateOAuthClientDTO, DeviceAuthorizationRequestDTO,
+    DeviceAuthorizationResponseDTO, DeviceVerificationFormDTO, DeviceVerificationQueryDTO,
+    OAuthClientDTO, PushedAuthorizationRequestDTO, PushedAuthorizationResponseDTO, RegisteredOAuthClientDTO,
+    RevocationRequestDTO, TokenRequestDTO,
+};
+use crate::api::audit::{claim_actor, record, request_event};
+use crate::api::csrf::{form_csrf_cookie, form_csrf_token, verify_form_csrf, FORM_CSRF_FIELD};
+use crate::api::extractors::{request_uri, AuthenticatedUser, DPOP_HEADER};
+use crate::domain::error::{ApiError, CommonError, OAuthError};
+use crate::domain::models::audit::{AuditAction, AuditOutcome, CreateAuditEvent};
+use crate::domain::models::oauth::{
+    AuthorizationRequest, DeviceAuthorizationRequest, DeviceCode, OAuthClient, PushedAuthorizationRequest,
+    RevocationRequest, TokenRequest,
+};
+use crate::domain::models::user::LoginUser;
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::dpop::DpopService;
+use crate::domain::services::oauth::OAuthService;
+use crate::domain::services::user::UserService;
+use crate::services::secret::format_user_code;
+
+const AUTHORIZE_TEMPLATE: &str = include_str!("../templates/authorize.html");
+const LOGIN_TEMPLATE: &str = include_str!("../templates/login.html");
+const CONSENT_TEMPLATE: &str = include_str!("../templates/consent.html");
+const ERROR_TEMPLATE: &str = include_str!("../templates/error.html");
+const DEVICE_TEMPLATE: &str = include_str!("../templates/device.html");
+const DEVICE_DONE_TEMPLATE: &str = include_str!("../templates/device_done.html");
+
+pub async fn register_client_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<CreateOAuthClientDTO>,
+) -> Result<web::Json<RegisteredOAuthClientDTO>, ApiError> {
+    let registered = oauth_service
+        .register_client(user.tenant()?, post_data.into_inner().into())
+        .await?;
+    Ok(web::Json(registered.into()))
+}
+
+pub async fn list_clients_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<OAuthClientDTO>>, ApiError> {
+    let clients = oauth_service.list_clients(user.tenant()?).await?;
+    Ok(web::Json(clients.into_iter().map(OAuthClientDTO::from).collect()))
+}
+
+pub async fn delete_client_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    oauth_service
+        .delete_client(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn rotate_client_secret_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<RegisteredOAuthClientDTO>, ApiError> {
+    let registered = oauth_service
+        .rotate_client_secret(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(web::Json(registered.into()))
+}
+
+pub async fn revoke_previous_client_secret_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    oauth_service
+        .revoke_previous_client_secret(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn list_consents_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<ConsentDTO>>, ApiError> {
+    let consents = oauth_service.list_consents(consent_owner(&user)?).await?;
+    Ok(web::Json(consents.into_iter().map(ConsentDTO::from).collect()))
+}
+
+pub async fn revoke_consent_handler(
+    req: HttpRequest,
+    oauth_service: web::Data<dyn OAuthService>,
+    audit_service: web::Data<dyn AuditService>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let client_id = path.into_inner();
+    oauth_service
+        .revoke_consent(consent_owner(&user)?, &client_id)
+        .await?;
+    let event = request_event(&req, AuditAction::ConsentRevoke, AuditOutcome::Success).target("client", client_id);
+    record(audit_service.as_ref(), &req, claim_actor(event, &user)).await;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Só o próprio usuário, com um token emitido pelo login e não por um cliente OAuth, gerencia
+/// os consentimentos; assim um aplicativo não consegue ver nem revogar os de outros.
+fn consent_owner(user: &AuthenticatedUser) -> Result<i32, ApiError> {
+    user.session_user_id().ok_or_else(|| {
+        ApiError::from(CommonError {
+            message: "Consents can only be managed with a user session token".to_string(),
+            code: 403,
+        })
+    })
+}
+
+pub async fn authorize_page_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    req: HttpRequest,
+    query: web::Query<AuthorizationRequest>,
+) -> HttpResponse {
+    let (client, request, redirect_uri) = match resolve_request(&oauth_service, query.into_inner()).await {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+    match oauth_service
+        .validate_authorization_request(&client, &request)
+        .await
+    {
+        Ok(scope) => render_authorize_page(&client, &request, &scope, &form_csrf_token(&req), None),
+        Err(e) => redirect_with_error(&redirect_uri, &e, request.state.as_deref()),
+    }
+}
+
+pub async fn authorize_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user_service: web::Data<dyn UserService>,
+    audit_service: web::Data<dyn AuditService>,
+    req: HttpRequest,
+    form: web::Form<AuthorizeFormDTO>,
+) -> HttpResponse {
+    let form = form.into_inner();
+    let Some(csrf_token) = verified_form_csrf(&req, form.csrf_token.as_deref()) else {
+        return render_form_expired_page();
+    };
+    // a tela de consentimento traz os parâmetros já resolvidos e o código pendente, emitido
+    // depois do login e, portanto, depois do PAR quando o cliente o exige
+    let resolved = match form.consent_code {
+        Some(_) => resolve_client(&oauth_service, &form.request)
+            .await
+            .map(|(client, redirect_uri)| (client, form.request, redirect_uri)),
+        None => resolve_request(&oauth_service, form.request).await,
+    };
+    let (client, request, redirect_uri) = match resolved {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+    let scope = match oauth_service
+        .validate_authorization_request(&client, &request)
+        .await
+    {
+        Ok(scope) => scope,
+        Err(e) => return redirect_with_error(&redirect_uri, &e, request.state.as_deref()),
+    };
+    if let Some(consent_code) = form.consent_code {
+        return match oauth_service
+            .decide_consent(&client, &consent_code, form.decision == "allow")
+            .await
+        {
+            Ok(()) => redirect_with_code(&redirect_uri, &consent_code, request.state.as_deref()),
+            Err(e) => redirect_with_error(&redirect_uri, &e, request.state.as_deref()),
+        };
+    }
+    if form.decision != "login" {
+        let error = OAuthError::new("access_denied", "The user denied the request");
+        return redirect_with_error(&redirect_uri, &error, request.state.as_deref());
+    }
+
+    let login_user = LoginUser {
+        username: form.username.clone(),
+        password: form.password,
+        organization: None,
+    };
+    let authenticated = user_service
+        .authenticate(client.organization_id, login_user)
+        .await;
+    let event = page_login_event(&req, &client, &form.username, authenticated.as_ref().ok().map(|user| user.id));
+    record(audit_service.as_ref(), &req, event).await;
+    let user = match authenticated {
+        Ok(user) => user,
+        Err(_) => {
+            let mut response = render_authorize_page(
+                &client,
+                &request,
+                &scope,
+                &csrf_token,
+                Some("Invalid username or password"),
+            );
+            *response.status_mut() = actix_web::http::StatusCode::UNAUTHORIZED;
+            return response;
+        }
+    };
+
+    match oauth_service
+        .create_authorization_code(&client, &request, user.id)
+        .await
+    {
+        Ok(issued) if issued.consent_required => {
+            render_authorization_consent_page(&client, &request, &scope, &issued.code, &csrf_token)
+        }
+        Ok(issued) => redirect_with_code(&redirect_uri, &issued.code, request.state.as_deref()),
+        Err(e) => redirect_with_error(&redirect_uri, &e, request.state.as_deref()),
+    }
+}
+
+/// Recebe os parâmetros de uma requisição de autorização por POST (RFC 9126), para que eles
+/// não passem pela URL do navegador.
+pub async fn pushed_authorization_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    req: HttpRequest,
+    form: web::Form<PushedAuthorizationRequestDTO>,
+) -> Result<HttpResponse, OAuthError> {
+    let mut request: PushedAuthorizationRequest = form.into_inner().into();
+    if let Some((client_id, client_secret)) = basic_credentials(&req) {
+        request.client_id = Some(client_id);
+        request.client_secret = Some(client_secret);
+    }
+    let pushed = oauth_service.push_authorization_request(request).await?;
+    Ok(HttpResponse::Created()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(PushedAuthorizationResponseDTO::from(pushed)))
+}
+
+pub async fn token_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    dpop_service: web::Data<dyn DpopService>,
+    req: HttpRequest,
+    form: web::Form<TokenRequestDTO>,
+) -> Result<HttpResponse, OAuthError> {
+    let mut request: TokenRequest = form.into_inner().into();
+    // client_secret_basic (RFC 6749, seção 2.3.1) tem precedência sobre client_secret_post
+    if let Some((client_id, client_secret)) = basic_credentials(&req) {
+        request.client_id = Some(client_id);
+        request.client_secret = Some(client_secret);
+    }
+    // com uma prova DPoP (RFC 9449, seção 5), os tokens emitidos ficam vinculados à chave dela
+    if let Some(proof) = req.headers().get(DPOP_HEADER) {
+        let proof = proof
+            .to_str()
+            .map_err(|_| OAuthError::new("invalid_dpop_proof", "Malformed DPoP proof"))?;
+        let jkt = dpop_service
+            .verify_proof(proof, "POST", &request_uri(&req), None)
+            .await
+            .map_err(|e| OAuthError::new("invalid_dpop_proof", e.message))?;
+        request.dpop_jkt = Some(jkt);
+    }
+    let response = oauth_service.exchange_token(request).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header((header::PRAGMA, "no-cache"))
+        .json(response))
+}
+
+pub async fn revoke_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    audit_service: web::Data<dyn AuditService>,
+    req: HttpRequest,
+    form: web::Form<RevocationRequestDTO>,
+) -> Result<HttpResponse, OAuthError> {
+    let mut request: RevocationRequest = form.into_inner().into();
+    if let Some((client_id, client_secret)) = basic_credentials(&req) {
+        request.client_id = Some(client_id);
+        request.client_secret = Some(client_secret);
+    }
+    let client_id = request.client_id.clone().unwrap_or_default();
+    let token_type_hint = request.token_type_hint.clone();
+    let result = oauth_service.revoke_token(request).await;
+    // o token não vai para a auditoria; só o cliente que pediu a revogação
+    let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+    let organization_id = oauth_service.get_client(&client_id).await.ok().map(|c| c.organization_id);
+    let event = request_event(&req, AuditAction::TokenRevoke, outcome)
+        .organization(organization_id)
+        .actor("client", &client_id)
+        .details(serde_json::json!({
+            "token_type_hint": token_type_hint,
+            "error": result.as_ref().err().map(|e| e.error.clone()),
+        }));
+    record(audit_service.as_ref(), &req, event).await;
+    result?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .finish())
+}
+
+pub async fn device_authorization_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    req: HttpRequest,
+    form: web::Form<DeviceAuthorizationRequestDTO>,
+) -> Result<HttpResponse, OAuthError> {
+    let mut request: DeviceAuthorizationRequest = form.into_inner().into();
+    if let Some((client_id, client_secret)) = basic_credentials(&req) {
+        request.client_id = Some(client_id);
+        request.client_secret = Some(client_secret);
+    }
+    let authorization = oauth_service.create_device_authorization(request).await?;
+    let connection = req.connection_info();
+    let verification_uri = format!("{}://{}/oauth/device", connection.scheme(), connection.host());
+    let mut verification_uri_complete =
+        Url::parse(&verification_uri).map_err(|e| OAuthError::new("server_error", e.to_string()))?;
+    verification_uri_complete
+        .query_pairs_mut()
+        .append_pair("user_code", &authorization.user_code);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(DeviceAuthorizationResponseDTO {
+            device_code: authorization.device_code,
+            user_code: authorization.user_code,
+            verification_uri,
+            verification_uri_complete: verification_uri_complete.to_string(),
+            expires_in: authorization.expires_in,
+            interval: authorization.interval,
+        }))
+}
+
+pub async fn device_page_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    req: HttpRequest,
+    query: web::Query<DeviceVerificationQueryDTO>,
+) -> HttpResponse {
+    let Some(user_code) = query.into_inner().user_code else {
+        return render_device_page(None);
+    };
+    match oauth_service
+        .get_pending_device_code(&user_code, &remote_addr(&req))
+        .await
+    {
+        Ok((device_code, client)) => {
+            render_device_consent_page(&client, &device_code, &form_csrf_token(&req), None)
+        }
+        Err(e) => render_device_lookup_error(&e),
+    }
+}
+
+pub async fn device_handler(
+    oauth_service: web::Data<dyn OAuthService>,
+    user_service: web::Data<dyn UserService>,
+    audit_service: web::Data<dyn AuditService>,
+    req: HttpRequest,
+    form: web::Form<DeviceVerificationFormDTO>,
+) -> HttpResponse {
+    let form = form.into_inner();
+    let Some(csrf_token) = verified_form_csrf(&req, form.csrf_token.as_deref()) else {
+        return render_form_expired_page();
+    };
+    let (device_code, client) = match oauth_service
+        .get_pending_device_code(&form.user_code, &remote_addr(&req))
+        .await
+    {
+        Ok(pending) => pending,
+        Err(e) => return render_device_lookup_error(&e),
+    };
+    if form.decision != "allow" {
+        return match oauth_service.deny_device_code(&form.user_code).await {
+            Ok(()) => render_device_done_page("Access denied. You can close this window."),
+            Err(e) => render_device_page(Some(&e.message)),
+        };
+    }
+
+    let login_user = LoginUser {
+        username: form.username.clone(),
+        password: form.password,
+        organization: None,
+    };
+    let authenticated = user_service
+        .authenticate(client.organization_id, login_user)
+        .await;
+    let event = page_login_event(&req, &client, &form.username, authenticated.as_ref().ok().map(|user| user.id));
+    record(audit_service.as_ref(), &req, event).await;
+    let user = match authenticated {
+        Ok(user) => user,
+        Err(_) => {
+            let mut response = render_device_consent_page(
+                &client,
+                &device_code,
+                &csrf_token,
+                Some("Invalid username or password"),
+            );
+            *response.status_mut() = actix_web::http::StatusCode::UNAUTHORIZED;
+            return response;
+        }
+    };
+
+    match oauth_service.approve_device_code(&form.user_code, user.id).await {
+        Ok(()) => render_device_done_page("Your device is connected. You can close this window."),
+        Err(e) => render_device_page(Some(&e.message)),
+    }
+}
+
+/// Login nas telas de autorização e de dispositivo, registrado como `user.login` na organização
+/// do cliente.
+fn page_login_event(req: &HttpRequest, client: &OAuthClient, username: &str, user_id: Option<i32>) -> CreateAuditEvent {
+    let outcome = match user_id {
+        Some(_) => AuditOutcome::Success,
+        None => AuditOutcome::Failure,
+    };
+    let event = request_event(req, AuditAction::UserLogin, outcome)
+        .organization(Some(client.organization_id))
+        .details(serde_json::json!({ "username": username, "client_id": client.client_id }));
+    match user_id {
+        Some(user_id) => event.actor("user", user_id).target("user", user_id),
+        None => event,
+    }
+}
+
+/// Resolve o cliente e o `redirect_uri`. Erros aqui não podem ser redirecionados, pois o
+/// destino não é confiável, e são mostrados ao usuário.
+async fn resolve_client(
+    oauth_service: &web::Data<dyn OAuthService>,
+    request: &AuthorizationRequest,
+) -> Result<(OAuthClient, String), HttpResponse> {
+    let client = oauth_service
+        .get_client(&request.client_id)
+        .await
+        .map_err(|_| render_error_page("Unknown client"))?;
+    let redirect_uri = client
+        .redirect_uri_for(request.redirect_uri.as_deref())
+        .ok_or_else(|| render_error_page("The redirect_uri is not registered for this client"))?;
+    Ok((client, redirect_uri))
+}
+
+/// Como `resolve_client`, mas antes troca um `request_uri` pelos parâmetros enviados a
+/// `/oauth/par` e recusa requisições sem ele de clientes que exigem PAR.
+async fn resolve_request(
+    oauth_service: &web::Data<dyn OAuthService>,
+    request: AuthorizationRequest,
+) -> Result<(OAuthClient, AuthorizationRequest, String), HttpResponse> {
+    let client = oauth_service
+        .get_client(&request.client_id)
+        .await
+        .map_err(|_| render_error_page("Unknown client"))?;
+    let request = oauth_service
+        .resolve_authorization_request(&client, request)
+        .await
+        .map_err(|e| render_error_page(e.error_description.as_deref().unwrap_or(&e.error)))?;
+    let redirect_uri = client
+        .redirect_uri_for(request.redirect_uri.as_deref())
+        .ok_or_else(|| render_error_page("The redirect_uri is not registered for this client"))?;
+    Ok((client, request, redirect_uri))
+}
+
+fn basic_credentials(req: &HttpRequest) -> Option<(String, String)> {
+    let encoded = req
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (client_id, client_secret) = decoded.split_once(':')?;
+    Some((client_id.to_string(), client_secret.to_string()))
+}
+
+fn redirect(redirect_uri: &str, params: &[(&str, &str)]) -> HttpResponse {
+    let mut url = match Url::parse(redirect_uri) {
+        Ok(url) => url,
+        Err(_) => return render_error_page("Invalid redirect_uri"),
+    };
+    url.query_pairs_mut().extend_pairs(params);
+    HttpResponse::Found()
+        .insert_header((header::LOCATION, url.to_string()))
+        .finish()
+}
+
+fn redirect_with_code(redirect_uri: &str, code: &str, state: Option<&str>) -> HttpResponse {
+    let mut params = vec![("code", code)];
+    params.extend(state.map(|state| ("state", state)));
+    redirect(redirect_uri, &params)
+}
+
+fn redirect_with_error(redirect_uri: &str, error: &OAuthError, state: Option<&str>) -> HttpResponse {
+    let mut params = vec![("error", error.error.as_str())];
+    params.extend(error.error_description.as_deref().map(|d| ("error_description", d)));
+    params.extend(state.map(|state| ("state", state)));
+    redirect(redirect_uri, &params)
+}
+
+/// Campos ocultos que levam a requisição adiante. Com PAR, só o `request_uri` vai no formulário
+/// e os parâmetros são buscados de novo no envio.
+fn authorization_fields(request: &AuthorizationRequest) -> Vec<(&str, Option<&str>)> {
+    if let Some(request_uri) = request.request_uri.as_deref() {
+        return vec![
+            ("client_id", Some(request.client_id.as_str())),
+            ("request_uri", Some(request_uri)),
+        ];
+    }
+    vec![
+        ("response_type", Some(request.response_type.as_str())),
+        ("client_id", Some(request.client_id.as_str())),
+        ("redirect_uri", request.redirect_uri.as_deref()),
+        ("scope", request.scope.as_deref()),
+        ("state", request.state.as_deref()),
+        ("code_challenge", request.code_challenge.as_deref()),
+        ("code_challenge_method", request.code_challenge_method.as_deref()),
+        ("nonce", request.nonce.as_deref()),
+    ]
+}
+
+/// Token CSRF enviado com o formulário, se for igual ao do cookie.
+fn verified_form_csrf(req: &HttpRequest, submitted: Option<&str>) -> Option<String> {
+    verify_form_csrf(req, submitted).then(|| submitted.unwrap_or_default().to_string())
+}
+
+fn render_authorize_page(
+    client: &OAuthClient,
+    request: &AuthorizationRequest,
+    scope: &str,
+    csrf_token: &str,
+    error: Option<&str>,
+) -> HttpResponse {
+    let mut fields = authorization_fields(request);
+    fields.push((FORM_CSRF_FIELD, Some(csrf_token)));
+    render_consent_page(LOGIN_TEMPLATE, client, "/oauth/authorize", &fields, scope, error)
+}
+
+fn render_authorization_consent_page(
+    client: &OAuthClient,
+    request: &AuthorizationRequest,
+    scope: &str,
+    consent_code: &str,
+    csrf_token: &str,
+) -> HttpResponse {
+    // o `request_uri` foi descartado ao emitir o código; a tela leva os parâmetros resolvidos
+    let request = AuthorizationRequest {
+        request_uri: None,
+        ..request.clone()
+    };
+    let mut fields = authorization_fields(&request);
+    fields.push(("consent_code", Some(consent_code)));
+    fields.push((FORM_CSRF_FIELD, Some(csrf_token)));
+    render_consent_page(CONSENT_TEMPLATE, client, "/oauth/authorize", &fields, scope, None)
+}
+
+fn render_device_consent_page(
+    client: &OAuthClient,
+    device_code: &DeviceCode,
+    csrf_token: &str,
+    error: Option<&str>,
+) -> HttpResponse {
+    let user_code = format_user_code(&device_code.user_code);
+    let fields = [
+        ("user_code", Some(user_code.as_str())),
+        (FORM_CSRF_FIELD, Some(csrf_token)),
+    ];
+    render_consent_page(AUTHORIZE_TEMPLATE, client, "/oauth/device", &fields, &device_code.scope, error)
+}
+
+/// Página de um formulário. O token CSRF, quando está entre os campos, é gravado também no
+/// cookie, que o envio do formulário precisa repetir.
+fn render_consent_page(
+    template: &str,
+    client: &OAuthClient,
+    action: &str,
+    fields: &[(&str, Option<&str>)],
+    scope: &str,
+    error: Option<&str>,
+) -> HttpResponse {
+    let hidden_fields: String = fields
+        .iter()
+        .filter_map(|(name, value)| {
+            value.map(|value| {
+                format!(
+                    r#"<input type="hidden" name="{}" value="{}">"#,
+                    name,
+                    escape_html(value)
+                )
+            })
+        })
+        .collect();
+    let scopes: String = scope
+        .split_whitespace()
+        .map(|scope| format!("<li>{}</li>", escape_html(scope)))
+        .collect();
+    let error = error
+        .map(|message| format!(r#"<p class="error">{}</p>"#, escape_html(message)))
+        .unwrap_or_default();
+    let body = template
+        .replace("{client_name}", &escape_html(&client.name))
+        .replace("{action}", action)
+        .replace("{error}", &error)
+        .replace("{hidden_fields}", &hidden_fields)
+        .replace("{scopes}", &scopes);
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(ContentType::html())
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header(("X-Frame-Options", "DENY"));
+    if let Some((_, Some(csrf_token))) = fields.iter().find(|(name, _)| *name == FORM_CSRF_FIELD) {
+        response.cookie(form_csrf_cookie(csrf_token));
+    }
+    response.body(body)
+}
+
+fn render_device_page(error: Option<&str>) -> HttpResponse {
+    let error = error
+        .map(|message| format!(r#"<p class="error">{}</p>"#, escape_html(message)))
+        .unwrap_or_default();
+    HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header(("X-Frame-Options", "DENY"))
+        .body(DEVICE_TEMPLATE.replace("{error}", &error))
+}
+
+/// Mostra a página do código de dispositivo com o erro da busca; quem excedeu o limite de
+/// falhas recebe 429.
+fn render_device_lookup_error(error: &CommonError) -> HttpResponse {
+    let mut response = render_device_page(Some(&error.message));
+    if error.code == 429 {
+        *response.status_mut() = actix_web::http::StatusCode::TOO_MANY_REQUESTS;
+    }
+    response
+}
+
+fn render_device_done_page(message: &str) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header(("X-Frame-Options", "DENY"))
+        .body(DEVICE_DONE_TEMPLATE.replace("{message}", &escape_html(message)))
+}
+
+/// Envio sem o token CSRF do formulário ou com um diferente do cookie: pode ter vindo de outro
+/// site ou de uma página aberta antes de o cookie expirar.
+fn render_form_expired_page() -> HttpResponse {
+    let mut response = render_error_page("This form has expired. Go back, reload the page and try again.");
+    *response.status_mut() = actix_web::http::StatusCode::FORBIDDEN;
+    response
+}
+
+fn render_error_page(message: &str) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .content_type(ContentType::html())
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header(("X-Frame-Options", "DENY"))
+        .body(ERROR_TEMPLATE.replace("{message}", &escape_html(message)))
+}
+
+/// Endereço da conexão. Cabeçalhos como `X-Forwarded-For` são ignorados, pois quem excede o
+/// limite de falhas poderia forjá-los.
+fn remote_addr(req: &HttpRequest) -> String {
+    req.peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default()
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+        .replace('{', "&#123;")
+        .replace('}', "&#125;")
+}
@@ -0,0 +1,24 @@
+use actix_web::{web, Result};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::models::oidc::{ProviderMetadata, UserInfo};
+use crate::domain::services::oidc::OidcService;
+
+pub async fn discovery_handler(
+    oidc_service: web::Data<dyn OidcService>,
+) -> Result<web::Json<ProviderMetadata>, ApiError> {
+    Ok(web::Json(oidc_service.provider_metadata().await?))
+}
+
+pub async fn jwks_handler(oidc_service: web::Data<dyn OidcService>) -> Result<web::Json<JwkSet>, ApiError> {
+    Ok(web::Json(oidc_service.jwks().await?))
+}
+
+pub async fn userinfo_handler(
+    oidc_service: web::Data<dyn OidcService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<UserInfo>, ApiError> {
+    Ok(web::Json(oidc_service.userinfo(&user).await?))
+}
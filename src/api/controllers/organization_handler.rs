@@ -0,0 +1,26 @@
+use actix_web::{web, Result};
+
+use crate::api::dto::organization::{CreateOrganizationDTO, OrganizationDTO};
+use crate::domain::error::ApiError;
+use crate::domain::models::organization::CreateOrganization;
+use crate::domain::services::organization::OrganizationService;
+
+pub async fn create_organization_handler(
+    organization_service: web::Data<dyn OrganizationService>,
+    post_data: web::Json<CreateOrganizationDTO>,
+) -> Result<web::Json<OrganizationDTO>, ApiError> {
+    let CreateOrganizationDTO { slug, name, owner } = post_data.into_inner();
+    let organization = organization_service
+        .create_organization(CreateOrganization { slug, name }, owner.into())
+        .await?;
+    Ok(web::Json(organization.into()))
+}
+
+pub async fn list_organizations_handler(
+    organization_service: web::Data<dyn OrganizationService>,
+) -> Result<web::Json<Vec<OrganizationDTO>>, ApiError> {
+    let organizations = organization_service.list_organizations().await?;
+    Ok(web::Json(
+        organizations.into_iter().map(OrganizationDTO::from).collect(),
+    ))
+}
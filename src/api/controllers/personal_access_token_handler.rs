@@ -0,0 +1,59 @@
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+
+use crate::api::audit::{claim_actor, record, request_event};
+use crate::api::dto::personal_access_token::{
+    CreatePersonalAccessTokenDTO, IssuedPersonalAccessTokenDTO, PersonalAccessTokenDTO,
+};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::models::audit::{AuditAction, AuditOutcome};
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::personal_access_token::PersonalAccessTokenService;
+
+pub async fn create_personal_access_token_handler(
+    personal_access_token_service: web::Data<dyn PersonalAccessTokenService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<CreatePersonalAccessTokenDTO>,
+) -> Result<HttpResponse, ApiError> {
+    let issued = personal_access_token_service
+        .create(user.tenant()?, token_owner(&user)?, post_data.into_inner().into())
+        .await?;
+    Ok(HttpResponse::Created()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(IssuedPersonalAccessTokenDTO::from(issued)))
+}
+
+pub async fn list_personal_access_tokens_handler(
+    personal_access_token_service: web::Data<dyn PersonalAccessTokenService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<PersonalAccessTokenDTO>>, ApiError> {
+    let tokens = personal_access_token_service.list(token_owner(&user)?).await?;
+    Ok(web::Json(tokens.into_iter().map(PersonalAccessTokenDTO::from).collect()))
+}
+
+pub async fn revoke_personal_access_token_handler(
+    req: HttpRequest,
+    personal_access_token_service: web::Data<dyn PersonalAccessTokenService>,
+    audit_service: web::Data<dyn AuditService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+    personal_access_token_service.revoke(token_owner(&user)?, id).await?;
+    let event = request_event(&req, AuditAction::TokenRevoke, AuditOutcome::Success)
+        .target("personal_access_token", id);
+    record(audit_service.as_ref(), &req, claim_actor(event, &user)).await;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Tokens são gerenciados só com um token de sessão: um personal access token não cria outros
+/// com escopo maior que o seu, nem um cliente OAuth cria tokens em nome do usuário.
+fn token_owner(user: &AuthenticatedUser) -> Result<i32, ApiError> {
+    user.session_user_id().ok_or_else(|| {
+        ApiError::from(CommonError {
+            message: "Personal access tokens can only be managed with a user session token".to_string(),
+            code: 403,
+        })
+    })
+}
@@ -0,0 +1,140 @@
+use actix_web::{web, HttpResponse, Result};
+
+use crate::api::dto::role::{CreatePermissionDTO, CreateRoleDTO, PermissionDTO, RoleDTO, RoleSessionPolicyDTO};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::services::authorization::AuthorizationService;
+
+pub async fn create_role_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<CreateRoleDTO>,
+) -> Result<web::Json<RoleDTO>, ApiError> {
+    let role = authorization_service
+        .create_role(user.tenant()?, post_data.into_inner().into())
+        .await?;
+    Ok(web::Json(role.into()))
+}
+
+pub async fn list_roles_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<RoleDTO>>, ApiError> {
+    let roles = authorization_service.list_roles(user.tenant()?).await?;
+    Ok(web::Json(roles.into_iter().map(RoleDTO::from).collect()))
+}
+
+pub async fn delete_role_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    authorization_service
+        .delete_role(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Substitui os limites de sessão do papel; valem a partir dos próximos logins.
+pub async fn set_role_session_policy_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    post_data: web::Json<RoleSessionPolicyDTO>,
+) -> Result<web::Json<RoleDTO>, ApiError> {
+    let role = authorization_service
+        .set_role_session_policy(user.tenant()?, path.into_inner(), post_data.into_inner().into())
+        .await?;
+    Ok(web::Json(role.into()))
+}
+
+pub async fn create_permission_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    post_data: web::Json<CreatePermissionDTO>,
+) -> Result<web::Json<PermissionDTO>, ApiError> {
+    let permission = authorization_service
+        .create_permission(post_data.into_inner().into())
+        .await?;
+    Ok(web::Json(permission.into()))
+}
+
+pub async fn list_permissions_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+) -> Result<web::Json<Vec<PermissionDTO>>, ApiError> {
+    let permissions = authorization_service.list_permissions().await?;
+    Ok(web::Json(
+        permissions.into_iter().map(PermissionDTO::from).collect(),
+    ))
+}
+
+pub async fn list_role_permissions_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<Vec<PermissionDTO>>, ApiError> {
+    let permissions = authorization_service
+        .get_role_permissions(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(web::Json(
+        permissions.into_iter().map(PermissionDTO::from).collect(),
+    ))
+}
+
+pub async fn grant_permission_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (role_id, permission_id) = path.into_inner();
+    authorization_service
+        .grant_permission(user.tenant()?, role_id, permission_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn revoke_permission_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (role_id, permission_id) = path.into_inner();
+    authorization_service
+        .revoke_permission(user.tenant()?, role_id, permission_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn list_user_roles_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<Vec<RoleDTO>>, ApiError> {
+    let roles = authorization_service
+        .get_user_roles(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(web::Json(roles.into_iter().map(RoleDTO::from).collect()))
+}
+
+pub async fn assign_role_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (user_id, role_id) = path.into_inner();
+    authorization_service
+        .assign_role(user.tenant()?, user_id, role_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn unassign_role_handler(
+    authorization_service: web::Data<dyn AuthorizationService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (user_id, role_id) = path.into_inner();
+    authorization_service
+        .unassign_role(user.tenant()?, user_id, role_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
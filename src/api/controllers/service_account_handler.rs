@@ -0,0 +1,77 @@
+use actix_web::http::header;
+use actix_web::{web, HttpResponse, Result};
+
+use crate::api::dto::personal_access_token::{
+    CreatePersonalAccessTokenDTO, IssuedPersonalAccessTokenDTO, PersonalAccessTokenDTO,
+};
+use crate::api::dto::service_account::{CreateServiceAccountDTO, ServiceAccountDTO};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::services::service_account::ServiceAccountService;
+
+pub async fn create_service_account_handler(
+    service_account_service: web::Data<dyn ServiceAccountService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<CreateServiceAccountDTO>,
+) -> Result<web::Json<ServiceAccountDTO>, ApiError> {
+    let account = service_account_service
+        .create(user.tenant()?, post_data.into_inner().into())
+        .await?;
+    Ok(web::Json(account.into()))
+}
+
+pub async fn list_service_accounts_handler(
+    service_account_service: web::Data<dyn ServiceAccountService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<ServiceAccountDTO>>, ApiError> {
+    let accounts = service_account_service.list(user.tenant()?).await?;
+    Ok(web::Json(accounts.into_iter().map(ServiceAccountDTO::from).collect()))
+}
+
+pub async fn delete_service_account_handler(
+    service_account_service: web::Data<dyn ServiceAccountService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    service_account_service
+        .delete(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn create_service_account_token_handler(
+    service_account_service: web::Data<dyn ServiceAccountService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    post_data: web::Json<CreatePersonalAccessTokenDTO>,
+) -> Result<HttpResponse, ApiError> {
+    let issued = service_account_service
+        .create_token(user.tenant()?, path.into_inner(), post_data.into_inner().into())
+        .await?;
+    Ok(HttpResponse::Created()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(IssuedPersonalAccessTokenDTO::from(issued)))
+}
+
+pub async fn list_service_account_tokens_handler(
+    service_account_service: web::Data<dyn ServiceAccountService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<Vec<PersonalAccessTokenDTO>>, ApiError> {
+    let tokens = service_account_service
+        .list_tokens(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(web::Json(tokens.into_iter().map(PersonalAccessTokenDTO::from).collect()))
+}
+
+pub async fn revoke_service_account_token_handler(
+    service_account_service: web::Data<dyn ServiceAccountService>,
+    user: AuthenticatedUser,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (id, token_id) = path.into_inner();
+    service_account_service
+        .revoke_token(user.tenant()?, id, token_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
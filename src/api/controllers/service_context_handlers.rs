@@ -1,16 +1,37 @@
-//
-//
-//
-// pub async fn update_service_context_handler(
-//     service_context_service: web::Data<dyn ServiceContextService>, post_data: web::Json<ServiceContextDTO>,
-// ) -> Result<web::Json<ServiceContextDTO>, ApiError> {
-//     let service_context = service_context_service.update(post_data.into_inner().into()).await?;
-//     Ok(web::Json(service_context.into()))
-// }
-//
-// pub async fn get_service_context(
-//     service_context_service: web::Data<dyn ServiceContextService>,
-// ) -> Result<web::Json<ServiceContextDTO>, ApiError> {
-//     let service_context = service_context_service.get_service_context().await?;
-//     Ok(web::Json(service_context.into()))
-// }
\ No newline at end of file
+use actix_web::{web, HttpRequest, Result};
+
+use crate::api::audit::{claim_actor, record, request_event};
+use crate::api::dto::service_context::ServiceContextDTO;
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::models::audit::{AuditAction, AuditOutcome};
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::service_context::ServiceContextService;
+
+pub async fn get_service_context_handler(
+    service_context_service: web::Data<dyn ServiceContextService>,
+) -> Result<web::Json<ServiceContextDTO>, ApiError> {
+    Ok(web::Json(service_context_service.get_service_context().into()))
+}
+
+/// Liga ou desliga a manutenção do serviço inteiro, para todas as organizações.
+pub async fn update_service_context_handler(
+    req: HttpRequest,
+    service_context_service: web::Data<dyn ServiceContextService>,
+    audit_service: web::Data<dyn AuditService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<ServiceContextDTO>,
+) -> Result<web::Json<ServiceContextDTO>, ApiError> {
+    let mut service_context = service_context_service.get_service_context();
+    let previous = service_context.maintenance;
+    service_context.maintenance = post_data.maintenance;
+    let service_context = service_context_service.update(service_context);
+    let event = request_event(&req, AuditAction::MaintenanceToggle, AuditOutcome::Success)
+        .target("service", "maintenance")
+        .details(serde_json::json!({
+            "previous": previous,
+            "maintenance": service_context.maintenance,
+        }));
+    record(audit_service.as_ref(), &req, claim_actor(event, &user)).await;
+    Ok(web::Json(service_context.into()))
+}
@@ -0,0 +1,45 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+
+use crate::api::audit::{claim_actor, record, request_event};
+use crate::api::dto::session::SessionDTO;
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::models::audit::{AuditAction, AuditOutcome};
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::session::SessionService;
+
+pub async fn list_sessions_handler(
+    session_service: web::Data<dyn SessionService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<SessionDTO>>, ApiError> {
+    let sessions = session_service.list(session_owner(&user)?).await?;
+    Ok(web::Json(
+        sessions
+            .into_iter()
+            .map(|session| SessionDTO::new(session, user.sid.as_deref()))
+            .collect(),
+    ))
+}
+
+pub async fn revoke_session_handler(
+    req: HttpRequest,
+    session_service: web::Data<dyn SessionService>,
+    audit_service: web::Data<dyn AuditService>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+    session_service.revoke(session_owner(&user)?, &id).await?;
+    let event = request_event(&req, AuditAction::SessionRevoke, AuditOutcome::Success).target("session", id);
+    record(audit_service.as_ref(), &req, claim_actor(event, &user)).await;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn session_owner(user: &AuthenticatedUser) -> Result<i32, ApiError> {
+    user.session_user_id().ok_or_else(|| {
+        ApiError::from(CommonError {
+            message: "Sessions can only be managed with a user session token".to_string(),
+            code: 403,
+        })
+    })
+}
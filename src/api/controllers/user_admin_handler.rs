@@ -0,0 +1,115 @@
+use actix_web::{web, HttpResponse, Result};
+
+use crate::api::dto::user_admin::{AdminUserDTO, RevokedSessionsDTO, UpdateUserDTO, UserQueryDTO};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::repositories::repository::ResultPaging;
+use crate::domain::services::user_admin::UserAdminService;
+
+/// Recusa as ações que tirariam o acesso do próprio administrador.
+fn not_self(user: &AuthenticatedUser, user_id: i32) -> Result<(), ApiError> {
+    if user.user_id() == Some(user_id) {
+        return Err(CommonError {
+            message: "This action can't be applied to your own account".to_string(),
+            code: 400,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+pub async fn list_users_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    query: web::Query<UserQueryDTO>,
+) -> Result<web::Json<ResultPaging<AdminUserDTO>>, ApiError> {
+    let (filter, params) = query.into_inner().into_parts();
+    let page = user_admin_service.list(user.tenant()?, &filter, &params).await?;
+    Ok(web::Json(ResultPaging {
+        total: page.total,
+        items: page.items.into_iter().map(AdminUserDTO::from).collect(),
+    }))
+}
+
+pub async fn get_user_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<AdminUserDTO>, ApiError> {
+    let target = user_admin_service.get(user.tenant()?, path.into_inner()).await?;
+    Ok(web::Json(target.into()))
+}
+
+pub async fn update_user_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    post_data: web::Json<UpdateUserDTO>,
+) -> Result<web::Json<AdminUserDTO>, ApiError> {
+    let target = user_admin_service
+        .update_email(user.tenant()?, path.into_inner(), &post_data.email)
+        .await?;
+    Ok(web::Json(target.into()))
+}
+
+pub async fn delete_user_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = path.into_inner();
+    not_self(&user, user_id)?;
+    user_admin_service.delete(user.tenant()?, user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn disable_user_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<AdminUserDTO>, ApiError> {
+    let user_id = path.into_inner();
+    not_self(&user, user_id)?;
+    let target = user_admin_service.disable(user.tenant()?, user_id).await?;
+    Ok(web::Json(target.into()))
+}
+
+pub async fn enable_user_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<AdminUserDTO>, ApiError> {
+    let target = user_admin_service.enable(user.tenant()?, path.into_inner()).await?;
+    Ok(web::Json(target.into()))
+}
+
+pub async fn require_password_reset_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<AdminUserDTO>, ApiError> {
+    let target = user_admin_service
+        .require_password_reset(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(web::Json(target.into()))
+}
+
+pub async fn unlock_user_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<AdminUserDTO>, ApiError> {
+    let target = user_admin_service.unlock(user.tenant()?, path.into_inner()).await?;
+    Ok(web::Json(target.into()))
+}
+
+pub async fn revoke_user_sessions_handler(
+    user_admin_service: web::Data<dyn UserAdminService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<web::Json<RevokedSessionsDTO>, ApiError> {
+    let revoked = user_admin_service
+        .revoke_sessions(user.tenant()?, path.into_inner())
+        .await?;
+    Ok(web::Json(RevokedSessionsDTO { revoked }))
+}
@@ -1,39 +1,272 @@
-use actix_web::{web, Result};
+use actix_web::http::header::{HeaderName, CACHE_CONTROL, USER_AGENT};
+use actix_web::http::Method;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use chrono::Utc;
 
-use crate::api::dto::user::{CreateUserDTO, LoginUserDTO, TokenDTO};
-use crate::domain::error::ApiError;
+use crate::api::audit::{claim_actor, record, request_event};
+use crate::api::csrf::{csrf_cookie, removal_cookies, session_cookie, CSRF_COOKIE, CSRF_HEADER};
+use crate::api::dto::user::{ChangePasswordDTO, CookieSessionDTO, CreateUserDTO, LoginMode, LoginUserDTO, TokenDTO};
+use crate::api::extractors::{authenticate_as, public_origin, request_uri, AuthenticatedUser, PublicOrigin};
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::models::audit::{AuditAction, AuditOutcome};
+use crate::domain::models::session::SessionClient;
 use crate::domain::models::token::Claim;
 use crate::domain::models::user::CreateUser;
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::dpop::DpopService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::token::TokenService;
 use crate::domain::services::user::UserService;
+use crate::services::secret::{generate_secret, hash_secret};
+
+/// Método e URI da requisição original, repassados pelo proxy no forward auth.
+const X_FORWARDED_METHOD: HeaderName = HeaderName::from_static("x-forwarded-method");
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+const X_FORWARDED_URI: HeaderName = HeaderName::from_static("x-forwarded-uri");
+const X_AUTH_SUBJECT: HeaderName = HeaderName::from_static("x-auth-subject");
+const X_AUTH_TENANT: HeaderName = HeaderName::from_static("x-auth-tenant");
+const X_AUTH_ROLES: HeaderName = HeaderName::from_static("x-auth-roles");
 
 pub async fn create_user_handler(
+    req: HttpRequest,
     user_service: web::Data<dyn UserService>,
+    audit_service: web::Data<dyn AuditService>,
     post_data: web::Json<CreateUserDTO>,
 ) -> Result<web::Json<String>, ApiError> {
     let create_user: CreateUser = post_data.into_inner().into();
     let password = create_user.clone().password;
     let username = create_user.clone().username;
-    user_service.create(create_user).await?;
+    let organization = create_user.clone().organization;
+    let mut details = serde_json::json!({ "username": username, "organization": organization });
+    let user = match user_service.create(create_user).await {
+        Ok(user) => user,
+        Err(e) => {
+            details["reason"] = e.message.clone().into();
+            let event = request_event(&req, AuditAction::UserRegister, AuditOutcome::Failure).details(details);
+            record(audit_service.as_ref(), &req, event).await;
+            return Err(e.into());
+        }
+    };
+    let event = request_event(&req, AuditAction::UserRegister, AuditOutcome::Success)
+        .organization(Some(user.organization_id))
+        .actor("user", user.id)
+        .target("user", user.id)
+        .details(details);
+    record(audit_service.as_ref(), &req, event).await;
     let token = user_service
-        .get_token(LoginUserDTO { username, password }.into())
+        .get_token(
+            LoginUserDTO {
+                username,
+                password,
+                organization,
+                device_name: None,
+                mode: LoginMode::Token,
+            }
+            .into(),
+            session_client(&req, None),
+        )
         .await?;
     Ok(web::Json(token))
 }
 
+/// No modo `token`, devolve o token no corpo. No modo `cookie`, o token vai num cookie
+/// `HttpOnly` e o corpo traz só o token CSRF, também disponível no cookie `__Host-csrf`.
 pub async fn login_user_handler(
+    req: HttpRequest,
     user_service: web::Data<dyn UserService>,
+    token_service: web::Data<dyn TokenService>,
     post_data: web::Json<LoginUserDTO>,
-) -> Result<web::Json<String>, ApiError> {
-    let token = user_service
-        .get_token(post_data.into_inner().into())
+) -> Result<HttpResponse, ApiError> {
+    let post_data = post_data.into_inner();
+    let mut client = session_client(&req, post_data.device_name.clone());
+    if post_data.mode == LoginMode::Token {
+        let token = user_service.get_token(post_data.into(), client).await?;
+        return Ok(HttpResponse::Ok().json(token));
+    }
+
+    let csrf_token = generate_secret(32);
+    client.csrf_token_hash = Some(hash_secret(&csrf_token));
+    let token = user_service.get_token(post_data.into(), client).await?;
+    // os cookies duram o mesmo que o token; `/auth/renew` os substitui enquanto a sessão estiver ativa
+    let expires_in = token_service.validate(token.clone()).await?.exp - Utc::now().timestamp();
+    Ok(HttpResponse::Ok()
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .cookie(session_cookie(token, expires_in))
+        .cookie(csrf_cookie(csrf_token.clone(), expires_in))
+        .json(CookieSessionDTO { csrf_token, expires_in }))
+}
+
+/// Encerra a sessão do token apresentado e remove os cookies do modo cookie. Tokens sem sessão,
+/// como os personal access tokens, só têm os cookies removidos.
+pub async fn logout_handler(
+    req: HttpRequest,
+    session_service: web::Data<dyn SessionService>,
+    audit_service: web::Data<dyn AuditService>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse, ApiError> {
+    if let (Some(user_id), Some(sid)) = (user.session_user_id(), user.sid.as_deref()) {
+        session_service.revoke(user_id, sid).await?;
+        let event = request_event(&req, AuditAction::UserLogout, AuditOutcome::Success).target("session", sid);
+        record(audit_service.as_ref(), &req, claim_actor(event, &user)).await;
+    }
+    let mut response = HttpResponse::NoContent();
+    for cookie in removal_cookies() {
+        response.cookie(cookie);
+    }
+    Ok(response.finish())
+}
+
+/// Emite um novo token para a sessão do token apresentado, que precisa continuar ativa. Se o
+/// login foi no modo `cookie`, o token só vai no cookie de sessão e o corpo traz o mesmo token
+/// CSRF, como no login.
+pub async fn renew_token_handler(
+    req: HttpRequest,
+    user_service: web::Data<dyn UserService>,
+    token_service: web::Data<dyn TokenService>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse, ApiError> {
+    let token = user_service.renew_session_token(&user.claim).await?;
+    if user.csrf.is_none() {
+        return Ok(HttpResponse::Ok().json(token));
+    }
+    let csrf_token = req
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| req.cookie(CSRF_COOKIE).map(|cookie| cookie.value().to_string()))
+        .unwrap_or_default();
+    let expires_in = token_service.validate(token.clone()).await?.exp - Utc::now().timestamp();
+    Ok(HttpResponse::Ok()
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .cookie(session_cookie(token, expires_in))
+        .cookie(csrf_cookie(csrf_token.clone(), expires_in))
+        .json(CookieSessionDTO { csrf_token, expires_in }))
+}
+
+/// Troca a senha com as credenciais atuais; é o caminho para entrar depois que um administrador
+/// exigiu a troca. As sessões abertas são revogadas.
+pub async fn change_password_handler(
+    req: HttpRequest,
+    user_service: web::Data<dyn UserService>,
+    post_data: web::Json<ChangePasswordDTO>,
+) -> Result<HttpResponse, ApiError> {
+    let (login_user, new_password) = post_data.into_inner().into_parts();
+    user_service
+        .change_password(login_user, new_password, session_client(&req, None))
         .await?;
-    Ok(web::Json(token))
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Forward auth para proxies reversos, como o `forwardAuth` do Traefik ou o `auth_request` do
+/// nginx: valida o token do header `Authorization` ou do cookie de sessão e responde com o
+/// usuário em headers `X-Auth-*`.
+///
+/// O proxy precisa repassar o método original em `X-Forwarded-Method`; sem ele, a requisição é
+/// tratada como uma que altera estado e o cookie de sessão exige o token CSRF. A URI original,
+/// com a qual o `htu` das provas DPoP é conferido, vem de `X-Forwarded-Proto`,
+/// `X-Forwarded-Host` e `X-Forwarded-Uri`. Esses headers só valem quando o proxy está entre os
+/// `TRUSTED_PROXIES`.
+pub async fn forward_auth_handler(req: HttpRequest) -> Result<HttpResponse, ApiError> {
+    let origin = public_origin(&req);
+    if !origin.trusts(&req) {
+        // sem um proxy confiável, os headers com a requisição original não valem
+        let claim = authenticate_as(&req, None, Method::POST, request_uri(&req)).await?;
+        return Ok(forward_auth_response(claim));
+    }
+    let method = req
+        .headers()
+        .get(X_FORWARDED_METHOD)
+        .and_then(|value| Method::from_bytes(value.as_bytes()).ok())
+        .unwrap_or(Method::POST);
+    let uri = forwarded_uri(&req, &origin);
+    let claim = authenticate_as(&req, None, method, uri).await?;
+    Ok(forward_auth_response(claim))
+}
+
+fn forward_auth_response(claim: Claim) -> HttpResponse {
+    let mut response = HttpResponse::Ok();
+    response.insert_header((X_AUTH_SUBJECT, claim.sub));
+    if let Some(tenant) = claim.tenant {
+        response.insert_header((X_AUTH_TENANT, tenant.to_string()));
+    }
+    if !claim.roles.is_empty() {
+        response.insert_header((X_AUTH_ROLES, claim.roles.join(",")));
+    }
+    response.finish()
 }
 
+/// URI da requisição original do forward auth, sem query, pelos headers de um proxy confiável.
+/// Sem os headers, vale o que a própria requisição tiver, e uma prova DPoP feita para a URI
+/// original não confere. O esquema e o host da `base_url` da `origin` prevalecem sobre os do proxy.
+fn forwarded_uri(req: &HttpRequest, origin: &PublicOrigin) -> String {
+    let header = |name: HeaderName| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let info = req.connection_info();
+    let scheme = header(X_FORWARDED_PROTO).unwrap_or_else(|| info.scheme().to_string());
+    let host = header(X_FORWARDED_HOST).unwrap_or_else(|| info.host().to_string());
+    let uri = header(X_FORWARDED_URI).unwrap_or_else(|| req.path().to_string());
+    let path = uri.split(['?', '#']).next().unwrap_or_default();
+    origin.uri(req, Some((scheme, host)), path)
+}
+
+/// Dispositivo do login, pelo header `User-Agent` e pelo IP do cliente.
+pub fn session_client(req: &HttpRequest, device_name: Option<String>) -> SessionClient {
+    SessionClient {
+        user_agent: req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        ip_address: req.connection_info().realip_remote_addr().map(str::to_string),
+        device_name: device_name.filter(|name| !name.trim().is_empty()),
+        csrf_token_hash: None,
+    }
+}
+
+/// Valida um token para outro serviço. Um token vinculado (`cnf.jkt`) só é aceito com uma
+/// `dpop_proof` da mesma chave, acompanhada de `htm` e `htu`, a menos que quem chamou declare
+/// `caller_verifies_dpop` e confira a prova por conta própria.
 pub async fn validate_token_handler(
     user_service: web::Data<dyn UserService>,
+    dpop_service: web::Data<dyn DpopService>,
     post_data: web::Json<TokenDTO>,
 ) -> Result<web::Json<Claim>, ApiError> {
-    let token = user_service.validate_token(post_data.token.clone()).await?;
-    Ok(web::Json(token))
+    let post_data = post_data.into_inner();
+    let claim = user_service.validate_token(post_data.token.clone()).await?;
+    if let Some(cnf) = &claim.cnf {
+        let Some(proof) = &post_data.dpop_proof else {
+            if post_data.caller_verifies_dpop {
+                return Ok(web::Json(claim));
+            }
+            return Err(CommonError {
+                message: "Token is bound to a DPoP key and requires a DPoP proof".to_string(),
+                code: 401,
+            }
+            .into());
+        };
+        let (Some(htm), Some(htu)) = (&post_data.htm, &post_data.htu) else {
+            return Err(CommonError {
+                message: "htm and htu are required with dpop_proof".to_string(),
+                code: 400,
+            }
+            .into());
+        };
+        let jkt = dpop_service
+            .verify_proof(proof, htm, htu, Some(&post_data.token))
+            .await?;
+        if jkt != cnf.jkt {
+            return Err(CommonError {
+                message: "DPoP proof was made with a different key".to_string(),
+                code: 401,
+            }
+            .into());
+        }
+    }
+    Ok(web::Json(claim))
 }
@@ -0,0 +1,65 @@
+use actix_web::http::header;
+use actix_web::{web, HttpResponse, Result};
+
+use crate::api::dto::webhook::{
+    CreateWebhookDTO, CreatedWebhookDTO, ReplayDTO, WebhookDTO, WebhookDeliveryDTO, WebhookDeliveryQueryDTO,
+};
+use crate::api::extractors::AuthenticatedUser;
+use crate::domain::error::ApiError;
+use crate::domain::repositories::repository::ResultPaging;
+use crate::domain::services::webhook::WebhookService;
+
+pub async fn create_webhook_handler(
+    webhook_service: web::Data<dyn WebhookService>,
+    user: AuthenticatedUser,
+    post_data: web::Json<CreateWebhookDTO>,
+) -> Result<HttpResponse, ApiError> {
+    let webhook = webhook_service
+        .create(user.tenant()?, post_data.into_inner().into())
+        .await?;
+    Ok(HttpResponse::Created()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(CreatedWebhookDTO::from(webhook)))
+}
+
+pub async fn list_webhooks_handler(
+    webhook_service: web::Data<dyn WebhookService>,
+    user: AuthenticatedUser,
+) -> Result<web::Json<Vec<WebhookDTO>>, ApiError> {
+    let webhooks = webhook_service.list(user.tenant()?).await?;
+    Ok(web::Json(webhooks.into_iter().map(WebhookDTO::from).collect()))
+}
+
+pub async fn delete_webhook_handler(
+    webhook_service: web::Data<dyn WebhookService>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    webhook_service.delete(user.tenant()?, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Entregas dos webhooks da organização do token, das mais recentes para as mais antigas.
+pub async fn list_webhook_deliveries_handler(
+    webhook_service: web::Data<dyn WebhookService>,
+    user: AuthenticatedUser,
+    query: web::Query<WebhookDeliveryQueryDTO>,
+) -> Result<web::Json<ResultPaging<WebhookDeliveryDTO>>, ApiError> {
+    let (filter, params) = query.into_inner().into_parts();
+    let page = webhook_service
+        .list_deliveries(user.tenant()?, &filter, &params)
+        .await?;
+    Ok(web::Json(ResultPaging {
+        total: page.total,
+        items: page.items.into_iter().map(WebhookDeliveryDTO::from).collect(),
+    }))
+}
+
+pub async fn replay_outbox_event_handler(
+    webhook_service: web::Data<dyn WebhookService>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<HttpResponse, ApiError> {
+    let queued = webhook_service.replay(user.tenant()?, path.into_inner()).await?;
+    Ok(HttpResponse::Accepted().json(ReplayDTO { queued }))
+}
@@ -0,0 +1,89 @@
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::http::header::HeaderName;
+use actix_web::http::Method;
+use actix_web::HttpRequest;
+use ring::constant_time::verify_slices_are_equal;
+
+use crate::services::secret::{generate_secret, hash_secret};
+
+/// Cookie com o token de acesso do login em modo cookie. O prefixo `__Host-` obriga `Secure`,
+/// `Path=/` e nenhum `Domain`, para que subdomínios não possam sobrescrevê-lo.
+pub const SESSION_COOKIE: &str = "__Host-session";
+/// Cookie legível pelo JavaScript com o token CSRF do login em modo cookie.
+pub const CSRF_COOKIE: &str = "__Host-csrf";
+/// Header em que o token CSRF acompanha as requisições que alteram estado.
+pub const CSRF_HEADER: HeaderName = HeaderName::from_static("x-csrf-token");
+/// Cookie com o token CSRF dos formulários de `/oauth/authorize` e `/oauth/device`.
+pub const FORM_CSRF_COOKIE: &str = "__Host-form_csrf";
+/// Campo oculto dos formulários que repete o valor de `FORM_CSRF_COOKIE`.
+pub const FORM_CSRF_FIELD: &str = "csrf_token";
+
+/// Métodos que não alteram estado e, por isso, dispensam o token CSRF.
+pub fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Confere o token CSRF enviado com o hash guardado na claim `csrf`.
+pub fn verify_csrf_token(token: Option<&str>, token_hash: &str) -> bool {
+    token.is_some_and(|token| {
+        verify_slices_are_equal(hash_secret(token).as_bytes(), token_hash.as_bytes()).is_ok()
+    })
+}
+
+fn host_cookie(name: &'static str, value: String, http_only: bool, same_site: SameSite) -> Cookie<'static> {
+    Cookie::build(name, value)
+        .path("/")
+        .secure(true)
+        .http_only(http_only)
+        .same_site(same_site)
+        .finish()
+}
+
+/// Cookie de sessão com o token de acesso, válido pelo mesmo tempo que o token.
+pub fn session_cookie(token: String, max_age_seconds: i64) -> Cookie<'static> {
+    let mut cookie = host_cookie(SESSION_COOKIE, token, true, SameSite::Lax);
+    cookie.set_max_age(Duration::seconds(max_age_seconds));
+    cookie
+}
+
+/// Cookie com o token CSRF, que o JavaScript da página lê e devolve em `CSRF_HEADER`.
+pub fn csrf_cookie(token: String, max_age_seconds: i64) -> Cookie<'static> {
+    let mut cookie = host_cookie(CSRF_COOKIE, token, false, SameSite::Lax);
+    cookie.set_max_age(Duration::seconds(max_age_seconds));
+    cookie
+}
+
+/// Cookies que removem a sessão do navegador no logout.
+pub fn removal_cookies() -> [Cookie<'static>; 2] {
+    let mut session = host_cookie(SESSION_COOKIE, String::new(), true, SameSite::Lax);
+    session.make_removal();
+    let mut csrf = host_cookie(CSRF_COOKIE, String::new(), false, SameSite::Lax);
+    csrf.make_removal();
+    [session, csrf]
+}
+
+/// Token CSRF de um formulário: reaproveita o do cookie, para que duas abas abertas ao mesmo
+/// tempo continuem válidas, ou gera um novo.
+pub fn form_csrf_token(req: &HttpRequest) -> String {
+    req.cookie(FORM_CSRF_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| generate_secret(16))
+}
+
+/// Cookie do token CSRF dos formulários; só o servidor o lê.
+pub fn form_csrf_cookie(token: &str) -> Cookie<'static> {
+    host_cookie(FORM_CSRF_COOKIE, token.to_string(), true, SameSite::Strict)
+}
+
+/// Double submit: o campo do formulário precisa ser igual ao cookie, que um site de terceiros
+/// não consegue ler nem definir.
+pub fn verify_form_csrf(req: &HttpRequest, submitted: Option<&str>) -> bool {
+    match (req.cookie(FORM_CSRF_COOKIE), submitted) {
+        (Some(cookie), Some(submitted)) if !submitted.is_empty() => {
+            verify_slices_are_equal(cookie.value().as_bytes(), submitted.as_bytes()).is_ok()
+        }
+        _ => false,
+    }
+}
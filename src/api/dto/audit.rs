@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::audit::{AuditAction, AuditEvent, AuditEventFilter, AuditOutcome};
+use crate::domain::repositories::repository::QueryParamsImpl;
+
+/// Filtros e página de `GET /admin/audit_events`; `since` e `until` vão em RFC 3339.
+#[derive(Debug, Deserialize)]
+pub struct AuditEventQueryDTO {
+    pub action: Option<AuditAction>,
+    pub outcome: Option<AuditOutcome>,
+    pub actor_id: Option<String>,
+    pub target_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl AuditEventQueryDTO {
+    pub fn into_parts(self) -> (AuditEventFilter, QueryParamsImpl) {
+        (
+            AuditEventFilter {
+                action: self.action,
+                outcome: self.outcome,
+                actor_id: self.actor_id,
+                target_id: self.target_id,
+                since: self.since,
+                until: self.until,
+            },
+            QueryParamsImpl {
+                limit: self.limit,
+                offset: self.offset,
+            },
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditEventDTO {
+    pub id: i64,
+    pub action: AuditAction,
+    pub outcome: AuditOutcome,
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for AuditEventDTO {
+    fn from(event: AuditEvent) -> Self {
+        AuditEventDTO {
+            id: event.id,
+            action: event.action,
+            outcome: event.outcome,
+            actor_type: event.actor_type,
+            actor_id: event.actor_id,
+            target_type: event.target_type,
+            target_id: event.target_id,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            details: event.details,
+            created_at: event.created_at,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::client_registration::{ClientInformation, ClientMetadata, InitialAccessToken};
+
+/// Corpo de `POST /oauth/register` e `PUT /oauth/register/{client_id}` (RFC 7591, seção 2).
+#[derive(Deserialize)]
+pub struct ClientMetadataDTO {
+    /// Só no `PUT`; se vier, precisa ser o mesmo do caminho (RFC 7592, seção 2.2).
+    pub client_id: Option<String>,
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+    pub client_name: Option<String>,
+    pub token_endpoint_auth_method: Option<String>,
+    #[serde(default)]
+    pub grant_types: Vec<String>,
+    #[serde(default)]
+    pub response_types: Vec<String>,
+    pub scope: Option<String>,
+    #[serde(default)]
+    pub require_pushed_authorization_requests: bool,
+}
+
+impl From<ClientMetadataDTO> for ClientMetadata {
+    fn from(dto: ClientMetadataDTO) -> Self {
+        ClientMetadata {
+            redirect_uris: dto.redirect_uris,
+            client_name: dto.client_name,
+            token_endpoint_auth_method: dto.token_endpoint_auth_method,
+            grant_types: dto.grant_types,
+            response_types: dto.response_types,
+            scope: dto.scope,
+            require_pushed_authorization_requests: dto.require_pushed_authorization_requests,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientInformationDTO {
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub client_id_issued_at: i64,
+    /// Sempre 0 para clientes confidenciais: o segredo não expira, só é rotacionado.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret_expires_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registration_access_token: Option<String>,
+    pub registration_client_uri: String,
+    pub redirect_uris: Vec<String>,
+    pub client_name: Option<String>,
+    pub token_endpoint_auth_method: Option<String>,
+    pub grant_types: Vec<String>,
+    pub response_types: Vec<String>,
+    pub scope: Option<String>,
+    pub require_pushed_authorization_requests: bool,
+}
+
+impl ClientInformationDTO {
+    pub fn new(information: ClientInformation, registration_client_uri: String) -> Self {
+        let metadata = ClientMetadata::from_client(&information.client);
+        ClientInformationDTO {
+            client_secret_expires_at: information.client.client_secret_hash.as_ref().map(|_| 0),
+            client_id: information.client.client_id,
+            client_secret: information.client_secret,
+            client_id_issued_at: information.client.created_at.timestamp(),
+            registration_access_token: information.registration_access_token,
+            registration_client_uri,
+            redirect_uris: metadata.redirect_uris,
+            client_name: metadata.client_name,
+            token_endpoint_auth_method: metadata.token_endpoint_auth_method,
+            grant_types: metadata.grant_types,
+            response_types: metadata.response_types,
+            scope: metadata.scope,
+            require_pushed_authorization_requests: metadata.require_pushed_authorization_requests,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InitialAccessTokenDTO {
+    pub initial_access_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<InitialAccessToken> for InitialAccessTokenDTO {
+    fn from(token: InitialAccessToken) -> Self {
+        InitialAccessTokenDTO {
+            initial_access_token: token.token,
+            expires_at: token.expires_at,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::group::{CreateGroup, Group, PermissionGrant};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateGroupDTO {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<CreateGroupDTO> for CreateGroup {
+    fn from(dto: CreateGroupDTO) -> Self {
+        CreateGroup {
+            name: dto.name,
+            description: dto.description,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupDTO {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Group> for GroupDTO {
+    fn from(group: Group) -> Self {
+        GroupDTO {
+            id: group.id,
+            name: group.name,
+            description: group.description,
+            created_at: group.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PermissionGrantDTO {
+    pub permission: String,
+    pub role: String,
+    pub groups: Vec<String>,
+}
+
+impl From<PermissionGrant> for PermissionGrantDTO {
+    fn from(grant: PermissionGrant) -> Self {
+        PermissionGrantDTO {
+            permission: grant.permission,
+            role: grant.role,
+            groups: grant.groups,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PermissionExplanationDTO {
+    pub user_id: i32,
+    pub permission: String,
+    pub granted: bool,
+    pub grants: Vec<PermissionGrantDTO>,
+}
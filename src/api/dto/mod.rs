@@ -1 +1,13 @@
+pub mod audit;
+pub mod client_registration;
+pub mod group;
+pub mod oauth;
+pub mod organization;
+pub mod personal_access_token;
+pub mod role;
+pub mod service_account;
+pub mod service_context;
+pub mod session;
 pub mod user;
+pub mod user_admin;
+pub mod webhook;
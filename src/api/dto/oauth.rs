@@ -0,0 +1,269 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::oauth::{
+    AuthorizationRequest, ClientType, Consent, CreateOAuthClient, DeviceAuthorizationRequest, OAuthClient,
+    PushedAuthorization, PushedAuthorizationRequest, RegisteredOAuthClient, RevocationRequest, TokenRequest,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateOAuthClientDTO {
+    pub name: String,
+    pub client_type: ClientType,
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+    #[serde(default)]
+    pub allowed_scopes: Vec<String>,
+    #[serde(default)]
+    pub allowed_audiences: Vec<String>,
+    /// Sem `grant_types`, o cliente recebe todos os grants suportados pelo tipo.
+    pub grant_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub require_pushed_authorization_requests: bool,
+}
+
+impl From<CreateOAuthClientDTO> for CreateOAuthClient {
+    fn from(dto: CreateOAuthClientDTO) -> Self {
+        CreateOAuthClient {
+            name: dto.name,
+            client_type: dto.client_type,
+            redirect_uris: dto.redirect_uris,
+            allowed_scopes: dto.allowed_scopes,
+            allowed_audiences: dto.allowed_audiences,
+            grant_types: dto
+                .grant_types
+                .unwrap_or_else(|| dto.client_type.default_grant_types()),
+            require_pushed_authorization_requests: dto.require_pushed_authorization_requests,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OAuthClientDTO {
+    pub id: i32,
+    pub client_id: String,
+    pub name: String,
+    pub client_type: ClientType,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub allowed_audiences: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub require_pushed_authorization_requests: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OAuthClient> for OAuthClientDTO {
+    fn from(client: OAuthClient) -> Self {
+        OAuthClientDTO {
+            id: client.id,
+            client_id: client.client_id,
+            name: client.name,
+            client_type: client.client_type,
+            redirect_uris: client.redirect_uris,
+            allowed_scopes: client.allowed_scopes,
+            allowed_audiences: client.allowed_audiences,
+            grant_types: client.grant_types,
+            require_pushed_authorization_requests: client.require_pushed_authorization_requests,
+            created_at: client.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegisteredOAuthClientDTO {
+    #[serde(flatten)]
+    pub client: OAuthClientDTO,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+}
+
+impl From<RegisteredOAuthClient> for RegisteredOAuthClientDTO {
+    fn from(registered: RegisteredOAuthClient) -> Self {
+        RegisteredOAuthClientDTO {
+            client: registered.client.into(),
+            client_secret: registered.client_secret,
+        }
+    }
+}
+
+/// Aplicativo autorizado pelo usuário, listado em `GET /auth/consents`.
+#[derive(Debug, Serialize)]
+pub struct ConsentDTO {
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    pub granted_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<(Consent, OAuthClient)> for ConsentDTO {
+    fn from((consent, client): (Consent, OAuthClient)) -> Self {
+        ConsentDTO {
+            client_id: client.client_id,
+            client_name: client.name,
+            scopes: consent.scopes,
+            granted_at: consent.created_at,
+            updated_at: consent.updated_at,
+        }
+    }
+}
+
+/// Formulários de login e de consentimento enviados para `POST /oauth/authorize`.
+#[derive(Deserialize)]
+pub struct AuthorizeFormDTO {
+    #[serde(flatten)]
+    pub request: AuthorizationRequest,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    /// Código pendente enviado pela tela de consentimento, depois do login.
+    pub consent_code: Option<String>,
+    pub decision: String,
+    /// Token CSRF do formulário, igual ao do cookie `__Host-form_csrf`.
+    #[serde(default)]
+    pub csrf_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenRequestDTO {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub code_verifier: Option<String>,
+    pub device_code: Option<String>,
+    pub refresh_token: Option<String>,
+    pub subject_token: Option<String>,
+    pub subject_token_type: Option<String>,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    pub audience: Option<String>,
+    pub requested_token_type: Option<String>,
+}
+
+impl From<TokenRequestDTO> for TokenRequest {
+    fn from(dto: TokenRequestDTO) -> Self {
+        TokenRequest {
+            grant_type: dto.grant_type,
+            code: dto.code,
+            redirect_uri: dto.redirect_uri,
+            scope: dto.scope,
+            client_id: dto.client_id,
+            client_secret: dto.client_secret,
+            code_verifier: dto.code_verifier,
+            device_code: dto.device_code,
+            refresh_token: dto.refresh_token,
+            subject_token: dto.subject_token,
+            subject_token_type: dto.subject_token_type,
+            actor_token: dto.actor_token,
+            actor_token_type: dto.actor_token_type,
+            audience: dto.audience,
+            requested_token_type: dto.requested_token_type,
+            dpop_jkt: None,
+        }
+    }
+}
+
+/// Corpo de `POST /oauth/par`: os parâmetros de `/oauth/authorize` e, com client_secret_post,
+/// o segredo do cliente.
+#[derive(Deserialize)]
+pub struct PushedAuthorizationRequestDTO {
+    #[serde(flatten)]
+    pub request: AuthorizationRequest,
+    pub client_secret: Option<String>,
+}
+
+impl From<PushedAuthorizationRequestDTO> for PushedAuthorizationRequest {
+    fn from(dto: PushedAuthorizationRequestDTO) -> Self {
+        PushedAuthorizationRequest {
+            client_id: Some(dto.request.client_id.clone()),
+            client_secret: dto.client_secret,
+            request: dto.request,
+        }
+    }
+}
+
+/// Resposta de `/oauth/par` (RFC 9126, seção 2.2).
+#[derive(Debug, Serialize)]
+pub struct PushedAuthorizationResponseDTO {
+    pub request_uri: String,
+    pub expires_in: i64,
+}
+
+impl From<PushedAuthorization> for PushedAuthorizationResponseDTO {
+    fn from(pushed: PushedAuthorization) -> Self {
+        PushedAuthorizationResponseDTO {
+            request_uri: pushed.request_uri,
+            expires_in: pushed.expires_in,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DeviceAuthorizationRequestDTO {
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+impl From<DeviceAuthorizationRequestDTO> for DeviceAuthorizationRequest {
+    fn from(dto: DeviceAuthorizationRequestDTO) -> Self {
+        DeviceAuthorizationRequest {
+            scope: dto.scope,
+            client_id: dto.client_id,
+            client_secret: dto.client_secret,
+        }
+    }
+}
+
+/// Resposta de `/oauth/device_authorization` (RFC 8628, seção 3.2).
+#[derive(Debug, Serialize)]
+pub struct DeviceAuthorizationResponseDTO {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: i64,
+    pub interval: i64,
+}
+
+#[derive(Deserialize)]
+pub struct DeviceVerificationQueryDTO {
+    pub user_code: Option<String>,
+}
+
+/// Formulário de login/aprovação enviado para `POST /oauth/device`.
+#[derive(Deserialize)]
+pub struct DeviceVerificationFormDTO {
+    pub user_code: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    pub decision: String,
+    /// Token CSRF do formulário, igual ao do cookie `__Host-form_csrf`.
+    #[serde(default)]
+    pub csrf_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RevocationRequestDTO {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+impl From<RevocationRequestDTO> for RevocationRequest {
+    fn from(dto: RevocationRequestDTO) -> Self {
+        RevocationRequest {
+            token: dto.token,
+            token_type_hint: dto.token_type_hint,
+            client_id: dto.client_id,
+            client_secret: dto.client_secret,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::dto::user::CreateUserDTO;
+use crate::domain::models::organization::Organization;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateOrganizationDTO {
+    pub slug: String,
+    pub name: String,
+    pub owner: CreateUserDTO,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationDTO {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Organization> for OrganizationDTO {
+    fn from(organization: Organization) -> Self {
+        OrganizationDTO {
+            id: organization.id,
+            slug: organization.slug,
+            name: organization.name,
+            created_at: organization.created_at,
+        }
+    }
+}
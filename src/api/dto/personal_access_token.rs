@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::personal_access_token::{
+    CreatePersonalAccessToken, IssuedPersonalAccessToken, PersonalAccessToken,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreatePersonalAccessTokenDTO {
+    pub name: String,
+    /// Permissões separadas por espaço, como o `scope` do OAuth.
+    pub scope: String,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<CreatePersonalAccessTokenDTO> for CreatePersonalAccessToken {
+    fn from(dto: CreatePersonalAccessTokenDTO) -> Self {
+        CreatePersonalAccessToken {
+            name: dto.name,
+            scope: dto.scope,
+            expires_at: dto.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalAccessTokenDTO {
+    pub id: i32,
+    pub name: String,
+    pub token_hint: String,
+    pub scope: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessToken> for PersonalAccessTokenDTO {
+    fn from(token: PersonalAccessToken) -> Self {
+        PersonalAccessTokenDTO {
+            id: token.id,
+            name: token.name,
+            token_hint: token.token_hint,
+            scope: token.scope,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            created_at: token.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssuedPersonalAccessTokenDTO {
+    #[serde(flatten)]
+    pub personal_access_token: PersonalAccessTokenDTO,
+    pub token: String,
+}
+
+impl From<IssuedPersonalAccessToken> for IssuedPersonalAccessTokenDTO {
+    fn from(issued: IssuedPersonalAccessToken) -> Self {
+        IssuedPersonalAccessTokenDTO {
+            personal_access_token: issued.personal_access_token.into(),
+            token: issued.token,
+        }
+    }
+}
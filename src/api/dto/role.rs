@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::role::{CreatePermission, CreateRole, Permission, Role, RoleSessionPolicy};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateRoleDTO {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<CreateRoleDTO> for CreateRole {
+    fn from(dto: CreateRoleDTO) -> Self {
+        CreateRole {
+            name: dto.name,
+            description: dto.description,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoleDTO {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub session_policy: RoleSessionPolicyDTO,
+}
+
+impl From<Role> for RoleDTO {
+    fn from(role: Role) -> Self {
+        RoleDTO {
+            id: role.id,
+            name: role.name,
+            description: role.description,
+            created_at: role.created_at,
+            session_policy: role.session_policy.into(),
+        }
+    }
+}
+
+/// Limites de sessão do papel; campos ausentes seguem a política do serviço.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RoleSessionPolicyDTO {
+    #[serde(default)]
+    pub idle_timeout_seconds: Option<i32>,
+    #[serde(default)]
+    pub max_lifetime_seconds: Option<i32>,
+    #[serde(default)]
+    pub max_concurrent_sessions: Option<i32>,
+}
+
+impl From<RoleSessionPolicyDTO> for RoleSessionPolicy {
+    fn from(dto: RoleSessionPolicyDTO) -> Self {
+        RoleSessionPolicy {
+            idle_timeout_seconds: dto.idle_timeout_seconds,
+            max_lifetime_seconds: dto.max_lifetime_seconds,
+            max_concurrent_sessions: dto.max_concurrent_sessions,
+        }
+    }
+}
+
+impl From<RoleSessionPolicy> for RoleSessionPolicyDTO {
+    fn from(policy: RoleSessionPolicy) -> Self {
+        RoleSessionPolicyDTO {
+            idle_timeout_seconds: policy.idle_timeout_seconds,
+            max_lifetime_seconds: policy.max_lifetime_seconds,
+            max_concurrent_sessions: policy.max_concurrent_sessions,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreatePermissionDTO {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<CreatePermissionDTO> for CreatePermission {
+    fn from(dto: CreatePermissionDTO) -> Self {
+        CreatePermission {
+            name: dto.name,
+            description: dto.description,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PermissionDTO {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<Permission> for PermissionDTO {
+    fn from(permission: Permission) -> Self {
+        PermissionDTO {
+            id: permission.id,
+            name: permission.name,
+            description: permission.description,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::user::{CreateServiceAccount, PrincipalType, User};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateServiceAccountDTO {
+    pub name: String,
+    /// Sem `owner_user_id`, a conta pertence à organização.
+    #[serde(default)]
+    pub owner_user_id: Option<i32>,
+}
+
+impl From<CreateServiceAccountDTO> for CreateServiceAccount {
+    fn from(dto: CreateServiceAccountDTO) -> Self {
+        CreateServiceAccount {
+            name: dto.name,
+            owner_user_id: dto.owner_user_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceAccountDTO {
+    pub id: i32,
+    pub name: String,
+    pub principal_type: PrincipalType,
+    pub owner_user_id: Option<i32>,
+    pub organization_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for ServiceAccountDTO {
+    fn from(user: User) -> Self {
+        ServiceAccountDTO {
+            id: user.id,
+            name: user.username,
+            principal_type: user.principal_type,
+            owner_user_id: user.owner_user_id,
+            organization_id: user.organization_id,
+            created_at: user.created_at,
+        }
+    }
+}
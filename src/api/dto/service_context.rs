@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::service_context::ServiceContext;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ServiceContextDTO {
+    pub maintenance: bool,
+}
+
+impl From<ServiceContext> for ServiceContextDTO {
+    fn from(service_context: ServiceContext) -> Self {
+        ServiceContextDTO {
+            maintenance: service_context.maintenance,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::domain::models::session::Session;
+
+#[derive(Debug, Serialize)]
+pub struct SessionDTO {
+    pub id: String,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Quando a sessão termina se não for usada até lá.
+    pub idle_expires_at: DateTime<Utc>,
+    /// Se é a sessão do token usado na requisição.
+    pub current: bool,
+}
+
+impl SessionDTO {
+    pub fn new(session: Session, current_sid: Option<&str>) -> Self {
+        SessionDTO {
+            current: current_sid == Some(session.id.as_str()),
+            idle_expires_at: session.idle_expires_at(),
+            id: session.id,
+            device_name: session.device_name,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
@@ -1,31 +1,35 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::user::{CreateUser, LoginUser, User};
+use crate::domain::models::user::{CreateUser, LoginUser, PrincipalType, User};
 
 #[derive(Deserialize, Serialize)]
 pub struct CreateUserDTO {
     pub username: String,
     pub password: String,
     pub email: String,
+    #[serde(default)]
+    pub organization: Option<String>,
 }
 
-impl Into<CreateUser> for CreateUserDTO {
-    fn into(self) -> CreateUser {
+impl From<CreateUserDTO> for CreateUser {
+    fn from(dto: CreateUserDTO) -> Self {
         CreateUser {
-            username: self.username,
-            password: self.password,
-            email: self.email,
+            username: dto.username,
+            password: dto.password,
+            email: dto.email,
+            organization: dto.organization,
         }
     }
 }
 
-impl Into<CreateUserDTO> for CreateUser {
-    fn into(self) -> CreateUserDTO {
+impl From<CreateUser> for CreateUserDTO {
+    fn from(user: CreateUser) -> Self {
         CreateUserDTO {
-            username: self.username,
-            password: self.password,
-            email: self.email,
+            username: user.username,
+            password: user.password,
+            email: user.email,
+            organization: user.organization,
         }
     }
 }
@@ -33,49 +37,121 @@ impl Into<CreateUserDTO> for CreateUser {
 #[derive(Deserialize, Serialize)]
 pub struct TokenDTO {
     pub token: String,
+    /// Prova DPoP que acompanhou o token, com o método (`htm`) e a URI (`htu`) da requisição
+    /// em que foi apresentada.
+    #[serde(default)]
+    pub dpop_proof: Option<String>,
+    #[serde(default)]
+    pub htm: Option<String>,
+    #[serde(default)]
+    pub htu: Option<String>,
+    /// Quem chama confere a prova DPoP por conta própria; um token vinculado sem `dpop_proof`
+    /// é então devolvido com `cnf` em vez de recusado.
+    #[serde(default)]
+    pub caller_verifies_dpop: bool,
 }
 
 #[derive(Deserialize, Serialize)]
 pub struct LoginUserDTO {
     pub username: String,
     pub password: String,
+    #[serde(default)]
+    pub organization: Option<String>,
+    /// Nome do dispositivo, exibido em `/auth/sessions`.
+    #[serde(default)]
+    pub device_name: Option<String>,
+    #[serde(default)]
+    pub mode: LoginMode,
 }
 
-impl Into<LoginUser> for LoginUserDTO {
-    fn into(self) -> LoginUser {
+/// Como o login entrega o token: no corpo da resposta ou num cookie de sessão, para apps
+/// renderizadas no servidor que não querem lidar com o token no JavaScript.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoginMode {
+    #[default]
+    Token,
+    Cookie,
+}
+
+/// Resposta do login em modo cookie. O `csrf_token` acompanha, no header `X-CSRF-Token`, as
+/// requisições que alteram estado.
+#[derive(Deserialize, Serialize)]
+pub struct CookieSessionDTO {
+    pub csrf_token: String,
+    pub expires_in: i64,
+}
+
+impl From<LoginUserDTO> for LoginUser {
+    fn from(dto: LoginUserDTO) -> Self {
         LoginUser {
-            username: self.username,
-            password: self.password,
+            username: dto.username,
+            password: dto.password,
+            organization: dto.organization,
         }
     }
 }
 
-impl Into<LoginUserDTO> for LoginUser {
-    fn into(self) -> LoginUserDTO {
+impl From<LoginUser> for LoginUserDTO {
+    fn from(user: LoginUser) -> Self {
         LoginUserDTO {
-            username: self.username,
-            password: self.password,
+            username: user.username,
+            password: user.password,
+            organization: user.organization,
+            device_name: None,
+            mode: LoginMode::Token,
         }
     }
 }
 
+/// Troca de senha em `POST /auth/password`: as credenciais atuais e a nova senha.
+#[derive(Deserialize, Serialize)]
+pub struct ChangePasswordDTO {
+    pub username: String,
+    pub password: String,
+    pub new_password: String,
+    #[serde(default)]
+    pub organization: Option<String>,
+}
+
+impl ChangePasswordDTO {
+    pub fn into_parts(self) -> (LoginUser, String) {
+        (
+            LoginUser {
+                username: self.username,
+                password: self.password,
+                organization: self.organization,
+            },
+            self.new_password,
+        )
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct UserDTO {
     pub id: i32,
     pub username: String,
-    pub password: String,
-    pub email: String,
+    pub password: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub organization_id: i32,
+    pub principal_type: PrincipalType,
+    pub owner_user_id: Option<i32>,
     pub created_at: DateTime<Utc>,
 }
 
-impl Into<UserDTO> for User {
-    fn into(self) -> UserDTO {
+impl From<User> for UserDTO {
+    fn from(user: User) -> Self {
         UserDTO {
-            id: self.id,
-            username: self.username,
-            password: self.password,
-            email: self.email,
-            created_at: self.created_at,
+            id: user.id,
+            username: user.username,
+            password: user.password,
+            email: user.email,
+            email_verified: user.email_verified,
+            organization_id: user.organization_id,
+            principal_type: user.principal_type,
+            owner_user_id: user.owner_user_id,
+            created_at: user.created_at,
         }
     }
 }
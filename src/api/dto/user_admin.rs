@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::user::{PrincipalType, SortDirection, User, UserFilter, UserSortField, UserStatus};
+use crate::domain::repositories::repository::QueryParamsImpl;
+
+/// Filtros, ordenação e página de `GET /admin/users`; `created_since` e `created_until` vão em
+/// RFC 3339. Sem `sort`, os mais recentes vêm primeiro.
+#[derive(Debug, Deserialize)]
+pub struct UserQueryDTO {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub created_since: Option<DateTime<Utc>>,
+    pub created_until: Option<DateTime<Utc>>,
+    pub status: Option<UserStatus>,
+    pub principal_type: Option<PrincipalType>,
+    #[serde(default)]
+    pub sort: UserSortField,
+    #[serde(default)]
+    pub direction: SortDirection,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl UserQueryDTO {
+    pub fn into_parts(self) -> (UserFilter, QueryParamsImpl) {
+        (
+            UserFilter {
+                username_prefix: self.username,
+                email_prefix: self.email,
+                created_since: self.created_since,
+                created_until: self.created_until,
+                status: self.status,
+                principal_type: self.principal_type,
+                sort: self.sort,
+                direction: self.direction,
+            },
+            QueryParamsImpl {
+                limit: self.limit,
+                offset: self.offset,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateUserDTO {
+    pub email: String,
+}
+
+/// Usuário como visto por um administrador, sem o hash da senha.
+#[derive(Debug, Serialize)]
+pub struct AdminUserDTO {
+    pub id: i32,
+    pub username: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub organization_id: i32,
+    pub principal_type: PrincipalType,
+    pub owner_user_id: Option<i32>,
+    pub status: UserStatus,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub failed_login_attempts: i32,
+    pub password_reset_required: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for AdminUserDTO {
+    fn from(user: User) -> Self {
+        AdminUserDTO {
+            id: user.id,
+            status: user.status(),
+            username: user.username,
+            email: user.email,
+            email_verified: user.email_verified,
+            organization_id: user.organization_id,
+            principal_type: user.principal_type,
+            owner_user_id: user.owner_user_id,
+            disabled_at: user.disabled_at,
+            locked_until: user.locked_until,
+            failed_login_attempts: user.failed_login_attempts,
+            password_reset_required: user.password_reset_required,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevokedSessionsDTO {
+    pub revoked: usize,
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::outbox::{
+    CreateWebhook, DeliveryStatus, OutboxEventType, Webhook, WebhookDelivery, WebhookDeliveryFilter,
+};
+use crate::domain::repositories::repository::QueryParamsImpl;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateWebhookDTO {
+    pub url: String,
+    /// Sem `event_types`, o webhook recebe todos os tipos de evento.
+    #[serde(default)]
+    pub event_types: Vec<OutboxEventType>,
+}
+
+impl From<CreateWebhookDTO> for CreateWebhook {
+    fn from(dto: CreateWebhookDTO) -> Self {
+        CreateWebhook {
+            url: dto.url,
+            event_types: dto.event_types,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDTO {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<OutboxEventType>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookDTO {
+    fn from(webhook: Webhook) -> Self {
+        WebhookDTO {
+            id: webhook.id,
+            url: webhook.url,
+            event_types: webhook.event_types,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// Resposta da criação, a única que mostra o segredo de assinatura.
+#[derive(Debug, Serialize)]
+pub struct CreatedWebhookDTO {
+    #[serde(flatten)]
+    pub webhook: WebhookDTO,
+    pub secret: String,
+}
+
+impl From<Webhook> for CreatedWebhookDTO {
+    fn from(webhook: Webhook) -> Self {
+        let secret = webhook.secret.clone();
+        CreatedWebhookDTO {
+            webhook: webhook.into(),
+            secret,
+        }
+    }
+}
+
+/// Filtros e página de `GET /admin/webhook_deliveries`.
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveryQueryDTO {
+    pub status: Option<DeliveryStatus>,
+    pub webhook_id: Option<i32>,
+    pub outbox_event_id: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl WebhookDeliveryQueryDTO {
+    pub fn into_parts(self) -> (WebhookDeliveryFilter, QueryParamsImpl) {
+        (
+            WebhookDeliveryFilter {
+                status: self.status,
+                webhook_id: self.webhook_id,
+                outbox_event_id: self.outbox_event_id,
+            },
+            QueryParamsImpl {
+                limit: self.limit,
+                offset: self.offset,
+            },
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryDTO {
+    pub id: i64,
+    pub webhook_id: i32,
+    pub outbox_event_id: i64,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDTO {
+    fn from(delivery: WebhookDelivery) -> Self {
+        WebhookDeliveryDTO {
+            id: delivery.id,
+            webhook_id: delivery.webhook_id,
+            outbox_event_id: delivery.outbox_event_id,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_error: delivery.last_error,
+            delivered_at: delivery.delivered_at,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayDTO {
+    /// Entregas colocadas de volta na fila.
+    pub queued: usize,
+}
@@ -0,0 +1,319 @@
+use std::env;
+use std::future::{ready, Future};
+use std::net::IpAddr;
+use std::ops::Deref;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::header::{HeaderName, AUTHORIZATION, HOST};
+use actix_web::http::Method;
+use actix_web::{web, FromRequest, HttpMessage, HttpRequest};
+
+use crate::api::csrf::{is_safe_method, verify_csrf_token, CSRF_HEADER, SESSION_COOKIE};
+use crate::domain::constants::{PUBLIC_BASE_URL, SERVICE_AUDIENCE, TRUSTED_PROXIES};
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::models::token::Claim;
+use crate::domain::services::dpop::DpopService;
+use crate::domain::services::token::TokenService;
+
+/// Header com a prova de posse da chave (RFC 9449, seção 4.1).
+pub const DPOP_HEADER: HeaderName = HeaderName::from_static("dpop");
+
+/// Audiência deste serviço, registrada em `app_data`.
+///
+/// Tokens com `aud` (emitidos pelo token exchange) só são aceitos quando a claim é igual a esta
+/// audiência; sem ela, são recusados. Tokens sem `aud` são aceitos em qualquer caso.
+#[derive(Clone, Debug, Default)]
+pub struct ServiceAudience(pub Option<String>);
+
+impl ServiceAudience {
+    pub fn new(audience: impl Into<String>) -> Self {
+        ServiceAudience(Some(audience.into()))
+    }
+
+    /// Lê a audiência de `SERVICE_AUDIENCE`; sem a variável, tokens com `aud` são recusados.
+    pub fn from_env() -> Self {
+        ServiceAudience(env::var(SERVICE_AUDIENCE).ok())
+    }
+}
+
+/// Origem pública do serviço, registrada em `app_data`, com a qual é montado o `htu` conferido
+/// nas provas DPoP.
+///
+/// Os headers `Forwarded` e `X-Forwarded-*` vêm de quem fez a requisição e só valem quando a
+/// conexão chega de um dos `trusted_proxies`; nas demais, valem o header `Host` e o esquema do
+/// próprio servidor. Com `base_url`, o esquema e o host vêm dela em qualquer caso.
+#[derive(Clone, Debug, Default)]
+pub struct PublicOrigin {
+    pub base_url: Option<String>,
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl PublicOrigin {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        PublicOrigin {
+            base_url: Some(base_url.into()),
+            trusted_proxies: Vec::new(),
+        }
+    }
+
+    /// Passa a aceitar os headers do proxy que conecta de `proxy`.
+    pub fn trust_proxy(mut self, proxy: IpAddr) -> Self {
+        self.trusted_proxies.push(proxy);
+        self
+    }
+
+    /// Lê `PUBLIC_BASE_URL` e `TRUSTED_PROXIES`; sem as variáveis, nenhum header de proxy vale.
+    pub fn from_env() -> Self {
+        let trusted_proxies = env::var(TRUSTED_PROXIES)
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|proxy| !proxy.is_empty())
+            .map(|proxy| proxy.parse().expect("TRUSTED_PROXIES must list IP addresses"))
+            .collect();
+        PublicOrigin {
+            base_url: env::var(PUBLIC_BASE_URL).ok().filter(|url| !url.is_empty()),
+            trusted_proxies,
+        }
+    }
+
+    /// Diz se a conexão vem de um proxy confiável.
+    pub fn trusts(&self, req: &HttpRequest) -> bool {
+        req.peer_addr()
+            .is_some_and(|peer| self.trusted_proxies.contains(&peer.ip()))
+    }
+
+    /// URI pública de `path`. `forwarded` traz o esquema e o host informados por um proxy
+    /// confiável, usados quando não há `base_url`.
+    pub fn uri(&self, req: &HttpRequest, forwarded: Option<(String, String)>, path: &str) -> String {
+        if let Some(base_url) = &self.base_url {
+            return format!("{}{}", base_url.trim_end_matches('/'), path);
+        }
+        let (scheme, host) = forwarded.unwrap_or_else(|| {
+            let config = req.app_config();
+            let scheme = if config.secure() { "https" } else { "http" };
+            let host = req
+                .headers()
+                .get(HOST)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or(config.host());
+            (scheme.to_string(), host.to_string())
+        });
+        format!("{}://{}{}", scheme, host, path)
+    }
+}
+
+/// `PublicOrigin` do app; sem ela, nenhum header de proxy vale.
+pub fn public_origin(req: &HttpRequest) -> PublicOrigin {
+    req.app_data::<PublicOrigin>().cloned().unwrap_or_default()
+}
+
+/// Usuário autenticado pelo header `Authorization: Bearer <token>` (ou `DPoP <token>`) ou, sem
+/// o header, pelo cookie de sessão do login em modo cookie.
+///
+/// A validação é feita pelo `TokenService` registrado em `app_data`, o que permite usar
+/// `TokenServiceImpl`, `JwksTokenService` ou `IntrospectionTokenService` conforme o app, e o
+/// `aud` é conferido com a `ServiceAudience` do app. Tokens vinculados a uma chave (`cnf.jkt`)
+/// exigem uma prova DPoP válida, conferida pelo `DpopService` do app. Tokens vindos do cookie
+/// exigem o token CSRF no header `X-CSRF-Token` nos métodos que alteram estado. Quando a rota está
+/// protegida por `RequireAuth`, reaproveita as claims já validadas.
+#[derive(Clone, Debug)]
+pub struct AuthenticatedUser {
+    pub claim: Claim,
+}
+
+impl AuthenticatedUser {
+    pub fn user_id(&self) -> Option<i32> {
+        self.claim.sub.parse().ok()
+    }
+
+    /// Usuário de um token de sessão, emitido pelo login; tokens emitidos a clientes OAuth e
+    /// personal access tokens não contam.
+    pub fn session_user_id(&self) -> Option<i32> {
+        match (&self.claim.client_id, self.claim.pat) {
+            (None, None) => self.user_id(),
+            _ => None,
+        }
+    }
+
+    /// Organização (tenant) do token. Tokens sem a claim `tenant` não acessam rotas por organização.
+    pub fn tenant(&self) -> Result<i32, ApiError> {
+        self.claim.tenant.ok_or_else(|| {
+            ApiError::from(CommonError {
+                message: "Token has no tenant".to_string(),
+                code: 403,
+            })
+        })
+    }
+}
+
+impl Deref for AuthenticatedUser {
+    type Target = Claim;
+
+    fn deref(&self) -> &Self::Target {
+        &self.claim
+    }
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = ApiError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        if let Some(claim) = req.extensions().get::<Claim>() {
+            let claim = claim.clone();
+            return Box::pin(ready(Ok(AuthenticatedUser { claim })));
+        }
+        let claim = authenticate(req, None);
+        Box::pin(async move {
+            Ok(AuthenticatedUser {
+                claim: claim.await.map_err(ApiError::from)?,
+            })
+        })
+    }
+}
+
+pub fn bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+}
+
+/// De onde veio o token da requisição.
+#[derive(Clone, Copy, PartialEq)]
+enum TokenSource {
+    Bearer,
+    Dpop,
+    Cookie,
+}
+
+/// Token do header `Authorization`, no esquema `Bearer` ou `DPoP`, ou, sem o header, do cookie
+/// de sessão.
+fn request_token(req: &HttpRequest) -> Option<(String, TokenSource)> {
+    if let Some(value) = req.headers().get(AUTHORIZATION) {
+        let value = value.to_str().ok()?;
+        if let Some(token) = value.strip_prefix("DPoP ") {
+            return Some((token.trim().to_string(), TokenSource::Dpop));
+        }
+        return value
+            .strip_prefix("Bearer ")
+            .map(|token| (token.trim().to_string(), TokenSource::Bearer));
+    }
+    req.cookie(SESSION_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|token| !token.is_empty())
+        .map(|token| (token, TokenSource::Cookie))
+}
+
+/// URI da requisição sem query, como o cliente a vê, para conferir o `htu` da prova DPoP. Os
+/// headers de proxy só contam vindos de um proxy confiável da `PublicOrigin`.
+pub fn request_uri(req: &HttpRequest) -> String {
+    let origin = public_origin(req);
+    let forwarded = origin.trusts(req).then(|| {
+        let info = req.connection_info();
+        (info.scheme().to_string(), info.host().to_string())
+    });
+    origin.uri(req, forwarded, req.path())
+}
+
+/// Valida o bearer token da requisição. `audience` substitui a `ServiceAudience` do app na
+/// conferência do `aud`.
+pub fn authenticate(
+    req: &HttpRequest,
+    audience: Option<String>,
+) -> impl Future<Output = Result<Claim, CommonError>> {
+    authenticate_as(req, audience, req.method().clone(), request_uri(req))
+}
+
+/// Como `authenticate`, mas considerando que a requisição usa `method` e tem a URI `uri`, com a
+/// qual o `htu` da prova DPoP é conferido; é o caso do forward auth, em que o proxy repassa o
+/// método e a URI da requisição original.
+pub fn authenticate_as(
+    req: &HttpRequest,
+    audience: Option<String>,
+    method: Method,
+    uri: String,
+) -> impl Future<Output = Result<Claim, CommonError>> {
+    let token = request_token(req);
+    let token_service = req.app_data::<web::Data<dyn TokenService>>().cloned();
+    let dpop_service = req.app_data::<web::Data<dyn DpopService>>().cloned();
+    let proof = req
+        .headers()
+        .get(DPOP_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let csrf_token = req
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let audience = audience.or_else(|| req.app_data::<ServiceAudience>().and_then(|a| a.0.clone()));
+    async move {
+        let (token, source) = token.ok_or(CommonError {
+            message: "Missing bearer token".to_string(),
+            code: 401,
+        })?;
+        let token_service = token_service.ok_or(CommonError {
+            message: "TokenService is not registered".to_string(),
+            code: 500,
+        })?;
+        let claim = token_service
+            .validate(token.clone())
+            .await
+            .map_err(|e| CommonError {
+                message: e.message,
+                code: 401,
+            })?;
+        if let Some(aud) = &claim.aud {
+            if audience.as_ref() != Some(aud) {
+                return Err(CommonError {
+                    message: "Token is intended for another audience".to_string(),
+                    code: 401,
+                });
+            }
+        }
+        if source == TokenSource::Cookie {
+            // só o login em modo cookie emite tokens com `csrf`; sem ela, o cookie foi forjado
+            // com um token de outra origem
+            let Some(csrf) = &claim.csrf else {
+                return Err(CommonError {
+                    message: "Token was not issued for a cookie session".to_string(),
+                    code: 401,
+                });
+            };
+            if !is_safe_method(&method) && !verify_csrf_token(csrf_token.as_deref(), csrf) {
+                return Err(CommonError {
+                    message: "Missing or invalid CSRF token".to_string(),
+                    code: 403,
+                });
+            }
+        }
+        if let Some(cnf) = &claim.cnf {
+            // um token vinculado apresentado como bearer é justamente o roubo que o DPoP impede
+            let (TokenSource::Dpop, Some(proof)) = (source, proof) else {
+                return Err(CommonError {
+                    message: "Token is bound to a DPoP key and requires a DPoP proof".to_string(),
+                    code: 401,
+                });
+            };
+            let dpop_service = dpop_service.ok_or(CommonError {
+                message: "DpopService is not registered".to_string(),
+                code: 500,
+            })?;
+            let jkt = dpop_service
+                .verify_proof(&proof, method.as_str(), &uri, Some(&token))
+                .await?;
+            if jkt != cnf.jkt {
+                return Err(CommonError {
+                    message: "DPoP proof was made with a different key".to_string(),
+                    code: 401,
+                });
+            }
+        }
+        Ok(claim)
+    }
+}
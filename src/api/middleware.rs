@@ -1,9 +1,22 @@
 use std::future::{ready, Ready};
+use std::rc::Rc;
 
-use actix_web::{body::EitherBody, dev::{self, Service, ServiceRequest, ServiceResponse, Transform}, Error, HttpResponse, web};
+use actix_web::{body::EitherBody, dev::{self, Service, ServiceRequest, ServiceResponse, Transform}, Error, HttpMessage, HttpResponse, web};
 use futures_util::future::LocalBoxFuture;
 use log::info;
+use crate::api::audit::{claim_actor, outcome_of, request_event, Audited};
+use crate::api::csrf::is_safe_method;
+use crate::api::extractors::authenticate;
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::models::audit::AuditAction;
+use crate::domain::models::token::Claim;
+use crate::domain::services::audit::AuditService;
 use crate::domain::services::service_context::ServiceContextService;
+use crate::services::audit::record_or_warn;
+
+/// Rota que liga e desliga a manutenção; continua acessível durante a manutenção para que ela
+/// possa ser desligada.
+pub const MAINTENANCE_PATH: &str = "/admin/maintenance";
 
 pub struct ServiceContextMaintenanceCheck;
 
@@ -43,7 +56,7 @@ where
         let service_context_service =
             request.app_data::<web::Data<dyn ServiceContextService>>().unwrap();
 
-        if service_context_service.is_maintenance_active() {
+        if request.path() != MAINTENANCE_PATH && service_context_service.is_maintenance_active() {
             info!("Service is in maintenance mode");
             let (request, _pl) = request.into_parts();
             let response = HttpResponse::ServiceUnavailable().finish().map_into_right_body();
@@ -56,4 +69,220 @@ where
             res.await.map(ServiceResponse::map_into_left_body)
         })
     }
-}
\ No newline at end of file
+}
+
+/// Exige um bearer token válido e, opcionalmente, papéis e escopos para as rotas envolvidas.
+///
+/// - `role`: o usuário precisa ter ao menos um dos papéis informados.
+/// - `scope`: o token precisa conter todos os escopos informados.
+/// - `permission`: o usuário precisa ter todas as permissões informadas.
+/// - `audience`: aceita tokens com esse `aud` no lugar da `ServiceAudience` do app.
+///
+/// As claims validadas ficam nas extensions da requisição e são usadas por `AuthenticatedUser` e,
+/// mesmo quando faltam papéis ou permissões, por `AuditTrail`.
+#[derive(Clone, Default)]
+pub struct RequireAuth {
+    roles: Vec<String>,
+    scopes: Vec<String>,
+    permissions: Vec<String>,
+    audience: Option<String>,
+}
+
+impl RequireAuth {
+    pub fn new() -> Self {
+        RequireAuth::default()
+    }
+
+    pub fn role(mut self, role: impl Into<String>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.permissions.push(permission.into());
+        self
+    }
+
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequireAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RequireAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequireAuthMiddleware {
+            service: Rc::new(service),
+            roles: Rc::new(self.roles.clone()),
+            scopes: Rc::new(self.scopes.clone()),
+            permissions: Rc::new(self.permissions.clone()),
+            audience: self.audience.clone(),
+        }))
+    }
+}
+
+pub struct RequireAuthMiddleware<S> {
+    service: Rc<S>,
+    roles: Rc<Vec<String>>,
+    scopes: Rc<Vec<String>>,
+    permissions: Rc<Vec<String>>,
+    audience: Option<String>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let roles = Rc::clone(&self.roles);
+        let scopes = Rc::clone(&self.scopes);
+        let permissions = Rc::clone(&self.permissions);
+        let claim = authenticate(request.request(), self.audience.clone());
+
+        Box::pin(async move {
+            let claim = match claim.await {
+                Ok(claim) => claim,
+                Err(e) => {
+                    return Ok(request.error_response(ApiError::from(e)).map_into_right_body())
+                }
+            };
+            let has_role = roles.is_empty() || roles.iter().any(|role| claim.has_role(role));
+            let has_scopes = scopes.iter().all(|scope| claim.has_scope(scope));
+            let has_permissions = permissions
+                .iter()
+                .all(|permission| claim.has_permission(permission));
+            request.extensions_mut().insert(claim);
+            if !has_role || !has_scopes || !has_permissions {
+                let error = ApiError::from(CommonError {
+                    message: "Insufficient permissions".to_string(),
+                    code: 403,
+                });
+                return Ok(request.error_response(error).map_into_right_body());
+            }
+
+            service
+                .call(request)
+                .await
+                .map(ServiceResponse::map_into_left_body)
+        })
+    }
+}
+
+/// Registra como `admin.action` as requisições que alteram estado nas rotas envolvidas, com o
+/// usuário das claims, o alvo tirado da rota e o resultado pelo status da resposta. Precisa
+/// envolver o `RequireAuth` das rotas, para ver também as requisições recusadas por ele.
+///
+/// Requisições cujo handler já registrou um evento mais específico, marcadas com `Audited`,
+/// não são registradas de novo.
+pub struct AuditTrail;
+
+impl<S, B> Transform<S, ServiceRequest> for AuditTrail
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = AuditTrailMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditTrailMiddleware { service }))
+    }
+}
+
+pub struct AuditTrailMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for AuditTrailMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let audit_service = request.app_data::<web::Data<dyn AuditService>>().cloned();
+        let res = self.service.call(request);
+        Box::pin(async move {
+            let response = res.await?;
+            let req = response.request();
+            let Some(audit_service) = audit_service else {
+                return Ok(response);
+            };
+            if is_safe_method(req.method()) || req.extensions().get::<Audited>().is_some() {
+                return Ok(response);
+            }
+
+            let status = response.status();
+            let mut event = request_event(req, AuditAction::AdminAction, outcome_of(status)).details(
+                serde_json::json!({
+                    "method": req.method().as_str(),
+                    "path": req.path(),
+                    "route": req.match_pattern(),
+                    "status": status.as_u16(),
+                }),
+            );
+            if let Some(claim) = req.extensions().get::<Claim>() {
+                event = claim_actor(event, claim);
+            }
+            let (target_type, target_id) = route_target(req);
+            event.target_type = target_type;
+            event.target_id = target_id;
+            record_or_warn(audit_service.as_ref(), event).await;
+            Ok(response)
+        })
+    }
+}
+
+/// Alvo de uma rota de administração: o primeiro parâmetro da rota e o segmento que o precede,
+/// como `users` e `7` em `/admin/users/{user_id}/roles/{role_id}`, ou só o último segmento
+/// fixo, como `roles` em `POST /admin/roles`. O valor vem do path, pois numa requisição recusada
+/// pelo `RequireAuth` a rota ainda não foi resolvida e `match_info` está vazio.
+fn route_target(req: &actix_web::HttpRequest) -> (Option<String>, Option<String>) {
+    let Some(pattern) = req.match_pattern() else {
+        return (None, None);
+    };
+    let mut target_type = None;
+    let segments = pattern.split('/').zip(req.path().split('/'));
+    for (segment, value) in segments.filter(|(segment, _)| !segment.is_empty()) {
+        if segment.starts_with('{') {
+            return (target_type, Some(value.to_string()));
+        }
+        target_type = Some(segment.to_string());
+    }
+    (target_type, None)
+}
@@ -1,3 +1,6 @@
+pub mod audit;
 pub mod controllers;
+pub mod csrf;
 pub mod dto;
-pub mod middleware;
\ No newline at end of file
+pub mod extractors;
+pub mod middleware;
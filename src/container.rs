@@ -1,32 +1,197 @@
+use crate::domain::models::session::SessionPolicy;
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::dpop::DpopRepository;
+use crate::domain::repositories::group::GroupRepository;
+use crate::domain::repositories::oauth::OAuthRepository;
+use crate::domain::repositories::organization::OrganizationRepository;
+use crate::domain::repositories::outbox::OutboxRepository;
+use crate::domain::repositories::personal_access_token::PersonalAccessTokenRepository;
+use crate::domain::repositories::role::RoleRepository;
+use crate::domain::repositories::session::SessionRepository;
+use crate::domain::repositories::token::TokenRepository;
 use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::authorization::AuthorizationService;
+use crate::domain::services::client_registration::ClientRegistrationService;
+use crate::domain::services::dpop::DpopService;
+use crate::domain::services::group::GroupService;
+use crate::domain::services::oauth::OAuthService;
+use crate::domain::services::oidc::OidcService;
+use crate::domain::services::organization::OrganizationService;
+use crate::domain::services::personal_access_token::PersonalAccessTokenService;
+use crate::domain::services::service_account::ServiceAccountService;
 use crate::domain::services::service_context::ServiceContextService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::token::TokenService;
 use crate::domain::services::user::UserService;
+use crate::domain::services::user_admin::UserAdminService;
+use crate::domain::services::webhook::WebhookService;
 use crate::infrastructure::databases::postgresql::db_pool;
+use crate::infrastructure::repositories::audit::AuditDieselRepository;
+use crate::infrastructure::repositories::dpop::DpopDieselRepository;
+use crate::infrastructure::repositories::group::GroupDieselRepository;
+use crate::infrastructure::repositories::oauth::OAuthDieselRepository;
+use crate::infrastructure::repositories::organization::OrganizationDieselRepository;
+use crate::infrastructure::repositories::outbox::OutboxDieselRepository;
+use crate::infrastructure::repositories::personal_access_token::PersonalAccessTokenDieselRepository;
+use crate::infrastructure::repositories::role::RoleDieselRepository;
+use crate::infrastructure::repositories::session::SessionDieselRepository;
+use crate::infrastructure::repositories::token::TokenDieselRepository;
 use crate::infrastructure::repositories::user::UserDieselRepository;
+use crate::infrastructure::services::audit_export::AuditExportServiceImpl;
 use crate::infrastructure::services::service_context::ServiceContextServiceImpl;
-use crate::services::token::TokenServiceImpl;
+use crate::infrastructure::services::webhook_sender::HttpWebhookSender;
+use crate::services::audit::{AuditServiceImpl, CheckpointSigner};
+use crate::services::authorization::AuthorizationServiceImpl;
+use crate::services::client_registration::ClientRegistrationServiceImpl;
+use crate::services::dpop::DpopServiceImpl;
+use crate::services::group::GroupServiceImpl;
+use crate::services::oauth::OAuthServiceImpl;
+use crate::services::oidc::{OidcServiceImpl, SigningKey};
+use crate::services::organization::OrganizationServiceImpl;
+use crate::services::personal_access_token::{PersonalAccessTokenAwareTokenService, PersonalAccessTokenServiceImpl};
+use crate::services::service_account::ServiceAccountServiceImpl;
+use crate::services::session::{SessionServiceImpl, SessionTokenService};
+use crate::services::token::{RevocableTokenService, TokenServiceImpl};
 use crate::services::user::UserServiceImpl;
+use crate::services::user_admin::UserAdminServiceImpl;
+use crate::services::webhook::WebhookServiceImpl;
 use std::sync::Arc;
 
 pub struct Container {
     pub service_context_service: Arc<dyn ServiceContextService>,
     pub user_service: Arc<dyn UserService>,
+    pub user_admin_service: Arc<dyn UserAdminService>,
+    pub token_service: Arc<dyn TokenService>,
+    pub authorization_service: Arc<dyn AuthorizationService>,
+    pub group_service: Arc<dyn GroupService>,
+    pub organization_service: Arc<dyn OrganizationService>,
+    pub oauth_service: Arc<dyn OAuthService>,
+    pub client_registration_service: Arc<dyn ClientRegistrationService>,
+    pub oidc_service: Arc<dyn OidcService>,
+    pub dpop_service: Arc<dyn DpopService>,
+    pub personal_access_token_service: Arc<dyn PersonalAccessTokenService>,
+    pub service_account_service: Arc<dyn ServiceAccountService>,
+    pub session_service: Arc<dyn SessionService>,
+    pub audit_service: Arc<dyn AuditService>,
+    pub webhook_service: Arc<dyn WebhookService>,
 }
 impl Container {
     pub fn new() -> Self {
         let db_pool = db_pool();
         let user_repository: Arc<dyn UserRepository> =
             Arc::new(UserDieselRepository::new(Arc::new(db_pool.clone())));
-        let token_service = Arc::new(TokenServiceImpl {});
-        let user_service = Arc::new(UserServiceImpl {
-            repository: user_repository,
-            token_service,
+        let role_repository: Arc<dyn RoleRepository> =
+            Arc::new(RoleDieselRepository::new(Arc::new(db_pool.clone())));
+        let group_repository: Arc<dyn GroupRepository> =
+            Arc::new(GroupDieselRepository::new(Arc::new(db_pool.clone())));
+        let organization_repository: Arc<dyn OrganizationRepository> =
+            Arc::new(OrganizationDieselRepository::new(Arc::new(db_pool.clone())));
+        let oauth_repository: Arc<dyn OAuthRepository> =
+            Arc::new(OAuthDieselRepository::new(Arc::new(db_pool.clone())));
+        let token_repository: Arc<dyn TokenRepository> =
+            Arc::new(TokenDieselRepository::new(Arc::new(db_pool.clone())));
+        let dpop_repository: Arc<dyn DpopRepository> =
+            Arc::new(DpopDieselRepository::new(Arc::new(db_pool.clone())));
+        let session_repository: Arc<dyn SessionRepository> =
+            Arc::new(SessionDieselRepository::new(Arc::new(db_pool.clone())));
+        let personal_access_token_repository: Arc<dyn PersonalAccessTokenRepository> =
+            Arc::new(PersonalAccessTokenDieselRepository::new(Arc::new(db_pool.clone())));
+        let audit_repository: Arc<dyn AuditRepository> =
+            Arc::new(AuditDieselRepository::new(Arc::new(db_pool.clone())));
+        let outbox_repository: Arc<dyn OutboxRepository> =
+            Arc::new(OutboxDieselRepository::new(Arc::new(db_pool.clone())));
+        let signing_key = SigningKey::from_env();
+        let mut audit_service_impl = AuditServiceImpl::new(audit_repository, CheckpointSigner::new(signing_key.clone()));
+        if let Some(audit_export_service) = AuditExportServiceImpl::shared() {
+            audit_service_impl = audit_service_impl.with_exporter(audit_export_service);
+        }
+        let audit_service: Arc<dyn AuditService> = Arc::new(audit_service_impl);
+        let token_service_impl = match &signing_key {
+            Some(signing_key) => TokenServiceImpl::with_signing_key(signing_key.clone()),
+            None => TokenServiceImpl::new(),
+        };
+        let authorization_service: Arc<dyn AuthorizationService> =
+            Arc::new(AuthorizationServiceImpl::new(role_repository, group_repository.clone()));
+        let personal_access_token_service: Arc<dyn PersonalAccessTokenService> = Arc::new(
+            PersonalAccessTokenServiceImpl::new(
+                personal_access_token_repository,
+                user_repository.clone(),
+                authorization_service.clone(),
+            ),
+        );
+        let session_service: Arc<dyn SessionService> = Arc::new(SessionServiceImpl::new(
+            session_repository,
+            authorization_service.clone(),
+            SessionPolicy::from_env(),
+        ));
+        let token_service: Arc<dyn TokenService> = Arc::new(PersonalAccessTokenAwareTokenService::new(
+            Arc::new(SessionTokenService::new(
+                Arc::new(RevocableTokenService::new(
+                    Arc::new(token_service_impl),
+                    token_repository.clone(),
+                )),
+                session_service.clone(),
+            )),
+            personal_access_token_service.clone(),
+        ));
+        let group_service = Arc::new(GroupServiceImpl::new(group_repository));
+        let service_account_service = Arc::new(ServiceAccountServiceImpl::new(
+            user_repository.clone(),
+            personal_access_token_service.clone(),
+        ));
+        let user_service: Arc<dyn UserService> = Arc::new(UserServiceImpl {
+            repository: user_repository.clone(),
+            token_service: token_service.clone(),
+            authorization_service: authorization_service.clone(),
+            organization_repository: organization_repository.clone(),
+            session_service: session_service.clone(),
+            audit_service: audit_service.clone(),
         });
+        let user_admin_service = Arc::new(UserAdminServiceImpl::new(
+            user_repository.clone(),
+            session_service.clone(),
+        ));
+        let organization_service = Arc::new(OrganizationServiceImpl::new(
+            organization_repository,
+            user_repository,
+            user_service.clone(),
+            authorization_service.clone(),
+        ));
+        let oidc_service: Arc<dyn OidcService> = Arc::new(OidcServiceImpl::new(user_service.clone(), signing_key));
+        let client_registration_service =
+            Arc::new(ClientRegistrationServiceImpl::new(oauth_repository.clone()));
+        let oauth_service = Arc::new(OAuthServiceImpl::new(
+            oauth_repository,
+            user_service.clone(),
+            token_service.clone(),
+            oidc_service.clone(),
+            token_repository,
+        ));
+        let dpop_service = Arc::new(DpopServiceImpl::new(dpop_repository));
+        let webhook_service = Arc::new(WebhookServiceImpl::new(
+            outbox_repository,
+            Arc::new(HttpWebhookSender::new()),
+        ));
         let service_context_service =
             Arc::new(ServiceContextServiceImpl::new(Arc::new(db_pool.clone())));
         Container {
             service_context_service,
             user_service,
+            user_admin_service,
+            token_service,
+            authorization_service,
+            group_service,
+            organization_service,
+            oauth_service,
+            client_registration_service,
+            oidc_service,
+            dpop_service,
+            personal_access_token_service,
+            service_account_service,
+            session_service,
+            audit_service,
+            webhook_service,
         }
     }
 }
@@ -4,11 +4,61 @@ use actix_web::middleware::Logger;
 use actix_web::Error;
 use actix_web::{web, App};
 
+use crate::api::controllers::audit_handler::list_audit_events_handler;
+use crate::api::controllers::group_handler::{
+    add_member_handler, add_subgroup_handler, assign_group_role_handler, create_group_handler,
+    delete_group_handler, explain_permission_handler, list_groups_handler, remove_member_handler,
+    remove_subgroup_handler, unassign_group_role_handler,
+};
+use crate::api::controllers::client_registration_handler::{
+    create_initial_access_token_handler, delete_registration_handler, get_registration_handler,
+    register_handler, update_registration_handler,
+};
+use crate::api::controllers::oauth_handler::{
+    authorize_handler, authorize_page_handler, delete_client_handler, device_authorization_handler,
+    device_handler, device_page_handler, list_clients_handler, list_consents_handler, pushed_authorization_handler,
+    register_client_handler,
+    revoke_consent_handler, revoke_handler, revoke_previous_client_secret_handler, rotate_client_secret_handler,
+    token_handler,
+};
+use crate::api::controllers::oidc_handler::{discovery_handler, jwks_handler, userinfo_handler};
+use crate::api::controllers::organization_handler::{
+    create_organization_handler, list_organizations_handler,
+};
+use crate::api::controllers::personal_access_token_handler::{
+    create_personal_access_token_handler, list_personal_access_tokens_handler,
+    revoke_personal_access_token_handler,
+};
+use crate::api::controllers::role_handler::{
+    assign_role_handler, create_permission_handler, create_role_handler, delete_role_handler,
+    grant_permission_handler, list_permissions_handler, list_role_permissions_handler,
+    list_roles_handler, list_user_roles_handler, revoke_permission_handler, set_role_session_policy_handler,
+    unassign_role_handler,
+};
+use crate::api::controllers::service_account_handler::{
+    create_service_account_handler, create_service_account_token_handler, delete_service_account_handler,
+    list_service_account_tokens_handler, list_service_accounts_handler, revoke_service_account_token_handler,
+};
+use crate::api::controllers::service_context_handlers::{
+    get_service_context_handler, update_service_context_handler,
+};
+use crate::api::controllers::session_handler::{list_sessions_handler, revoke_session_handler};
+use crate::api::controllers::user_admin_handler::{
+    delete_user_handler, disable_user_handler, enable_user_handler, get_user_handler, list_users_handler,
+    require_password_reset_handler, revoke_user_sessions_handler, unlock_user_handler, update_user_handler,
+};
 use crate::api::controllers::user_handler::{
-    create_user_handler, login_user_handler, validate_token_handler,
+    change_password_handler, create_user_handler, forward_auth_handler, login_user_handler, logout_handler,
+    renew_token_handler, validate_token_handler,
+};
+use crate::api::controllers::webhook_handler::{
+    create_webhook_handler, delete_webhook_handler, list_webhook_deliveries_handler, list_webhooks_handler,
+    replay_outbox_event_handler,
 };
-use crate::api::middleware::ServiceContextMaintenanceCheck;
+use crate::api::extractors::{PublicOrigin, ServiceAudience};
+use crate::api::middleware::{AuditTrail, RequireAuth, ServiceContextMaintenanceCheck};
 use crate::container::Container;
+use crate::domain::constants::ADMIN_ROLE;
 
 pub fn create_app() -> App<
     impl ServiceFactory<
@@ -21,17 +71,170 @@ pub fn create_app() -> App<
 > {
     let container = Container::new();
     let user_service = container.user_service.clone();
+    let user_admin_service = container.user_admin_service.clone();
+    let token_service = container.token_service.clone();
+    let authorization_service = container.authorization_service.clone();
+    let group_service = container.group_service.clone();
+    let organization_service = container.organization_service.clone();
+    let oauth_service = container.oauth_service.clone();
+    let client_registration_service = container.client_registration_service.clone();
+    let oidc_service = container.oidc_service.clone();
+    let dpop_service = container.dpop_service.clone();
+    let personal_access_token_service = container.personal_access_token_service.clone();
+    let service_account_service = container.service_account_service.clone();
+    let session_service = container.session_service.clone();
+    let audit_service = container.audit_service.clone();
+    let webhook_service = container.webhook_service.clone();
     // the last
     let service_context_service = container.service_context_service.clone();
     App::new()
         .app_data(web::Data::from(user_service.clone()))
+        .app_data(web::Data::from(user_admin_service.clone()))
+        .app_data(web::Data::from(token_service.clone()))
+        .app_data(web::Data::from(authorization_service.clone()))
+        .app_data(web::Data::from(group_service.clone()))
+        .app_data(web::Data::from(organization_service.clone()))
+        .app_data(web::Data::from(oauth_service.clone()))
+        .app_data(web::Data::from(client_registration_service.clone()))
+        .app_data(web::Data::from(oidc_service.clone()))
+        .app_data(web::Data::from(dpop_service.clone()))
+        .app_data(web::Data::from(personal_access_token_service.clone()))
+        .app_data(web::Data::from(service_account_service.clone()))
+        .app_data(web::Data::from(session_service.clone()))
+        .app_data(web::Data::from(audit_service.clone()))
+        .app_data(web::Data::from(webhook_service.clone()))
         .app_data(web::Data::from(service_context_service.clone()))
+        .app_data(ServiceAudience::from_env())
+        .app_data(PublicOrigin::from_env())
         .wrap(Logger::default())
         .wrap(ServiceContextMaintenanceCheck)
         .service(
             web::scope("/auth")
                 .route("/register", web::post().to(create_user_handler))
                 .route("/login", web::post().to(login_user_handler))
-                .route("/validate", web::post().to(validate_token_handler)),
+                .route("/logout", web::post().to(logout_handler))
+                .route("/renew", web::post().to(renew_token_handler))
+                .route("/password", web::post().to(change_password_handler))
+                .route("/forward", web::get().to(forward_auth_handler))
+                .route("/validate", web::post().to(validate_token_handler))
+                .route("/consents", web::get().to(list_consents_handler))
+                .route("/consents/{client_id}", web::delete().to(revoke_consent_handler))
+                .route("/tokens", web::get().to(list_personal_access_tokens_handler))
+                .route("/tokens", web::post().to(create_personal_access_token_handler))
+                .route("/tokens/{id}", web::delete().to(revoke_personal_access_token_handler))
+                .route("/sessions", web::get().to(list_sessions_handler))
+                .route("/sessions/{id}", web::delete().to(revoke_session_handler)),
+        )
+        .service(
+            web::scope("/oauth")
+                .route("/authorize", web::get().to(authorize_page_handler))
+                .route("/authorize", web::post().to(authorize_handler))
+                .route("/par", web::post().to(pushed_authorization_handler))
+                .route("/token", web::post().to(token_handler))
+                .route("/revoke", web::post().to(revoke_handler))
+                .route("/register", web::post().to(register_handler))
+                .route("/register/{client_id}", web::get().to(get_registration_handler))
+                .route("/register/{client_id}", web::put().to(update_registration_handler))
+                .route("/register/{client_id}", web::delete().to(delete_registration_handler))
+                .route("/device_authorization", web::post().to(device_authorization_handler))
+                .route("/device", web::get().to(device_page_handler))
+                .route("/device", web::post().to(device_handler)),
+        )
+        .service(
+            web::scope("/.well-known")
+                .route("/openid-configuration", web::get().to(discovery_handler))
+                .route("/jwks.json", web::get().to(jwks_handler)),
+        )
+        .route("/userinfo", web::get().to(userinfo_handler))
+        .route("/userinfo", web::post().to(userinfo_handler))
+        .service(
+            web::scope("/admin")
+                .wrap(RequireAuth::new().role(ADMIN_ROLE))
+                .wrap(AuditTrail)
+                .service(
+                    web::scope("/organizations")
+                        .wrap(RequireAuth::new().permission("organizations:write"))
+                        .route("", web::get().to(list_organizations_handler))
+                        .route("", web::post().to(create_organization_handler)),
+                )
+                .service(
+                    web::scope("/maintenance")
+                        .wrap(RequireAuth::new().permission("maintenance:write"))
+                        .route("", web::get().to(get_service_context_handler))
+                        .route("", web::put().to(update_service_context_handler)),
+                )
+                .route("/audit_events", web::get().to(list_audit_events_handler))
+                .route("/webhooks", web::get().to(list_webhooks_handler))
+                .route("/webhooks", web::post().to(create_webhook_handler))
+                .route("/webhooks/{id}", web::delete().to(delete_webhook_handler))
+                .route("/webhook_deliveries", web::get().to(list_webhook_deliveries_handler))
+                .route("/outbox/{id}/replay", web::post().to(replay_outbox_event_handler))
+                .route("/roles", web::get().to(list_roles_handler))
+                .route("/roles", web::post().to(create_role_handler))
+                .route("/roles/{role_id}", web::delete().to(delete_role_handler))
+                .route("/roles/{role_id}/session_policy", web::put().to(set_role_session_policy_handler))
+                .route("/roles/{role_id}/permissions", web::get().to(list_role_permissions_handler))
+                .route(
+                    "/roles/{role_id}/permissions/{permission_id}",
+                    web::put().to(grant_permission_handler),
+                )
+                .route(
+                    "/roles/{role_id}/permissions/{permission_id}",
+                    web::delete().to(revoke_permission_handler),
+                )
+                .route("/permissions", web::get().to(list_permissions_handler))
+                .route("/permissions", web::post().to(create_permission_handler))
+                .route("/users", web::get().to(list_users_handler))
+                .route("/users/{user_id}", web::get().to(get_user_handler))
+                .route("/users/{user_id}", web::patch().to(update_user_handler))
+                .route("/users/{user_id}", web::delete().to(delete_user_handler))
+                .route("/users/{user_id}/disable", web::post().to(disable_user_handler))
+                .route("/users/{user_id}/enable", web::post().to(enable_user_handler))
+                .route("/users/{user_id}/password_reset", web::post().to(require_password_reset_handler))
+                .route("/users/{user_id}/unlock", web::post().to(unlock_user_handler))
+                .route("/users/{user_id}/sessions", web::delete().to(revoke_user_sessions_handler))
+                .route("/users/{user_id}/roles", web::get().to(list_user_roles_handler))
+                .route("/users/{user_id}/roles/{role_id}", web::put().to(assign_role_handler))
+                .route("/users/{user_id}/roles/{role_id}", web::delete().to(unassign_role_handler))
+                .route(
+                    "/users/{user_id}/permissions/{permission}/explain",
+                    web::get().to(explain_permission_handler),
+                )
+                .route("/service_accounts", web::get().to(list_service_accounts_handler))
+                .route("/service_accounts", web::post().to(create_service_account_handler))
+                .route("/service_accounts/{id}", web::delete().to(delete_service_account_handler))
+                .route("/service_accounts/{id}/tokens", web::get().to(list_service_account_tokens_handler))
+                .route("/service_accounts/{id}/tokens", web::post().to(create_service_account_token_handler))
+                .route(
+                    "/service_accounts/{id}/tokens/{token_id}",
+                    web::delete().to(revoke_service_account_token_handler),
+                )
+                .route("/oauth/clients", web::get().to(list_clients_handler))
+                .route("/oauth/clients", web::post().to(register_client_handler))
+                .route("/oauth/clients/{id}", web::delete().to(delete_client_handler))
+                .route("/oauth/clients/{id}/secret", web::post().to(rotate_client_secret_handler))
+                .route(
+                    "/oauth/clients/{id}/secret/previous",
+                    web::delete().to(revoke_previous_client_secret_handler),
+                )
+                .route(
+                    "/oauth/initial_access_tokens",
+                    web::post().to(create_initial_access_token_handler),
+                )
+                .route("/groups", web::get().to(list_groups_handler))
+                .route("/groups", web::post().to(create_group_handler))
+                .route("/groups/{group_id}", web::delete().to(delete_group_handler))
+                .route("/groups/{group_id}/members/{user_id}", web::put().to(add_member_handler))
+                .route("/groups/{group_id}/members/{user_id}", web::delete().to(remove_member_handler))
+                .route("/groups/{group_id}/subgroups/{subgroup_id}", web::put().to(add_subgroup_handler))
+                .route(
+                    "/groups/{group_id}/subgroups/{subgroup_id}",
+                    web::delete().to(remove_subgroup_handler),
+                )
+                .route("/groups/{group_id}/roles/{role_id}", web::put().to(assign_group_role_handler))
+                .route(
+                    "/groups/{group_id}/roles/{role_id}",
+                    web::delete().to(unassign_group_role_handler),
+                ),
         )
 }
@@ -1,3 +1,63 @@
 pub const POSTGRESQL_DB_URI: &str = "DATABASE_URL";
 pub const SECRET_KEY_TOKEN: &str = "SECRET_KEY";
 pub const SALT_KEY: &str = "SALT_KEY";
+pub const ADMIN_USERNAME: &str = "ADMIN_USERNAME";
+pub const ADMIN_PASSWORD: &str = "ADMIN_PASSWORD";
+pub const ADMIN_EMAIL: &str = "ADMIN_EMAIL";
+pub const ADMIN_ROLE: &str = "admin";
+pub const DEFAULT_ADMIN_PERMISSIONS: [&str; 4] =
+    ["roles:read", "roles:write", "users:read", "users:write"];
+/// Permissões do admin da organização padrão, que valem para o serviço inteiro.
+pub const ORGANIZATION_ADMIN_PERMISSIONS: [&str; 3] =
+    ["organizations:read", "organizations:write", "maintenance:write"];
+pub const DEFAULT_ORGANIZATION: &str = "default";
+pub const ACCESS_TOKEN_TTL_SECONDS: i64 = 3600;
+pub const AUTHORIZATION_CODE_TTL_SECONDS: i64 = 60;
+pub const PENDING_CONSENT_TTL_SECONDS: i64 = 600;
+pub const REFRESH_TOKEN_TTL_SECONDS: i64 = 30 * 24 * 3600;
+pub const INITIAL_ACCESS_TOKEN_TTL_SECONDS: i64 = 24 * 3600;
+pub const DEVICE_CODE_TTL_SECONDS: i64 = 600;
+/// Validade de um `request_uri` de `/oauth/par`; precisa cobrir o login na tela de autorização.
+pub const PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS: i64 = 300;
+pub const DEVICE_CODE_INTERVAL_SECONDS: i32 = 5;
+pub const USER_CODE_MAX_FAILED_ATTEMPTS: i32 = 5;
+pub const USER_CODE_FAILED_ATTEMPTS_TTL_SECONDS: i64 = 900;
+/// Logins errados seguidos que bloqueiam a conta por `LOGIN_LOCKOUT_SECONDS`.
+pub const LOGIN_MAX_FAILED_ATTEMPTS: i32 = 5;
+pub const LOGIN_LOCKOUT_SECONDS: i64 = 900;
+pub const EXPIRED_CODES_PURGE_INTERVAL_SECONDS: u64 = 300;
+pub const DPOP_PROOF_MAX_AGE_SECONDS: i64 = 60;
+/// Prefixo dos personal access tokens, para que scanners de segredos os reconheçam.
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "aspat_";
+pub const OIDC_ISSUER: &str = "OIDC_ISSUER";
+pub const REGISTRATION_ALLOWED_SCOPES: &str = "REGISTRATION_ALLOWED_SCOPES";
+pub const OIDC_SIGNING_KEY: &str = "OIDC_SIGNING_KEY";
+pub const SERVICE_AUDIENCE: &str = "SERVICE_AUDIENCE";
+/// URL pública do serviço (esquema e host, como `https://auth.example.com`), usada no `htu` das
+/// provas DPoP no lugar do que a requisição informa.
+pub const PUBLIC_BASE_URL: &str = "PUBLIC_BASE_URL";
+/// IPs, separados por vírgula, dos proxies cujos headers `Forwarded` e `X-Forwarded-*` valem.
+pub const TRUSTED_PROXIES: &str = "TRUSTED_PROXIES";
+pub const SESSION_IDLE_TIMEOUT_SECONDS: &str = "SESSION_IDLE_TIMEOUT_SECONDS";
+pub const SESSION_MAX_LIFETIME_SECONDS: &str = "SESSION_MAX_LIFETIME_SECONDS";
+pub const SESSION_MAX_CONCURRENT: &str = "SESSION_MAX_CONCURRENT";
+pub const SESSION_LIMIT_ACTION: &str = "SESSION_LIMIT_ACTION";
+pub const AUDIT_EXPORT_SINK: &str = "AUDIT_EXPORT_SINK";
+pub const AUDIT_EXPORT_FORMAT: &str = "AUDIT_EXPORT_FORMAT";
+pub const AUDIT_EXPORT_FILE: &str = "AUDIT_EXPORT_FILE";
+pub const AUDIT_EXPORT_FILE_MAX_BYTES: &str = "AUDIT_EXPORT_FILE_MAX_BYTES";
+pub const AUDIT_EXPORT_FILE_MAX_FILES: &str = "AUDIT_EXPORT_FILE_MAX_FILES";
+pub const AUDIT_EXPORT_SYSLOG_ADDRESS: &str = "AUDIT_EXPORT_SYSLOG_ADDRESS";
+pub const AUDIT_EXPORT_SYSLOG_PROTOCOL: &str = "AUDIT_EXPORT_SYSLOG_PROTOCOL";
+pub const AUDIT_EXPORT_SPOOL: &str = "AUDIT_EXPORT_SPOOL";
+pub const AUDIT_EXPORT_QUEUE_SIZE: &str = "AUDIT_EXPORT_QUEUE_SIZE";
+/// Tentativas de uma entrega de webhook antes de ela ficar `dead`.
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 8;
+/// Espera antes da segunda tentativa; dobra a cada falha, até `WEBHOOK_RETRY_MAX_SECONDS`.
+pub const WEBHOOK_RETRY_BASE_SECONDS: i64 = 30;
+pub const WEBHOOK_RETRY_MAX_SECONDS: i64 = 6 * 3600;
+pub const WEBHOOK_DISPATCH_INTERVAL_SECONDS: u64 = 5;
+pub const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+/// Tempo máximo para conectar e para ler a resposta de `/auth/validate` na introspecção.
+pub const INTROSPECTION_CONNECT_TIMEOUT_SECONDS: u64 = 2;
+pub const INTROSPECTION_READ_TIMEOUT_SECONDS: u64 = 5;
@@ -28,8 +28,89 @@ impl std::fmt::Display for ApiError {
 }
 
 impl actix_web::ResponseError for ApiError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        use actix_web::http::StatusCode;
+        match self.0.code {
+            401 => StatusCode::UNAUTHORIZED,
+            403 => StatusCode::FORBIDDEN,
+            404 => StatusCode::NOT_FOUND,
+            500 => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
     fn error_response(&self) -> actix_web::HttpResponse {
-        actix_web::HttpResponse::BadRequest().json(&self.0)
+        actix_web::HttpResponse::build(self.status_code()).json(&self.0)
+    }
+}
+
+/// Erro dos endpoints OAuth, no formato da RFC 6749 (seção 5.2).
+#[derive(Debug, Serialize)]
+pub struct OAuthError {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
+}
+
+impl OAuthError {
+    pub fn new(error: &str, description: impl Into<String>) -> Self {
+        OAuthError {
+            error: error.to_string(),
+            error_description: Some(description.into()),
+        }
+    }
+}
+
+/// Falhas internas viram `server_error` com uma descrição genérica; a mensagem original, que
+/// pode trazer detalhes do banco, só vai para o log.
+impl From<CommonError> for OAuthError {
+    fn from(error: CommonError) -> OAuthError {
+        log::error!("OAuth request failed: {}", error.message);
+        OAuthError::new("server_error", "The server could not complete the request")
+    }
+}
+
+impl From<RepositoryError> for OAuthError {
+    fn from(error: RepositoryError) -> OAuthError {
+        CommonError::from(error).into()
+    }
+}
+
+impl std::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.error_description {
+            Some(description) => write!(f, "{}: {}", self.error, description),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl actix_web::ResponseError for OAuthError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        use actix_web::http::StatusCode;
+        match self.error.as_str() {
+            "invalid_client" | "invalid_token" => StatusCode::UNAUTHORIZED,
+            "server_error" => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        let mut response = actix_web::HttpResponse::build(self.status_code());
+        response.insert_header((actix_web::http::header::CACHE_CONTROL, "no-store"));
+        match self.error.as_str() {
+            "invalid_client" => {
+                response.insert_header((actix_web::http::header::WWW_AUTHENTICATE, "Basic"));
+            }
+            "invalid_token" => {
+                response.insert_header((
+                    actix_web::http::header::WWW_AUTHENTICATE,
+                    r#"Bearer error="invalid_token""#,
+                ));
+            }
+            _ => {}
+        }
+        response.json(self)
     }
 }
 
@@ -38,10 +119,10 @@ pub struct RepositoryError {
     pub message: String,
 }
 
-impl Into<CommonError> for RepositoryError {
-    fn into(self) -> CommonError {
+impl From<RepositoryError> for CommonError {
+    fn from(error: RepositoryError) -> CommonError {
         CommonError {
-            message: self.message,
+            message: error.message,
             code: 1,
         }
     }
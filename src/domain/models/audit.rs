@@ -0,0 +1,273 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// `prev_hash` da primeira entrada de cada dia.
+pub const AUDIT_CHAIN_GENESIS: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Ação registrada no log de auditoria.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AuditAction {
+    #[serde(rename = "user.login")]
+    UserLogin,
+    #[serde(rename = "user.logout")]
+    UserLogout,
+    #[serde(rename = "user.register")]
+    UserRegister,
+    #[serde(rename = "user.password_change")]
+    PasswordChange,
+    #[serde(rename = "token.revoke")]
+    TokenRevoke,
+    #[serde(rename = "session.revoke")]
+    SessionRevoke,
+    #[serde(rename = "consent.revoke")]
+    ConsentRevoke,
+    #[serde(rename = "service.maintenance")]
+    MaintenanceToggle,
+    /// Requisição que altera estado na API de administração.
+    #[serde(rename = "admin.action")]
+    AdminAction,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 9] = [
+        AuditAction::UserLogin,
+        AuditAction::UserLogout,
+        AuditAction::UserRegister,
+        AuditAction::PasswordChange,
+        AuditAction::TokenRevoke,
+        AuditAction::SessionRevoke,
+        AuditAction::ConsentRevoke,
+        AuditAction::MaintenanceToggle,
+        AuditAction::AdminAction,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::UserLogin => "user.login",
+            AuditAction::UserLogout => "user.logout",
+            AuditAction::UserRegister => "user.register",
+            AuditAction::PasswordChange => "user.password_change",
+            AuditAction::TokenRevoke => "token.revoke",
+            AuditAction::SessionRevoke => "session.revoke",
+            AuditAction::ConsentRevoke => "consent.revoke",
+            AuditAction::MaintenanceToggle => "service.maintenance",
+            AuditAction::AdminAction => "admin.action",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        AuditAction::ALL.into_iter().find(|action| action.as_str() == value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+impl AuditOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOutcome::Success => "success",
+            AuditOutcome::Failure => "failure",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "success" => Some(AuditOutcome::Success),
+            "failure" => Some(AuditOutcome::Failure),
+            _ => None,
+        }
+    }
+}
+
+/// Evento registrado. `actor_*` é quem fez a ação (usuário, conta de serviço ou cliente OAuth)
+/// e `target_*` é sobre o que ela foi feita; ambos podem faltar, como num login que falhou.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub organization_id: Option<i32>,
+    pub action: AuditAction,
+    pub outcome: AuditOutcome,
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// Dados próprios da ação, como o nome de usuário de um login ou a rota de uma ação de
+    /// administração. Nunca guarda senhas nem tokens.
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    /// `hash` da entrada anterior do mesmo dia (UTC) ou `AUDIT_CHAIN_GENESIS` na primeira.
+    pub prev_hash: String,
+    /// SHA-256 de `prev_hash` e do conteúdo da entrada; vazio nas entradas anteriores à cadeia.
+    pub hash: String,
+}
+
+impl AuditEvent {
+    /// Dia (UTC) da cadeia a que a entrada pertence.
+    pub fn day(&self) -> NaiveDate {
+        self.created_at.date_naive()
+    }
+
+    /// Hash que a entrada deveria ter pelo seu conteúdo atual.
+    pub fn expected_hash(&self) -> String {
+        let event = CreateAuditEvent {
+            organization_id: self.organization_id,
+            action: self.action,
+            outcome: self.outcome,
+            actor_type: self.actor_type.clone(),
+            actor_id: self.actor_id.clone(),
+            target_type: self.target_type.clone(),
+            target_id: self.target_id.clone(),
+            ip_address: self.ip_address.clone(),
+            user_agent: self.user_agent.clone(),
+            details: self.details.clone(),
+        };
+        event.chain_hash(&self.prev_hash, self.created_at)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateAuditEvent {
+    pub organization_id: Option<i32>,
+    pub action: AuditAction,
+    pub outcome: AuditOutcome,
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: serde_json::Value,
+}
+
+impl CreateAuditEvent {
+    pub fn new(action: AuditAction, outcome: AuditOutcome) -> Self {
+        CreateAuditEvent {
+            organization_id: None,
+            action,
+            outcome,
+            actor_type: None,
+            actor_id: None,
+            target_type: None,
+            target_id: None,
+            ip_address: None,
+            user_agent: None,
+            details: serde_json::Value::Object(Default::default()),
+        }
+    }
+
+    pub fn actor(mut self, actor_type: &str, actor_id: impl ToString) -> Self {
+        self.actor_type = Some(actor_type.to_string());
+        self.actor_id = Some(actor_id.to_string());
+        self
+    }
+
+    pub fn target(mut self, target_type: &str, target_id: impl ToString) -> Self {
+        self.target_type = Some(target_type.to_string());
+        self.target_id = Some(target_id.to_string());
+        self
+    }
+
+    pub fn client(mut self, ip_address: Option<String>, user_agent: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self.user_agent = user_agent;
+        self
+    }
+
+    pub fn organization(mut self, organization_id: Option<i32>) -> Self {
+        self.organization_id = organization_id;
+        self
+    }
+
+    pub fn details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// SHA-256, em hexadecimal, de `prev_hash` e de todos os campos gravados. `created_at` entra
+    /// em microssegundos, a precisão do Postgres, e `details` com as chaves em ordem.
+    pub fn chain_hash(&self, prev_hash: &str, created_at: DateTime<Utc>) -> String {
+        let contents = serde_json::json!([
+            prev_hash,
+            self.organization_id,
+            self.action.as_str(),
+            self.outcome.as_str(),
+            self.actor_type,
+            self.actor_id,
+            self.target_type,
+            self.target_id,
+            self.ip_address,
+            self.user_agent,
+            self.details,
+            created_at.timestamp_micros(),
+        ]);
+        format!("{:x}", Sha256::digest(contents.to_string().as_bytes()))
+    }
+}
+
+/// Filtros da consulta de eventos; os ausentes não restringem.
+#[derive(Clone, Debug, Default)]
+pub struct AuditEventFilter {
+    pub action: Option<AuditAction>,
+    pub outcome: Option<AuditOutcome>,
+    pub actor_id: Option<String>,
+    pub target_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Resumo assinado de um dia encerrado do log. `signature` é um JWT assinado com a chave do
+/// serviço cujas claims repetem os demais campos.
+#[derive(Clone, Debug)]
+pub struct AuditCheckpoint {
+    pub day: NaiveDate,
+    pub event_count: i64,
+    pub last_event_id: i64,
+    pub last_hash: String,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Claims assinadas de um `AuditCheckpoint`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AuditCheckpointClaims {
+    pub day: NaiveDate,
+    pub event_count: i64,
+    pub last_event_id: i64,
+    pub last_hash: String,
+}
+
+/// Primeiro ponto em que a cadeia não confere.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditChainBreak {
+    pub day: NaiveDate,
+    /// Entrada em que a quebra foi encontrada; `None` quando é o checkpoint do dia que não confere.
+    pub event_id: Option<i64>,
+    pub reason: String,
+}
+
+/// Resultado da verificação do log.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AuditVerification {
+    pub events_checked: i64,
+    pub days_checked: i64,
+    /// Entradas gravadas antes da cadeia existir, sem hash.
+    pub unchained_events: i64,
+    /// Dias encerrados que ainda não têm checkpoint; o fim deles não está protegido.
+    pub unsigned_days: Vec<NaiveDate>,
+    pub first_broken_link: Option<AuditChainBreak>,
+}
+
+impl AuditVerification {
+    pub fn is_intact(&self) -> bool {
+        self.first_broken_link.is_none()
+    }
+}
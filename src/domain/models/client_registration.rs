@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::oauth::{ClientType, OAuthClient};
+
+/// Metadados de um cliente no registro dinâmico (RFC 7591, seção 2).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClientMetadata {
+    pub redirect_uris: Vec<String>,
+    pub client_name: Option<String>,
+    pub token_endpoint_auth_method: Option<String>,
+    pub grant_types: Vec<String>,
+    pub response_types: Vec<String>,
+    pub scope: Option<String>,
+    /// Exige PAR em `/oauth/authorize` (RFC 9126, seção 6).
+    pub require_pushed_authorization_requests: bool,
+}
+
+impl ClientMetadata {
+    /// Metadados efetivos de um cliente já registrado, devolvidos nas respostas do registro.
+    pub fn from_client(client: &OAuthClient) -> Self {
+        let response_types = if client.redirect_uris.is_empty() {
+            Vec::new()
+        } else {
+            vec!["code".to_string()]
+        };
+        let token_endpoint_auth_method = match client.client_type {
+            ClientType::Confidential => "client_secret_basic",
+            ClientType::Public => "none",
+        };
+        ClientMetadata {
+            redirect_uris: client.redirect_uris.clone(),
+            client_name: Some(client.name.clone()),
+            token_endpoint_auth_method: Some(token_endpoint_auth_method.to_string()),
+            grant_types: client.grant_types.clone(),
+            response_types,
+            scope: Some(client.allowed_scopes.join(" ")),
+            require_pushed_authorization_requests: client.require_pushed_authorization_requests,
+        }
+    }
+}
+
+/// Resposta do registro e do endpoint de configuração do cliente (RFC 7591, seção 3.2.1).
+///
+/// O `client_secret` e o `registration_access_token` só existem em texto claro no registro;
+/// o banco guarda apenas os hashes.
+#[derive(Clone, Debug)]
+pub struct ClientInformation {
+    pub client: OAuthClient,
+    pub client_secret: Option<String>,
+    pub registration_access_token: Option<String>,
+}
+
+/// Token emitido por um administrador para autorizar registros dinâmicos na organização.
+#[derive(Clone, Debug)]
+pub struct InitialAccessToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
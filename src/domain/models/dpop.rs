@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Algoritmos aceitos em provas DPoP. Só assinaturas assimétricas fazem sentido: a chave
+/// pública vai no header da prova.
+pub const DPOP_SIGNING_ALGORITHMS: [&str; 4] = ["ES256", "ES384", "RS256", "PS256"];
+
+/// Claims de uma prova DPoP (RFC 9449, seção 4.2).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DpopProofClaims {
+    pub jti: String,
+    /// Método HTTP da requisição que a prova acompanha.
+    pub htm: String,
+    /// URI da requisição, sem query nem fragmento.
+    pub htu: String,
+    pub iat: i64,
+    /// Hash do access token apresentado junto com a prova.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ath: Option<String>,
+}
@@ -0,0 +1,75 @@
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Group {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub organization_id: i32,
+}
+
+#[derive(Clone)]
+pub struct CreateGroup {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Caminho pelo qual um usuário recebeu uma permissão.
+///
+/// `groups` vai do grupo do qual o usuário é membro direto até o grupo que possui o papel;
+/// fica vazio quando o papel foi atribuído diretamente ao usuário.
+#[derive(Clone, Debug)]
+pub struct PermissionGrant {
+    pub permission: String,
+    pub role: String,
+    pub groups: Vec<String>,
+}
+
+/// Grafo de aninhamento de grupos, montado a partir das arestas `(pai, filho)`.
+pub struct GroupGraph {
+    parents: HashMap<i32, Vec<i32>>,
+}
+
+impl GroupGraph {
+    pub fn new(edges: &[(i32, i32)]) -> Self {
+        let mut parents: HashMap<i32, Vec<i32>> = HashMap::new();
+        for (parent, child) in edges {
+            parents.entry(*child).or_default().push(*parent);
+        }
+        GroupGraph { parents }
+    }
+
+    /// Todos os grupos alcançáveis subindo a partir de `start` (incluindo os próprios),
+    /// cada um com o menor caminho desde um dos grupos de partida.
+    pub fn ancestor_paths(&self, start: &[i32]) -> HashMap<i32, Vec<i32>> {
+        let mut paths: HashMap<i32, Vec<i32>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for group_id in start {
+            if !paths.contains_key(group_id) {
+                paths.insert(*group_id, vec![*group_id]);
+                queue.push_back(*group_id);
+            }
+        }
+        while let Some(group_id) = queue.pop_front() {
+            let path = paths[&group_id].clone();
+            for parent in self.parents.get(&group_id).into_iter().flatten() {
+                if !paths.contains_key(parent) {
+                    let mut parent_path = path.clone();
+                    parent_path.push(*parent);
+                    paths.insert(*parent, parent_path);
+                    queue.push_back(*parent);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Indica se colocar `child` dentro de `parent` fecharia um ciclo.
+    pub fn would_create_cycle(&self, parent: i32, child: i32) -> bool {
+        self.ancestor_paths(&[parent]).contains_key(&child)
+    }
+}
@@ -1,3 +1,14 @@
+pub mod audit;
+pub mod client_registration;
+pub mod dpop;
+pub mod group;
+pub mod oauth;
+pub mod oidc;
+pub mod organization;
+pub mod outbox;
+pub mod personal_access_token;
+pub mod role;
 pub mod service_context;
-pub(crate) mod token;
+pub mod session;
+pub mod token;
 pub mod user;
@@ -0,0 +1,356 @@
+use chrono::{DateTime, Utc};
+use ring::constant_time::verify_slices_are_equal;
+use serde::{Deserialize, Serialize};
+
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+pub const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+pub const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+pub const JWT_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:jwt";
+/// Grants aceitos em `/oauth/token`.
+pub const GRANT_TYPES: [&str; 5] = [
+    "authorization_code",
+    "refresh_token",
+    DEVICE_CODE_GRANT_TYPE,
+    "client_credentials",
+    TOKEN_EXCHANGE_GRANT_TYPE,
+];
+
+/// Tipo do cliente OAuth (RFC 6749, seção 2.1).
+///
+/// Clientes públicos (SPAs, apps nativos) não guardam segredo e precisam usar PKCE com `S256`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientType {
+    Public,
+    Confidential,
+}
+
+impl ClientType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClientType::Public => "public",
+            ClientType::Confidential => "confidential",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "public" => Some(ClientType::Public),
+            "confidential" => Some(ClientType::Confidential),
+            _ => None,
+        }
+    }
+
+    /// Grants que um cliente desse tipo pode usar; `client_credentials` e o token exchange
+    /// exigem um cliente confidencial.
+    pub fn supports_grant_type(&self, grant_type: &str) -> bool {
+        match grant_type {
+            "authorization_code" | "refresh_token" | DEVICE_CODE_GRANT_TYPE => true,
+            "client_credentials" | TOKEN_EXCHANGE_GRANT_TYPE => *self == ClientType::Confidential,
+            _ => false,
+        }
+    }
+
+    /// Todos os grants suportados pelo tipo, usados quando o administrador não escolhe nenhum.
+    pub fn default_grant_types(&self) -> Vec<String> {
+        GRANT_TYPES
+            .into_iter()
+            .filter(|grant_type| self.supports_grant_type(grant_type))
+            .map(String::from)
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OAuthClient {
+    pub id: i32,
+    pub client_id: String,
+    pub client_secret_hash: Option<String>,
+    /// Segredo substituído na última rotação, aceito até ser revogado.
+    pub previous_client_secret_hash: Option<String>,
+    /// Hash do token de acesso ao endpoint de configuração, só em clientes registrados dinamicamente.
+    pub registration_access_token_hash: Option<String>,
+    pub name: String,
+    pub client_type: ClientType,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    /// Audiências que o cliente pode pedir no token exchange.
+    pub allowed_audiences: Vec<String>,
+    /// Grants que o cliente pode usar em `/oauth/token`.
+    pub grant_types: Vec<String>,
+    /// Se `/oauth/authorize` só aceita requisições enviadas antes a `/oauth/par`.
+    pub require_pushed_authorization_requests: bool,
+    pub organization_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OAuthClient {
+    /// Resolve o `redirect_uri` da requisição: precisa ser idêntico a um dos registrados e só
+    /// pode ser omitido quando o cliente tem um único URI registrado.
+    pub fn redirect_uri_for(&self, requested: Option<&str>) -> Option<String> {
+        match requested {
+            Some(uri) => self.redirect_uris.iter().find(|r| *r == uri).cloned(),
+            None if self.redirect_uris.len() == 1 => self.redirect_uris.first().cloned(),
+            None => None,
+        }
+    }
+
+    pub fn allows_scope(&self, scope: &str) -> bool {
+        self.allowed_scopes.iter().any(|s| s == scope)
+    }
+
+    pub fn allows_audience(&self, audience: &str) -> bool {
+        self.allowed_audiences.iter().any(|a| a == audience)
+    }
+
+    pub fn allows_grant_type(&self, grant_type: &str) -> bool {
+        self.grant_types.iter().any(|g| g == grant_type)
+    }
+
+    /// Confere o hash de um segredo com o atual e, durante uma rotação, com o anterior. A
+    /// comparação é em tempo constante.
+    pub fn has_secret_hash(&self, secret_hash: &str) -> bool {
+        [&self.client_secret_hash, &self.previous_client_secret_hash]
+            .into_iter()
+            .flatten()
+            .fold(false, |matched, hash| {
+                verify_slices_are_equal(hash.as_bytes(), secret_hash.as_bytes()).is_ok() | matched
+            })
+    }
+}
+
+#[derive(Clone)]
+pub struct CreateOAuthClient {
+    pub name: String,
+    pub client_type: ClientType,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub allowed_audiences: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub require_pushed_authorization_requests: bool,
+}
+
+/// Cliente recém-registrado. O segredo em texto claro só existe aqui; o banco guarda o hash.
+#[derive(Clone, Debug)]
+pub struct RegisteredOAuthClient {
+    pub client: OAuthClient,
+    pub client_secret: Option<String>,
+}
+
+/// Parâmetros de `/oauth/authorize` (RFC 6749, seção 4.1.1, e RFC 7636).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AuthorizationRequest {
+    /// Ausente quando os parâmetros vêm de um `request_uri`.
+    #[serde(default)]
+    pub response_type: String,
+    pub client_id: String,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    /// Repassado ao ID token quando o escopo `openid` é concedido.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Referência aos parâmetros enviados antes a `/oauth/par` (RFC 9126, seção 4). Com ela,
+    /// os demais parâmetros da URL são ignorados.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_uri: Option<String>,
+}
+
+/// Parâmetros de `/oauth/par` (RFC 9126, seção 2.1): os de `/oauth/authorize` mais as
+/// credenciais do cliente.
+#[derive(Clone, Debug, Default)]
+pub struct PushedAuthorizationRequest {
+    pub request: AuthorizationRequest,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// Resposta de `/oauth/par` (RFC 9126, seção 2.2).
+#[derive(Clone, Debug)]
+pub struct PushedAuthorization {
+    pub request_uri: String,
+    pub expires_in: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthorizationCode {
+    pub code_hash: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub nonce: Option<String>,
+    /// Momento em que o usuário se autenticou para aprovar a requisição.
+    pub auth_time: DateTime<Utc>,
+    /// Código criado após o login que ainda aguarda a tela de consentimento.
+    pub consent_pending: bool,
+}
+
+#[derive(Clone)]
+pub struct CreateAuthorizationCode {
+    pub code_hash: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub nonce: Option<String>,
+    pub auth_time: DateTime<Utc>,
+    pub consent_pending: bool,
+}
+
+/// Código de autorização recém-criado. Com `consent_required`, o código só pode ser trocado
+/// depois que o usuário aprovar os escopos com `OAuthService::decide_consent`.
+#[derive(Clone, Debug)]
+pub struct IssuedAuthorizationCode {
+    pub code: String,
+    pub consent_required: bool,
+}
+
+/// Escopos que um usuário já aprovou para um cliente.
+#[derive(Clone, Debug)]
+pub struct Consent {
+    pub user_id: i32,
+    pub oauth_client_id: i32,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Consent {
+    /// Indica se todos os escopos pedidos já foram aprovados.
+    pub fn covers(&self, scope: &str) -> bool {
+        scope
+            .split_whitespace()
+            .all(|scope| self.scopes.iter().any(|s| s == scope))
+    }
+}
+
+/// Estado de um código de dispositivo: aguardando o usuário, aprovado ou negado.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceCodeStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+impl DeviceCodeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeviceCodeStatus::Pending => "pending",
+            DeviceCodeStatus::Approved => "approved",
+            DeviceCodeStatus::Denied => "denied",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(DeviceCodeStatus::Pending),
+            "approved" => Some(DeviceCodeStatus::Approved),
+            "denied" => Some(DeviceCodeStatus::Denied),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DeviceCode {
+    pub device_code_hash: String,
+    /// Código digitado pelo usuário, normalizado (sem hífen, em maiúsculas).
+    pub user_code: String,
+    pub oauth_client_id: i32,
+    pub scope: String,
+    pub status: DeviceCodeStatus,
+    pub user_id: Option<i32>,
+    /// Intervalo mínimo, em segundos, entre duas consultas em `/oauth/token`.
+    pub interval: i32,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct CreateDeviceCode {
+    pub device_code_hash: String,
+    pub user_code: String,
+    pub oauth_client_id: i32,
+    pub scope: String,
+    pub interval: i32,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Parâmetros de `/oauth/device_authorization` (RFC 8628, seção 3.1).
+#[derive(Clone, Debug, Default)]
+pub struct DeviceAuthorizationRequest {
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// Códigos emitidos para o dispositivo. O `user_code` já vem formatado para exibição (`XXXX-XXXX`).
+#[derive(Clone, Debug)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub expires_in: i64,
+    pub interval: i64,
+}
+
+/// Parâmetros de `/oauth/token` (RFC 6749, seções 4.1.3, 4.4.2 e 6, e RFC 8628, seção 3.4).
+#[derive(Clone, Debug, Default)]
+pub struct TokenRequest {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub code_verifier: Option<String>,
+    pub device_code: Option<String>,
+    pub refresh_token: Option<String>,
+    /// Parâmetros do token exchange (RFC 8693, seção 2.1).
+    pub subject_token: Option<String>,
+    pub subject_token_type: Option<String>,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    pub audience: Option<String>,
+    pub requested_token_type: Option<String>,
+    /// Thumbprint da chave provada no header `DPoP`, preenchido pelo handler depois de validar
+    /// a prova; nunca vem do corpo da requisição.
+    pub dpop_jkt: Option<String>,
+}
+
+/// Parâmetros de `/oauth/revoke` (RFC 7009, seção 2.1).
+#[derive(Clone, Debug, Default)]
+pub struct RevocationRequest {
+    pub token: String,
+    /// `access_token` ou `refresh_token`; só define a ordem da busca.
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    /// Só no token exchange.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_token_type: Option<String>,
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::user::User;
+
+pub const OPENID_SCOPE: &str = "openid";
+pub const PROFILE_SCOPE: &str = "profile";
+pub const EMAIL_SCOPE: &str = "email";
+
+/// Claims do usuário liberadas pelos escopos `profile` e `email` (OpenID Connect Core, seção 5.4).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardClaims {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+impl StandardClaims {
+    pub fn for_scopes(user: &User, scopes: &[&str]) -> Self {
+        let mut claims = StandardClaims::default();
+        if scopes.contains(&PROFILE_SCOPE) {
+            claims.preferred_username = Some(user.username.clone());
+        }
+        if scopes.contains(&EMAIL_SCOPE) {
+            claims.email = user.email.clone();
+            claims.email_verified = user.email.as_ref().map(|_| user.email_verified);
+        }
+        claims
+    }
+}
+
+/// Claims de um ID token (OpenID Connect Core, seção 2).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub auth_time: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(flatten)]
+    pub claims: StandardClaims,
+}
+
+/// Resposta de `/userinfo`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserInfo {
+    pub sub: String,
+    #[serde(flatten)]
+    pub claims: StandardClaims,
+}
+
+/// Documento de `/.well-known/openid-configuration` (OpenID Connect Discovery, seção 3).
+#[derive(Clone, Debug, Serialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub device_authorization_endpoint: String,
+    pub pushed_authorization_request_endpoint: String,
+    pub revocation_endpoint: String,
+    pub registration_endpoint: String,
+    pub userinfo_endpoint: String,
+    pub jwks_uri: String,
+    pub scopes_supported: Vec<&'static str>,
+    pub response_types_supported: Vec<&'static str>,
+    pub grant_types_supported: Vec<&'static str>,
+    pub subject_types_supported: Vec<&'static str>,
+    pub id_token_signing_alg_values_supported: Vec<&'static str>,
+    pub token_endpoint_auth_methods_supported: Vec<&'static str>,
+    pub revocation_endpoint_auth_methods_supported: Vec<&'static str>,
+    pub code_challenge_methods_supported: Vec<&'static str>,
+    pub dpop_signing_alg_values_supported: Vec<&'static str>,
+    pub claims_supported: Vec<&'static str>,
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Organization {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct CreateOrganization {
+    pub slug: String,
+    pub name: String,
+}
@@ -0,0 +1,177 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::user::User;
+
+/// Tipo de evento do ciclo de vida dos usuários publicado para os webhooks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum OutboxEventType {
+    #[serde(rename = "user.created")]
+    UserCreated,
+    #[serde(rename = "user.deleted")]
+    UserDeleted,
+    #[serde(rename = "user.email_changed")]
+    UserEmailChanged,
+}
+
+impl OutboxEventType {
+    pub const ALL: [OutboxEventType; 3] = [
+        OutboxEventType::UserCreated,
+        OutboxEventType::UserDeleted,
+        OutboxEventType::UserEmailChanged,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxEventType::UserCreated => "user.created",
+            OutboxEventType::UserDeleted => "user.deleted",
+            OutboxEventType::UserEmailChanged => "user.email_changed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        OutboxEventType::ALL.into_iter().find(|event_type| event_type.as_str() == value)
+    }
+}
+
+/// Evento gravado na mesma transação da mudança que ele descreve.
+#[derive(Clone, Debug, Serialize)]
+pub struct OutboxEvent {
+    pub id: i64,
+    pub organization_id: i32,
+    pub event_type: OutboxEventType,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    /// Quando as entregas para os webhooks inscritos foram criadas; `None` enquanto não foram.
+    pub dispatched_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateOutboxEvent {
+    pub organization_id: i32,
+    pub event_type: OutboxEventType,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub payload: serde_json::Value,
+}
+
+impl CreateOutboxEvent {
+    /// Evento sobre o usuário, com os dados públicos dele; a senha nunca entra no payload.
+    pub fn user(event_type: OutboxEventType, user: &User) -> Self {
+        CreateOutboxEvent {
+            organization_id: user.organization_id,
+            event_type,
+            aggregate_type: "user".to_string(),
+            aggregate_id: user.id.to_string(),
+            payload: serde_json::json!({
+                "id": user.id,
+                "username": user.username,
+                "email": user.email,
+                "email_verified": user.email_verified,
+                "principal_type": user.principal_type,
+                "created_at": user.created_at,
+            }),
+        }
+    }
+
+    /// `user.email_changed`, com o e-mail anterior em `previous_email`.
+    pub fn email_changed(user: &User, previous_email: Option<String>) -> Self {
+        let mut event = CreateOutboxEvent::user(OutboxEventType::UserEmailChanged, user);
+        event.payload["previous_email"] = serde_json::json!(previous_email);
+        event
+    }
+}
+
+/// Endpoint que recebe os eventos da organização. `secret` assina cada entrega e só é mostrado
+/// na criação.
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    pub id: i32,
+    pub organization_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<OutboxEventType>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event_type: OutboxEventType) -> bool {
+        self.event_types.contains(&event_type)
+    }
+}
+
+/// `event_types` vazio inscreve o webhook em todos os tipos.
+#[derive(Clone, Debug)]
+pub struct CreateWebhook {
+    pub url: String,
+    pub event_types: Vec<OutboxEventType>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    /// Aguardando a primeira tentativa ou uma nova tentativa.
+    Pending,
+    Delivered,
+    /// Esgotou as tentativas; só volta a ser enviada se o evento for reenviado.
+    Dead,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Dead => "dead",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(DeliveryStatus::Pending),
+            "delivered" => Some(DeliveryStatus::Delivered),
+            "dead" => Some(DeliveryStatus::Dead),
+            _ => None,
+        }
+    }
+}
+
+/// Entrega de um evento a um webhook.
+#[derive(Clone, Debug)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i32,
+    pub outbox_event_id: i64,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Entrega reservada para envio, com o webhook e o evento dela.
+#[derive(Clone, Debug)]
+pub struct PendingDelivery {
+    pub delivery: WebhookDelivery,
+    pub webhook: Webhook,
+    pub event: OutboxEvent,
+}
+
+/// Filtros da consulta de entregas; os ausentes não restringem.
+#[derive(Clone, Debug, Default)]
+pub struct WebhookDeliveryFilter {
+    pub status: Option<DeliveryStatus>,
+    pub webhook_id: Option<i32>,
+    pub outbox_event_id: Option<i64>,
+}
+
+/// Requisição assinada que leva um evento a um webhook.
+#[derive(Clone, Debug)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
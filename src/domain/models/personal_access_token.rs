@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+
+/// Token criado por um usuário para scripts e CI. Só o hash é guardado; o valor em texto claro
+/// aparece uma única vez, em `IssuedPersonalAccessToken`.
+#[derive(Clone, Debug)]
+pub struct PersonalAccessToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    /// Prefixo e primeiros caracteres do token, para identificá-lo nas listagens.
+    pub token_hint: String,
+    /// Permissões do usuário que o token pode exercer, separadas por espaço.
+    pub scope: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PersonalAccessToken {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CreatePersonalAccessToken {
+    pub name: String,
+    pub scope: String,
+    /// Sem `expires_at`, o token vale até ser revogado.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Token recém-criado, com o único acesso ao valor em texto claro.
+#[derive(Clone, Debug)]
+pub struct IssuedPersonalAccessToken {
+    pub token: String,
+    pub personal_access_token: PersonalAccessToken,
+}
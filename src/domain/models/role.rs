@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Role {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub organization_id: i32,
+    /// Limites de sessão próprios do papel; os ausentes seguem a política do serviço.
+    pub session_policy: RoleSessionPolicy,
+}
+
+/// Sobrescritas da política de sessão para quem tem o papel, como sessões mais curtas para
+/// administradores. Com vários papéis, vale o valor mais restritivo.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RoleSessionPolicy {
+    pub idle_timeout_seconds: Option<i32>,
+    pub max_lifetime_seconds: Option<i32>,
+    pub max_concurrent_sessions: Option<i32>,
+}
+
+#[derive(Clone)]
+pub struct CreateRole {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Permission {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct CreatePermission {
+    pub name: String,
+    pub description: Option<String>,
+}
@@ -0,0 +1,142 @@
+use std::env;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::constants::{
+    ACCESS_TOKEN_TTL_SECONDS, SESSION_IDLE_TIMEOUT_SECONDS, SESSION_LIMIT_ACTION, SESSION_MAX_CONCURRENT,
+    SESSION_MAX_LIFETIME_SECONDS,
+};
+use crate::domain::models::role::Role;
+
+/// Sessão criada a cada login; o `id` vai na claim `sid` do access token.
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub id: String,
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub device_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Tempo sem uso depois do qual a sessão termina, mesmo antes do `expires_at`.
+    pub idle_timeout_seconds: i32,
+}
+
+impl Session {
+    /// Momento em que a sessão termina por falta de uso, se não for usada antes.
+    pub fn idle_expires_at(&self) -> DateTime<Utc> {
+        (self.last_seen_at + Duration::seconds(self.idle_timeout_seconds.into())).min(self.expires_at)
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now && self.idle_expires_at() > now
+    }
+}
+
+/// Dispositivo de onde veio o login, como o usuário o verá em `/auth/sessions`.
+#[derive(Clone, Debug, Default)]
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    /// Nome escolhido pelo usuário, como "notebook do trabalho".
+    pub device_name: Option<String>,
+    /// Hash do token CSRF quando o login é em modo cookie; vai na claim `csrf` do access token.
+    pub csrf_token_hash: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateSession {
+    pub id: String,
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub client: SessionClient,
+    pub expires_at: DateTime<Utc>,
+    pub idle_timeout_seconds: i32,
+}
+
+/// O que fazer com um login que passaria do limite de sessões simultâneas.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SessionLimitAction {
+    /// Revoga as sessões usadas há mais tempo para abrir a nova.
+    #[default]
+    EvictOldest,
+    /// Recusa o login.
+    RejectNew,
+}
+
+impl SessionLimitAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "evict_oldest" => Some(SessionLimitAction::EvictOldest),
+            "reject_new" => Some(SessionLimitAction::RejectNew),
+            _ => None,
+        }
+    }
+}
+
+/// Limites das sessões de login: tempo máximo sem uso (renovado a cada requisição), duração
+/// máxima desde o login e quantidade de sessões ativas por usuário.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionPolicy {
+    pub idle_timeout_seconds: i32,
+    pub max_lifetime_seconds: i32,
+    /// Sem limite quando ausente.
+    pub max_concurrent_sessions: Option<i32>,
+    pub limit_action: SessionLimitAction,
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        SessionPolicy {
+            idle_timeout_seconds: ACCESS_TOKEN_TTL_SECONDS as i32,
+            max_lifetime_seconds: ACCESS_TOKEN_TTL_SECONDS as i32,
+            max_concurrent_sessions: None,
+            limit_action: SessionLimitAction::default(),
+        }
+    }
+}
+
+impl SessionPolicy {
+    /// Lê a política de `SESSION_IDLE_TIMEOUT_SECONDS`, `SESSION_MAX_LIFETIME_SECONDS`,
+    /// `SESSION_MAX_CONCURRENT` e `SESSION_LIMIT_ACTION` (`evict_oldest` ou `reject_new`).
+    /// Variáveis ausentes ou inválidas mantêm o padrão: uma hora, sem limite de sessões.
+    pub fn from_env() -> Self {
+        let positive = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<i32>().ok())
+                .filter(|value| *value > 0)
+        };
+        let default = SessionPolicy::default();
+        SessionPolicy {
+            idle_timeout_seconds: positive(SESSION_IDLE_TIMEOUT_SECONDS).unwrap_or(default.idle_timeout_seconds),
+            max_lifetime_seconds: positive(SESSION_MAX_LIFETIME_SECONDS).unwrap_or(default.max_lifetime_seconds),
+            max_concurrent_sessions: positive(SESSION_MAX_CONCURRENT),
+            limit_action: env::var(SESSION_LIMIT_ACTION)
+                .ok()
+                .and_then(|value| SessionLimitAction::parse(&value))
+                .unwrap_or(default.limit_action),
+        }
+    }
+
+    /// Política de quem tem os papéis: cada limite é o menor entre o do serviço e os dos papéis.
+    pub fn for_roles(&self, roles: &[Role]) -> SessionPolicy {
+        let mut policy = self.clone();
+        for role in roles {
+            let overrides = &role.session_policy;
+            if let Some(idle) = overrides.idle_timeout_seconds {
+                policy.idle_timeout_seconds = policy.idle_timeout_seconds.min(idle);
+            }
+            if let Some(lifetime) = overrides.max_lifetime_seconds {
+                policy.max_lifetime_seconds = policy.max_lifetime_seconds.min(lifetime);
+            }
+            if let Some(max) = overrides.max_concurrent_sessions {
+                policy.max_concurrent_sessions = Some(policy.max_concurrent_sessions.map_or(max, |m| m.min(max)));
+            }
+        }
+        policy
+    }
+}
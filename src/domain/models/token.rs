@@ -1,7 +1,132 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Serialize, Deserialize)]
+use crate::domain::models::user::PrincipalType;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Claim {
     pub sub: String,
     pub exp: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// ID da organização (tenant) à qual o `sub` pertence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<i32>,
+    /// Cliente OAuth para o qual o token foi emitido.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Identificador único do token, usado para revogá-lo antes do `exp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    /// Serviço ao qual o token se destina; definido no token exchange.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    /// Quem age em nome do `sub` (RFC 8693, seção 4.1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+    /// Chave à qual o token está vinculado (RFC 9449, seção 6); sem ela, o token é bearer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cnf: Option<Confirmation>,
+    /// ID do personal access token apresentado; ausente nos tokens de sessão e nos emitidos a clientes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pat: Option<i32>,
+    /// Se o `sub` é uma pessoa ou uma conta de serviço; ausente nos tokens emitidos a clientes
+    /// pelo `client_credentials`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal_type: Option<PrincipalType>,
+    /// Sessão do login que emitiu o token; revogá-la invalida o token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    /// Hash do token CSRF dos logins em modo cookie; o token precisa acompanhar as requisições
+    /// que alteram estado quando este token vem do cookie de sessão.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub csrf: Option<String>,
+}
+
+/// Confirmação de posse de chave: `jkt` é o thumbprint SHA-256 (RFC 7638) da chave DPoP.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Confirmation {
+    pub jkt: String,
+}
+
+/// Parte que age em nome do `sub`. Delegações em cadeia aninham o ator anterior em `act`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Actor {
+    pub sub: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Box<Actor>>,
+}
+
+impl Claim {
+    pub fn new(sub: String, exp: i64) -> Self {
+        Claim {
+            sub,
+            exp,
+            roles: Vec::new(),
+            permissions: Vec::new(),
+            scope: None,
+            tenant: None,
+            client_id: None,
+            jti: None,
+            aud: None,
+            act: None,
+            cnf: None,
+            pat: None,
+            principal_type: None,
+            sid: None,
+            csrf: None,
+        }
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|p| p == permission)
+    }
+
+    // `scope` segue o formato do OAuth 2.0: valores separados por espaço
+    pub fn scopes(&self) -> Vec<&str> {
+        self.scope
+            .as_deref()
+            .map(|s| s.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes().contains(&scope)
+    }
+}
+
+/// Refresh token emitido a um cliente OAuth. Só o hash é guardado.
+#[derive(Clone, Debug)]
+pub struct RefreshToken {
+    pub token_hash: String,
+    /// Compartilhado por todos os tokens gerados por rotação a partir da mesma autorização.
+    pub family_id: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub scope: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// Thumbprint da chave DPoP à qual o token está vinculado.
+    pub dpop_jkt: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct CreateRefreshToken {
+    pub token_hash: String,
+    pub family_id: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub scope: String,
+    pub expires_at: DateTime<Utc>,
+    pub dpop_jkt: Option<String>,
 }
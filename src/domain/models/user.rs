@@ -1,22 +1,126 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
-#[derive(Clone, Deserialize)]
+use serde::{Deserialize, Serialize};
+
+/// Tipo de principal. Contas de serviço representam automações: não têm senha, então não
+/// fazem login, e se autenticam com personal access tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrincipalType {
+    #[default]
+    User,
+    Service,
+}
+
+impl PrincipalType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrincipalType::User => "user",
+            PrincipalType::Service => "service",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(PrincipalType::User),
+            "service" => Some(PrincipalType::Service),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct User {
     pub id: i32,
     pub username: String,
-    pub password: String,
-    pub email: String,
+    /// Hash da senha; contas de serviço não têm.
+    pub password: Option<String>,
+    pub email: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub organization_id: i32,
+    pub email_verified: bool,
+    pub principal_type: PrincipalType,
+    /// Usuário responsável por uma conta de serviço; `None` quando ela é da organização.
+    pub owner_user_id: Option<i32>,
+    pub disabled_at: Option<DateTime<Utc>>,
+    /// O próximo login precisa passar pela troca de senha.
+    pub password_reset_required: bool,
+    /// Logins errados seguidos desde o último certo ou desbloqueio.
+    pub failed_login_attempts: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl User {
+    /// Situação atual da conta; uma conta desativada continua `disabled` mesmo bloqueada.
+    pub fn status(&self) -> UserStatus {
+        if self.disabled_at.is_some() {
+            UserStatus::Disabled
+        } else if self.locked_until.is_some_and(|locked_until| locked_until > Utc::now()) {
+            UserStatus::Locked
+        } else {
+            UserStatus::Active
+        }
+    }
 }
+
+/// Situação de uma conta. `locked` é temporário: vem de logins errados seguidos e acaba sozinho
+/// em `locked_until`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserStatus {
+    Active,
+    Disabled,
+    Locked,
+}
+
+/// Campo de ordenação da listagem de usuários.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    #[default]
+    CreatedAt,
+    Username,
+    Email,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Filtros e ordenação da listagem de usuários; os filtros ausentes não restringem. Os
+/// prefixos não diferenciam maiúsculas de minúsculas.
+#[derive(Clone, Debug, Default)]
+pub struct UserFilter {
+    pub username_prefix: Option<String>,
+    pub email_prefix: Option<String>,
+    pub created_since: Option<DateTime<Utc>>,
+    pub created_until: Option<DateTime<Utc>>,
+    pub status: Option<UserStatus>,
+    pub principal_type: Option<PrincipalType>,
+    pub sort: UserSortField,
+    pub direction: SortDirection,
+}
+/// `organization` é o slug da organização; `None` usa a organização padrão.
 #[derive(Clone)]
 pub struct CreateUser {
     pub username: String,
     pub password: String,
     pub email: String,
+    pub organization: Option<String>,
 }
 
 #[derive(Clone)]
 pub struct LoginUser {
     pub username: String,
     pub password: String,
+    pub organization: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateServiceAccount {
+    pub name: String,
+    pub owner_user_id: Option<i32>,
 }
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::domain::models::audit::{
+    AuditCheckpoint, AuditCheckpointClaims, AuditEvent, AuditEventFilter, CreateAuditEvent,
+};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    /// Grava o evento encadeado à última entrada do dia, com `prev_hash` e `hash` calculados
+    /// enquanto nenhuma outra entrada pode ser gravada.
+    async fn create(&self, new_event: &CreateAuditEvent) -> RepositoryResult<AuditEvent>;
+    /// Eventos da organização que atendem ao filtro, dos mais recentes para os mais antigos.
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &AuditEventFilter,
+        params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<AuditEvent>>;
+    /// Até `limit` entradas de todas as organizações com ID maior que `after_id`, em ordem de ID,
+    /// a partir do dia `from`.
+    async fn list_chain(
+        &self,
+        from: Option<NaiveDate>,
+        after_id: i64,
+        limit: i64,
+    ) -> RepositoryResult<Vec<AuditEvent>>;
+    /// Entradas de um dia (UTC), em ordem de ID.
+    async fn list_day(&self, day: NaiveDate) -> RepositoryResult<Vec<AuditEvent>>;
+    /// Dias anteriores a `before` com entradas encadeadas e sem checkpoint, em ordem.
+    async fn days_without_checkpoint(&self, before: NaiveDate) -> RepositoryResult<Vec<NaiveDate>>;
+    async fn create_checkpoint(
+        &self,
+        claims: &AuditCheckpointClaims,
+        signature: &str,
+    ) -> RepositoryResult<AuditCheckpoint>;
+    /// Checkpoints a partir do dia `from`, em ordem.
+    async fn list_checkpoints(
+        &self,
+        from: Option<NaiveDate>,
+    ) -> RepositoryResult<Vec<AuditCheckpoint>>;
+}
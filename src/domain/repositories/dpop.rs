@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait DpopRepository: Send + Sync {
+    /// Registra uma prova até `expires_at` e retorna `false` se ela já tiver sido registrada.
+    async fn record_proof(&self, proof_hash: &str, expires_at: DateTime<Utc>) -> RepositoryResult<bool>;
+    /// Remove as provas expiradas e retorna quantas foram removidas.
+    async fn delete_expired_proofs(&self) -> RepositoryResult<usize>;
+}
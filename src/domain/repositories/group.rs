@@ -0,0 +1,23 @@
+use crate::domain::models::group::{CreateGroup, Group};
+use crate::domain::models::role::Role;
+use crate::domain::repositories::repository::RepositoryResult;
+use async_trait::async_trait;
+
+/// Todas as operações são restritas à organização (tenant) informada.
+#[async_trait]
+pub trait GroupRepository: Send + Sync {
+    async fn create_group(&self, organization_id: i32, new_group: &CreateGroup) -> RepositoryResult<Group>;
+    async fn list_groups(&self, organization_id: i32) -> RepositoryResult<Vec<Group>>;
+    async fn delete_group(&self, organization_id: i32, group_id: i32) -> RepositoryResult<()>;
+    async fn add_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> RepositoryResult<()>;
+    async fn remove_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> RepositoryResult<()>;
+    async fn get_user_group_ids(&self, organization_id: i32, user_id: i32) -> RepositoryResult<Vec<i32>>;
+    /// Verifica o ciclo e grava a aresta na mesma transação. Retorna `false`, sem gravar nada,
+    /// se a inclusão fechar um ciclo.
+    async fn add_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> RepositoryResult<bool>;
+    async fn remove_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> RepositoryResult<()>;
+    async fn list_subgroup_edges(&self, organization_id: i32) -> RepositoryResult<Vec<(i32, i32)>>;
+    async fn assign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> RepositoryResult<()>;
+    async fn unassign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> RepositoryResult<()>;
+    async fn get_groups_roles(&self, organization_id: i32, group_ids: Vec<i32>) -> RepositoryResult<Vec<(i32, Role)>>;
+}
@@ -1,2 +1,12 @@
+pub mod audit;
+pub mod dpop;
+pub mod group;
+pub mod oauth;
+pub mod organization;
+pub mod outbox;
+pub mod personal_access_token;
 pub mod repository;
+pub mod role;
+pub mod session;
+pub mod token;
 pub mod user;
@@ -0,0 +1,97 @@
+use crate::domain::models::oauth::{
+    AuthorizationCode, AuthorizationRequest, Consent, CreateAuthorizationCode, CreateDeviceCode, CreateOAuthClient, DeviceCode,
+    DeviceCodeStatus, OAuthClient,
+};
+use crate::domain::repositories::repository::RepositoryResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait OAuthRepository: Send + Sync {
+    async fn create_client(
+        &self,
+        organization_id: i32,
+        client_id: &str,
+        client_secret_hash: Option<&str>,
+        registration_access_token_hash: Option<&str>,
+        new_client: &CreateOAuthClient,
+    ) -> RepositoryResult<OAuthClient>;
+    /// Atualiza o nome, os redirect URIs, os escopos e os grants de um cliente; o tipo não muda.
+    async fn update_client(&self, id: i32, client: &CreateOAuthClient) -> RepositoryResult<OAuthClient>;
+    async fn list_clients(&self, organization_id: i32) -> RepositoryResult<Vec<OAuthClient>>;
+    async fn delete_client(&self, organization_id: i32, id: i32) -> RepositoryResult<()>;
+    async fn get_client(&self, client_id: &str) -> RepositoryResult<OAuthClient>;
+    /// Guarda o novo hash e mantém o atual como segredo anterior. Só vale para clientes confidenciais.
+    async fn rotate_client_secret(&self, organization_id: i32, id: i32, client_secret_hash: &str) -> RepositoryResult<OAuthClient>;
+    async fn revoke_previous_client_secret(&self, organization_id: i32, id: i32) -> RepositoryResult<()>;
+    async fn create_authorization_code(&self, new_code: &CreateAuthorizationCode) -> RepositoryResult<()>;
+    /// Marca o código como usado e o retorna; falha se ele não existir, já tiver sido usado ou
+    /// ainda aguardar consentimento.
+    async fn consume_authorization_code(&self, code_hash: &str) -> RepositoryResult<AuthorizationCode>;
+    /// Libera um código que aguardava consentimento, com uma nova validade, e o retorna.
+    async fn approve_authorization_code(
+        &self,
+        code_hash: &str,
+        oauth_client_id: i32,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<AuthorizationCode>;
+    /// Remove um código que aguardava consentimento e retorna quantos foram removidos.
+    async fn delete_pending_authorization_code(&self, code_hash: &str, oauth_client_id: i32) -> RepositoryResult<usize>;
+    async fn create_pushed_authorization_request(
+        &self,
+        request_uri_hash: &str,
+        oauth_client_id: i32,
+        request: &AuthorizationRequest,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<()>;
+    /// Busca os parâmetros de um `request_uri` do cliente que ainda não expirou.
+    async fn get_pushed_authorization_request(
+        &self,
+        request_uri_hash: &str,
+        oauth_client_id: i32,
+    ) -> RepositoryResult<AuthorizationRequest>;
+    /// Remove um `request_uri` do cliente que ainda não expirou e retorna quantos foram removidos.
+    async fn delete_pushed_authorization_request(&self, request_uri_hash: &str, oauth_client_id: i32) -> RepositoryResult<usize>;
+    async fn get_consent(&self, user_id: i32, oauth_client_id: i32) -> RepositoryResult<Option<Consent>>;
+    /// Cria ou substitui os escopos aprovados pelo usuário para o cliente.
+    async fn save_consent(&self, user_id: i32, oauth_client_id: i32, scopes: &[String]) -> RepositoryResult<Consent>;
+    /// Lista os consentimentos do usuário com os clientes, ordenados pelo nome do cliente.
+    async fn list_consents(&self, user_id: i32) -> RepositoryResult<Vec<(Consent, OAuthClient)>>;
+    /// Remove um consentimento e retorna quantos foram removidos.
+    async fn delete_consent(&self, user_id: i32, oauth_client_id: i32) -> RepositoryResult<usize>;
+    async fn create_device_code(&self, new_code: &CreateDeviceCode) -> RepositoryResult<()>;
+    /// Busca um código de dispositivo pendente e não expirado pelo `user_code`, com o cliente que o pediu.
+    async fn get_pending_device_code(&self, user_code: &str) -> RepositoryResult<(DeviceCode, OAuthClient)>;
+    /// Aprova ou nega um código pendente e não expirado; falha se ele já tiver sido decidido.
+    async fn decide_device_code(
+        &self,
+        user_code: &str,
+        user_id: Option<i32>,
+        status: DeviceCodeStatus,
+    ) -> RepositoryResult<DeviceCode>;
+    /// Registra uma consulta em `/oauth/token` e retorna o código como estava antes dela.
+    async fn poll_device_code(&self, device_code_hash: &str) -> RepositoryResult<DeviceCode>;
+    /// Aumenta o intervalo mínimo entre consultas após um `slow_down`.
+    async fn slow_down_device_code(&self, device_code_hash: &str, increment: i32) -> RepositoryResult<()>;
+    /// Remove e retorna um código aprovado, para que ele seja trocado por um token uma única vez.
+    async fn consume_device_code(&self, device_code_hash: &str) -> RepositoryResult<DeviceCode>;
+    async fn create_initial_access_token(
+        &self,
+        organization_id: i32,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<()>;
+    /// Retorna a organização de um token de acesso inicial que ainda não expirou.
+    async fn get_initial_access_token_organization(&self, token_hash: &str) -> RepositoryResult<i32>;
+    /// Remove um token de acesso inicial que ainda não expirou e retorna a organização dele;
+    /// falha se outro registro já o tiver usado.
+    async fn consume_initial_access_token(&self, token_hash: &str) -> RepositoryResult<i32>;
+    /// Retorna quantas falhas ainda não expiradas foram registradas para `subject`.
+    async fn count_failed_attempts(&self, subject: &str) -> RepositoryResult<i32>;
+    /// Registra uma falha para `subject` e retorna o total atual. Se as falhas anteriores já
+    /// expiraram, a contagem recomeça e passa a expirar em `expires_at`.
+    async fn record_failed_attempt(&self, subject: &str, expires_at: DateTime<Utc>) -> RepositoryResult<i32>;
+    /// Remove os códigos de autorização e de dispositivo, os `request_uri`, os tokens de acesso
+    /// inicial e as falhas registradas já expirados e retorna quantos foram removidos.
+    async fn delete_expired_codes(&self) -> RepositoryResult<usize>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::models::organization::{CreateOrganization, Organization};
+use crate::domain::repositories::repository::RepositoryResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+    async fn create(&self, new_organization: &CreateOrganization) -> RepositoryResult<Organization>;
+    async fn list(&self) -> RepositoryResult<Vec<Organization>>;
+    async fn get_by_slug(&self, slug: &str) -> RepositoryResult<Organization>;
+}
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::models::outbox::{
+    CreateWebhook, OutboxEvent, PendingDelivery, Webhook, WebhookDelivery, WebhookDeliveryFilter,
+};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+
+/// Os eventos são gravados pelo `UserRepository`, na transação da mudança; aqui ficam os
+/// webhooks e as entregas.
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    async fn get_event(&self, organization_id: i32, id: i64) -> RepositoryResult<OutboxEvent>;
+    async fn create_webhook(
+        &self,
+        organization_id: i32,
+        new_webhook: &CreateWebhook,
+        secret: &str,
+    ) -> RepositoryResult<Webhook>;
+    async fn list_webhooks(&self, organization_id: i32) -> RepositoryResult<Vec<Webhook>>;
+    /// Remove o webhook com as entregas dele e retorna quantos foram removidos.
+    async fn delete_webhook(&self, organization_id: i32, id: i32) -> RepositoryResult<usize>;
+    /// Cria as entregas de até `limit` eventos ainda não distribuídos, uma por webhook da
+    /// organização inscrito no tipo, e marca os eventos como distribuídos. Retorna quantos
+    /// eventos foram distribuídos.
+    async fn fan_out(&self, limit: i64) -> RepositoryResult<usize>;
+    /// Reserva até `limit` entregas pendentes já vencidas, adiando a próxima tentativa delas por
+    /// `lease` para que outra instância não as envie ao mesmo tempo.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> RepositoryResult<Vec<PendingDelivery>>;
+    async fn mark_delivered(&self, id: i64) -> RepositoryResult<usize>;
+    /// Conta a tentativa que falhou. Com `next_attempt_at` a entrega volta a ser tentada nesse
+    /// momento; sem ele, fica como `dead`.
+    async fn mark_failed(
+        &self,
+        id: i64,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> RepositoryResult<usize>;
+    /// Entregas dos webhooks da organização que atendem ao filtro, das mais recentes para as
+    /// mais antigas.
+    async fn list_deliveries(
+        &self,
+        organization_id: i32,
+        filter: &WebhookDeliveryFilter,
+        params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<WebhookDelivery>>;
+    /// Volta as entregas do evento para pendentes, sem tentativas, e cria as que faltam para os
+    /// webhooks inscritos hoje. Retorna quantas entregas ficaram pendentes.
+    async fn replay(&self, organization_id: i32, outbox_event_id: i64) -> RepositoryResult<usize>;
+}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::domain::models::personal_access_token::{CreatePersonalAccessToken, PersonalAccessToken};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait PersonalAccessTokenRepository: Send + Sync {
+    async fn create(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        token_hash: &str,
+        token_hint: &str,
+        new_token: &CreatePersonalAccessToken,
+    ) -> RepositoryResult<PersonalAccessToken>;
+    async fn list(&self, user_id: i32) -> RepositoryResult<Vec<PersonalAccessToken>>;
+    async fn get(&self, id: i32) -> RepositoryResult<PersonalAccessToken>;
+    async fn get_by_hash(&self, token_hash: &str) -> RepositoryResult<PersonalAccessToken>;
+    /// Registra o uso do token agora.
+    async fn touch(&self, id: i32) -> RepositoryResult<()>;
+    /// Remove um token do usuário e retorna quantos foram removidos (0 se for de outro usuário).
+    async fn delete(&self, user_id: i32, id: i32) -> RepositoryResult<usize>;
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::domain::error::{RepositoryError};
+use crate::domain::error::{CommonError, RepositoryError};
 
 pub type RepositoryResult<T> = Result<T, RepositoryError>;
 
@@ -11,6 +11,8 @@ pub struct ResultPaging<T> {
 
 pub const DEFAULT_OFFSET: Option<i64> = Some(0);
 pub const DEFAULT_LIMIT: Option<i64> = Some(25);
+/// Maior página aceita pelas listagens paginadas.
+pub const MAX_LIMIT: i64 = 100;
 
 pub trait QueryParams: Send + Sync {
     fn limit(&self) -> i64;
@@ -31,3 +33,14 @@ impl QueryParams for QueryParamsImpl {
         self.offset.or(DEFAULT_OFFSET).unwrap_or_default()
     }
 }
+
+/// Recusa, com código 400, páginas fora de `1..=MAX_LIMIT` ou com `offset` negativo.
+pub fn validate_page(params: &dyn QueryParams) -> Result<(), CommonError> {
+    if !(1..=MAX_LIMIT).contains(&params.limit()) || params.offset() < 0 {
+        return Err(CommonError {
+            message: format!("limit must be between 1 and {} and offset must not be negative", MAX_LIMIT),
+            code: 400,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::domain::models::role::{CreatePermission, CreateRole, Permission, Role, RoleSessionPolicy};
+use crate::domain::repositories::repository::RepositoryResult;
+use async_trait::async_trait;
+
+/// Papéis e atribuições são restritos à organização (tenant) informada; permissões são globais.
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+    async fn create_role(&self, organization_id: i32, new_role: &CreateRole) -> RepositoryResult<Role>;
+    async fn list_roles(&self, organization_id: i32) -> RepositoryResult<Vec<Role>>;
+    async fn get_role_by_name(&self, organization_id: i32, name: &str) -> RepositoryResult<Role>;
+    async fn delete_role(&self, organization_id: i32, role_id: i32) -> RepositoryResult<()>;
+    /// Substitui as sobrescritas da política de sessão do papel.
+    async fn set_role_session_policy(
+        &self,
+        organization_id: i32,
+        role_id: i32,
+        policy: &RoleSessionPolicy,
+    ) -> RepositoryResult<Role>;
+    async fn create_permission(&self, new_permission: &CreatePermission) -> RepositoryResult<Permission>;
+    async fn list_permissions(&self) -> RepositoryResult<Vec<Permission>>;
+    async fn get_role_permissions(&self, organization_id: i32, role_id: i32) -> RepositoryResult<Vec<Permission>>;
+    async fn grant_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> RepositoryResult<()>;
+    async fn revoke_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> RepositoryResult<()>;
+    async fn assign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> RepositoryResult<()>;
+    async fn unassign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> RepositoryResult<()>;
+    async fn get_user_roles(&self, organization_id: i32, user_id: i32) -> RepositoryResult<Vec<Role>>;
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+
+use crate::domain::models::session::{CreateSession, Session, SessionLimitAction};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create(&self, new_session: &CreateSession) -> RepositoryResult<Session>;
+    /// Abre a sessão sem passar de `max_concurrent` sessões ativas do usuário. A contagem e a
+    /// gravação acontecem numa transação que trava a linha do usuário, para que logins
+    /// simultâneos não passem do limite: as sessões abertas há mais tempo são revogadas
+    /// (`EvictOldest`) ou a sessão não é aberta e o retorno é `None` (`RejectNew`).
+    async fn create_within_limit(
+        &self,
+        new_session: &CreateSession,
+        max_concurrent: i32,
+        limit_action: SessionLimitAction,
+    ) -> RepositoryResult<Option<Session>>;
+    /// Sessões do usuário ainda não revogadas, expiradas nem paradas por mais que o tempo máximo
+    /// sem uso, das usadas mais recentemente para as mais antigas.
+    async fn list_active(&self, user_id: i32) -> RepositoryResult<Vec<Session>>;
+    /// Atualiza `last_seen_at` de uma sessão ativa e a retorna; falha se ela tiver sido revogada,
+    /// tiver expirado ou tiver ficado sem uso por mais que `idle_timeout_seconds`.
+    async fn touch(&self, id: &str) -> RepositoryResult<Session>;
+    /// Revoga uma sessão ativa do usuário e retorna quantas foram revogadas.
+    async fn revoke(&self, user_id: i32, id: &str) -> RepositoryResult<usize>;
+    /// Revoga todas as sessões ativas do usuário e retorna quantas foram revogadas.
+    async fn revoke_all(&self, user_id: i32) -> RepositoryResult<usize>;
+    /// Remove as sessões expiradas e retorna quantas foram removidas.
+    async fn delete_expired(&self) -> RepositoryResult<usize>;
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::token::{CreateRefreshToken, RefreshToken};
+use crate::domain::repositories::repository::RepositoryResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait TokenRepository: Send + Sync {
+    async fn create_refresh_token(&self, new_token: &CreateRefreshToken) -> RepositoryResult<()>;
+    async fn get_refresh_token(&self, token_hash: &str) -> RepositoryResult<RefreshToken>;
+    /// Marca o token como usado e o retorna; falha se ele já tiver sido usado ou revogado.
+    async fn use_refresh_token(&self, token_hash: &str) -> RepositoryResult<RefreshToken>;
+    /// Revoga todos os refresh tokens da família ainda não revogados.
+    async fn revoke_refresh_token_family(&self, family_id: &str) -> RepositoryResult<()>;
+    /// Revoga os refresh tokens que um cliente recebeu de um usuário.
+    async fn revoke_user_refresh_tokens(&self, user_id: i32, oauth_client_id: i32) -> RepositoryResult<()>;
+    /// Adiciona o `jti` de um access token à lista de revogados até `expires_at`.
+    async fn revoke_jti(&self, jti: &str, expires_at: DateTime<Utc>) -> RepositoryResult<()>;
+    async fn is_jti_revoked(&self, jti: &str) -> RepositoryResult<bool>;
+    /// Remove os refresh tokens e as revogações de `jti` expirados e retorna quantos foram removidos.
+    async fn delete_expired(&self) -> RepositoryResult<usize>;
+}
@@ -1,9 +1,51 @@
-use crate::domain::models::user::{CreateUser, LoginUser, User};
-use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::models::user::{CreateServiceAccount, CreateUser, LoginUser, User, UserFilter};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
 use async_trait::async_trait;
+use chrono::Duration;
 
+/// Todas as operações são restritas à organização (tenant) informada. As que criam, removem ou
+/// mudam o e-mail de um usuário gravam o evento correspondente no outbox na mesma transação.
 #[async_trait]
 pub trait UserRepository: Send + Sync {
-    async fn create(&self, new_user: &CreateUser) -> RepositoryResult<User>;
-    async fn get(&self, login_user: &LoginUser) -> RepositoryResult<User>;
+    async fn create(&self, organization_id: i32, new_user: &CreateUser) -> RepositoryResult<User>;
+    async fn get(&self, organization_id: i32, login_user: &LoginUser) -> RepositoryResult<User>;
+    async fn get_by_username(&self, organization_id: i32, username: &str) -> RepositoryResult<User>;
+    async fn get_by_id(&self, organization_id: i32, user_id: i32) -> RepositoryResult<User>;
+    /// Troca o e-mail, que volta a ficar não verificado.
+    async fn update_email(&self, organization_id: i32, user_id: i32, email: &str) -> RepositoryResult<User>;
+    /// Remove o usuário ou a conta de serviço e retorna quantos foram removidos.
+    async fn delete(&self, organization_id: i32, user_id: i32) -> RepositoryResult<usize>;
+    /// Usuários e contas de serviço que atendem ao filtro, na ordem pedida.
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &UserFilter,
+        params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<User>>;
+    /// Troca o hash da senha e desfaz a exigência de troca.
+    async fn update_password(&self, organization_id: i32, user_id: i32, password: &str) -> RepositoryResult<User>;
+    /// Desativa (`disabled = true`) ou reativa a conta.
+    async fn set_disabled(&self, organization_id: i32, user_id: i32, disabled: bool) -> RepositoryResult<User>;
+    /// Exige a troca de senha no próximo login.
+    async fn require_password_reset(&self, organization_id: i32, user_id: i32) -> RepositoryResult<User>;
+    /// Zera os logins errados e desfaz o bloqueio.
+    async fn unlock(&self, organization_id: i32, user_id: i32) -> RepositoryResult<User>;
+    /// Conta um login errado para o usuário, se ele existir e não estiver bloqueado. Ao chegar em
+    /// `max_attempts`, bloqueia a conta por `lockout` e zera a contagem. Retorna quantos usuários
+    /// foram atualizados.
+    async fn record_failed_login(
+        &self,
+        organization_id: i32,
+        username: &str,
+        max_attempts: i32,
+        lockout: Duration,
+    ) -> RepositoryResult<usize>;
+    async fn create_service_account(
+        &self,
+        organization_id: i32,
+        new_account: &CreateServiceAccount,
+    ) -> RepositoryResult<User>;
+    async fn list_service_accounts(&self, organization_id: i32) -> RepositoryResult<Vec<User>>;
+    /// Remove uma conta de serviço e retorna quantas foram removidas (0 se o ID for de um usuário).
+    async fn delete_service_account(&self, organization_id: i32, user_id: i32) -> RepositoryResult<usize>;
 }
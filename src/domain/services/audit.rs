@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::audit::{
+    AuditEvent, AuditEventFilter, AuditVerification, CreateAuditEvent,
+};
+use crate::domain::repositories::repository::{QueryParams, ResultPaging};
+
+#[async_trait]
+pub trait AuditService: Sync + Send {
+    /// Registra um evento de autenticação ou de administração, encadeado à entrada anterior do
+    /// mesmo dia pelo hash dela.
+    ///
+    /// # Parâmetros
+    /// - `event`: Ação, resultado, ator, alvo, IP, user agent e detalhes do evento.
+    ///
+    /// # Retornos
+    /// - `Result<AuditEvent, CommonError>`: Retorna o evento registrado em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::audit::{AuditAction, AuditOutcome, CreateAuditEvent};
+    /// use auth_service::domain::services::audit::AuditService;
+    ///  async fn example_usage(service: &impl AuditService) {
+    ///     let event = CreateAuditEvent::new(AuditAction::UserLogin, AuditOutcome::Failure)
+    ///         .organization(Some(1))
+    ///         .details(serde_json::json!({ "username": "alice" }));
+    ///
+    ///     match service.record(event).await {
+    ///         Ok(event) => println!("Evento {} registrado", event.id),
+    ///         Err(e) => eprintln!("Erro ao registrar o evento: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn record(&self, event: CreateAuditEvent) -> Result<AuditEvent, CommonError>;
+    /// Lista uma página dos eventos de uma organização, dos mais recentes para os mais antigos.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) dos eventos.
+    /// - `filter`: Ação, resultado, ator, alvo e intervalo de datas; os campos vazios não filtram.
+    /// - `params`: Limite e deslocamento da página.
+    ///
+    /// # Retornos
+    /// - `Result<ResultPaging<AuditEvent>, CommonError>`: Retorna a página e o total de eventos que atendem ao filtro ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se o limite não estiver entre 1 e 100 ou o deslocamento for negativo.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::audit::{AuditEventFilter, AuditOutcome};
+    /// use auth_service::domain::repositories::repository::QueryParamsImpl;
+    /// use auth_service::domain::services::audit::AuditService;
+    ///  async fn example_usage(service: &impl AuditService) {
+    ///     let filter = AuditEventFilter {
+    ///         outcome: Some(AuditOutcome::Failure),
+    ///         ..Default::default()
+    ///     };
+    ///     let params = QueryParamsImpl { limit: Some(50), offset: None };
+    ///
+    ///     match service.list(1, &filter, &params).await {
+    ///         Ok(page) => println!("{} de {} eventos", page.items.len(), page.total),
+    ///         Err(e) => eprintln!("Erro ao listar os eventos: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &AuditEventFilter,
+        params: &dyn QueryParams,
+    ) -> Result<ResultPaging<AuditEvent>, CommonError>;
+    /// Assina um checkpoint para cada dia já encerrado que ainda não tem um. O dia é conferido
+    /// antes; um dia com a cadeia quebrada não é assinado e a quebra vai para o log.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantos checkpoints foram criados ou um `CommonError` em caso de falha.
+    async fn create_checkpoints(&self) -> Result<usize, CommonError>;
+    /// Percorre o log de todas as organizações, entrada a entrada, conferindo o encadeamento de
+    /// cada dia e os checkpoints assinados, e para na primeira quebra.
+    ///
+    /// # Parâmetros
+    /// - `from`: Primeiro dia (UTC) verificado; `None` verifica o log inteiro.
+    ///
+    /// # Retornos
+    /// - `Result<AuditVerification, CommonError>`: Retorna o resumo da verificação, com a primeira quebra em
+    ///   `first_broken_link`, ou um `CommonError` se o log não puder ser lido.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::audit::AuditService;
+    ///  async fn example_usage(service: &impl AuditService) {
+    ///     match service.verify(None).await {
+    ///         Ok(report) => match report.first_broken_link {
+    ///             Some(broken) => eprintln!("Log adulterado em {:?}: {}", broken.event_id, broken.reason),
+    ///             None => println!("{} entradas conferidas", report.events_checked),
+    ///         },
+    ///         Err(e) => eprintln!("Erro ao verificar o log: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn verify(&self, from: Option<NaiveDate>) -> Result<AuditVerification, CommonError>;
+}
@@ -0,0 +1,9 @@
+use crate::domain::models::audit::AuditEvent;
+
+/// Envia os eventos de auditoria já gravados para fora do serviço (arquivo JSON Lines ou syslog
+/// de um SIEM).
+pub trait AuditExportService: Sync + Send {
+    /// Entrega o evento para envio assíncrono; não bloqueia nem falha quem o registrou. Se o
+    /// destino estiver fora do ar, o evento espera em disco até poder ser enviado.
+    fn export(&self, event: &AuditEvent);
+}
@@ -0,0 +1,218 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::group::PermissionGrant;
+use crate::domain::models::role::{CreatePermission, CreateRole, Permission, Role, RoleSessionPolicy};
+
+#[async_trait]
+pub trait AuthorizationService: Sync + Send {
+    /// Cria um novo papel (role) na organização.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `role`: Estrutura `CreateRole` com o nome único e a descrição do papel.
+    ///
+    /// # Retornos
+    /// - `Result<Role, CommonError>`: Retorna o `Role` criado em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - Já existir um papel com o mesmo nome.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::role::CreateRole;
+    /// use auth_service::domain::services::authorization::AuthorizationService;
+    ///  async fn example_usage(service: &impl AuthorizationService) {
+    ///     let new_role = CreateRole {
+    ///         name: "support".to_string(),
+    ///         description: Some("Equipe de suporte".to_string()),
+    ///     };
+    ///
+    ///     match service.create_role(1, new_role).await {
+    ///         Ok(role) => println!("Papel criado: {:?}", role),
+    ///         Err(e) => eprintln!("Erro ao criar o papel: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create_role(&self, organization_id: i32, role: CreateRole) -> Result<Role, CommonError>;
+    /// Lista os papéis da organização, ordenados por nome.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Role>, CommonError>`: Retorna os papéis em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list_roles(&self, organization_id: i32) -> Result<Vec<Role>, CommonError>;
+    /// Remove um papel e todas as suas atribuições.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `role_id`: ID do papel a ser removido.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover o papel.
+    async fn delete_role(&self, organization_id: i32, role_id: i32) -> Result<(), CommonError>;
+    /// Define os limites de sessão do papel, que valem a partir dos próximos logins de quem o tem.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `role_id`: ID do papel.
+    /// - `policy`: Estrutura `RoleSessionPolicy`; campos ausentes voltam à política do serviço.
+    ///
+    /// # Retornos
+    /// - `Result<Role, CommonError>`: Retorna o papel atualizado em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se algum limite não for positivo ou se o papel não existir.
+    async fn set_role_session_policy(
+        &self,
+        organization_id: i32,
+        role_id: i32,
+        policy: RoleSessionPolicy,
+    ) -> Result<Role, CommonError>;
+    /// Cria uma nova permissão, por convenção no formato `recurso:ação` (ex.: `users:read`).
+    ///
+    /// # Parâmetros
+    /// - `permission`: Estrutura `CreatePermission` com o nome único e a descrição da permissão.
+    ///
+    /// # Retornos
+    /// - `Result<Permission, CommonError>`: Retorna a `Permission` criada em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - Já existir uma permissão com o mesmo nome.
+    async fn create_permission(&self, permission: CreatePermission) -> Result<Permission, CommonError>;
+    /// Lista todas as permissões cadastradas, ordenadas por nome.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Permission>, CommonError>`: Retorna as permissões em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list_permissions(&self) -> Result<Vec<Permission>, CommonError>;
+    /// Lista as permissões concedidas a um papel.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `role_id`: ID do papel.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Permission>, CommonError>`: Retorna as permissões do papel em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn get_role_permissions(&self, organization_id: i32, role_id: i32) -> Result<Vec<Permission>, CommonError>;
+    /// Concede uma permissão a um papel. Conceder uma permissão já concedida não tem efeito.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `role_id`: ID do papel.
+    /// - `permission_id`: ID da permissão.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o papel ou a permissão não existirem.
+    async fn grant_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> Result<(), CommonError>;
+    /// Revoga uma permissão de um papel.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `role_id`: ID do papel.
+    /// - `permission_id`: ID da permissão.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir revogar a permissão.
+    async fn revoke_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> Result<(), CommonError>;
+    /// Atribui um papel a um usuário. Atribuir um papel já atribuído não tem efeito.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `user_id`: ID do usuário.
+    /// - `role_id`: ID do papel.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o usuário ou o papel não existirem.
+    async fn assign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> Result<(), CommonError>;
+    /// Remove um papel de um usuário.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `user_id`: ID do usuário.
+    /// - `role_id`: ID do papel.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover a atribuição.
+    async fn unassign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> Result<(), CommonError>;
+    /// Lista os papéis efetivos de um usuário: os atribuídos diretamente e os herdados
+    /// dos grupos dos quais ele é membro, direta ou indiretamente.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Role>, CommonError>`: Retorna os papéis do usuário em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn get_user_roles(&self, organization_id: i32, user_id: i32) -> Result<Vec<Role>, CommonError>;
+    /// Lista as permissões efetivas de um usuário, achatadas a partir de todos os seus papéis
+    /// efetivos (diretos e herdados de grupos).
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Permission>, CommonError>`: Retorna as permissões sem repetição em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::authorization::AuthorizationService;
+    ///  async fn example_usage(service: &impl AuthorizationService) {
+    ///     match service.get_user_permissions(1, 1).await {
+    ///         Ok(permissions) => println!("Permissões: {:?}", permissions),
+    ///         Err(e) => eprintln!("Erro ao buscar as permissões: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn get_user_permissions(&self, organization_id: i32, user_id: i32) -> Result<Vec<Permission>, CommonError>;
+    /// Explica por que um usuário tem uma permissão, listando cada caminho grupo/papel que a concede.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `user_id`: ID do usuário.
+    /// - `permission`: Nome da permissão (ex.: `users:read`).
+    ///
+    /// # Retornos
+    /// - `Result<Vec<PermissionGrant>, CommonError>`: Retorna os caminhos que concedem a permissão
+    ///   (vazio se o usuário não a possui) ou um `CommonError` em caso de falha.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::authorization::AuthorizationService;
+    ///  async fn example_usage(service: &impl AuthorizationService) {
+    ///     match service.explain_permission(1, 1, "users:read".to_string()).await {
+    ///         Ok(grants) => {
+    ///             for grant in grants {
+    ///                 println!("{} via {} ({:?})", grant.permission, grant.role, grant.groups);
+    ///             }
+    ///         }
+    ///         Err(e) => eprintln!("Erro ao explicar a permissão: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn explain_permission(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        permission: String,
+    ) -> Result<Vec<PermissionGrant>, CommonError>;
+    /// Garante que a organização tenha o papel `admin` com as permissões informadas,
+    /// criando as permissões que ainda não existirem. Pode ser chamado várias vezes.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `permissions`: Nomes das permissões a conceder ao papel `admin`.
+    ///
+    /// # Retornos
+    /// - `Result<Role, CommonError>`: Retorna o papel `admin` da organização em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir criar os registros.
+    async fn ensure_admin_role(&self, organization_id: i32, permissions: &[&str]) -> Result<Role, CommonError>;
+}
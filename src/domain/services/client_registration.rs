@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, OAuthError};
+use crate::domain::models::client_registration::{ClientInformation, ClientMetadata, InitialAccessToken};
+
+#[async_trait]
+pub trait ClientRegistrationService: Sync + Send {
+    /// Emite um token de acesso inicial que autoriza um registro dinâmico na organização.
+    ///
+    /// O token vale para um único registro e é removido quando ele é usado; para registrar
+    /// vários clientes, emita um token para cada. Registros com metadados inválidos não o gastam.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) que receberá os clientes registrados.
+    ///
+    /// # Retornos
+    /// - `Result<InitialAccessToken, CommonError>`: Retorna o token, em texto claro apenas nesta chamada,
+    ///   em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir guardar o token.
+    async fn create_initial_access_token(&self, organization_id: i32) -> Result<InitialAccessToken, CommonError>;
+    /// Registra um cliente em `/oauth/register` (RFC 7591).
+    ///
+    /// `token_endpoint_auth_method` define o tipo do cliente: `none` cria um cliente público e
+    /// `client_secret_basic` (o padrão) ou `client_secret_post`, um confidencial. O `scope` só pode
+    /// ter escopos de `REGISTRATION_ALLOWED_SCOPES` (por padrão, `openid profile email`); sem ele, o
+    /// cliente recebe todos esses escopos. Os `grant_types` informados são os únicos que o cliente
+    /// poderá usar; sem eles, só `authorization_code`.
+    ///
+    /// # Parâmetros
+    /// - `initial_access_token`: Token de acesso inicial emitido por um administrador.
+    /// - `metadata`: Metadados enviados pelo cliente.
+    ///
+    /// # Retornos
+    /// - `Result<ClientInformation, OAuthError>`: Retorna o cliente criado com o `client_secret` e o
+    ///   `registration_access_token` em caso de sucesso ou um `OAuthError` em caso de falha.
+    ///
+    /// # Erros
+    /// - `invalid_token` se o token de acesso inicial não existir, tiver expirado ou já tiver sido usado.
+    /// - `invalid_redirect_uri` se algum redirect URI tiver curingas, fragmento ou não usar `https`
+    ///   fora de `localhost`.
+    /// - `invalid_client_metadata` se o método de autenticação, os grants ou os response types não
+    ///   forem suportados ou se algum escopo não puder ser registrado.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::client_registration::ClientMetadata;
+    /// use auth_service::domain::services::client_registration::ClientRegistrationService;
+    ///  async fn example_usage(service: &impl ClientRegistrationService) {
+    ///     let metadata = ClientMetadata {
+    ///         client_name: Some("Dashboard".to_string()),
+    ///         redirect_uris: vec!["https://app.example.com/callback".to_string()],
+    ///         token_endpoint_auth_method: Some("none".to_string()),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     match service.register_client("token de acesso inicial", metadata).await {
+    ///         Ok(information) => println!("client_id: {}", information.client.client_id),
+    ///         Err(e) => eprintln!("Erro ao registrar o cliente: {}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn register_client(
+        &self,
+        initial_access_token: &str,
+        metadata: ClientMetadata,
+    ) -> Result<ClientInformation, OAuthError>;
+    /// Lê a configuração de um cliente registrado dinamicamente (RFC 7592, seção 2.1).
+    ///
+    /// # Erros
+    /// - `invalid_token` se o cliente não existir ou o `registration_access_token` não for o dele.
+    async fn get_client(&self, client_id: &str, registration_access_token: &str) -> Result<ClientInformation, OAuthError>;
+    /// Substitui os metadados de um cliente registrado dinamicamente (RFC 7592, seção 2.2).
+    /// Campos omitidos voltam ao padrão; o tipo do cliente não pode mudar.
+    ///
+    /// # Erros
+    /// - `invalid_token` se o cliente não existir ou o `registration_access_token` não for o dele.
+    /// - `invalid_redirect_uri` e `invalid_client_metadata` como em `register_client`, e também se o
+    ///   `token_endpoint_auth_method` mudar o tipo do cliente.
+    async fn update_client(
+        &self,
+        client_id: &str,
+        registration_access_token: &str,
+        metadata: ClientMetadata,
+    ) -> Result<ClientInformation, OAuthError>;
+    /// Remove um cliente registrado dinamicamente (RFC 7592, seção 2.3).
+    ///
+    /// # Erros
+    /// - `invalid_token` se o cliente não existir ou o `registration_access_token` não for o dele.
+    async fn delete_client(&self, client_id: &str, registration_access_token: &str) -> Result<(), OAuthError>;
+}
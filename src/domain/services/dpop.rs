@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+
+#[async_trait]
+pub trait DpopService: Sync + Send {
+    /// Valida uma prova DPoP (RFC 9449, seção 4.3) e retorna o thumbprint da chave que a assinou.
+    ///
+    /// A prova precisa ter `typ` `dpop+jwt`, ser assinada com um algoritmo assimétrico pela chave
+    /// pública do header `jwk`, corresponder ao método e à URI da requisição e ter sido emitida há
+    /// no máximo `DPOP_PROOF_MAX_AGE_SECONDS`, para mais ou para menos. Cada prova só é aceita uma
+    /// vez dentro dessa janela.
+    ///
+    /// # Parâmetros
+    /// - `proof`: Valor do header `DPoP`.
+    /// - `htm`: Método HTTP da requisição.
+    /// - `htu`: URI da requisição; query e fragmento são ignorados.
+    /// - `access_token`: Access token apresentado com a prova, cujo hash precisa estar em `ath`.
+    ///
+    /// # Retornos
+    /// - `Result<String, CommonError>`: Retorna o thumbprint SHA-256 (RFC 7638) da chave em caso de
+    ///   sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 401 se a prova for inválida, antiga, de outra
+    ///   requisição ou já tiver sido usada.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::dpop::DpopService;
+    ///  async fn example_usage(service: &impl DpopService, proof: &str) {
+    ///     match service.verify_proof(proof, "POST", "https://auth.example.com/oauth/token", None).await {
+    ///         Ok(jkt) => println!("Token vinculado à chave {}", jkt),
+    ///         Err(e) => eprintln!("Prova DPoP inválida: {}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn verify_proof(
+        &self,
+        proof: &str,
+        htm: &str,
+        htu: &str,
+        access_token: Option<&str>,
+    ) -> Result<String, CommonError>;
+    /// Remove os registros de provas que já não seriam aceitas.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantos registros foram removidos ou um `CommonError` em caso de falha.
+    async fn purge_expired(&self) -> Result<usize, CommonError>;
+}
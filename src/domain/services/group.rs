@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::group::{CreateGroup, Group};
+
+#[async_trait]
+pub trait GroupService: Sync + Send {
+    /// Cria um novo grupo na organização.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `group`: Estrutura `CreateGroup` com o nome único e a descrição do grupo.
+    ///
+    /// # Retornos
+    /// - `Result<Group, CommonError>`: Retorna o `Group` criado em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - Já existir um grupo com o mesmo nome.
+    async fn create_group(&self, organization_id: i32, group: CreateGroup) -> Result<Group, CommonError>;
+    /// Lista os grupos da organização, ordenados por nome.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Group>, CommonError>`: Retorna os grupos em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list_groups(&self, organization_id: i32) -> Result<Vec<Group>, CommonError>;
+    /// Remove um grupo, seus membros, subgrupos e papéis atribuídos.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `group_id`: ID do grupo a ser removido.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover o grupo.
+    async fn delete_group(&self, organization_id: i32, group_id: i32) -> Result<(), CommonError>;
+    /// Adiciona um usuário como membro direto de um grupo.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `group_id`: ID do grupo.
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o grupo ou o usuário não existirem.
+    async fn add_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> Result<(), CommonError>;
+    /// Remove um usuário de um grupo.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `group_id`: ID do grupo.
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover o membro.
+    async fn remove_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> Result<(), CommonError>;
+    /// Coloca um grupo dentro de outro. Os membros de `child_group_id` herdam os papéis de `parent_group_id`.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `parent_group_id`: ID do grupo que passa a conter o subgrupo.
+    /// - `child_group_id`: ID do subgrupo.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - A inclusão fechar um ciclo (o grupo pai já estiver contido, direta ou indiretamente, no subgrupo).
+    ///   - Algum dos grupos não existir.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::group::GroupService;
+    ///  async fn example_usage(service: &impl GroupService) {
+    ///     let engineering = 1;
+    ///     let backend = 2;
+    ///
+    ///     match service.add_subgroup(1, engineering, backend).await {
+    ///         Ok(()) => println!("Subgrupo adicionado"),
+    ///         Err(e) => eprintln!("Erro ao adicionar o subgrupo: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn add_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> Result<(), CommonError>;
+    /// Remove um subgrupo de um grupo.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `parent_group_id`: ID do grupo pai.
+    /// - `child_group_id`: ID do subgrupo.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover o subgrupo.
+    async fn remove_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> Result<(), CommonError>;
+    /// Atribui um papel a um grupo; todos os membros diretos e indiretos o herdam.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `group_id`: ID do grupo.
+    /// - `role_id`: ID do papel.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o grupo ou o papel não existirem.
+    async fn assign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> Result<(), CommonError>;
+    /// Remove um papel de um grupo.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `group_id`: ID do grupo.
+    /// - `role_id`: ID do papel.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover a atribuição.
+    async fn unassign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> Result<(), CommonError>;
+}
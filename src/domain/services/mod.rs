@@ -1,3 +1,18 @@
+pub mod audit;
+pub mod audit_export;
+pub mod authorization;
+pub mod client_registration;
+pub mod dpop;
+pub mod group;
+pub mod oauth;
+pub mod oidc;
+pub mod organization;
+pub mod personal_access_token;
+pub mod service_account;
 pub mod service_context;
+pub mod session;
 pub mod token;
 pub mod user;
+pub mod user_admin;
+pub mod webhook;
+pub mod webhook_sender;
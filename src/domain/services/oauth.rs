@@ -0,0 +1,457 @@
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, OAuthError};
+use crate::domain::models::oauth::{
+    AuthorizationRequest, Consent, CreateOAuthClient, DeviceAuthorization, DeviceAuthorizationRequest,
+    DeviceCode, IssuedAuthorizationCode, OAuthClient, PushedAuthorization, PushedAuthorizationRequest,
+    RegisteredOAuthClient, RevocationRequest, TokenRequest, TokenResponse,
+};
+
+#[async_trait]
+pub trait OAuthService: Sync + Send {
+    /// Registra um novo cliente OAuth na organização.
+    ///
+    /// Clientes confidenciais recebem um `client_secret`, retornado apenas nesta chamada;
+    /// somente o hash dele é armazenado. Clientes que só usam `client_credentials` não
+    /// precisam de redirect URIs.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) dona do cliente.
+    /// - `client`: Estrutura `CreateOAuthClient` com o nome, o tipo, os redirect URIs, os escopos e os
+    ///   grants permitidos, além de se o cliente exige PAR.
+    ///
+    /// # Retornos
+    /// - `Result<RegisteredOAuthClient, CommonError>`: Retorna o cliente criado e o segredo gerado em caso de sucesso
+    ///   ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - Um cliente público não informar redirect URIs ou algum deles não for uma URL `https` absoluta,
+    ///     sem fragmento nem curingas (`http` só é aceito em `localhost`).
+    ///   - Algum grant não for suportado pelo tipo do cliente.
+    ///   - O repositório não conseguir criar o cliente.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::oauth::{ClientType, CreateOAuthClient};
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     let client = CreateOAuthClient {
+    ///         name: "Dashboard".to_string(),
+    ///         client_type: ClientType::Public,
+    ///         redirect_uris: vec!["https://app.example.com/callback".to_string()],
+    ///         allowed_scopes: vec!["users:read".to_string()],
+    ///         allowed_audiences: Vec::new(),
+    ///         grant_types: ClientType::Public.default_grant_types(),
+    ///         require_pushed_authorization_requests: false,
+    ///     };
+    ///
+    ///     match service.register_client(1, client).await {
+    ///         Ok(registered) => println!("client_id: {}", registered.client.client_id),
+    ///         Err(e) => eprintln!("Erro ao registrar o cliente: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn register_client(
+        &self,
+        organization_id: i32,
+        client: CreateOAuthClient,
+    ) -> Result<RegisteredOAuthClient, CommonError>;
+    /// Lista os clientes OAuth da organização, ordenados por nome.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    ///
+    /// # Retornos
+    /// - `Result<Vec<OAuthClient>, CommonError>`: Retorna os clientes em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list_clients(&self, organization_id: i32) -> Result<Vec<OAuthClient>, CommonError>;
+    /// Remove um cliente OAuth e os códigos de autorização pendentes dele.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `id`: ID interno do cliente.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir remover o cliente.
+    async fn delete_client(&self, organization_id: i32, id: i32) -> Result<(), CommonError>;
+    /// Gera um novo segredo para um cliente confidencial. O segredo atual continua aceito,
+    /// como segredo anterior, até `revoke_previous_client_secret`; assim há no máximo dois
+    /// segredos ativos durante a troca.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `id`: ID interno do cliente.
+    ///
+    /// # Retornos
+    /// - `Result<RegisteredOAuthClient, CommonError>`: Retorna o cliente e o novo segredo em caso de sucesso
+    ///   ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o cliente não existir na organização ou for público.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     match service.rotate_client_secret(1, 2).await {
+    ///         Ok(registered) => println!("Novo segredo: {:?}", registered.client_secret),
+    ///         Err(e) => eprintln!("Erro ao rotacionar o segredo: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn rotate_client_secret(&self, organization_id: i32, id: i32) -> Result<RegisteredOAuthClient, CommonError>;
+    /// Revoga o segredo anterior de um cliente, encerrando a rotação.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `id`: ID interno do cliente.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir atualizar o cliente.
+    async fn revoke_previous_client_secret(&self, organization_id: i32, id: i32) -> Result<(), CommonError>;
+    /// Busca um cliente pelo `client_id` público.
+    ///
+    /// # Erros
+    /// - Retorna `invalid_client` se o cliente não existir.
+    async fn get_client(&self, client_id: &str) -> Result<OAuthClient, OAuthError>;
+    /// Guarda os parâmetros de uma requisição de autorização enviada pelo cliente por POST
+    /// (RFC 9126) e retorna o `request_uri` que os substitui em `/oauth/authorize`.
+    ///
+    /// Os parâmetros são validados como em `validate_authorization_request`, e o `redirect_uri`
+    /// precisa estar registrado. O `request_uri` vale por `PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS`
+    /// e é descartado quando o código de autorização é emitido.
+    ///
+    /// # Parâmetros
+    /// - `request`: Parâmetros recebidos em `/oauth/par`, com as credenciais do cliente já extraídas.
+    ///
+    /// # Retornos
+    /// - `Result<PushedAuthorization, OAuthError>`: Retorna o `request_uri` e a validade dele em
+    ///   segundos, ou um `OAuthError` em caso de falha.
+    ///
+    /// # Erros
+    /// - `invalid_client` se o cliente não existir ou o segredo de um cliente confidencial não conferir.
+    /// - `invalid_request` se o `client_id` dos parâmetros não for o do cliente autenticado, se vier
+    ///   um `request_uri` ou se o `redirect_uri` não estiver registrado.
+    /// - Os mesmos de `validate_authorization_request`.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::oauth::{AuthorizationRequest, PushedAuthorizationRequest};
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     let request = PushedAuthorizationRequest {
+    ///         request: AuthorizationRequest {
+    ///             response_type: "code".to_string(),
+    ///             client_id: "client_id".to_string(),
+    ///             redirect_uri: Some("https://app.example.com/callback".to_string()),
+    ///             scope: Some("openid".to_string()),
+    ///             ..Default::default()
+    ///         },
+    ///         client_id: Some("client_id".to_string()),
+    ///         client_secret: Some("client_secret".to_string()),
+    ///     };
+    ///
+    ///     match service.push_authorization_request(request).await {
+    ///         Ok(pushed) => println!("/oauth/authorize?client_id=client_id&request_uri={}", pushed.request_uri),
+    ///         Err(e) => eprintln!("Erro ao enviar a requisição: {}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn push_authorization_request(
+        &self,
+        request: PushedAuthorizationRequest,
+    ) -> Result<PushedAuthorization, OAuthError>;
+    /// Troca o `request_uri` de uma requisição de autorização pelos parâmetros enviados a
+    /// `/oauth/par`, mantendo o `request_uri` no resultado. Sem `request_uri`, retorna a
+    /// requisição como veio, a menos que o cliente exija PAR.
+    ///
+    /// # Parâmetros
+    /// - `client`: Cliente da requisição.
+    /// - `request`: Parâmetros recebidos em `/oauth/authorize`.
+    ///
+    /// # Erros
+    /// - `invalid_request_uri` se o `request_uri` não existir, for de outro cliente, já tiver sido
+    ///   usado ou tiver expirado.
+    /// - `invalid_request` se o cliente exigir PAR e a requisição não trouxer um `request_uri`.
+    async fn resolve_authorization_request(
+        &self,
+        client: &OAuthClient,
+        request: AuthorizationRequest,
+    ) -> Result<AuthorizationRequest, OAuthError>;
+    /// Valida uma requisição de autorização de um cliente já resolvido e retorna o escopo concedido.
+    ///
+    /// O `redirect_uri` deve ter sido conferido antes com `OAuthClient::redirect_uri_for`, pois
+    /// os erros retornados aqui são enviados ao cliente por redirecionamento.
+    ///
+    /// # Parâmetros
+    /// - `client`: Cliente da requisição.
+    /// - `request`: Parâmetros recebidos em `/oauth/authorize`.
+    ///
+    /// # Retornos
+    /// - `Result<String, OAuthError>`: Retorna o escopo concedido (os permitidos ao cliente quando nenhum
+    ///   é pedido) ou um `OAuthError` em caso de falha.
+    ///
+    /// # Erros
+    /// - `unsupported_response_type` se `response_type` não for `code`.
+    /// - `unauthorized_client` se o cliente não estiver registrado para o grant `authorization_code`.
+    /// - `invalid_scope` se algum escopo não for permitido ao cliente.
+    /// - `invalid_request` se o PKCE estiver ausente em um cliente público ou usar um método diferente de `S256`.
+    async fn validate_authorization_request(
+        &self,
+        client: &OAuthClient,
+        request: &AuthorizationRequest,
+    ) -> Result<String, OAuthError>;
+    /// Emite um código de autorização de uso único para o usuário autenticado.
+    ///
+    /// Se o usuário já aprovou todos os escopos pedidos para o cliente, o código sai liberado e a
+    /// tela de consentimento é pulada. Caso contrário, o código fica pendente até `decide_consent`.
+    /// Um `request_uri` da requisição é descartado, de modo que só emite um código.
+    ///
+    /// # Parâmetros
+    /// - `client`: Cliente da requisição.
+    /// - `request`: Parâmetros recebidos em `/oauth/authorize`.
+    /// - `user_id`: ID do usuário autenticado, da mesma organização do cliente.
+    ///
+    /// # Retornos
+    /// - `Result<IssuedAuthorizationCode, OAuthError>`: Retorna o código e se ele ainda depende do
+    ///   consentimento do usuário, ou um `OAuthError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Os mesmos de `validate_authorization_request`.
+    /// - `invalid_request_uri` se o `request_uri` já tiver sido usado ou tiver expirado.
+    async fn create_authorization_code(
+        &self,
+        client: &OAuthClient,
+        request: &AuthorizationRequest,
+        user_id: i32,
+    ) -> Result<IssuedAuthorizationCode, OAuthError>;
+    /// Registra a resposta do usuário à tela de consentimento de um código pendente.
+    ///
+    /// Aprovado, os escopos do código passam a fazer parte do consentimento do usuário para o
+    /// cliente e o código pode ser trocado por um token.
+    ///
+    /// # Parâmetros
+    /// - `client`: Cliente da requisição.
+    /// - `code`: Código pendente retornado por `create_authorization_code`.
+    /// - `allow`: Se o usuário aprovou os escopos.
+    ///
+    /// # Erros
+    /// - `access_denied` se o usuário negar; o código é descartado.
+    /// - `invalid_request` se o código não existir, não estiver pendente, for de outro cliente ou tiver expirado.
+    async fn decide_consent(&self, client: &OAuthClient, code: &str, allow: bool) -> Result<(), OAuthError>;
+    /// Lista os clientes autorizados pelo usuário, com os escopos aprovados.
+    ///
+    /// # Parâmetros
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<(Consent, OAuthClient)>, CommonError>`: Retorna os consentimentos ordenados pelo nome
+    ///   do cliente em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list_consents(&self, user_id: i32) -> Result<Vec<(Consent, OAuthClient)>, CommonError>;
+    /// Retira o consentimento do usuário para um cliente e revoga os refresh tokens que o cliente
+    /// recebeu dele. Access tokens já emitidos continuam válidos até expirar.
+    ///
+    /// # Parâmetros
+    /// - `user_id`: ID do usuário.
+    /// - `client_id`: `client_id` público do cliente.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o usuário não tiver consentimento para o cliente.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     match service.revoke_consent(1, "client_id").await {
+    ///         Ok(()) => println!("Consentimento retirado"),
+    ///         Err(e) => eprintln!("Erro ao retirar o consentimento: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn revoke_consent(&self, user_id: i32, client_id: &str) -> Result<(), CommonError>;
+    /// Inicia o device authorization grant (RFC 8628) para um dispositivo sem navegador.
+    ///
+    /// # Parâmetros
+    /// - `request`: Parâmetros recebidos em `/oauth/device_authorization`, com as credenciais do cliente já extraídas.
+    ///
+    /// # Retornos
+    /// - `Result<DeviceAuthorization, OAuthError>`: Retorna o `device_code`, usado pelo dispositivo para consultar
+    ///   `/oauth/token`, e o `user_code`, digitado pelo usuário na página de verificação.
+    ///
+    /// # Erros
+    /// - `invalid_client` se o cliente não existir ou o segredo de um cliente confidencial não conferir.
+    /// - `unauthorized_client` se o cliente não estiver registrado para o grant de dispositivo.
+    /// - `invalid_scope` se algum escopo pedido não for permitido ao cliente.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::oauth::DeviceAuthorizationRequest;
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     let request = DeviceAuthorizationRequest {
+    ///         client_id: Some("client_id".to_string()),
+    ///         scope: Some("users:read".to_string()),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     match service.create_device_authorization(request).await {
+    ///         Ok(authorization) => println!("Digite o código {}", authorization.user_code),
+    ///         Err(e) => eprintln!("Erro ao iniciar a autorização: {}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create_device_authorization(
+        &self,
+        request: DeviceAuthorizationRequest,
+    ) -> Result<DeviceAuthorization, OAuthError>;
+    /// Busca um código de dispositivo pendente pelo `user_code` digitado (com ou sem hífen), junto
+    /// com o cliente que o pediu, para mostrar a página de aprovação.
+    ///
+    /// Como o `user_code` é curto, as buscas que falham são contadas por `remote_addr`: depois de
+    /// `USER_CODE_MAX_FAILED_ATTEMPTS` falhas, novas buscas desse endereço são recusadas até a
+    /// contagem expirar, mesmo com um código válido.
+    ///
+    /// # Parâmetros
+    /// - `user_code`: O código digitado pelo usuário.
+    /// - `remote_addr`: O endereço de quem fez a busca.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 429 se o endereço excedeu o limite de falhas.
+    /// - Retorna um `CommonError` se o código não existir, já tiver sido decidido ou tiver expirado.
+    async fn get_pending_device_code(
+        &self,
+        user_code: &str,
+        remote_addr: &str,
+    ) -> Result<(DeviceCode, OAuthClient), CommonError>;
+    /// Aprova um código de dispositivo pendente; a próxima consulta do dispositivo recebe um
+    /// token desse usuário. Os escopos do código passam a fazer parte do consentimento do
+    /// usuário para o cliente.
+    ///
+    /// # Parâmetros
+    /// - `user_code`: Código digitado pelo usuário.
+    /// - `user_id`: ID do usuário autenticado, da mesma organização do cliente.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o código não existir, já tiver sido decidido ou tiver expirado.
+    async fn approve_device_code(&self, user_code: &str, user_id: i32) -> Result<(), CommonError>;
+    /// Nega um código de dispositivo pendente; a próxima consulta do dispositivo recebe `access_denied`.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o código não existir, já tiver sido decidido ou tiver expirado.
+    async fn deny_device_code(&self, user_code: &str) -> Result<(), CommonError>;
+    /// Remove os códigos de autorização e de dispositivo, os refresh tokens e as revogações de
+    /// access tokens já expirados.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantos registros foram removidos ou um `CommonError` em caso de falha.
+    async fn purge_expired(&self) -> Result<usize, CommonError>;
+    /// Emite um access token no endpoint `/oauth/token`.
+    ///
+    /// - `authorization_code`: troca o código de autorização por um token do usuário que o aprovou.
+    ///   Com o escopo `openid`, a resposta também traz um `id_token` emitido pelo `OidcService`.
+    /// - `client_credentials`: emite um token para o próprio cliente confidencial, cujo `sub` é o
+    ///   `client_id`, sem papéis nem permissões de usuário.
+    /// - `urn:ietf:params:oauth:grant-type:device_code`: consulta um código de dispositivo e, depois
+    ///   da aprovação, emite um token do usuário que o aprovou.
+    /// - `refresh_token`: troca um refresh token por um novo par de tokens na mesma família. O escopo
+    ///   pedido pode reduzir o do access token. Reusar um refresh token já trocado revoga a família.
+    /// - `urn:ietf:params:oauth:grant-type:token-exchange`: troca um access token deste serviço
+    ///   (`subject_token`) por outro destinado a `audience`, que precisa estar entre as audiências
+    ///   permitidas ao cliente confidencial. Os escopos só podem ser reduzidos, a validade não
+    ///   passa da do token original e a claim `act` registra o `actor_token` ou o cliente.
+    ///
+    /// O grant precisa estar entre os registrados para o cliente. Os grants de usuário também
+    /// emitem um `refresh_token` quando o cliente está registrado para o grant `refresh_token`.
+    /// Nenhum token emitido aqui leva os papéis ou as permissões do usuário: o acesso do cliente é
+    /// definido só pelo `scope`.
+    ///
+    /// Com `dpop_jkt`, os tokens emitidos ficam vinculados à chave DPoP: o access token leva
+    /// `cnf.jkt` e `token_type` `DPoP`, e o refresh token só pode ser trocado com prova da mesma chave.
+    ///
+    /// # Parâmetros
+    /// - `request`: Parâmetros recebidos em `/oauth/token`, com as credenciais do cliente e o
+    ///   thumbprint da prova DPoP já extraídos.
+    ///
+    /// # Retornos
+    /// - `Result<TokenResponse, OAuthError>`: Retorna o access token emitido pelo `TokenService` ou um `OAuthError` em caso de falha.
+    ///
+    /// # Erros
+    /// - `invalid_client` se o cliente não existir ou o segredo de um cliente confidencial não conferir
+    ///   com o atual nem com o anterior.
+    /// - `unsupported_grant_type` se `grant_type` não for suportado.
+    /// - `unauthorized_client` se o cliente não estiver registrado para o grant ou se um cliente
+    ///   público pedir o grant `client_credentials` ou o token exchange.
+    /// - `invalid_scope` se algum escopo pedido não for permitido ao cliente ou, no token exchange,
+    ///   não estiver no `subject_token`.
+    /// - `invalid_target` se a `audience` não for permitida ao cliente.
+    /// - `invalid_grant` se o código não existir, já tiver sido usado, tiver expirado, for de outro cliente,
+    ///   o `redirect_uri` não for o mesmo da autorização ou o `code_verifier` não conferir; ou se o
+    ///   refresh token for desconhecido, de outro cliente, revogado, expirado, já usado ou vinculado a
+    ///   outra chave DPoP; ou se o
+    ///   `subject_token` ou o `actor_token` for inválido, estiver vinculado a uma chave DPoP sem prova
+    ///   dela no pedido ou o `subject_token` for de outra organização.
+    /// - `authorization_pending`, `slow_down`, `access_denied` e `expired_token` enquanto um código de
+    ///   dispositivo aguarda o usuário, é consultado antes do intervalo, foi negado ou expirou.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::oauth::TokenRequest;
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     let request = TokenRequest {
+    ///         grant_type: "authorization_code".to_string(),
+    ///         code: Some("código recebido no redirect_uri".to_string()),
+    ///         redirect_uri: Some("https://app.example.com/callback".to_string()),
+    ///         client_id: Some("client_id".to_string()),
+    ///         code_verifier: Some("verificador PKCE".to_string()),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     match service.exchange_token(request).await {
+    ///         Ok(response) => println!("Access token: {}", response.access_token),
+    ///         Err(e) => eprintln!("Erro ao emitir o token: {}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn exchange_token(&self, request: TokenRequest) -> Result<TokenResponse, OAuthError>;
+    /// Revoga um token no endpoint `/oauth/revoke` (RFC 7009).
+    ///
+    /// Um refresh token revoga toda a sua família. Um access token tem o `jti` bloqueado até expirar.
+    /// `token_type_hint` só define qual tipo é procurado primeiro.
+    ///
+    /// # Parâmetros
+    /// - `request`: Token a revogar, com as credenciais do cliente já extraídas.
+    ///
+    /// # Retornos
+    /// - `Result<(), OAuthError>`: Retorna `Ok` também para tokens inválidos, expirados ou de outro
+    ///   cliente, conforme a RFC 7009.
+    ///
+    /// # Erros
+    /// - `invalid_client` se o cliente não existir ou o segredo de um cliente confidencial não conferir.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::oauth::RevocationRequest;
+    /// use auth_service::domain::services::oauth::OAuthService;
+    ///  async fn example_usage(service: &impl OAuthService) {
+    ///     let request = RevocationRequest {
+    ///         token: "refresh token".to_string(),
+    ///         token_type_hint: Some("refresh_token".to_string()),
+    ///         client_id: Some("client_id".to_string()),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     if let Err(e) = service.revoke_token(request).await {
+    ///         eprintln!("Erro ao revogar o token: {}", e);
+    ///     }
+    /// }
+    /// ```
+    async fn revoke_token(&self, request: RevocationRequest) -> Result<(), OAuthError>;
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::oauth::{AuthorizationCode, OAuthClient};
+use crate::domain::models::oidc::{ProviderMetadata, UserInfo};
+use crate::domain::models::token::Claim;
+
+#[async_trait]
+pub trait OidcService: Sync + Send {
+    /// Monta o documento de descoberta publicado em `/.well-known/openid-configuration`.
+    ///
+    /// # Retornos
+    /// - `Result<ProviderMetadata, CommonError>`: Retorna os metadados do provedor em caso de sucesso
+    ///   ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o OpenID Connect não estiver configurado (`OIDC_ISSUER` e `OIDC_SIGNING_KEY`).
+    async fn provider_metadata(&self) -> Result<ProviderMetadata, CommonError>;
+    /// Retorna as chaves públicas usadas para assinar os ID tokens.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o OpenID Connect não estiver configurado.
+    async fn jwks(&self) -> Result<JwkSet, CommonError>;
+    /// Emite um ID token (RS256) para o usuário que aprovou o código de autorização.
+    ///
+    /// As claims `preferred_username`, `email` e `email_verified` são incluídas conforme os
+    /// escopos `profile` e `email` concedidos ao código.
+    ///
+    /// # Parâmetros
+    /// - `client`: Cliente para o qual o token é emitido; o `client_id` vira a claim `aud`.
+    /// - `code`: Código de autorização já consumido, com o `nonce` e o `auth_time` da autorização.
+    ///
+    /// # Retornos
+    /// - `Result<String, CommonError>`: Retorna o ID token assinado em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - O OpenID Connect não estiver configurado.
+    ///   - O usuário do código não existir mais.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::oauth::{AuthorizationCode, OAuthClient};
+    /// use auth_service::domain::services::oidc::OidcService;
+    ///  async fn example_usage(service: &impl OidcService, client: &OAuthClient, code: &AuthorizationCode) {
+    ///     match service.create_id_token(client, code).await {
+    ///         Ok(id_token) => println!("ID token: {}", id_token),
+    ///         Err(e) => eprintln!("Erro ao emitir o ID token: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create_id_token(&self, client: &OAuthClient, code: &AuthorizationCode) -> Result<String, CommonError>;
+    /// Retorna as claims do usuário dono de um access token com o escopo `openid`.
+    ///
+    /// # Parâmetros
+    /// - `claim`: Claims do access token já validado.
+    ///
+    /// # Retornos
+    /// - `Result<UserInfo, CommonError>`: Retorna o `sub` e as claims liberadas pelos escopos do token
+    ///   em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - O token não tiver o escopo `openid`.
+    ///   - O token não for de um usuário ou o usuário não existir mais.
+    async fn userinfo(&self, claim: &Claim) -> Result<UserInfo, CommonError>;
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::organization::{CreateOrganization, Organization};
+use crate::domain::models::user::CreateUser;
+
+#[async_trait]
+pub trait OrganizationService: Sync + Send {
+    /// Cria uma nova organização (tenant) com o seu papel `admin` e o usuário dono.
+    ///
+    /// # Parâmetros
+    /// - `organization`: Estrutura `CreateOrganization` com o slug único e o nome da organização.
+    /// - `owner`: Dados do primeiro usuário da organização, que recebe o papel `admin`.
+    ///   O campo `organization` é ignorado.
+    ///
+    /// # Retornos
+    /// - `Result<Organization, CommonError>`: Retorna a `Organization` criada em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - Já existir uma organização com o mesmo slug.
+    ///   - O usuário dono não puder ser criado.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::organization::CreateOrganization;
+    /// use auth_service::domain::models::user::CreateUser;
+    /// use auth_service::domain::services::organization::OrganizationService;
+    ///  async fn example_usage(service: &impl OrganizationService) {
+    ///     let organization = CreateOrganization {
+    ///         slug: "acme".to_string(),
+    ///         name: "Acme Corp".to_string(),
+    ///     };
+    ///     let owner = CreateUser {
+    ///         username: "owner".to_string(),
+    ///         email: "owner@acme.com".to_string(),
+    ///         password: "password123".to_string(),
+    ///         organization: None,
+    ///     };
+    ///
+    ///     match service.create_organization(organization, owner).await {
+    ///         Ok(organization) => println!("Organização criada: {:?}", organization),
+    ///         Err(e) => eprintln!("Erro ao criar a organização: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create_organization(
+        &self,
+        organization: CreateOrganization,
+        owner: CreateUser,
+    ) -> Result<Organization, CommonError>;
+    /// Lista todas as organizações, ordenadas por slug.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<Organization>, CommonError>`: Retorna as organizações em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list_organizations(&self) -> Result<Vec<Organization>, CommonError>;
+    /// Garante que a organização padrão e o seu papel `admin` existam.
+    ///
+    /// O `admin` da organização padrão também pode gerenciar organizações. Se a variável de
+    /// ambiente `ADMIN_USERNAME` estiver definida e o usuário ainda não existir na organização
+    /// padrão, ele é criado com a senha de `ADMIN_PASSWORD` (e o e-mail de `ADMIN_EMAIL`, se
+    /// houver) e recebe o papel `admin`. Um usuário já existente com esse nome nunca é promovido,
+    /// para que ninguém ganhe o papel registrando o nome antes. Pode ser chamado em toda
+    /// inicialização.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o repositório não conseguir criar os registros padrão.
+    async fn seed_defaults(&self) -> Result<(), CommonError>;
+}
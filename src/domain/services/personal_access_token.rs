@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::personal_access_token::{
+    CreatePersonalAccessToken, IssuedPersonalAccessToken, PersonalAccessToken,
+};
+use crate::domain::models::token::Claim;
+
+#[async_trait]
+pub trait PersonalAccessTokenService: Sync + Send {
+    /// Cria um personal access token para o usuário.
+    ///
+    /// O token tem o prefixo `PERSONAL_ACCESS_TOKEN_PREFIX` e só é retornado aqui; o banco guarda
+    /// apenas o hash.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) do usuário.
+    /// - `user_id`: ID do usuário dono do token.
+    /// - `new_token`: Nome, escopo e validade opcional do token. O escopo precisa ser um
+    ///   subconjunto das permissões atuais do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<IssuedPersonalAccessToken, CommonError>`: Retorna o token em texto claro e seus
+    ///   metadados em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se o nome ou o escopo estiverem vazios, se o
+    ///   escopo tiver permissões que o usuário não tem ou se `expires_at` já tiver passado.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::personal_access_token::CreatePersonalAccessToken;
+    /// use auth_service::domain::services::personal_access_token::PersonalAccessTokenService;
+    ///  async fn example_usage(service: &impl PersonalAccessTokenService) {
+    ///     let new_token = CreatePersonalAccessToken {
+    ///         name: "CI".to_string(),
+    ///         scope: "users:read".to_string(),
+    ///         expires_at: None,
+    ///     };
+    ///
+    ///     match service.create(1, 1, new_token).await {
+    ///         Ok(issued) => println!("Guarde o token, ele não será exibido de novo: {}", issued.token),
+    ///         Err(e) => eprintln!("Erro ao criar o token: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        new_token: CreatePersonalAccessToken,
+    ) -> Result<IssuedPersonalAccessToken, CommonError>;
+    /// Lista os personal access tokens do usuário, sem o valor dos tokens.
+    ///
+    /// # Parâmetros
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<Vec<PersonalAccessToken>, CommonError>`: Retorna os tokens em ordem de criação
+    ///   em caso de sucesso ou um `CommonError` em caso de falha.
+    async fn list(&self, user_id: i32) -> Result<Vec<PersonalAccessToken>, CommonError>;
+    /// Revoga um personal access token do usuário.
+    ///
+    /// # Parâmetros
+    /// - `user_id`: ID do usuário dono do token.
+    /// - `id`: ID do token.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se o token não existir ou for de outro usuário.
+    async fn revoke(&self, user_id: i32, id: i32) -> Result<(), CommonError>;
+    /// Valida um personal access token e retorna as claims equivalentes às de um access token.
+    ///
+    /// As claims não têm papéis e as permissões são as do escopo do token que o usuário ainda
+    /// tem; `pat` traz o ID do token. Cada uso atualiza `last_used_at`.
+    ///
+    /// # Parâmetros
+    /// - `token`: Token em texto claro, com o prefixo.
+    ///
+    /// # Retornos
+    /// - `Result<Claim, CommonError>`: Retorna as claims em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 401 se o token não existir, tiver sido revogado ou tiver expirado.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::personal_access_token::PersonalAccessTokenService;
+    ///  async fn example_usage(service: &impl PersonalAccessTokenService, token: String) {
+    ///     match service.validate(token).await {
+    ///         Ok(claim) => println!("Token do usuário {} com escopo {:?}", claim.sub, claim.scope),
+    ///         Err(e) => eprintln!("Token inválido: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn validate(&self, token: String) -> Result<Claim, CommonError>;
+    /// Confere que o personal access token `id` continua ativo. Vale para os tokens trocados a
+    /// partir dele, que o referenciam na claim `pat`.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 401 se o token tiver sido revogado ou tiver expirado.
+    async fn check_active(&self, id: i32) -> Result<(), CommonError>;
+}
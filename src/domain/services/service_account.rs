@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::personal_access_token::{
+    CreatePersonalAccessToken, IssuedPersonalAccessToken, PersonalAccessToken,
+};
+use crate::domain::models::user::{CreateServiceAccount, User};
+
+#[async_trait]
+pub trait ServiceAccountService: Sync + Send {
+    /// Cria uma conta de serviço na organização.
+    ///
+    /// A conta é um `User` com `principal_type` `service` e sem senha: recebe papéis e grupos
+    /// pelas mesmas rotas dos usuários e se autentica com API keys (personal access tokens).
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `new_account`: Nome da conta e, opcionalmente, o usuário responsável por ela.
+    ///
+    /// # Retornos
+    /// - `Result<User, CommonError>`: Retorna a conta criada em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se o nome estiver vazio ou se o dono não for um
+    ///   usuário da organização.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::user::CreateServiceAccount;
+    /// use auth_service::domain::services::service_account::ServiceAccountService;
+    ///  async fn example_usage(service: &impl ServiceAccountService) {
+    ///     let new_account = CreateServiceAccount {
+    ///         name: "deploy-bot".to_string(),
+    ///         owner_user_id: None,
+    ///     };
+    ///
+    ///     match service.create(1, new_account).await {
+    ///         Ok(account) => println!("Conta de serviço criada: {}", account.username),
+    ///         Err(e) => eprintln!("Erro ao criar a conta de serviço: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create(&self, organization_id: i32, new_account: CreateServiceAccount) -> Result<User, CommonError>;
+    /// Lista as contas de serviço da organização, em ordem de nome.
+    async fn list(&self, organization_id: i32) -> Result<Vec<User>, CommonError>;
+    /// Remove uma conta de serviço com seus papéis e API keys.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se a conta não existir na organização.
+    async fn delete(&self, organization_id: i32, id: i32) -> Result<(), CommonError>;
+    /// Cria uma API key para a conta de serviço.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `id`: ID da conta de serviço.
+    /// - `new_token`: Nome, escopo e validade da chave; o escopo precisa estar entre as
+    ///   permissões da conta.
+    ///
+    /// # Retornos
+    /// - `Result<IssuedPersonalAccessToken, CommonError>`: Retorna a chave em texto claro, exibida
+    ///   só desta vez, ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se a conta não existir na organização.
+    /// - Os mesmos de `PersonalAccessTokenService::create`.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::personal_access_token::CreatePersonalAccessToken;
+    /// use auth_service::domain::services::service_account::ServiceAccountService;
+    ///  async fn example_usage(service: &impl ServiceAccountService) {
+    ///     let new_token = CreatePersonalAccessToken {
+    ///         name: "pipeline".to_string(),
+    ///         scope: "users:read".to_string(),
+    ///         expires_at: None,
+    ///     };
+    ///
+    ///     match service.create_token(1, 42, new_token).await {
+    ///         Ok(issued) => println!("API key: {}", issued.token),
+    ///         Err(e) => eprintln!("Erro ao criar a API key: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create_token(
+        &self,
+        organization_id: i32,
+        id: i32,
+        new_token: CreatePersonalAccessToken,
+    ) -> Result<IssuedPersonalAccessToken, CommonError>;
+    /// Lista as API keys da conta de serviço, sem o valor das chaves.
+    async fn list_tokens(&self, organization_id: i32, id: i32) -> Result<Vec<PersonalAccessToken>, CommonError>;
+    /// Revoga uma API key da conta de serviço.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se a conta ou a chave não existirem.
+    async fn revoke_token(&self, organization_id: i32, id: i32, token_id: i32) -> Result<(), CommonError>;
+}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::session::{Session, SessionClient};
+
+#[async_trait]
+pub trait SessionService: Sync + Send {
+    /// Abre uma sessão para um login, válida pelo mesmo tempo que o access token emitido.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) do usuário.
+    /// - `user_id`: ID do usuário.
+    /// - `client`: User agent, IP e nome do dispositivo do login.
+    ///
+    /// # Retornos
+    /// - `Result<Session, CommonError>`: Retorna a sessão criada em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::session::SessionClient;
+    /// use auth_service::domain::services::session::SessionService;
+    ///  async fn example_usage(service: &impl SessionService) {
+    ///     let client = SessionClient {
+    ///         user_agent: Some("curl/8.0".to_string()),
+    ///         ip_address: Some("203.0.113.7".to_string()),
+    ///         device_name: Some("notebook".to_string()),
+    ///         csrf_token_hash: None,
+    ///     };
+    ///
+    ///     match service.create(1, 1, client).await {
+    ///         Ok(session) => println!("Sessão {} aberta", session.id),
+    ///         Err(e) => eprintln!("Erro ao abrir a sessão: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create(&self, organization_id: i32, user_id: i32, client: SessionClient) -> Result<Session, CommonError>;
+    /// Lista as sessões ativas do usuário, das usadas mais recentemente para as mais antigas.
+    async fn list(&self, user_id: i32) -> Result<Vec<Session>, CommonError>;
+    /// Revoga uma sessão do usuário; os tokens com esse `sid` deixam de ser aceitos.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se a sessão não existir, for de outro usuário
+    ///   ou já tiver sido revogada.
+    async fn revoke(&self, user_id: i32, id: &str) -> Result<(), CommonError>;
+    /// Revoga todas as sessões ativas do usuário, como ao desativá-lo.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantas sessões foram revogadas ou um `CommonError` em caso de falha.
+    async fn revoke_all(&self, user_id: i32) -> Result<usize, CommonError>;
+    /// Confere que a sessão de um token continua ativa e registra o uso em `last_seen_at`.
+    ///
+    /// # Parâmetros
+    /// - `id`: Valor da claim `sid`.
+    ///
+    /// # Retornos
+    /// - `Result<Session, CommonError>`: Retorna a sessão em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 401 se a sessão não existir, tiver sido revogada ou tiver expirado.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::session::SessionService;
+    ///  async fn example_usage(service: &impl SessionService, sid: &str) {
+    ///     match service.validate(sid).await {
+    ///         Ok(session) => println!("Sessão ativa desde {}", session.created_at),
+    ///         Err(e) => eprintln!("Sessão encerrada: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn validate(&self, id: &str) -> Result<Session, CommonError>;
+    /// Remove as sessões expiradas.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantas sessões foram removidas ou um `CommonError` em caso de falha.
+    async fn purge_expired(&self) -> Result<usize, CommonError>;
+}
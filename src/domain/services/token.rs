@@ -31,6 +31,35 @@ pub trait TokenService: Sync + Send {
     /// }
     /// ```
     async fn create(&self, user_id: i32) -> Result<String, CommonError>;
+    /// Cria um token JWT a partir de claims já montadas (papéis, permissões, escopos).
+    ///
+    /// # Parâmetros
+    /// - `claim`: Claims do token. O campo `exp` é definido pelo serviço de token; se já vier
+    ///   preenchido, só pode encurtar a validade padrão, inclusive nos tokens de login (com `sid`).
+    ///
+    /// # Retornos
+    /// - `Result<String, CommonError>`: Retorna o token JWT como uma `String` em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - O serviço de token não conseguir gerar o token.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::token::Claim;
+    /// use auth_service::domain::services::token::TokenService;
+    ///  async fn example_usage(service: &impl TokenService) {
+    ///     let mut claim = Claim::new("1".to_string(), 0);
+    ///     claim.roles.push("admin".to_string());
+    ///
+    ///     match service.create_with_claim(claim).await {
+    ///         Ok(token) => println!("Token JWT criado: {}", token),
+    ///         Err(e) => eprintln!("Erro ao criar o token JWT: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create_with_claim(&self, claim: Claim) -> Result<String, CommonError>;
     /// Valida um token JWT e retorna suas claims se válido.
     ///
     /// # Parâmetros
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 
 use crate::domain::error::CommonError;
+use crate::domain::models::session::SessionClient;
 use crate::domain::models::token::Claim;
 use crate::domain::models::user::{CreateUser, LoginUser, User};
 
@@ -9,7 +10,8 @@ pub trait UserService: Sync + Send {
     /// Cria um novo usuário no sistema.
     ///
     /// # Parâmetros
-    /// - `user`: Estrutura `CreateUser` contendo os dados do usuário a ser criado (incluindo a senha em texto claro).
+    /// - `user`: Estrutura `CreateUser` contendo os dados do usuário a ser criado (incluindo a senha em texto claro
+    ///   e o slug da organização; `None` usa a organização padrão).
     ///
     /// # Retornos
     /// - `Result<User, CommonError>`: Retorna o objeto `User` criado em caso de sucesso ou um `CommonError` em caso de falha.
@@ -17,7 +19,8 @@ pub trait UserService: Sync + Send {
     /// # Erros
     /// - Retorna um `CommonError` se:
     ///   - Houver um erro ao fazer o hash da senha do usuário.
-    ///   - O repositório não conseguir criar o usuário.
+    ///   - A organização informada não existir.
+    ///   - O repositório não conseguir criar o usuário (ex.: nome de usuário ou e-mail já usados na organização).
     ///
     /// # Exemplos
     ///
@@ -29,6 +32,7 @@ pub trait UserService: Sync + Send {
     ///         username: "example".to_string(),
     ///         email: "example@example.com".to_string(),
     ///         password: "password123".to_string(),
+    ///         organization: None,
     ///     };
     ///
     ///     match service.create(new_user).await {
@@ -38,10 +42,14 @@ pub trait UserService: Sync + Send {
     /// }
     /// ```
     async fn create(&self, user: CreateUser) -> Result<User, CommonError>;
-    /// Gera um token JWT para um usuário autenticado.
+    /// Gera um token JWT para um usuário autenticado e abre uma sessão para o login, cujo ID vai
+    /// na claim `sid`. Cada tentativa, com sucesso ou não, fica no log de auditoria como `user.login`.
     ///
     /// # Parâmetros
-    /// - `login_user`: Estrutura `LoginUser` contendo as credenciais do usuário (nome de usuário e senha).
+    /// - `login_user`: Estrutura `LoginUser` contendo as credenciais do usuário (nome de usuário e senha)
+    ///   e a organização escolhida no login (`None` usa a organização padrão).
+    /// - `client`: Dispositivo de onde veio o login, guardado na sessão. Com `csrf_token_hash`, o
+    ///   hash vai na claim `csrf` e o token passa a exigir o token CSRF quando vier do cookie.
     ///
     /// # Retornos
     /// - `Result<String, CommonError>`: Retorna o token JWT como uma `String` em caso de sucesso ou um `CommonError` em caso de falha.
@@ -49,27 +57,131 @@ pub trait UserService: Sync + Send {
     /// # Erros
     /// - Retorna um `CommonError` se:
     ///   - Houver um erro ao fazer o hash da senha do usuário.
-    ///   - O repositório não conseguir encontrar um usuário correspondente.
+    ///   - A organização informada não existir.
+    ///   - O repositório não conseguir encontrar um usuário correspondente na organização.
     ///   - O serviço de token não conseguir criar um token.
     ///
     /// # Exemplos
     ///
     /// ```rust
+    /// use auth_service::domain::models::session::SessionClient;
     /// use auth_service::domain::models::user::LoginUser;
     /// use auth_service::domain::services::user::UserService;
     ///  async fn example_usage(service: &impl UserService) {
     ///     let login_user = LoginUser {
     ///         username: "example".to_string(),
     ///         password: "password123".to_string(),
+    ///         organization: None,
     ///     };
     ///
-    ///     match service.get_token(login_user).await {
+    ///     match service.get_token(login_user, SessionClient::default()).await {
     ///         Ok(token) => println!("Token gerado: {}", token),
     ///         Err(e) => eprintln!("Erro ao gerar o token: {:?}", e),
     ///     }
     /// }
     /// ```
-    async fn get_token(&self, login_user: LoginUser) -> Result<String, CommonError>;
+    async fn get_token(&self, login_user: LoginUser, client: SessionClient) -> Result<String, CommonError>;
+    /// Renova o token de uma sessão de login ainda ativa. O access token vale no máximo
+    /// `ACCESS_TOKEN_TTL_SECONDS`; é a sessão, com o seu tempo máximo e o de inatividade, que
+    /// limita por quanto tempo o token pode ser renovado.
+    ///
+    /// # Parâmetros
+    /// - `claim`: Claim do token atual, com a claim `sid`.
+    ///
+    /// # Retornos
+    /// - `Result<String, CommonError>`: Retorna o novo token JWT em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 403 se o token não for de uma sessão de login.
+    /// - Retorna um `CommonError` com código 401 se a sessão tiver sido revogada ou tiver expirado.
+    async fn renew_session_token(&self, claim: &Claim) -> Result<String, CommonError>;
+    /// Verifica as credenciais de um usuário em uma organização já conhecida.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) do usuário.
+    /// - `login_user`: Credenciais do usuário. O campo `organization` é ignorado.
+    ///
+    /// # Retornos
+    /// - `Result<User, CommonError>`: Retorna o `User` autenticado em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - Houver um erro ao fazer o hash da senha do usuário.
+    ///   - O nome de usuário ou a senha estiverem incorretos; cada erro conta para o bloqueio da
+    ///     conta após `LOGIN_MAX_FAILED_ATTEMPTS` tentativas seguidas.
+    ///   - A conta estiver desativada ou bloqueada, ou exigir a troca de senha (código 403).
+    async fn authenticate(&self, organization_id: i32, login_user: LoginUser) -> Result<User, CommonError>;
+    /// Troca a senha de um usuário que informou a atual, inclusive quando a troca foi exigida
+    /// por um administrador, e revoga as sessões abertas. Fica no log de auditoria como
+    /// `user.password_change`.
+    ///
+    /// # Parâmetros
+    /// - `login_user`: Credenciais atuais do usuário e a organização (`None` usa a organização padrão).
+    /// - `new_password`: Nova senha em texto claro.
+    /// - `client`: User agent e IP da requisição, para a auditoria.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se:
+    ///   - A nova senha for vazia ou igual à atual (código 400).
+    ///   - As credenciais estiverem incorretas ou a conta estiver desativada ou bloqueada.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::session::SessionClient;
+    /// use auth_service::domain::models::user::LoginUser;
+    /// use auth_service::domain::services::user::UserService;
+    ///  async fn example_usage(service: &impl UserService) {
+    ///     let login_user = LoginUser {
+    ///         username: "example".to_string(),
+    ///         password: "password123".to_string(),
+    ///         organization: None,
+    ///     };
+    ///
+    ///     match service.change_password(login_user, "n3w-password".to_string(), SessionClient::default()).await {
+    ///         Ok(()) => println!("Senha trocada"),
+    ///         Err(e) => eprintln!("Erro ao trocar a senha: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn change_password(
+        &self,
+        login_user: LoginUser,
+        new_password: String,
+        client: SessionClient,
+    ) -> Result<(), CommonError>;
+    /// Monta as claims de um usuário (tenant, papéis e permissões efetivos), sem `exp`,
+    /// para serem assinadas pelo `TokenService`. Usuários desativados ou com troca de senha
+    /// pendente não recebem claims (código 403).
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) do usuário.
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<Claim, CommonError>`: Retorna as claims em caso de sucesso ou um `CommonError` em caso de falha.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::services::user::UserService;
+    ///  async fn example_usage(service: &impl UserService) {
+    ///     match service.get_claim(1, 1).await {
+    ///         Ok(claim) => println!("Papéis: {:?}", claim.roles),
+    ///         Err(e) => eprintln!("Erro ao montar as claims: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn get_claim(&self, organization_id: i32, user_id: i32) -> Result<Claim, CommonError>;
+    /// Busca um usuário pelo ID dentro da organização.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant) do usuário.
+    /// - `user_id`: ID do usuário.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` se o usuário não existir na organização.
+    async fn get_user(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError>;
     /// Valida um token JWT e retorna suas claims se válido.
     ///
     /// # Parâmetros
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::user::{User, UserFilter};
+use crate::domain::repositories::repository::{QueryParams, ResultPaging};
+
+/// Gestão dos usuários de uma organização por um administrador. Todas as operações são
+/// restritas à organização informada; um ID de outra organização dá 404.
+#[async_trait]
+pub trait UserAdminService: Sync + Send {
+    /// Lista os usuários e as contas de serviço da organização.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `filter`: Prefixos de nome e e-mail, intervalo de criação, situação e ordenação.
+    /// - `params`: Página; `limit` vai de 1 a 100.
+    ///
+    /// # Retornos
+    /// - `Result<ResultPaging<User>, CommonError>`: Retorna o total que atende ao filtro e a página pedida.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se a página for inválida.
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::user::{UserFilter, UserStatus};
+    /// use auth_service::domain::repositories::repository::QueryParamsImpl;
+    /// use auth_service::domain::services::user_admin::UserAdminService;
+    ///  async fn example_usage(service: &impl UserAdminService) {
+    ///     let filter = UserFilter {
+    ///         username_prefix: Some("ali".to_string()),
+    ///         status: Some(UserStatus::Locked),
+    ///         ..Default::default()
+    ///     };
+    ///     let params = QueryParamsImpl { limit: Some(50), offset: None };
+    ///
+    ///     match service.list(1, &filter, &params).await {
+    ///         Ok(page) => println!("{} usuários bloqueados", page.total),
+    ///         Err(e) => eprintln!("Erro ao listar os usuários: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &UserFilter,
+        params: &dyn QueryParams,
+    ) -> Result<ResultPaging<User>, CommonError>;
+    /// Busca um usuário da organização.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se o usuário não existir na organização.
+    async fn get(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError>;
+    /// Troca o e-mail do usuário, que volta a ficar não verificado.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se o e-mail for inválido ou já for de outro
+    ///   usuário da organização, e 404 se o usuário não existir.
+    async fn update_email(&self, organization_id: i32, user_id: i32, email: &str) -> Result<User, CommonError>;
+    /// Remove o usuário com seus papéis, sessões e tokens.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se o usuário não existir na organização.
+    async fn delete(&self, organization_id: i32, user_id: i32) -> Result<(), CommonError>;
+    /// Desativa a conta e revoga as sessões abertas: o usuário não consegue entrar e seus
+    /// personal access tokens deixam de ser aceitos até a conta ser reativada.
+    async fn disable(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError>;
+    /// Reativa uma conta desativada.
+    async fn enable(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError>;
+    /// Exige a troca de senha, em `POST /auth/password`, antes do próximo login e revoga as
+    /// sessões abertas. Até a troca, os personal access tokens do usuário deixam de ser aceitos
+    /// e os refresh tokens são recusados.
+    async fn require_password_reset(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError>;
+    /// Desfaz o bloqueio por logins errados antes do fim de `locked_until`.
+    async fn unlock(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError>;
+    /// Revoga todas as sessões abertas do usuário.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantas sessões foram revogadas.
+    async fn revoke_sessions(&self, organization_id: i32, user_id: i32) -> Result<usize, CommonError>;
+}
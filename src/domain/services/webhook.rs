@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::outbox::{CreateWebhook, Webhook, WebhookDelivery, WebhookDeliveryFilter};
+use crate::domain::repositories::repository::{QueryParams, ResultPaging};
+
+#[async_trait]
+pub trait WebhookService: Sync + Send {
+    /// Registra um webhook para receber os eventos do ciclo de vida dos usuários da organização.
+    ///
+    /// Cada entrega é um POST com o evento em JSON, assinado com HMAC-SHA256 pelo segredo
+    /// gerado aqui: o cabeçalho `X-Webhook-Signature` traz `sha256=` e o HMAC, em hexadecimal,
+    /// de `X-Webhook-Timestamp`, um ponto e o corpo.
+    ///
+    /// # Parâmetros
+    /// - `organization_id`: ID da organização (tenant).
+    /// - `new_webhook`: URL e tipos de evento; sem tipos, o webhook recebe todos.
+    ///
+    /// # Retornos
+    /// - `Result<Webhook, CommonError>`: Retorna o webhook com o segredo, que não é mostrado de novo, ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se a URL não for `https://` (`http://` só é
+    ///   aceito em loopback).
+    ///
+    /// # Exemplos
+    ///
+    /// ```rust
+    /// use auth_service::domain::models::outbox::{CreateWebhook, OutboxEventType};
+    /// use auth_service::domain::services::webhook::WebhookService;
+    ///  async fn example_usage(service: &impl WebhookService) {
+    ///     let new_webhook = CreateWebhook {
+    ///         url: "https://billing.example.com/hooks/users".to_string(),
+    ///         event_types: vec![OutboxEventType::UserCreated, OutboxEventType::UserDeleted],
+    ///     };
+    ///
+    ///     match service.create(1, new_webhook).await {
+    ///         Ok(webhook) => println!("Webhook {} registrado", webhook.id),
+    ///         Err(e) => eprintln!("Erro ao registrar o webhook: {:?}", e),
+    ///     }
+    /// }
+    /// ```
+    async fn create(&self, organization_id: i32, new_webhook: CreateWebhook) -> Result<Webhook, CommonError>;
+    /// Lista os webhooks da organização.
+    async fn list(&self, organization_id: i32) -> Result<Vec<Webhook>, CommonError>;
+    /// Remove um webhook e as entregas dele.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se o webhook não existir na organização.
+    async fn delete(&self, organization_id: i32, id: i32) -> Result<(), CommonError>;
+    /// Lista uma página das entregas dos webhooks da organização, das mais recentes para as mais
+    /// antigas; `status` `dead` lista as que esgotaram as tentativas.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 400 se o limite não estiver entre 1 e 100 ou o deslocamento for negativo.
+    async fn list_deliveries(
+        &self,
+        organization_id: i32,
+        filter: &WebhookDeliveryFilter,
+        params: &dyn QueryParams,
+    ) -> Result<ResultPaging<WebhookDelivery>, CommonError>;
+    /// Reenvia um evento: as entregas dele, inclusive as já feitas e as `dead`, voltam para a
+    /// fila com as tentativas zeradas, e os webhooks inscritos depois do evento passam a recebê-lo.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantas entregas foram colocadas na fila ou um `CommonError` em caso de falha.
+    ///
+    /// # Erros
+    /// - Retorna um `CommonError` com código 404 se o evento não existir na organização.
+    async fn replay(&self, organization_id: i32, outbox_event_id: i64) -> Result<usize, CommonError>;
+    /// Uma rodada do despachante: cria as entregas dos eventos novos e envia as que venceram.
+    /// Uma entrega que falha volta com espera exponencial até esgotar as tentativas e ficar
+    /// `dead`.
+    ///
+    /// # Retornos
+    /// - `Result<usize, CommonError>`: Retorna quantas entregas foram tentadas ou um `CommonError` se o banco não puder ser lido.
+    async fn dispatch(&self) -> Result<usize, CommonError>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::outbox::WebhookRequest;
+
+/// Faz a chamada HTTP de uma entrega de webhook.
+#[async_trait]
+pub trait WebhookSender: Sync + Send {
+    /// Envia `request` por POST. Só uma resposta 2xx conta como entregue; qualquer outra
+    /// resposta, timeout ou erro de conexão volta como `CommonError` com a descrição da falha.
+    async fn send(&self, request: &WebhookRequest) -> Result<(), CommonError>;
+}
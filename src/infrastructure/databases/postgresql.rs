@@ -2,8 +2,10 @@ use std::env;
 
 use diesel;
 use diesel::pg::PgConnection;
+use diesel::prelude::*;
 use diesel::r2d2;
 use diesel::r2d2::ConnectionManager;
+use diesel::sql_types::Text;
 use dotenv::dotenv;
 
 use crate::domain::constants::POSTGRESQL_DB_URI;
@@ -15,9 +17,26 @@ pub type DBConn = PostgresPool;
 pub fn db_pool() -> DBConn {
     dotenv().ok();
     let database_url = env::var(POSTGRESQL_DB_URI)
-        .expect(&*format!("{value} must be set", value = POSTGRESQL_DB_URI));
+        .unwrap_or_else(|_| panic!("{value} must be set", value = POSTGRESQL_DB_URI));
     let manager = ConnectionManager::<PgConnection>::new(database_url);
     Pool::builder()
         .build(manager)
         .expect("Failed to create pool")
 }
+
+/// Executa `f` numa transação com a variável `app.current_tenant` definida para a organização.
+///
+/// As políticas de row-level security das tabelas por organização só expõem as linhas desse
+/// tenant. A variável é local à transação, então não vaza para o próximo uso da conexão do pool;
+/// sem ela, nenhuma linha dessas tabelas fica visível.
+pub fn with_tenant<T, F>(conn: &mut PgConnection, organization_id: i32, f: F) -> QueryResult<T>
+where
+    F: FnOnce(&mut PgConnection) -> QueryResult<T>,
+{
+    conn.transaction(|conn| {
+        diesel::sql_query("SELECT set_config('app.current_tenant', $1, true)")
+            .bind::<Text, _>(organization_id.to_string())
+            .execute(conn)?;
+        f(conn)
+    })
+}
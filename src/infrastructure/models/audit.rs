@@ -0,0 +1,117 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::audit::{AuditAction, AuditCheckpoint, AuditEvent, AuditOutcome, CreateAuditEvent};
+use crate::infrastructure::schema::{audit_checkpoints, audit_events};
+
+#[derive(Queryable)]
+pub struct AuditEventDiesel {
+    pub id: i64,
+    pub organization_id: Option<i32>,
+    pub action: String,
+    pub outcome: String,
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl From<AuditEventDiesel> for AuditEvent {
+    fn from(t: AuditEventDiesel) -> Self {
+        AuditEvent {
+            id: t.id,
+            organization_id: t.organization_id,
+            action: AuditAction::parse(&t.action).unwrap_or(AuditAction::AdminAction),
+            outcome: AuditOutcome::parse(&t.outcome).unwrap_or(AuditOutcome::Failure),
+            actor_type: t.actor_type,
+            actor_id: t.actor_id,
+            target_type: t.target_type,
+            target_id: t.target_id,
+            ip_address: t.ip_address,
+            user_agent: t.user_agent,
+            details: t.details,
+            created_at: t.created_at,
+            prev_hash: t.prev_hash,
+            hash: t.hash,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_events)]
+pub struct CreateAuditEventDiesel {
+    pub organization_id: Option<i32>,
+    pub action: String,
+    pub outcome: String,
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl CreateAuditEventDiesel {
+    /// Linha da entrada encadeada a `prev_hash`.
+    pub fn chained(t: CreateAuditEvent, prev_hash: String, created_at: DateTime<Utc>) -> Self {
+        let hash = t.chain_hash(&prev_hash, created_at);
+        CreateAuditEventDiesel {
+            organization_id: t.organization_id,
+            action: t.action.as_str().to_string(),
+            outcome: t.outcome.as_str().to_string(),
+            actor_type: t.actor_type,
+            actor_id: t.actor_id,
+            target_type: t.target_type,
+            target_id: t.target_id,
+            ip_address: t.ip_address,
+            user_agent: t.user_agent,
+            details: t.details,
+            created_at,
+            prev_hash,
+            hash,
+        }
+    }
+}
+
+#[derive(Queryable)]
+pub struct AuditCheckpointDiesel {
+    pub day: NaiveDate,
+    pub event_count: i64,
+    pub last_event_id: i64,
+    pub last_hash: String,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditCheckpointDiesel> for AuditCheckpoint {
+    fn from(t: AuditCheckpointDiesel) -> Self {
+        AuditCheckpoint {
+            day: t.day,
+            event_count: t.event_count,
+            last_event_id: t.last_event_id,
+            last_hash: t.last_hash,
+            signature: t.signature,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_checkpoints)]
+pub struct CreateAuditCheckpointDiesel {
+    pub day: NaiveDate,
+    pub event_count: i64,
+    pub last_event_id: i64,
+    pub last_hash: String,
+    pub signature: String,
+}
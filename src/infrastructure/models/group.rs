@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::group::{CreateGroup, Group};
+use crate::infrastructure::schema::{group_members, group_roles, group_subgroups, groups};
+
+#[derive(Queryable)]
+pub struct GroupDiesel {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub organization_id: i32,
+}
+
+impl From<GroupDiesel> for Group {
+    fn from(t: GroupDiesel) -> Self {
+        Group {
+            id: t.id,
+            name: t.name,
+            description: t.description,
+            created_at: t.created_at,
+            organization_id: t.organization_id,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = groups)]
+pub struct CreateGroupDiesel {
+    pub name: String,
+    pub description: Option<String>,
+    pub organization_id: i32,
+}
+
+impl CreateGroupDiesel {
+    pub fn new(organization_id: i32, t: CreateGroup) -> Self {
+        CreateGroupDiesel {
+            name: t.name,
+            description: t.description,
+            organization_id,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = group_members)]
+pub struct GroupMemberDiesel {
+    pub group_id: i32,
+    pub user_id: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = group_subgroups)]
+pub struct GroupSubgroupDiesel {
+    pub parent_group_id: i32,
+    pub child_group_id: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = group_roles)]
+pub struct GroupRoleDiesel {
+    pub group_id: i32,
+    pub role_id: i32,
+}
@@ -1,2 +1,11 @@
+pub mod audit;
+pub mod group;
+pub mod oauth;
+pub mod organization;
+pub mod outbox;
+pub mod personal_access_token;
+pub mod role;
 pub mod service_context;
+pub mod session;
+pub mod token;
 pub mod user;
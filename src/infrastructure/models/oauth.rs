@@ -0,0 +1,345 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::oauth::{
+    AuthorizationCode, AuthorizationRequest, ClientType, Consent, CreateAuthorizationCode,
+    CreateDeviceCode, CreateOAuthClient, DeviceCode, DeviceCodeStatus, OAuthClient,
+};
+use crate::infrastructure::schema::{
+    authorization_codes, consents, device_codes, initial_access_tokens, oauth_clients,
+    pushed_authorization_requests,
+};
+
+#[derive(Queryable)]
+pub struct OAuthClientDiesel {
+    pub id: i32,
+    pub client_id: String,
+    pub client_secret_hash: Option<String>,
+    pub name: String,
+    pub client_type: String,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub organization_id: i32,
+    pub created_at: DateTime<Utc>,
+    pub previous_client_secret_hash: Option<String>,
+    pub registration_access_token_hash: Option<String>,
+    pub allowed_audiences: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub require_pushed_authorization_requests: bool,
+}
+
+impl From<OAuthClientDiesel> for OAuthClient {
+    fn from(t: OAuthClientDiesel) -> Self {
+        OAuthClient {
+            id: t.id,
+            client_id: t.client_id,
+            client_secret_hash: t.client_secret_hash,
+            previous_client_secret_hash: t.previous_client_secret_hash,
+            registration_access_token_hash: t.registration_access_token_hash,
+            name: t.name,
+            client_type: ClientType::parse(&t.client_type)
+                .expect("client_type is checked by the database"),
+            redirect_uris: t.redirect_uris,
+            allowed_scopes: t.allowed_scopes,
+            allowed_audiences: t.allowed_audiences,
+            grant_types: t.grant_types,
+            require_pushed_authorization_requests: t.require_pushed_authorization_requests,
+            organization_id: t.organization_id,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = oauth_clients)]
+pub struct CreateOAuthClientDiesel {
+    pub client_id: String,
+    pub client_secret_hash: Option<String>,
+    pub registration_access_token_hash: Option<String>,
+    pub name: String,
+    pub client_type: String,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub allowed_audiences: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub require_pushed_authorization_requests: bool,
+    pub organization_id: i32,
+}
+
+impl CreateOAuthClientDiesel {
+    pub fn new(
+        organization_id: i32,
+        client_id: String,
+        client_secret_hash: Option<String>,
+        registration_access_token_hash: Option<String>,
+        t: CreateOAuthClient,
+    ) -> Self {
+        CreateOAuthClientDiesel {
+            client_id,
+            client_secret_hash,
+            registration_access_token_hash,
+            name: t.name,
+            client_type: t.client_type.as_str().to_string(),
+            redirect_uris: t.redirect_uris,
+            allowed_scopes: t.allowed_scopes,
+            allowed_audiences: t.allowed_audiences,
+            grant_types: t.grant_types,
+            require_pushed_authorization_requests: t.require_pushed_authorization_requests,
+            organization_id,
+        }
+    }
+}
+
+#[derive(Queryable)]
+pub struct AuthorizationCodeDiesel {
+    pub code_hash: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub nonce: Option<String>,
+    pub auth_time: DateTime<Utc>,
+    pub consent_pending: bool,
+}
+
+impl From<AuthorizationCodeDiesel> for AuthorizationCode {
+    fn from(t: AuthorizationCodeDiesel) -> Self {
+        AuthorizationCode {
+            code_hash: t.code_hash,
+            oauth_client_id: t.oauth_client_id,
+            user_id: t.user_id,
+            redirect_uri: t.redirect_uri,
+            scope: t.scope,
+            code_challenge: t.code_challenge,
+            code_challenge_method: t.code_challenge_method,
+            expires_at: t.expires_at,
+            used_at: t.used_at,
+            created_at: t.created_at,
+            nonce: t.nonce,
+            auth_time: t.auth_time,
+            consent_pending: t.consent_pending,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = authorization_codes)]
+pub struct CreateAuthorizationCodeDiesel {
+    pub code_hash: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub nonce: Option<String>,
+    pub auth_time: DateTime<Utc>,
+    pub consent_pending: bool,
+}
+
+impl From<CreateAuthorizationCode> for CreateAuthorizationCodeDiesel {
+    fn from(t: CreateAuthorizationCode) -> Self {
+        CreateAuthorizationCodeDiesel {
+            code_hash: t.code_hash,
+            oauth_client_id: t.oauth_client_id,
+            user_id: t.user_id,
+            redirect_uri: t.redirect_uri,
+            scope: t.scope,
+            code_challenge: t.code_challenge,
+            code_challenge_method: t.code_challenge_method,
+            expires_at: t.expires_at,
+            nonce: t.nonce,
+            auth_time: t.auth_time,
+            consent_pending: t.consent_pending,
+        }
+    }
+}
+
+#[derive(Queryable)]
+pub struct DeviceCodeDiesel {
+    pub device_code_hash: String,
+    pub user_code: String,
+    pub oauth_client_id: i32,
+    pub scope: String,
+    pub status: String,
+    pub user_id: Option<i32>,
+    pub interval: i32,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DeviceCodeDiesel> for DeviceCode {
+    fn from(t: DeviceCodeDiesel) -> Self {
+        DeviceCode {
+            device_code_hash: t.device_code_hash,
+            user_code: t.user_code,
+            oauth_client_id: t.oauth_client_id,
+            scope: t.scope,
+            status: DeviceCodeStatus::parse(&t.status).expect("status is checked by the database"),
+            user_id: t.user_id,
+            interval: t.interval,
+            last_polled_at: t.last_polled_at,
+            expires_at: t.expires_at,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = device_codes)]
+pub struct CreateDeviceCodeDiesel {
+    pub device_code_hash: String,
+    pub user_code: String,
+    pub oauth_client_id: i32,
+    pub scope: String,
+    pub interval: i32,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<CreateDeviceCode> for CreateDeviceCodeDiesel {
+    fn from(t: CreateDeviceCode) -> Self {
+        CreateDeviceCodeDiesel {
+            device_code_hash: t.device_code_hash,
+            user_code: t.user_code,
+            oauth_client_id: t.oauth_client_id,
+            scope: t.scope,
+            interval: t.interval,
+            expires_at: t.expires_at,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = oauth_clients)]
+pub struct UpdateOAuthClientDiesel {
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub require_pushed_authorization_requests: bool,
+}
+
+impl From<CreateOAuthClient> for UpdateOAuthClientDiesel {
+    fn from(t: CreateOAuthClient) -> Self {
+        UpdateOAuthClientDiesel {
+            name: t.name,
+            redirect_uris: t.redirect_uris,
+            allowed_scopes: t.allowed_scopes,
+            grant_types: t.grant_types,
+            require_pushed_authorization_requests: t.require_pushed_authorization_requests,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = initial_access_tokens)]
+pub struct CreateInitialAccessTokenDiesel {
+    pub token_hash: String,
+    pub organization_id: i32,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Queryable)]
+pub struct ConsentDiesel {
+    pub user_id: i32,
+    pub oauth_client_id: i32,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ConsentDiesel> for Consent {
+    fn from(t: ConsentDiesel) -> Self {
+        Consent {
+            user_id: t.user_id,
+            oauth_client_id: t.oauth_client_id,
+            scopes: t.scopes,
+            created_at: t.created_at,
+            updated_at: t.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = consents)]
+pub struct CreateConsentDiesel {
+    pub user_id: i32,
+    pub oauth_client_id: i32,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Queryable)]
+pub struct PushedAuthorizationRequestDiesel {
+    pub request_uri_hash: String,
+    pub oauth_client_id: i32,
+    pub response_type: String,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PushedAuthorizationRequestDiesel {
+    /// Parâmetros guardados, com o `client_id` do cliente que os enviou.
+    pub fn into_request(self, client_id: String) -> AuthorizationRequest {
+        AuthorizationRequest {
+            response_type: self.response_type,
+            client_id,
+            redirect_uri: self.redirect_uri,
+            scope: self.scope,
+            state: self.state,
+            code_challenge: self.code_challenge,
+            code_challenge_method: self.code_challenge_method,
+            nonce: self.nonce,
+            request_uri: None,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = pushed_authorization_requests)]
+pub struct CreatePushedAuthorizationRequestDiesel {
+    pub request_uri_hash: String,
+    pub oauth_client_id: i32,
+    pub response_type: String,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CreatePushedAuthorizationRequestDiesel {
+    pub fn new(
+        request_uri_hash: String,
+        oauth_client_id: i32,
+        t: AuthorizationRequest,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        CreatePushedAuthorizationRequestDiesel {
+            request_uri_hash,
+            oauth_client_id,
+            response_type: t.response_type,
+            redirect_uri: t.redirect_uri,
+            scope: t.scope,
+            state: t.state,
+            code_challenge: t.code_challenge,
+            code_challenge_method: t.code_challenge_method,
+            nonce: t.nonce,
+            expires_at,
+        }
+    }
+}
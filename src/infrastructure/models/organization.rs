@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::organization::{CreateOrganization, Organization};
+use crate::infrastructure::schema::organizations;
+
+#[derive(Queryable)]
+pub struct OrganizationDiesel {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OrganizationDiesel> for Organization {
+    fn from(t: OrganizationDiesel) -> Self {
+        Organization {
+            id: t.id,
+            slug: t.slug,
+            name: t.name,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = organizations)]
+pub struct CreateOrganizationDiesel {
+    pub slug: String,
+    pub name: String,
+}
+
+impl From<CreateOrganization> for CreateOrganizationDiesel {
+    fn from(t: CreateOrganization) -> Self {
+        CreateOrganizationDiesel {
+            slug: t.slug,
+            name: t.name,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::outbox::{
+    CreateOutboxEvent, DeliveryStatus, OutboxEvent, OutboxEventType, Webhook, WebhookDelivery,
+};
+use crate::infrastructure::schema::{outbox, webhook_deliveries, webhooks};
+
+#[derive(Queryable)]
+pub struct OutboxEventDiesel {
+    pub id: i64,
+    pub organization_id: i32,
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub dispatched_at: Option<DateTime<Utc>>,
+}
+
+impl From<OutboxEventDiesel> for OutboxEvent {
+    fn from(t: OutboxEventDiesel) -> Self {
+        OutboxEvent {
+            id: t.id,
+            organization_id: t.organization_id,
+            event_type: OutboxEventType::parse(&t.event_type)
+                .expect("event_type is written by the repository"),
+            aggregate_type: t.aggregate_type,
+            aggregate_id: t.aggregate_id,
+            payload: t.payload,
+            created_at: t.created_at,
+            dispatched_at: t.dispatched_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = outbox)]
+pub struct CreateOutboxEventDiesel {
+    pub organization_id: i32,
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub payload: serde_json::Value,
+}
+
+impl From<CreateOutboxEvent> for CreateOutboxEventDiesel {
+    fn from(t: CreateOutboxEvent) -> Self {
+        CreateOutboxEventDiesel {
+            organization_id: t.organization_id,
+            event_type: t.event_type.as_str().to_string(),
+            aggregate_type: t.aggregate_type,
+            aggregate_id: t.aggregate_id,
+            payload: t.payload,
+        }
+    }
+}
+
+#[derive(Queryable)]
+pub struct WebhookDiesel {
+    pub id: i32,
+    pub organization_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDiesel> for Webhook {
+    fn from(t: WebhookDiesel) -> Self {
+        Webhook {
+            id: t.id,
+            organization_id: t.organization_id,
+            url: t.url,
+            secret: t.secret,
+            event_types: t
+                .event_types
+                .iter()
+                .filter_map(|event_type| OutboxEventType::parse(event_type))
+                .collect(),
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = webhooks)]
+pub struct CreateWebhookDiesel {
+    pub organization_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+}
+
+#[derive(Queryable)]
+pub struct WebhookDeliveryDiesel {
+    pub id: i64,
+    pub webhook_id: i32,
+    pub outbox_event_id: i64,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDeliveryDiesel> for WebhookDelivery {
+    fn from(t: WebhookDeliveryDiesel) -> Self {
+        WebhookDelivery {
+            id: t.id,
+            webhook_id: t.webhook_id,
+            outbox_event_id: t.outbox_event_id,
+            status: DeliveryStatus::parse(&t.status).expect("status is checked by the database"),
+            attempts: t.attempts,
+            next_attempt_at: t.next_attempt_at,
+            last_error: t.last_error,
+            delivered_at: t.delivered_at,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct CreateWebhookDeliveryDiesel {
+    pub webhook_id: i32,
+    pub outbox_event_id: i64,
+}
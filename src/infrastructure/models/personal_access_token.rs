@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::personal_access_token::{CreatePersonalAccessToken, PersonalAccessToken};
+use crate::infrastructure::schema::personal_access_tokens;
+
+#[derive(Queryable)]
+pub struct PersonalAccessTokenDiesel {
+    pub id: i32,
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub token_hash: String,
+    pub token_hint: String,
+    pub scope: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessTokenDiesel> for PersonalAccessToken {
+    fn from(t: PersonalAccessTokenDiesel) -> Self {
+        PersonalAccessToken {
+            id: t.id,
+            user_id: t.user_id,
+            organization_id: t.organization_id,
+            name: t.name,
+            token_hint: t.token_hint,
+            scope: t.scope,
+            expires_at: t.expires_at,
+            last_used_at: t.last_used_at,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = personal_access_tokens)]
+pub struct CreatePersonalAccessTokenDiesel {
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub token_hash: String,
+    pub token_hint: String,
+    pub scope: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CreatePersonalAccessTokenDiesel {
+    pub fn new(
+        organization_id: i32,
+        user_id: i32,
+        token_hash: String,
+        token_hint: String,
+        t: CreatePersonalAccessToken,
+    ) -> Self {
+        CreatePersonalAccessTokenDiesel {
+            user_id,
+            organization_id,
+            name: t.name,
+            token_hash,
+            token_hint,
+            scope: t.scope,
+            expires_at: t.expires_at,
+        }
+    }
+}
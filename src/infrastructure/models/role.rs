@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::role::{CreatePermission, CreateRole, Permission, Role, RoleSessionPolicy};
+use crate::infrastructure::schema::{permissions, role_permissions, roles, user_roles};
+
+#[derive(Queryable)]
+pub struct RoleDiesel {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub organization_id: i32,
+    pub session_idle_timeout_seconds: Option<i32>,
+    pub session_max_lifetime_seconds: Option<i32>,
+    pub max_concurrent_sessions: Option<i32>,
+}
+
+impl From<RoleDiesel> for Role {
+    fn from(t: RoleDiesel) -> Self {
+        Role {
+            id: t.id,
+            name: t.name,
+            description: t.description,
+            created_at: t.created_at,
+            organization_id: t.organization_id,
+            session_policy: RoleSessionPolicy {
+                idle_timeout_seconds: t.session_idle_timeout_seconds,
+                max_lifetime_seconds: t.session_max_lifetime_seconds,
+                max_concurrent_sessions: t.max_concurrent_sessions,
+            },
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = roles, treat_none_as_null = true)]
+pub struct RoleSessionPolicyDiesel {
+    pub session_idle_timeout_seconds: Option<i32>,
+    pub session_max_lifetime_seconds: Option<i32>,
+    pub max_concurrent_sessions: Option<i32>,
+}
+
+impl From<RoleSessionPolicy> for RoleSessionPolicyDiesel {
+    fn from(t: RoleSessionPolicy) -> Self {
+        RoleSessionPolicyDiesel {
+            session_idle_timeout_seconds: t.idle_timeout_seconds,
+            session_max_lifetime_seconds: t.max_lifetime_seconds,
+            max_concurrent_sessions: t.max_concurrent_sessions,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = roles)]
+pub struct CreateRoleDiesel {
+    pub name: String,
+    pub description: Option<String>,
+    pub organization_id: i32,
+}
+
+impl CreateRoleDiesel {
+    pub fn new(organization_id: i32, t: CreateRole) -> Self {
+        CreateRoleDiesel {
+            name: t.name,
+            description: t.description,
+            organization_id,
+        }
+    }
+}
+
+#[derive(Queryable)]
+pub struct PermissionDiesel {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<PermissionDiesel> for Permission {
+    fn from(t: PermissionDiesel) -> Self {
+        Permission {
+            id: t.id,
+            name: t.name,
+            description: t.description,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = permissions)]
+pub struct CreatePermissionDiesel {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<CreatePermission> for CreatePermissionDiesel {
+    fn from(t: CreatePermission) -> Self {
+        CreatePermissionDiesel {
+            name: t.name,
+            description: t.description,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = role_permissions)]
+pub struct RolePermissionDiesel {
+    pub role_id: i32,
+    pub permission_id: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = user_roles)]
+pub struct UserRoleDiesel {
+    pub user_id: i32,
+    pub role_id: i32,
+}
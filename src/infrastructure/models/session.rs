@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::session::{CreateSession, Session};
+use crate::infrastructure::schema::sessions;
+
+#[derive(Queryable)]
+pub struct SessionDiesel {
+    pub id: String,
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub device_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub idle_timeout_seconds: i32,
+}
+
+impl From<SessionDiesel> for Session {
+    fn from(t: SessionDiesel) -> Self {
+        Session {
+            id: t.id,
+            user_id: t.user_id,
+            organization_id: t.organization_id,
+            user_agent: t.user_agent,
+            ip_address: t.ip_address,
+            device_name: t.device_name,
+            created_at: t.created_at,
+            last_seen_at: t.last_seen_at,
+            expires_at: t.expires_at,
+            revoked_at: t.revoked_at,
+            idle_timeout_seconds: t.idle_timeout_seconds,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = sessions)]
+pub struct CreateSessionDiesel {
+    pub id: String,
+    pub user_id: i32,
+    pub organization_id: i32,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub device_name: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub idle_timeout_seconds: i32,
+}
+
+impl From<CreateSession> for CreateSessionDiesel {
+    fn from(t: CreateSession) -> Self {
+        CreateSessionDiesel {
+            id: t.id,
+            user_id: t.user_id,
+            organization_id: t.organization_id,
+            user_agent: t.client.user_agent,
+            ip_address: t.client.ip_address,
+            device_name: t.client.device_name,
+            expires_at: t.expires_at,
+            idle_timeout_seconds: t.idle_timeout_seconds,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::token::{CreateRefreshToken, RefreshToken};
+use crate::infrastructure::schema::{refresh_tokens, revoked_tokens};
+
+#[derive(Queryable)]
+pub struct RefreshTokenDiesel {
+    pub token_hash: String,
+    pub family_id: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub scope: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub dpop_jkt: Option<String>,
+}
+
+impl From<RefreshTokenDiesel> for RefreshToken {
+    fn from(t: RefreshTokenDiesel) -> Self {
+        RefreshToken {
+            token_hash: t.token_hash,
+            family_id: t.family_id,
+            oauth_client_id: t.oauth_client_id,
+            user_id: t.user_id,
+            scope: t.scope,
+            expires_at: t.expires_at,
+            used_at: t.used_at,
+            revoked_at: t.revoked_at,
+            created_at: t.created_at,
+            dpop_jkt: t.dpop_jkt,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct CreateRefreshTokenDiesel {
+    pub token_hash: String,
+    pub family_id: String,
+    pub oauth_client_id: i32,
+    pub user_id: i32,
+    pub scope: String,
+    pub expires_at: DateTime<Utc>,
+    pub dpop_jkt: Option<String>,
+}
+
+impl From<CreateRefreshToken> for CreateRefreshTokenDiesel {
+    fn from(t: CreateRefreshToken) -> Self {
+        CreateRefreshTokenDiesel {
+            token_hash: t.token_hash,
+            family_id: t.family_id,
+            oauth_client_id: t.oauth_client_id,
+            user_id: t.user_id,
+            scope: t.scope,
+            expires_at: t.expires_at,
+            dpop_jkt: t.dpop_jkt,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = revoked_tokens)]
+pub struct CreateRevokedTokenDiesel {
+    pub jti: String,
+    pub expires_at: DateTime<Utc>,
+}
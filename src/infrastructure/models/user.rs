@@ -1,16 +1,24 @@
 use chrono::{DateTime, Utc};
 use diesel;
 use diesel::prelude::*;
-use crate::domain::models::user::{CreateUser, User};
+use crate::domain::models::user::{CreateServiceAccount, CreateUser, PrincipalType, User};
 use crate::infrastructure::schema::users;
 
 #[derive(Queryable)]
 pub struct UserDiesel {
     pub id: i32,
     pub username: String,
-    pub email: String,
-    pub password:String,
+    pub email: Option<String>,
+    pub password: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub organization_id: i32,
+    pub email_verified: bool,
+    pub principal_type: String,
+    pub owner_user_id: Option<i32>,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub password_reset_required: bool,
+    pub failed_login_attempts: i32,
+    pub locked_until: Option<DateTime<Utc>>,
 }
 
 // Factory method for creating a new UserDiesel from a User
@@ -22,6 +30,14 @@ impl From<User> for UserDiesel {
             email: t.email,
             created_at: t.created_at,
             password:t.password,
+            organization_id: t.organization_id,
+            email_verified: t.email_verified,
+            principal_type: t.principal_type.as_str().to_string(),
+            owner_user_id: t.owner_user_id,
+            disabled_at: t.disabled_at,
+            password_reset_required: t.password_reset_required,
+            failed_login_attempts: t.failed_login_attempts,
+            locked_until: t.locked_until,
         }
     }
 }
@@ -32,39 +48,78 @@ pub struct CreateUserDiesel {
     pub username: String,
     pub email: String,
     pub password:String,
+    pub organization_id: i32,
 }
 
 // Factory method for creating a new User from a UserDiesel
-impl Into<User> for UserDiesel {
-    fn into(self) -> User {
+impl From<UserDiesel> for User {
+    fn from(t: UserDiesel) -> Self {
         User {
-            id: self.id,
-            username: self.username,
-            email: self.email,
-            password: self.password,
-            created_at:self.created_at,
+            id: t.id,
+            username: t.username,
+            email: t.email,
+            password: t.password,
+            created_at: t.created_at,
+            organization_id: t.organization_id,
+            email_verified: t.email_verified,
+            principal_type: PrincipalType::parse(&t.principal_type)
+                .expect("principal_type is checked by the database"),
+            owner_user_id: t.owner_user_id,
+            disabled_at: t.disabled_at,
+            password_reset_required: t.password_reset_required,
+            failed_login_attempts: t.failed_login_attempts,
+            locked_until: t.locked_until,
         }
     }
 }
 
-impl From<CreateUser> for CreateUserDiesel {
-    fn from(t: CreateUser) -> Self {
+impl CreateUserDiesel {
+    pub fn new(organization_id: i32, t: CreateUser) -> Self {
         CreateUserDiesel {
             username: t.username,
             email:t.email,
             password: t.password,
+            organization_id,
         }
     }
 }
 
-impl Into<User> for CreateUserDiesel {
-    fn into(self) -> User {
+impl From<CreateUserDiesel> for User {
+    fn from(t: CreateUserDiesel) -> Self {
         User {
             id: 0,
-            username: self.username,
-            email: self.email,
-            password: self.password,
-            created_at:chrono::Utc::now(),
+            username: t.username,
+            email: Some(t.email),
+            password: Some(t.password),
+            created_at: chrono::Utc::now(),
+            organization_id: t.organization_id,
+            email_verified: false,
+            principal_type: PrincipalType::User,
+            owner_user_id: None,
+            disabled_at: None,
+            password_reset_required: false,
+            failed_login_attempts: 0,
+            locked_until: None,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = users)]
+pub struct CreateServiceAccountDiesel {
+    pub username: String,
+    pub organization_id: i32,
+    pub principal_type: String,
+    pub owner_user_id: Option<i32>,
+}
+
+impl CreateServiceAccountDiesel {
+    pub fn new(organization_id: i32, t: CreateServiceAccount) -> Self {
+        CreateServiceAccountDiesel {
+            username: t.name,
+            organization_id,
+            principal_type: PrincipalType::Service.as_str().to_string(),
+            owner_user_id: t.owner_user_id,
         }
     }
-}
\ No newline at end of file
+}
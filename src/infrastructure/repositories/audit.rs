@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::Date;
+
+use crate::domain::models::audit::{
+    AuditCheckpoint, AuditCheckpointClaims, AuditEvent, AuditEventFilter, CreateAuditEvent,
+    AUDIT_CHAIN_GENESIS,
+};
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::audit::{
+    AuditCheckpointDiesel, AuditEventDiesel, CreateAuditCheckpointDiesel, CreateAuditEventDiesel,
+};
+use crate::infrastructure::schema::{audit_checkpoints, audit_events};
+
+/// Início do dia (UTC).
+fn start_of(day: NaiveDate) -> DateTime<Utc> {
+    day.and_hms_opt(0, 0, 0).unwrap().and_utc()
+}
+
+/// Dia (UTC) de uma entrada, calculado no banco.
+fn event_day() -> diesel::expression::SqlLiteral<Date> {
+    sql::<Date>(r#"("created_at" AT TIME ZONE 'UTC')::date"#)
+}
+
+fn filtered(organization_id: i32, filter: &AuditEventFilter) -> audit_events::BoxedQuery<'static, Pg> {
+    let mut query = audit_events::table
+        .filter(audit_events::organization_id.eq(organization_id))
+        .into_boxed();
+    if let Some(action) = filter.action {
+        query = query.filter(audit_events::action.eq(action.as_str()));
+    }
+    if let Some(outcome) = filter.outcome {
+        query = query.filter(audit_events::outcome.eq(outcome.as_str()));
+    }
+    if let Some(actor_id) = filter.actor_id.clone() {
+        query = query.filter(audit_events::actor_id.eq(actor_id));
+    }
+    if let Some(target_id) = filter.target_id.clone() {
+        query = query.filter(audit_events::target_id.eq(target_id));
+    }
+    if let Some(since) = filter.since {
+        query = query.filter(audit_events::created_at.ge(since));
+    }
+    if let Some(until) = filter.until {
+        query = query.filter(audit_events::created_at.lt(until));
+    }
+    query
+}
+
+pub struct AuditDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl AuditDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        AuditDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for AuditDieselRepository {
+    async fn create(&self, new_event: &CreateAuditEvent) -> RepositoryResult<AuditEvent> {
+        let new_event = new_event.clone();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction(|conn| {
+                // uma entrada por vez, para que duas não se encadeiem à mesma anterior
+                diesel::sql_query("SELECT pg_advisory_xact_lock(hashtext('audit_events'))")
+                    .execute(conn)?;
+                let now = Utc::now();
+                let created_at =
+                    DateTime::from_timestamp_micros(now.timestamp_micros()).unwrap_or(now);
+                let prev_hash = audit_events::table
+                    .filter(audit_events::created_at.ge(start_of(created_at.date_naive())))
+                    .filter(audit_events::hash.ne(""))
+                    .order(audit_events::id.desc())
+                    .select(audit_events::hash)
+                    .first::<String>(conn)
+                    .optional()?
+                    .unwrap_or_else(|| AUDIT_CHAIN_GENESIS.to_string());
+                diesel::insert_into(audit_events::table)
+                    .values(CreateAuditEventDiesel::chained(
+                        new_event, prev_hash, created_at,
+                    ))
+                    .get_result::<AuditEventDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> AuditEvent { v.into() })
+    }
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &AuditEventFilter,
+        params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<AuditEvent>> {
+        let filter = filter.clone();
+        let (limit, offset) = (params.limit(), params.offset());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let total = filtered(organization_id, &filter)
+                .count()
+                .get_result::<i64>(&mut conn)?;
+            let items = filtered(organization_id, &filter)
+                .order((audit_events::created_at.desc(), audit_events::id.desc()))
+                .limit(limit)
+                .offset(offset)
+                .load::<AuditEventDiesel>(&mut conn)?;
+            Ok::<_, diesel::result::Error>((total, items))
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|(total, items)| ResultPaging {
+            total,
+            items: items.into_iter().map(AuditEvent::from).collect(),
+        })
+    }
+    async fn list_chain(
+        &self,
+        from: Option<NaiveDate>,
+        after_id: i64,
+        limit: i64,
+    ) -> RepositoryResult<Vec<AuditEvent>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let mut query = audit_events::table
+                .filter(audit_events::id.gt(after_id))
+                .into_boxed();
+            if let Some(from) = from {
+                query = query.filter(audit_events::created_at.ge(start_of(from)));
+            }
+            query
+                .order(audit_events::id.asc())
+                .limit(limit)
+                .load::<AuditEventDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(AuditEvent::from).collect())
+    }
+    async fn list_day(&self, day: NaiveDate) -> RepositoryResult<Vec<AuditEvent>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            audit_events::table
+                .filter(audit_events::created_at.ge(start_of(day)))
+                .filter(audit_events::created_at.lt(start_of(day) + Duration::days(1)))
+                .order(audit_events::id.asc())
+                .load::<AuditEventDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(AuditEvent::from).collect())
+    }
+    async fn days_without_checkpoint(&self, before: NaiveDate) -> RepositoryResult<Vec<NaiveDate>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let signed = audit_checkpoints::table.select(audit_checkpoints::day);
+            audit_events::table
+                .filter(audit_events::created_at.lt(start_of(before)))
+                .filter(audit_events::hash.ne(""))
+                .filter(event_day().ne_all(signed.load::<NaiveDate>(&mut conn)?))
+                .select(event_day())
+                .distinct()
+                .order(event_day())
+                .load::<NaiveDate>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn create_checkpoint(
+        &self,
+        claims: &AuditCheckpointClaims,
+        signature: &str,
+    ) -> RepositoryResult<AuditCheckpoint> {
+        let new_checkpoint = CreateAuditCheckpointDiesel {
+            day: claims.day,
+            event_count: claims.event_count,
+            last_event_id: claims.last_event_id,
+            last_hash: claims.last_hash.clone(),
+            signature: signature.to_string(),
+        };
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(audit_checkpoints::table)
+                .values(new_checkpoint)
+                .get_result::<AuditCheckpointDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> AuditCheckpoint { v.into() })
+    }
+    async fn list_checkpoints(
+        &self,
+        from: Option<NaiveDate>,
+    ) -> RepositoryResult<Vec<AuditCheckpoint>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let mut query = audit_checkpoints::table.into_boxed();
+            if let Some(from) = from {
+                query = query.filter(audit_checkpoints::day.ge(from));
+            }
+            query
+                .order(audit_checkpoints::day.asc())
+                .load::<AuditCheckpointDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(AuditCheckpoint::from).collect())
+    }
+}
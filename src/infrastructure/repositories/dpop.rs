@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::domain::repositories::dpop::DpopRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::schema::dpop_proofs;
+
+pub struct DpopDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl DpopDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        DpopDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl DpopRepository for DpopDieselRepository {
+    async fn record_proof(&self, proof_hash: &str, expires_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let proof_hash = proof_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(dpop_proofs::table)
+                .values((
+                    dpop_proofs::proof_hash.eq(proof_hash),
+                    dpop_proofs::expires_at.eq(expires_at),
+                ))
+                .on_conflict_do_nothing()
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|inserted| inserted == 1)
+    }
+    async fn delete_expired_proofs(&self) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(dpop_proofs::table.filter(dpop_proofs::expires_at.lt(Utc::now())))
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::models::group::{CreateGroup, Group, GroupGraph};
+use crate::domain::models::role::Role;
+use crate::domain::repositories::group::GroupRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::databases::postgresql::{with_tenant, DBConn};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::group::{
+    CreateGroupDiesel, GroupDiesel, GroupMemberDiesel, GroupRoleDiesel, GroupSubgroupDiesel,
+};
+use crate::infrastructure::models::role::RoleDiesel;
+use crate::infrastructure::schema::{group_members, group_roles, group_subgroups, groups, roles, users};
+
+pub struct GroupDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl GroupDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        GroupDieselRepository { pool: db }
+    }
+}
+
+fn ensure_group(conn: &mut PgConnection, organization_id: i32, group_id: i32) -> QueryResult<i32> {
+    groups::table
+        .filter(groups::id.eq(group_id))
+        .filter(groups::organization_id.eq(organization_id))
+        .select(groups::id)
+        .first::<i32>(conn)
+}
+
+fn subgroup_edges(conn: &mut PgConnection, organization_id: i32) -> QueryResult<Vec<(i32, i32)>> {
+    let group_ids = groups::table
+        .filter(groups::organization_id.eq(organization_id))
+        .select(groups::id);
+    group_subgroups::table
+        .filter(group_subgroups::parent_group_id.eq_any(group_ids))
+        .select((group_subgroups::parent_group_id, group_subgroups::child_group_id))
+        .load::<(i32, i32)>(conn)
+}
+
+#[async_trait]
+impl GroupRepository for GroupDieselRepository {
+    async fn create_group(&self, organization_id: i32, new_group: &CreateGroup) -> RepositoryResult<Group> {
+        let new_group_diesel = CreateGroupDiesel::new(organization_id, new_group.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::insert_into(groups::table)
+                    .values(new_group_diesel)
+                    .get_result::<GroupDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Group { v.into() })
+    }
+    async fn list_groups(&self, organization_id: i32) -> RepositoryResult<Vec<Group>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                groups::table
+                    .filter(groups::organization_id.eq(organization_id))
+                    .order(groups::name)
+                    .load::<GroupDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Group::from).collect())
+    }
+    async fn delete_group(&self, organization_id: i32, group_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::delete(
+                    groups::table
+                        .filter(groups::id.eq(group_id))
+                        .filter(groups::organization_id.eq(organization_id)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn add_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                ensure_group(conn, organization_id, group_id)?;
+                users::table
+                    .filter(users::id.eq(user_id))
+                    .filter(users::organization_id.eq(organization_id))
+                    .select(users::id)
+                    .first::<i32>(conn)?;
+                diesel::insert_into(group_members::table)
+                    .values(GroupMemberDiesel { group_id, user_id })
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn remove_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let group_ids = groups::table
+                    .filter(groups::organization_id.eq(organization_id))
+                    .select(groups::id);
+                diesel::delete(
+                    group_members::table
+                        .filter(group_members::group_id.eq(group_id))
+                        .filter(group_members::user_id.eq(user_id))
+                        .filter(group_members::group_id.eq_any(group_ids)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_user_group_ids(&self, organization_id: i32, user_id: i32) -> RepositoryResult<Vec<i32>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let group_ids = groups::table
+                    .filter(groups::organization_id.eq(organization_id))
+                    .select(groups::id);
+                group_members::table
+                    .filter(group_members::user_id.eq(user_id))
+                    .filter(group_members::group_id.eq_any(group_ids))
+                    .select(group_members::group_id)
+                    .load::<i32>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn add_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> RepositoryResult<bool> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                // Trava os grupos da organização até o fim da transação, para que duas inclusões
+                // concorrentes não passem pela verificação de ciclo ao mesmo tempo.
+                groups::table
+                    .filter(groups::organization_id.eq(organization_id))
+                    .select(groups::id)
+                    .for_no_key_update()
+                    .load::<i32>(conn)?;
+                ensure_group(conn, organization_id, parent_group_id)?;
+                ensure_group(conn, organization_id, child_group_id)?;
+                let edges = subgroup_edges(conn, organization_id)?;
+                if GroupGraph::new(&edges).would_create_cycle(parent_group_id, child_group_id) {
+                    return Ok(false);
+                }
+                diesel::insert_into(group_subgroups::table)
+                    .values(GroupSubgroupDiesel {
+                        parent_group_id,
+                        child_group_id,
+                    })
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                Ok(true)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn remove_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let group_ids = groups::table
+                    .filter(groups::organization_id.eq(organization_id))
+                    .select(groups::id);
+                diesel::delete(
+                    group_subgroups::table
+                        .filter(group_subgroups::parent_group_id.eq(parent_group_id))
+                        .filter(group_subgroups::child_group_id.eq(child_group_id))
+                        .filter(group_subgroups::parent_group_id.eq_any(group_ids)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn list_subgroup_edges(&self, organization_id: i32) -> RepositoryResult<Vec<(i32, i32)>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || with_tenant(&mut conn, organization_id, |conn| subgroup_edges(conn, organization_id)))
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn assign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                ensure_group(conn, organization_id, group_id)?;
+                roles::table
+                    .filter(roles::id.eq(role_id))
+                    .filter(roles::organization_id.eq(organization_id))
+                    .select(roles::id)
+                    .first::<i32>(conn)?;
+                diesel::insert_into(group_roles::table)
+                    .values(GroupRoleDiesel { group_id, role_id })
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn unassign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let group_ids = groups::table
+                    .filter(groups::organization_id.eq(organization_id))
+                    .select(groups::id);
+                diesel::delete(
+                    group_roles::table
+                        .filter(group_roles::group_id.eq(group_id))
+                        .filter(group_roles::role_id.eq(role_id))
+                        .filter(group_roles::group_id.eq_any(group_ids)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_groups_roles(&self, organization_id: i32, group_ids: Vec<i32>) -> RepositoryResult<Vec<(i32, Role)>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                group_roles::table
+                    .inner_join(roles::table)
+                    .filter(roles::organization_id.eq(organization_id))
+                    .filter(group_roles::group_id.eq_any(group_ids))
+                    .select((group_roles::group_id, roles::all_columns))
+                    .order(roles::name)
+                    .load::<(i32, RoleDiesel)>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(|(group_id, role)| (group_id, role.into())).collect())
+    }
+}
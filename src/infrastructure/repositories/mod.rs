@@ -1 +1,11 @@
+pub mod audit;
+pub mod dpop;
+pub mod group;
+pub mod oauth;
+pub mod organization;
+pub mod outbox;
+pub mod personal_access_token;
+pub mod role;
+pub mod session;
+pub mod token;
 pub mod user;
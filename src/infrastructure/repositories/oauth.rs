@@ -0,0 +1,568 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::domain::models::oauth::{
+    AuthorizationCode, AuthorizationRequest, ClientType, Consent, CreateAuthorizationCode, CreateDeviceCode,
+    CreateOAuthClient, DeviceCode, DeviceCodeStatus, OAuthClient,
+};
+use crate::domain::repositories::oauth::OAuthRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::oauth::{
+    AuthorizationCodeDiesel, ConsentDiesel, CreateAuthorizationCodeDiesel, CreateConsentDiesel,
+    CreateDeviceCodeDiesel, CreateInitialAccessTokenDiesel, CreateOAuthClientDiesel,
+    CreatePushedAuthorizationRequestDiesel, DeviceCodeDiesel, OAuthClientDiesel,
+    PushedAuthorizationRequestDiesel, UpdateOAuthClientDiesel,
+};
+use crate::infrastructure::schema::{
+    authorization_codes, consents, device_codes, failed_attempts, initial_access_tokens, oauth_clients,
+    pushed_authorization_requests,
+};
+
+pub struct OAuthDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl OAuthDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        OAuthDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl OAuthRepository for OAuthDieselRepository {
+    async fn create_client(
+        &self,
+        organization_id: i32,
+        client_id: &str,
+        client_secret_hash: Option<&str>,
+        registration_access_token_hash: Option<&str>,
+        new_client: &CreateOAuthClient,
+    ) -> RepositoryResult<OAuthClient> {
+        let new_client_diesel = CreateOAuthClientDiesel::new(
+            organization_id,
+            client_id.to_string(),
+            client_secret_hash.map(str::to_string),
+            registration_access_token_hash.map(str::to_string),
+            new_client.clone(),
+        );
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(oauth_clients::table)
+                .values(new_client_diesel)
+                .get_result::<OAuthClientDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> OAuthClient { v.into() })
+    }
+    async fn update_client(&self, id: i32, client: &CreateOAuthClient) -> RepositoryResult<OAuthClient> {
+        let changes = UpdateOAuthClientDiesel::from(client.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(oauth_clients::table.find(id))
+                .set(changes)
+                .get_result::<OAuthClientDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> OAuthClient { v.into() })
+    }
+    async fn list_clients(&self, organization_id: i32) -> RepositoryResult<Vec<OAuthClient>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            oauth_clients::table
+                .filter(oauth_clients::organization_id.eq(organization_id))
+                .order(oauth_clients::name)
+                .load::<OAuthClientDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(OAuthClient::from).collect())
+    }
+    async fn delete_client(&self, organization_id: i32, id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                oauth_clients::table
+                    .filter(oauth_clients::id.eq(id))
+                    .filter(oauth_clients::organization_id.eq(organization_id)),
+            )
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_client(&self, client_id: &str) -> RepositoryResult<OAuthClient> {
+        let client_id = client_id.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            oauth_clients::table
+                .filter(oauth_clients::client_id.eq(client_id))
+                .first::<OAuthClientDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> OAuthClient { v.into() })
+    }
+    async fn rotate_client_secret(&self, organization_id: i32, id: i32, client_secret_hash: &str) -> RepositoryResult<OAuthClient> {
+        let client_secret_hash = client_secret_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                oauth_clients::table
+                    .filter(oauth_clients::id.eq(id))
+                    .filter(oauth_clients::organization_id.eq(organization_id))
+                    .filter(oauth_clients::client_type.eq(ClientType::Confidential.as_str())),
+            )
+            .set((
+                oauth_clients::previous_client_secret_hash.eq(oauth_clients::client_secret_hash),
+                oauth_clients::client_secret_hash.eq(client_secret_hash),
+            ))
+            .get_result::<OAuthClientDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> OAuthClient { v.into() })
+    }
+    async fn revoke_previous_client_secret(&self, organization_id: i32, id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                oauth_clients::table
+                    .filter(oauth_clients::id.eq(id))
+                    .filter(oauth_clients::organization_id.eq(organization_id)),
+            )
+            .set(oauth_clients::previous_client_secret_hash.eq(None::<String>))
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn create_authorization_code(&self, new_code: &CreateAuthorizationCode) -> RepositoryResult<()> {
+        let new_code_diesel = CreateAuthorizationCodeDiesel::from(new_code.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(authorization_codes::table)
+                .values(new_code_diesel)
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn consume_authorization_code(&self, code_hash: &str) -> RepositoryResult<AuthorizationCode> {
+        let code_hash = code_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                authorization_codes::table
+                    .filter(authorization_codes::code_hash.eq(code_hash))
+                    .filter(authorization_codes::used_at.is_null())
+                    .filter(authorization_codes::consent_pending.eq(false)),
+            )
+            .set(authorization_codes::used_at.eq(Utc::now()))
+            .get_result::<AuthorizationCodeDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> AuthorizationCode { v.into() })
+    }
+    async fn approve_authorization_code(
+        &self,
+        code_hash: &str,
+        oauth_client_id: i32,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<AuthorizationCode> {
+        let code_hash = code_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                authorization_codes::table
+                    .filter(authorization_codes::code_hash.eq(code_hash))
+                    .filter(authorization_codes::oauth_client_id.eq(oauth_client_id))
+                    .filter(authorization_codes::consent_pending.eq(true))
+                    .filter(authorization_codes::expires_at.gt(Utc::now())),
+            )
+            .set((
+                authorization_codes::consent_pending.eq(false),
+                authorization_codes::expires_at.eq(expires_at),
+            ))
+            .get_result::<AuthorizationCodeDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> AuthorizationCode { v.into() })
+    }
+    async fn delete_pending_authorization_code(&self, code_hash: &str, oauth_client_id: i32) -> RepositoryResult<usize> {
+        let code_hash = code_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                authorization_codes::table
+                    .filter(authorization_codes::code_hash.eq(code_hash))
+                    .filter(authorization_codes::oauth_client_id.eq(oauth_client_id))
+                    .filter(authorization_codes::consent_pending.eq(true)),
+            )
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn create_pushed_authorization_request(
+        &self,
+        request_uri_hash: &str,
+        oauth_client_id: i32,
+        request: &AuthorizationRequest,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        let new_request_diesel = CreatePushedAuthorizationRequestDiesel::new(
+            request_uri_hash.to_string(),
+            oauth_client_id,
+            request.clone(),
+            expires_at,
+        );
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(pushed_authorization_requests::table)
+                .values(new_request_diesel)
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_pushed_authorization_request(
+        &self,
+        request_uri_hash: &str,
+        oauth_client_id: i32,
+    ) -> RepositoryResult<AuthorizationRequest> {
+        let request_uri_hash = request_uri_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            pushed_authorization_requests::table
+                .inner_join(oauth_clients::table)
+                .filter(pushed_authorization_requests::request_uri_hash.eq(request_uri_hash))
+                .filter(pushed_authorization_requests::oauth_client_id.eq(oauth_client_id))
+                .filter(pushed_authorization_requests::expires_at.gt(Utc::now()))
+                .select((
+                    pushed_authorization_requests::all_columns,
+                    oauth_clients::client_id,
+                ))
+                .first::<(PushedAuthorizationRequestDiesel, String)>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|(request, client_id)| request.into_request(client_id))
+    }
+    async fn delete_pushed_authorization_request(&self, request_uri_hash: &str, oauth_client_id: i32) -> RepositoryResult<usize> {
+        let request_uri_hash = request_uri_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                pushed_authorization_requests::table
+                    .filter(pushed_authorization_requests::request_uri_hash.eq(request_uri_hash))
+                    .filter(pushed_authorization_requests::oauth_client_id.eq(oauth_client_id))
+                    .filter(pushed_authorization_requests::expires_at.gt(Utc::now())),
+            )
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn get_consent(&self, user_id: i32, oauth_client_id: i32) -> RepositoryResult<Option<Consent>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            consents::table
+                .find((user_id, oauth_client_id))
+                .first::<ConsentDiesel>(&mut conn)
+                .optional()
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.map(Consent::from))
+    }
+    async fn save_consent(&self, user_id: i32, oauth_client_id: i32, scopes: &[String]) -> RepositoryResult<Consent> {
+        let new_consent = CreateConsentDiesel {
+            user_id,
+            oauth_client_id,
+            scopes: scopes.to_vec(),
+        };
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(consents::table)
+                .values(&new_consent)
+                .on_conflict((consents::user_id, consents::oauth_client_id))
+                .do_update()
+                .set((
+                    consents::scopes.eq(&new_consent.scopes),
+                    consents::updated_at.eq(Utc::now()),
+                ))
+                .get_result::<ConsentDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Consent { v.into() })
+    }
+    async fn list_consents(&self, user_id: i32) -> RepositoryResult<Vec<(Consent, OAuthClient)>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            consents::table
+                .inner_join(oauth_clients::table)
+                .filter(consents::user_id.eq(user_id))
+                .order(oauth_clients::name)
+                .load::<(ConsentDiesel, OAuthClientDiesel)>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| {
+            v.into_iter()
+                .map(|(consent, client)| (consent.into(), client.into()))
+                .collect()
+        })
+    }
+    async fn delete_consent(&self, user_id: i32, oauth_client_id: i32) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || diesel::delete(consents::table.find((user_id, oauth_client_id))).execute(&mut conn))
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn create_device_code(&self, new_code: &CreateDeviceCode) -> RepositoryResult<()> {
+        let new_code_diesel = CreateDeviceCodeDiesel::from(new_code.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(device_codes::table)
+                .values(new_code_diesel)
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_pending_device_code(&self, user_code: &str) -> RepositoryResult<(DeviceCode, OAuthClient)> {
+        let user_code = user_code.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            device_codes::table
+                .inner_join(oauth_clients::table)
+                .filter(device_codes::user_code.eq(user_code))
+                .filter(device_codes::status.eq(DeviceCodeStatus::Pending.as_str()))
+                .filter(device_codes::expires_at.gt(Utc::now()))
+                .first::<(DeviceCodeDiesel, OAuthClientDiesel)>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|(code, client)| (code.into(), client.into()))
+    }
+    async fn decide_device_code(
+        &self,
+        user_code: &str,
+        user_id: Option<i32>,
+        status: DeviceCodeStatus,
+    ) -> RepositoryResult<DeviceCode> {
+        let user_code = user_code.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                device_codes::table
+                    .filter(device_codes::user_code.eq(user_code))
+                    .filter(device_codes::status.eq(DeviceCodeStatus::Pending.as_str()))
+                    .filter(device_codes::expires_at.gt(Utc::now())),
+            )
+            .set((
+                device_codes::status.eq(status.as_str()),
+                device_codes::user_id.eq(user_id),
+            ))
+            .get_result::<DeviceCodeDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> DeviceCode { v.into() })
+    }
+    async fn poll_device_code(&self, device_code_hash: &str) -> RepositoryResult<DeviceCode> {
+        let device_code_hash = device_code_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let code = device_codes::table
+                    .find(&device_code_hash)
+                    .for_update()
+                    .first::<DeviceCodeDiesel>(conn)?;
+                diesel::update(device_codes::table.find(&device_code_hash))
+                    .set(device_codes::last_polled_at.eq(Utc::now()))
+                    .execute(conn)?;
+                Ok(code)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v: DeviceCodeDiesel| -> DeviceCode { v.into() })
+    }
+    async fn slow_down_device_code(&self, device_code_hash: &str, increment: i32) -> RepositoryResult<()> {
+        let device_code_hash = device_code_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(device_codes::table.find(device_code_hash))
+                .set(device_codes::interval.eq(device_codes::interval + increment))
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn consume_device_code(&self, device_code_hash: &str) -> RepositoryResult<DeviceCode> {
+        let device_code_hash = device_code_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                device_codes::table
+                    .filter(device_codes::device_code_hash.eq(device_code_hash))
+                    .filter(device_codes::status.eq(DeviceCodeStatus::Approved.as_str())),
+            )
+            .get_result::<DeviceCodeDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> DeviceCode { v.into() })
+    }
+    async fn create_initial_access_token(
+        &self,
+        organization_id: i32,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        let new_token_diesel = CreateInitialAccessTokenDiesel {
+            token_hash: token_hash.to_string(),
+            organization_id,
+            expires_at,
+        };
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(initial_access_tokens::table)
+                .values(new_token_diesel)
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_initial_access_token_organization(&self, token_hash: &str) -> RepositoryResult<i32> {
+        let token_hash = token_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            initial_access_tokens::table
+                .find(token_hash)
+                .filter(initial_access_tokens::expires_at.gt(Utc::now()))
+                .select(initial_access_tokens::organization_id)
+                .first::<i32>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn consume_initial_access_token(&self, token_hash: &str) -> RepositoryResult<i32> {
+        let token_hash = token_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                initial_access_tokens::table
+                    .find(token_hash)
+                    .filter(initial_access_tokens::expires_at.gt(Utc::now())),
+            )
+            .returning(initial_access_tokens::organization_id)
+            .get_result::<i32>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn count_failed_attempts(&self, subject: &str) -> RepositoryResult<i32> {
+        let subject = subject.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            failed_attempts::table
+                .filter(failed_attempts::subject.eq(subject))
+                .filter(failed_attempts::expires_at.gt(Utc::now()))
+                .select(failed_attempts::failures)
+                .first::<i32>(&mut conn)
+                .optional()
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|failures| failures.unwrap_or(0))
+    }
+    async fn record_failed_attempt(&self, subject: &str, expires_at: DateTime<Utc>) -> RepositoryResult<i32> {
+        let subject = subject.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::insert_into(failed_attempts::table)
+                    .values((
+                        failed_attempts::subject.eq(&subject),
+                        failed_attempts::failures.eq(0),
+                        failed_attempts::expires_at.eq(expires_at),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                let (failures, current_expires_at) = failed_attempts::table
+                    .filter(failed_attempts::subject.eq(&subject))
+                    .select((failed_attempts::failures, failed_attempts::expires_at))
+                    .for_update()
+                    .first::<(i32, DateTime<Utc>)>(conn)?;
+                let (failures, expires_at) = if current_expires_at > Utc::now() {
+                    (failures + 1, current_expires_at)
+                } else {
+                    (1, expires_at)
+                };
+                diesel::update(failed_attempts::table.filter(failed_attempts::subject.eq(&subject)))
+                    .set((
+                        failed_attempts::failures.eq(failures),
+                        failed_attempts::expires_at.eq(expires_at),
+                    ))
+                    .execute(conn)?;
+                Ok(failures)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn delete_expired_codes(&self) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let now = Utc::now();
+                let authorization_codes = diesel::delete(
+                    authorization_codes::table.filter(authorization_codes::expires_at.lt(now)),
+                )
+                .execute(conn)?;
+                let device_codes =
+                    diesel::delete(device_codes::table.filter(device_codes::expires_at.lt(now)))
+                        .execute(conn)?;
+                let initial_access_tokens = diesel::delete(
+                    initial_access_tokens::table.filter(initial_access_tokens::expires_at.lt(now)),
+                )
+                .execute(conn)?;
+                let failed_attempts =
+                    diesel::delete(failed_attempts::table.filter(failed_attempts::expires_at.lt(now)))
+                        .execute(conn)?;
+                let pushed_authorization_requests = diesel::delete(
+                    pushed_authorization_requests::table
+                        .filter(pushed_authorization_requests::expires_at.lt(now)),
+                )
+                .execute(conn)?;
+                Ok(authorization_codes
+                    + device_codes
+                    + pushed_authorization_requests
+                    + initial_access_tokens
+                    + failed_attempts)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::models::organization::{CreateOrganization, Organization};
+use crate::domain::repositories::organization::OrganizationRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::organization::{CreateOrganizationDiesel, OrganizationDiesel};
+use crate::infrastructure::schema::organizations;
+
+pub struct OrganizationDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl OrganizationDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        OrganizationDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for OrganizationDieselRepository {
+    async fn create(&self, new_organization: &CreateOrganization) -> RepositoryResult<Organization> {
+        let new_organization_diesel = CreateOrganizationDiesel::from(new_organization.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(organizations::table)
+                .values(new_organization_diesel)
+                .get_result::<OrganizationDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Organization { v.into() })
+    }
+    async fn list(&self) -> RepositoryResult<Vec<Organization>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            organizations::table
+                .order(organizations::slug)
+                .load::<OrganizationDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Organization::from).collect())
+    }
+    async fn get_by_slug(&self, slug: &str) -> RepositoryResult<Organization> {
+        let slug = slug.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            organizations::table
+                .filter(organizations::slug.eq(slug))
+                .first::<OrganizationDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Organization { v.into() })
+    }
+}
@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use diesel::pg::{Pg, PgConnection};
+use diesel::prelude::*;
+
+use crate::domain::models::outbox::{
+    CreateOutboxEvent, CreateWebhook, DeliveryStatus, OutboxEvent, PendingDelivery, Webhook,
+    WebhookDelivery, WebhookDeliveryFilter,
+};
+use crate::domain::repositories::outbox::OutboxRepository;
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::outbox::{
+    CreateOutboxEventDiesel, CreateWebhookDeliveryDiesel, CreateWebhookDiesel, OutboxEventDiesel,
+    WebhookDeliveryDiesel, WebhookDiesel,
+};
+use crate::infrastructure::schema::{outbox, webhook_deliveries, webhooks};
+
+/// Grava o evento na transação de `conn`, para que ele só exista se a mudança for confirmada.
+pub fn insert_outbox_event(conn: &mut PgConnection, event: CreateOutboxEvent) -> QueryResult<()> {
+    diesel::insert_into(outbox::table)
+        .values(CreateOutboxEventDiesel::from(event))
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Cria as entregas que faltam do evento para os webhooks da organização inscritos no tipo.
+fn queue_deliveries(
+    conn: &mut PgConnection,
+    organization_id: i32,
+    outbox_event_id: i64,
+    event_type: &str,
+) -> QueryResult<usize> {
+    let rows: Vec<CreateWebhookDeliveryDiesel> = webhooks::table
+        .filter(webhooks::organization_id.eq(organization_id))
+        .filter(webhooks::event_types.contains(vec![event_type.to_string()]))
+        .select(webhooks::id)
+        .load::<i32>(conn)?
+        .into_iter()
+        .map(|webhook_id| CreateWebhookDeliveryDiesel {
+            webhook_id,
+            outbox_event_id,
+        })
+        .collect();
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    diesel::insert_into(webhook_deliveries::table)
+        .values(&rows)
+        .on_conflict((webhook_deliveries::webhook_id, webhook_deliveries::outbox_event_id))
+        .do_nothing()
+        .execute(conn)
+}
+
+fn filtered_deliveries(
+    organization_id: i32,
+    filter: &WebhookDeliveryFilter,
+) -> webhook_deliveries::BoxedQuery<'static, Pg> {
+    let organization_webhooks = webhooks::table
+        .filter(webhooks::organization_id.eq(organization_id))
+        .select(webhooks::id);
+    let mut query = webhook_deliveries::table
+        .filter(webhook_deliveries::webhook_id.eq_any(organization_webhooks))
+        .into_boxed();
+    if let Some(status) = filter.status {
+        query = query.filter(webhook_deliveries::status.eq(status.as_str()));
+    }
+    if let Some(webhook_id) = filter.webhook_id {
+        query = query.filter(webhook_deliveries::webhook_id.eq(webhook_id));
+    }
+    if let Some(outbox_event_id) = filter.outbox_event_id {
+        query = query.filter(webhook_deliveries::outbox_event_id.eq(outbox_event_id));
+    }
+    query
+}
+
+pub struct OutboxDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl OutboxDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        OutboxDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxDieselRepository {
+    async fn get_event(&self, organization_id: i32, id: i64) -> RepositoryResult<OutboxEvent> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            outbox::table
+                .filter(outbox::organization_id.eq(organization_id))
+                .filter(outbox::id.eq(id))
+                .first::<OutboxEventDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> OutboxEvent { v.into() })
+    }
+    async fn create_webhook(
+        &self,
+        organization_id: i32,
+        new_webhook: &CreateWebhook,
+        secret: &str,
+    ) -> RepositoryResult<Webhook> {
+        let new_webhook = CreateWebhookDiesel {
+            organization_id,
+            url: new_webhook.url.clone(),
+            secret: secret.to_string(),
+            event_types: new_webhook
+                .event_types
+                .iter()
+                .map(|event_type| event_type.as_str().to_string())
+                .collect(),
+        };
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(webhooks::table)
+                .values(new_webhook)
+                .get_result::<WebhookDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Webhook { v.into() })
+    }
+    async fn list_webhooks(&self, organization_id: i32) -> RepositoryResult<Vec<Webhook>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            webhooks::table
+                .filter(webhooks::organization_id.eq(organization_id))
+                .order(webhooks::id)
+                .load::<WebhookDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Webhook::from).collect())
+    }
+    async fn delete_webhook(&self, organization_id: i32, id: i32) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                webhooks::table
+                    .filter(webhooks::organization_id.eq(organization_id))
+                    .filter(webhooks::id.eq(id)),
+            )
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn fan_out(&self, limit: i64) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction(|conn| {
+                // outra instância distribuindo ao mesmo tempo pula os eventos travados aqui
+                let events = outbox::table
+                    .filter(outbox::dispatched_at.is_null())
+                    .order(outbox::id.asc())
+                    .limit(limit)
+                    .for_update()
+                    .skip_locked()
+                    .load::<OutboxEventDiesel>(conn)?;
+                for event in &events {
+                    queue_deliveries(conn, event.organization_id, event.id, &event.event_type)?;
+                }
+                let ids: Vec<i64> = events.iter().map(|event| event.id).collect();
+                diesel::update(outbox::table.filter(outbox::id.eq_any(ids)))
+                    .set(outbox::dispatched_at.eq(Utc::now()))
+                    .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn claim_due(&self, limit: i64, lease: Duration) -> RepositoryResult<Vec<PendingDelivery>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction(|conn| {
+                let now = Utc::now();
+                let ids = webhook_deliveries::table
+                    .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()))
+                    .filter(webhook_deliveries::next_attempt_at.le(now))
+                    .order(webhook_deliveries::next_attempt_at.asc())
+                    .limit(limit)
+                    .select(webhook_deliveries::id)
+                    .for_update()
+                    .skip_locked()
+                    .load::<i64>(conn)?;
+                diesel::update(webhook_deliveries::table.filter(webhook_deliveries::id.eq_any(&ids)))
+                    .set(webhook_deliveries::next_attempt_at.eq(now + lease))
+                    .execute(conn)?;
+                webhook_deliveries::table
+                    .inner_join(webhooks::table)
+                    .inner_join(outbox::table)
+                    .filter(webhook_deliveries::id.eq_any(&ids))
+                    .order(webhook_deliveries::id.asc())
+                    .load::<(WebhookDeliveryDiesel, WebhookDiesel, OutboxEventDiesel)>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| {
+            v.into_iter()
+                .map(|(delivery, webhook, event)| PendingDelivery {
+                    delivery: delivery.into(),
+                    webhook: webhook.into(),
+                    event: event.into(),
+                })
+                .collect()
+        })
+    }
+    async fn mark_delivered(&self, id: i64) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                webhook_deliveries::table
+                    .filter(webhook_deliveries::id.eq(id))
+                    .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str())),
+            )
+            .set((
+                webhook_deliveries::status.eq(DeliveryStatus::Delivered.as_str()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::last_error.eq(None::<String>),
+                webhook_deliveries::delivered_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn mark_failed(
+        &self,
+        id: i64,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> RepositoryResult<usize> {
+        let error = error.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let target = webhook_deliveries::table
+                .filter(webhook_deliveries::id.eq(id))
+                .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()));
+            match next_attempt_at {
+                Some(next_attempt_at) => diesel::update(target)
+                    .set((
+                        webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                        webhook_deliveries::last_error.eq(error),
+                        webhook_deliveries::next_attempt_at.eq(next_attempt_at),
+                    ))
+                    .execute(&mut conn),
+                None => diesel::update(target)
+                    .set((
+                        webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                        webhook_deliveries::last_error.eq(error),
+                        webhook_deliveries::status.eq(DeliveryStatus::Dead.as_str()),
+                    ))
+                    .execute(&mut conn),
+            }
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn list_deliveries(
+        &self,
+        organization_id: i32,
+        filter: &WebhookDeliveryFilter,
+        params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<WebhookDelivery>> {
+        let filter = filter.clone();
+        let (limit, offset) = (params.limit(), params.offset());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let total = filtered_deliveries(organization_id, &filter)
+                .count()
+                .get_result::<i64>(&mut conn)?;
+            let items = filtered_deliveries(organization_id, &filter)
+                .order(webhook_deliveries::id.desc())
+                .limit(limit)
+                .offset(offset)
+                .load::<WebhookDeliveryDiesel>(&mut conn)?;
+            Ok::<_, diesel::result::Error>((total, items))
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|(total, items)| ResultPaging {
+            total,
+            items: items.into_iter().map(WebhookDelivery::from).collect(),
+        })
+    }
+    async fn replay(&self, organization_id: i32, outbox_event_id: i64) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction(|conn| {
+                let event_type = outbox::table
+                    .filter(outbox::organization_id.eq(organization_id))
+                    .filter(outbox::id.eq(outbox_event_id))
+                    .select(outbox::event_type)
+                    .first::<String>(conn)?;
+                queue_deliveries(conn, organization_id, outbox_event_id, &event_type)?;
+                diesel::update(
+                    webhook_deliveries::table
+                        .filter(webhook_deliveries::outbox_event_id.eq(outbox_event_id)),
+                )
+                .set((
+                    webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()),
+                    webhook_deliveries::attempts.eq(0),
+                    webhook_deliveries::next_attempt_at.eq(Utc::now()),
+                    webhook_deliveries::last_error.eq(None::<String>),
+                    webhook_deliveries::delivered_at.eq(None::<DateTime<Utc>>),
+                ))
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::domain::models::personal_access_token::{CreatePersonalAccessToken, PersonalAccessToken};
+use crate::domain::repositories::personal_access_token::PersonalAccessTokenRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::personal_access_token::{
+    CreatePersonalAccessTokenDiesel, PersonalAccessTokenDiesel,
+};
+use crate::infrastructure::schema::personal_access_tokens;
+
+pub struct PersonalAccessTokenDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl PersonalAccessTokenDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        PersonalAccessTokenDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepository for PersonalAccessTokenDieselRepository {
+    async fn create(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        token_hash: &str,
+        token_hint: &str,
+        new_token: &CreatePersonalAccessToken,
+    ) -> RepositoryResult<PersonalAccessToken> {
+        let new_token_diesel = CreatePersonalAccessTokenDiesel::new(
+            organization_id,
+            user_id,
+            token_hash.to_string(),
+            token_hint.to_string(),
+            new_token.clone(),
+        );
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(personal_access_tokens::table)
+                .values(new_token_diesel)
+                .get_result::<PersonalAccessTokenDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> PersonalAccessToken { v.into() })
+    }
+    async fn list(&self, user_id: i32) -> RepositoryResult<Vec<PersonalAccessToken>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            personal_access_tokens::table
+                .filter(personal_access_tokens::user_id.eq(user_id))
+                .order(personal_access_tokens::id)
+                .load::<PersonalAccessTokenDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(PersonalAccessToken::from).collect())
+    }
+    async fn get(&self, id: i32) -> RepositoryResult<PersonalAccessToken> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            personal_access_tokens::table
+                .find(id)
+                .first::<PersonalAccessTokenDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> PersonalAccessToken { v.into() })
+    }
+    async fn get_by_hash(&self, token_hash: &str) -> RepositoryResult<PersonalAccessToken> {
+        let token_hash = token_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            personal_access_tokens::table
+                .filter(personal_access_tokens::token_hash.eq(token_hash))
+                .first::<PersonalAccessTokenDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> PersonalAccessToken { v.into() })
+    }
+    async fn touch(&self, id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(personal_access_tokens::table.find(id))
+                .set(personal_access_tokens::last_used_at.eq(Utc::now()))
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn delete(&self, user_id: i32, id: i32) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(
+                personal_access_tokens::table
+                    .filter(personal_access_tokens::id.eq(id))
+                    .filter(personal_access_tokens::user_id.eq(user_id)),
+            )
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::models::role::{CreatePermission, CreateRole, Permission, Role, RoleSessionPolicy};
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::role::RoleRepository;
+use crate::infrastructure::databases::postgresql::{with_tenant, DBConn};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::role::{
+    CreatePermissionDiesel, CreateRoleDiesel, PermissionDiesel, RoleDiesel, RolePermissionDiesel,
+    RoleSessionPolicyDiesel, UserRoleDiesel,
+};
+use crate::infrastructure::schema::{permissions, role_permissions, roles, user_roles, users};
+
+pub struct RoleDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl RoleDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        RoleDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl RoleRepository for RoleDieselRepository {
+    async fn create_role(&self, organization_id: i32, new_role: &CreateRole) -> RepositoryResult<Role> {
+        let new_role_diesel = CreateRoleDiesel::new(organization_id, new_role.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::insert_into(roles::table)
+                    .values(new_role_diesel)
+                    .get_result::<RoleDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Role { v.into() })
+    }
+    async fn list_roles(&self, organization_id: i32) -> RepositoryResult<Vec<Role>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                roles::table
+                    .filter(roles::organization_id.eq(organization_id))
+                    .order(roles::name)
+                    .load::<RoleDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Role::from).collect())
+    }
+    async fn get_role_by_name(&self, organization_id: i32, name: &str) -> RepositoryResult<Role> {
+        let name = name.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                roles::table
+                    .filter(roles::organization_id.eq(organization_id))
+                    .filter(roles::name.eq(name))
+                    .first::<RoleDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Role { v.into() })
+    }
+    async fn delete_role(&self, organization_id: i32, role_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::delete(
+                    roles::table
+                        .filter(roles::id.eq(role_id))
+                        .filter(roles::organization_id.eq(organization_id)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn set_role_session_policy(
+        &self,
+        organization_id: i32,
+        role_id: i32,
+        policy: &RoleSessionPolicy,
+    ) -> RepositoryResult<Role> {
+        let changes = RoleSessionPolicyDiesel::from(policy.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::update(
+                    roles::table
+                        .filter(roles::id.eq(role_id))
+                        .filter(roles::organization_id.eq(organization_id)),
+                )
+                .set(changes)
+                .get_result::<RoleDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Role { v.into() })
+    }
+    async fn create_permission(&self, new_permission: &CreatePermission) -> RepositoryResult<Permission> {
+        let new_permission_diesel = CreatePermissionDiesel::from(new_permission.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(permissions::table)
+                .values(new_permission_diesel)
+                .get_result::<PermissionDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Permission { v.into() })
+    }
+    async fn list_permissions(&self) -> RepositoryResult<Vec<Permission>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            permissions::table
+                .order(permissions::name)
+                .load::<PermissionDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Permission::from).collect())
+    }
+    async fn get_role_permissions(&self, organization_id: i32, role_id: i32) -> RepositoryResult<Vec<Permission>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let role_ids = roles::table
+                    .filter(roles::id.eq(role_id))
+                    .filter(roles::organization_id.eq(organization_id))
+                    .select(roles::id);
+                let permission_ids = role_permissions::table
+                    .filter(role_permissions::role_id.eq_any(role_ids))
+                    .select(role_permissions::permission_id);
+                permissions::table
+                    .filter(permissions::id.eq_any(permission_ids))
+                    .order(permissions::name)
+                    .load::<PermissionDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Permission::from).collect())
+    }
+    async fn grant_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                roles::table
+                    .filter(roles::id.eq(role_id))
+                    .filter(roles::organization_id.eq(organization_id))
+                    .select(roles::id)
+                    .first::<i32>(conn)?;
+                diesel::insert_into(role_permissions::table)
+                    .values(RolePermissionDiesel { role_id, permission_id })
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn revoke_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let role_ids = roles::table
+                    .filter(roles::organization_id.eq(organization_id))
+                    .select(roles::id);
+                diesel::delete(
+                    role_permissions::table
+                        .filter(role_permissions::role_id.eq(role_id))
+                        .filter(role_permissions::permission_id.eq(permission_id))
+                        .filter(role_permissions::role_id.eq_any(role_ids)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn assign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                users::table
+                    .filter(users::id.eq(user_id))
+                    .filter(users::organization_id.eq(organization_id))
+                    .select(users::id)
+                    .first::<i32>(conn)?;
+                roles::table
+                    .filter(roles::id.eq(role_id))
+                    .filter(roles::organization_id.eq(organization_id))
+                    .select(roles::id)
+                    .first::<i32>(conn)?;
+                diesel::insert_into(user_roles::table)
+                    .values(UserRoleDiesel { user_id, role_id })
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn unassign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let role_ids = roles::table
+                    .filter(roles::organization_id.eq(organization_id))
+                    .select(roles::id);
+                diesel::delete(
+                    user_roles::table
+                        .filter(user_roles::user_id.eq(user_id))
+                        .filter(user_roles::role_id.eq(role_id))
+                        .filter(user_roles::role_id.eq_any(role_ids)),
+                )
+                .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_user_roles(&self, organization_id: i32, user_id: i32) -> RepositoryResult<Vec<Role>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let role_ids = user_roles::table
+                    .filter(user_roles::user_id.eq(user_id))
+                    .select(user_roles::role_id);
+                roles::table
+                    .filter(roles::organization_id.eq(organization_id))
+                    .filter(roles::id.eq_any(role_ids))
+                    .order(roles::name)
+                    .load::<RoleDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Role::from).collect())
+    }
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Bool;
+
+use crate::domain::models::session::{CreateSession, Session, SessionLimitAction};
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::session::SessionRepository;
+use crate::infrastructure::databases::postgresql::{with_tenant, DBConn};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::session::{CreateSessionDiesel, SessionDiesel};
+use crate::infrastructure::schema::{sessions, users};
+
+/// Sessões usadas dentro do tempo máximo sem uso, que é próprio de cada sessão.
+fn not_idle() -> diesel::expression::SqlLiteral<Bool> {
+    sql::<Bool>(r#""last_seen_at" + "idle_timeout_seconds" * INTERVAL '1 second' > NOW()"#)
+}
+
+pub struct SessionDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl SessionDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        SessionDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SessionDieselRepository {
+    async fn create(&self, new_session: &CreateSession) -> RepositoryResult<Session> {
+        let new_session_diesel = CreateSessionDiesel::from(new_session.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(sessions::table)
+                .values(new_session_diesel)
+                .get_result::<SessionDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Session { v.into() })
+    }
+    async fn create_within_limit(
+        &self,
+        new_session: &CreateSession,
+        max_concurrent: i32,
+        limit_action: SessionLimitAction,
+    ) -> RepositoryResult<Option<Session>> {
+        let organization_id = new_session.organization_id;
+        let user_id = new_session.user_id;
+        let new_session_diesel = CreateSessionDiesel::from(new_session.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                // logins simultâneos do mesmo usuário esperam aqui até o anterior gravar a sessão
+                users::table
+                    .find(user_id)
+                    .select(users::id)
+                    .for_update()
+                    .first::<i32>(conn)?;
+                let now = Utc::now();
+                let active = sessions::table
+                    .filter(sessions::user_id.eq(user_id))
+                    .filter(sessions::revoked_at.is_null())
+                    .filter(sessions::expires_at.gt(now))
+                    .filter(not_idle())
+                    .order(sessions::created_at.asc())
+                    .select(sessions::id)
+                    .load::<String>(conn)?;
+                let excess = (active.len() + 1).saturating_sub(max_concurrent.max(0) as usize);
+                if excess > 0 {
+                    if limit_action == SessionLimitAction::RejectNew {
+                        return Ok(None);
+                    }
+                    diesel::update(sessions::table.filter(sessions::id.eq_any(&active[..excess])))
+                        .set(sessions::revoked_at.eq(now))
+                        .execute(conn)?;
+                }
+                diesel::insert_into(sessions::table)
+                    .values(new_session_diesel)
+                    .get_result::<SessionDiesel>(conn)
+                    .map(Some)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.map(Session::from))
+    }
+    async fn list_active(&self, user_id: i32) -> RepositoryResult<Vec<Session>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            sessions::table
+                .filter(sessions::user_id.eq(user_id))
+                .filter(sessions::revoked_at.is_null())
+                .filter(sessions::expires_at.gt(Utc::now()))
+                .filter(not_idle())
+                .order(sessions::last_seen_at.desc())
+                .load::<SessionDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(Session::from).collect())
+    }
+    async fn touch(&self, id: &str) -> RepositoryResult<Session> {
+        let id = id.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            let now = Utc::now();
+            diesel::update(
+                sessions::table
+                    .filter(sessions::id.eq(id))
+                    .filter(sessions::revoked_at.is_null())
+                    .filter(sessions::expires_at.gt(now))
+                    .filter(not_idle()),
+            )
+            .set(sessions::last_seen_at.eq(now))
+            .get_result::<SessionDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> Session { v.into() })
+    }
+    async fn revoke(&self, user_id: i32, id: &str) -> RepositoryResult<usize> {
+        let id = id.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                sessions::table
+                    .filter(sessions::id.eq(id))
+                    .filter(sessions::user_id.eq(user_id))
+                    .filter(sessions::revoked_at.is_null()),
+            )
+            .set(sessions::revoked_at.eq(Utc::now()))
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn revoke_all(&self, user_id: i32) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                sessions::table
+                    .filter(sessions::user_id.eq(user_id))
+                    .filter(sessions::revoked_at.is_null()),
+            )
+            .set(sessions::revoked_at.eq(Utc::now()))
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn delete_expired(&self) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::delete(sessions::table.filter(sessions::expires_at.lt(Utc::now())))
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::dsl::exists;
+use diesel::prelude::*;
+
+use crate::domain::models::token::{CreateRefreshToken, RefreshToken};
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::token::TokenRepository;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::models::token::{
+    CreateRefreshTokenDiesel, CreateRevokedTokenDiesel, RefreshTokenDiesel,
+};
+use crate::infrastructure::schema::{refresh_tokens, revoked_tokens};
+
+pub struct TokenDieselRepository {
+    pub pool: Arc<DBConn>,
+}
+
+impl TokenDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        TokenDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl TokenRepository for TokenDieselRepository {
+    async fn create_refresh_token(&self, new_token: &CreateRefreshToken) -> RepositoryResult<()> {
+        let new_token_diesel = CreateRefreshTokenDiesel::from(new_token.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(refresh_tokens::table)
+                .values(new_token_diesel)
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn get_refresh_token(&self, token_hash: &str) -> RepositoryResult<RefreshToken> {
+        let token_hash = token_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            refresh_tokens::table
+                .find(token_hash)
+                .first::<RefreshTokenDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> RefreshToken { v.into() })
+    }
+    async fn use_refresh_token(&self, token_hash: &str) -> RepositoryResult<RefreshToken> {
+        let token_hash = token_hash.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                refresh_tokens::table
+                    .filter(refresh_tokens::token_hash.eq(token_hash))
+                    .filter(refresh_tokens::used_at.is_null())
+                    .filter(refresh_tokens::revoked_at.is_null()),
+            )
+            .set(refresh_tokens::used_at.eq(Utc::now()))
+            .get_result::<RefreshTokenDiesel>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> RefreshToken { v.into() })
+    }
+    async fn revoke_refresh_token_family(&self, family_id: &str) -> RepositoryResult<()> {
+        let family_id = family_id.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                refresh_tokens::table
+                    .filter(refresh_tokens::family_id.eq(family_id))
+                    .filter(refresh_tokens::revoked_at.is_null()),
+            )
+            .set(refresh_tokens::revoked_at.eq(Utc::now()))
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn revoke_user_refresh_tokens(&self, user_id: i32, oauth_client_id: i32) -> RepositoryResult<()> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::update(
+                refresh_tokens::table
+                    .filter(refresh_tokens::user_id.eq(user_id))
+                    .filter(refresh_tokens::oauth_client_id.eq(oauth_client_id))
+                    .filter(refresh_tokens::revoked_at.is_null()),
+            )
+            .set(refresh_tokens::revoked_at.eq(Utc::now()))
+            .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn revoke_jti(&self, jti: &str, expires_at: DateTime<Utc>) -> RepositoryResult<()> {
+        let revoked = CreateRevokedTokenDiesel {
+            jti: jti.to_string(),
+            expires_at,
+        };
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::insert_into(revoked_tokens::table)
+                .values(revoked)
+                .on_conflict_do_nothing()
+                .execute(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+    async fn is_jti_revoked(&self, jti: &str) -> RepositoryResult<bool> {
+        let jti = jti.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            diesel::select(exists(revoked_tokens::table.find(jti))).get_result::<bool>(&mut conn)
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn delete_expired(&self) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let now = Utc::now();
+                let refresh_tokens =
+                    diesel::delete(refresh_tokens::table.filter(refresh_tokens::expires_at.lt(now)))
+                        .execute(conn)?;
+                let revoked_tokens =
+                    diesel::delete(revoked_tokens::table.filter(revoked_tokens::expires_at.lt(now)))
+                        .execute(conn)?;
+                Ok(refresh_tokens + revoked_tokens)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
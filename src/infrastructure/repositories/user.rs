@@ -2,15 +2,80 @@ use std::sync::Arc;
 
 use actix_threadpool::run;
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 
-use crate::domain::models::user::{CreateUser, LoginUser, User};
-use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::models::outbox::{CreateOutboxEvent, OutboxEventType};
+use crate::domain::models::user::{
+    CreateServiceAccount, CreateUser, LoginUser, PrincipalType, SortDirection, User, UserFilter, UserSortField,
+    UserStatus,
+};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
 use crate::domain::repositories::user::UserRepository;
-use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::databases::postgresql::{with_tenant, DBConn};
 use crate::infrastructure::error::DieselRepositoryError;
-use crate::infrastructure::models::user::{CreateUserDiesel, UserDiesel};
-use crate::infrastructure::schema::users::{password, username};
+use crate::infrastructure::models::user::{CreateServiceAccountDiesel, CreateUserDiesel, UserDiesel};
+use crate::infrastructure::repositories::outbox::insert_outbox_event;
+use crate::infrastructure::schema::users;
+
+/// Padrão `ILIKE` que casa com o que começa por `prefix`, com os curingas escapados.
+fn prefix_pattern(prefix: &str) -> String {
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{}%", escaped)
+}
+
+fn filtered(organization_id: i32, filter: &UserFilter) -> users::BoxedQuery<'static, Pg> {
+    let mut query = users::table
+        .filter(users::organization_id.eq(organization_id))
+        .into_boxed();
+    if let Some(prefix) = &filter.username_prefix {
+        query = query.filter(users::username.ilike(prefix_pattern(prefix)));
+    }
+    if let Some(prefix) = &filter.email_prefix {
+        query = query.filter(users::email.ilike(prefix_pattern(prefix)));
+    }
+    if let Some(since) = filter.created_since {
+        query = query.filter(users::created_at.ge(since));
+    }
+    if let Some(until) = filter.created_until {
+        query = query.filter(users::created_at.lt(until));
+    }
+    if let Some(principal_type) = filter.principal_type {
+        query = query.filter(users::principal_type.eq(principal_type.as_str()));
+    }
+    let now = Utc::now();
+    query = match filter.status {
+        None => query,
+        Some(UserStatus::Disabled) => query.filter(users::disabled_at.is_not_null()),
+        Some(UserStatus::Locked) => query
+            .filter(users::disabled_at.is_null())
+            .filter(users::locked_until.gt(now)),
+        Some(UserStatus::Active) => query
+            .filter(users::disabled_at.is_null())
+            .filter(users::locked_until.is_null().or(users::locked_until.le(now))),
+    };
+    query
+}
+
+fn sorted(query: users::BoxedQuery<'static, Pg>, filter: &UserFilter) -> users::BoxedQuery<'static, Pg> {
+    // o ID desempata, para que a paginação não repita nem pule usuários
+    match (filter.sort, filter.direction) {
+        (UserSortField::CreatedAt, SortDirection::Asc) => query.order((users::created_at.asc(), users::id.asc())),
+        (UserSortField::CreatedAt, SortDirection::Desc) => query.order((users::created_at.desc(), users::id.desc())),
+        (UserSortField::Username, SortDirection::Asc) => query.order((users::username.asc(), users::id.asc())),
+        (UserSortField::Username, SortDirection::Desc) => query.order((users::username.desc(), users::id.desc())),
+        (UserSortField::Email, SortDirection::Asc) => {
+            query.order((users::email.asc().nulls_last(), users::id.asc()))
+        }
+        (UserSortField::Email, SortDirection::Desc) => {
+            query.order((users::email.desc().nulls_last(), users::id.desc()))
+        }
+    }
+}
 
 pub struct UserDieselRepository {
     pub pool: Arc<DBConn>,
@@ -24,31 +89,316 @@ impl UserDieselRepository {
 
 #[async_trait]
 impl UserRepository for UserDieselRepository {
-    async fn create(&self, new_user: &CreateUser) -> RepositoryResult<User> {
-        use crate::infrastructure::schema::users::dsl::users;
-        let new_user_diesel: CreateUserDiesel = CreateUserDiesel::from(new_user.clone());
+    async fn create(&self, organization_id: i32, new_user: &CreateUser) -> RepositoryResult<User> {
+        let new_user_diesel = CreateUserDiesel::new(organization_id, new_user.clone());
         let mut conn = self.pool.get().unwrap();
-        let result: UserDiesel = run(move || {
-            diesel::insert_into(users)
-                .values(new_user_diesel)
-                .get_result(&mut conn)
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let user: User = diesel::insert_into(users::table)
+                    .values(new_user_diesel)
+                    .get_result::<UserDiesel>(conn)?
+                    .into();
+                insert_outbox_event(conn, CreateOutboxEvent::user(OutboxEventType::UserCreated, &user))?;
+                Ok(user)
+            })
         })
         .await
-        .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
-        Ok(result.into())
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
     }
-    async fn get(&self, user_login: &LoginUser) -> RepositoryResult<User> {
-        use crate::infrastructure::schema::users::dsl::users;
+    async fn get(&self, organization_id: i32, user_login: &LoginUser) -> RepositoryResult<User> {
         let user = user_login.clone();
         let mut conn = self.pool.get().unwrap();
         run(move || {
-            users
-                .filter(username.eq(user.username.clone()))
-                .filter(password.eq(user.password.clone()))
-                .first::<UserDiesel>(&mut conn)
+            with_tenant(&mut conn, organization_id, |conn| {
+                users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::username.eq(user.username.clone()))
+                    .filter(users::password.eq(user.password.clone()))
+                    .filter(users::principal_type.eq(PrincipalType::User.as_str()))
+                    .first::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> User { v.into() })
+    }
+    async fn get_by_username(&self, organization_id: i32, name: &str) -> RepositoryResult<User> {
+        let name = name.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::username.eq(name))
+                    .first::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> User { v.into() })
+    }
+    async fn get_by_id(&self, organization_id: i32, user_id: i32) -> RepositoryResult<User> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::id.eq(user_id))
+                    .first::<UserDiesel>(conn)
+            })
         })
         .await
         .map_err(|v| DieselRepositoryError::from(v).into_inner())
         .map(|v| -> User { v.into() })
     }
+    async fn update_email(&self, organization_id: i32, user_id: i32, email: &str) -> RepositoryResult<User> {
+        let email = email.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let target = users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::id.eq(user_id));
+                let previous_email = target
+                    .select(users::email)
+                    .for_update()
+                    .first::<Option<String>>(conn)?;
+                let user: User = diesel::update(target)
+                    .set((users::email.eq(&email), users::email_verified.eq(false)))
+                    .get_result::<UserDiesel>(conn)?
+                    .into();
+                if previous_email.as_deref() != Some(email.as_str()) {
+                    insert_outbox_event(conn, CreateOutboxEvent::email_changed(&user, previous_email))?;
+                }
+                Ok(user)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn delete(&self, organization_id: i32, user_id: i32) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let deleted = diesel::delete(
+                    users::table
+                        .filter(users::organization_id.eq(organization_id))
+                        .filter(users::id.eq(user_id)),
+                )
+                .get_results::<UserDiesel>(conn)?;
+                let count = deleted.len();
+                for user in deleted {
+                    let user: User = user.into();
+                    insert_outbox_event(conn, CreateOutboxEvent::user(OutboxEventType::UserDeleted, &user))?;
+                }
+                Ok(count)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &UserFilter,
+        params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<User>> {
+        let filter = filter.clone();
+        let (limit, offset) = (params.limit(), params.offset());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let total = filtered(organization_id, &filter)
+                    .count()
+                    .get_result::<i64>(conn)?;
+                let items = sorted(filtered(organization_id, &filter), &filter)
+                    .limit(limit)
+                    .offset(offset)
+                    .load::<UserDiesel>(conn)?;
+                Ok((total, items))
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|(total, items)| ResultPaging {
+            total,
+            items: items.into_iter().map(User::from).collect(),
+        })
+    }
+    async fn update_password(&self, organization_id: i32, user_id: i32, password: &str) -> RepositoryResult<User> {
+        let password = password.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::update(
+                    users::table
+                        .filter(users::organization_id.eq(organization_id))
+                        .filter(users::id.eq(user_id)),
+                )
+                .set((users::password.eq(password), users::password_reset_required.eq(false)))
+                .get_result::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> User { v.into() })
+    }
+    async fn set_disabled(&self, organization_id: i32, user_id: i32, disabled: bool) -> RepositoryResult<User> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let target = users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::id.eq(user_id));
+                let disabled_at = target
+                    .select(users::disabled_at)
+                    .for_update()
+                    .first::<Option<DateTime<Utc>>>(conn)?;
+                // desativar de novo mantém a data da primeira vez
+                let disabled_at = match disabled {
+                    true => disabled_at.or_else(|| Some(Utc::now())),
+                    false => None,
+                };
+                diesel::update(target)
+                    .set(users::disabled_at.eq(disabled_at))
+                    .get_result::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> User { v.into() })
+    }
+    async fn require_password_reset(&self, organization_id: i32, user_id: i32) -> RepositoryResult<User> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::update(
+                    users::table
+                        .filter(users::organization_id.eq(organization_id))
+                        .filter(users::id.eq(user_id)),
+                )
+                .set(users::password_reset_required.eq(true))
+                .get_result::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> User { v.into() })
+    }
+    async fn unlock(&self, organization_id: i32, user_id: i32) -> RepositoryResult<User> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                diesel::update(
+                    users::table
+                        .filter(users::organization_id.eq(organization_id))
+                        .filter(users::id.eq(user_id)),
+                )
+                .set((
+                    users::failed_login_attempts.eq(0),
+                    users::locked_until.eq(None::<DateTime<Utc>>),
+                ))
+                .get_result::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| -> User { v.into() })
+    }
+    async fn record_failed_login(
+        &self,
+        organization_id: i32,
+        username: &str,
+        max_attempts: i32,
+        lockout: Duration,
+    ) -> RepositoryResult<usize> {
+        let username = username.to_string();
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let now = Utc::now();
+                let Some((id, attempts)) = users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::username.eq(username))
+                    .filter(users::locked_until.is_null().or(users::locked_until.le(now)))
+                    .select((users::id, users::failed_login_attempts))
+                    .for_update()
+                    .first::<(i32, i32)>(conn)
+                    .optional()?
+                else {
+                    return Ok(0);
+                };
+                // ao bloquear, a contagem recomeça para depois do bloqueio
+                let (attempts, locked_until) = match attempts + 1 >= max_attempts {
+                    true => (0, Some(now + lockout)),
+                    false => (attempts + 1, None),
+                };
+                diesel::update(users::table.filter(users::id.eq(id)))
+                    .set((
+                        users::failed_login_attempts.eq(attempts),
+                        users::locked_until.eq(locked_until),
+                    ))
+                    .execute(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn create_service_account(
+        &self,
+        organization_id: i32,
+        new_account: &CreateServiceAccount,
+    ) -> RepositoryResult<User> {
+        let new_account_diesel = CreateServiceAccountDiesel::new(organization_id, new_account.clone());
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let account: User = diesel::insert_into(users::table)
+                    .values(new_account_diesel)
+                    .get_result::<UserDiesel>(conn)?
+                    .into();
+                insert_outbox_event(conn, CreateOutboxEvent::user(OutboxEventType::UserCreated, &account))?;
+                Ok(account)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+    async fn list_service_accounts(&self, organization_id: i32) -> RepositoryResult<Vec<User>> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                users::table
+                    .filter(users::organization_id.eq(organization_id))
+                    .filter(users::principal_type.eq(PrincipalType::Service.as_str()))
+                    .order(users::username)
+                    .load::<UserDiesel>(conn)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+        .map(|v| v.into_iter().map(User::from).collect())
+    }
+    async fn delete_service_account(&self, organization_id: i32, user_id: i32) -> RepositoryResult<usize> {
+        let mut conn = self.pool.get().unwrap();
+        run(move || {
+            with_tenant(&mut conn, organization_id, |conn| {
+                let deleted = diesel::delete(
+                    users::table
+                        .filter(users::organization_id.eq(organization_id))
+                        .filter(users::id.eq(user_id))
+                        .filter(users::principal_type.eq(PrincipalType::Service.as_str())),
+                )
+                .get_results::<UserDiesel>(conn)?;
+                let count = deleted.len();
+                for account in deleted {
+                    let account: User = account.into();
+                    insert_outbox_event(conn, CreateOutboxEvent::user(OutboxEventType::UserDeleted, &account))?;
+                }
+                Ok(count)
+            })
+        })
+        .await
+        .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
 }
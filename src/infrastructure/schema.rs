@@ -1,5 +1,256 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    audit_checkpoints (day) {
+        day -> Date,
+        event_count -> Int8,
+        last_event_id -> Int8,
+        last_hash -> Varchar,
+        signature -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    audit_events (id) {
+        id -> Int8,
+        organization_id -> Nullable<Int4>,
+        action -> Varchar,
+        outcome -> Varchar,
+        actor_type -> Nullable<Varchar>,
+        actor_id -> Nullable<Varchar>,
+        target_type -> Nullable<Varchar>,
+        target_id -> Nullable<Varchar>,
+        ip_address -> Nullable<Varchar>,
+        user_agent -> Nullable<Varchar>,
+        details -> Jsonb,
+        created_at -> Timestamptz,
+        prev_hash -> Varchar,
+        hash -> Varchar,
+    }
+}
+
+diesel::table! {
+    authorization_codes (code_hash) {
+        code_hash -> Varchar,
+        oauth_client_id -> Int4,
+        user_id -> Int4,
+        redirect_uri -> Varchar,
+        scope -> Varchar,
+        code_challenge -> Nullable<Varchar>,
+        code_challenge_method -> Nullable<Varchar>,
+        expires_at -> Timestamptz,
+        used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        nonce -> Nullable<Varchar>,
+        auth_time -> Timestamptz,
+        consent_pending -> Bool,
+    }
+}
+
+diesel::table! {
+    consents (user_id, oauth_client_id) {
+        user_id -> Int4,
+        oauth_client_id -> Int4,
+        scopes -> Array<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    device_codes (device_code_hash) {
+        device_code_hash -> Varchar,
+        user_code -> Varchar,
+        oauth_client_id -> Int4,
+        scope -> Varchar,
+        status -> Varchar,
+        user_id -> Nullable<Int4>,
+        interval -> Int4,
+        last_polled_at -> Nullable<Timestamptz>,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    dpop_proofs (proof_hash) {
+        proof_hash -> Varchar,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    failed_attempts (subject) {
+        subject -> Varchar,
+        failures -> Int4,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    group_members (group_id, user_id) {
+        group_id -> Int4,
+        user_id -> Int4,
+    }
+}
+
+diesel::table! {
+    group_roles (group_id, role_id) {
+        group_id -> Int4,
+        role_id -> Int4,
+    }
+}
+
+diesel::table! {
+    group_subgroups (parent_group_id, child_group_id) {
+        parent_group_id -> Int4,
+        child_group_id -> Int4,
+    }
+}
+
+diesel::table! {
+    groups (id) {
+        id -> Int4,
+        name -> Varchar,
+        description -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        organization_id -> Int4,
+    }
+}
+
+diesel::table! {
+    initial_access_tokens (token_hash) {
+        token_hash -> Varchar,
+        organization_id -> Int4,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    oauth_clients (id) {
+        id -> Int4,
+        client_id -> Varchar,
+        client_secret_hash -> Nullable<Varchar>,
+        name -> Varchar,
+        client_type -> Varchar,
+        redirect_uris -> Array<Text>,
+        allowed_scopes -> Array<Text>,
+        organization_id -> Int4,
+        created_at -> Timestamptz,
+        previous_client_secret_hash -> Nullable<Varchar>,
+        registration_access_token_hash -> Nullable<Varchar>,
+        allowed_audiences -> Array<Text>,
+        grant_types -> Array<Text>,
+        require_pushed_authorization_requests -> Bool,
+    }
+}
+
+diesel::table! {
+    organizations (id) {
+        id -> Int4,
+        slug -> Varchar,
+        name -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    outbox (id) {
+        id -> Int8,
+        organization_id -> Int4,
+        event_type -> Varchar,
+        aggregate_type -> Varchar,
+        aggregate_id -> Varchar,
+        payload -> Jsonb,
+        created_at -> Timestamptz,
+        dispatched_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    permissions (id) {
+        id -> Int4,
+        name -> Varchar,
+        description -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    personal_access_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        organization_id -> Int4,
+        name -> Varchar,
+        token_hash -> Varchar,
+        token_hint -> Varchar,
+        scope -> Varchar,
+        expires_at -> Nullable<Timestamptz>,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    pushed_authorization_requests (request_uri_hash) {
+        request_uri_hash -> Varchar,
+        oauth_client_id -> Int4,
+        response_type -> Varchar,
+        redirect_uri -> Nullable<Varchar>,
+        scope -> Nullable<Varchar>,
+        state -> Nullable<Varchar>,
+        code_challenge -> Nullable<Varchar>,
+        code_challenge_method -> Nullable<Varchar>,
+        nonce -> Nullable<Varchar>,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    refresh_tokens (token_hash) {
+        token_hash -> Varchar,
+        family_id -> Varchar,
+        oauth_client_id -> Int4,
+        user_id -> Int4,
+        scope -> Varchar,
+        expires_at -> Timestamptz,
+        used_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        dpop_jkt -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    revoked_tokens (jti) {
+        jti -> Varchar,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    role_permissions (role_id, permission_id) {
+        role_id -> Int4,
+        permission_id -> Int4,
+    }
+}
+
+diesel::table! {
+    roles (id) {
+        id -> Int4,
+        name -> Varchar,
+        description -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        organization_id -> Int4,
+        session_idle_timeout_seconds -> Nullable<Int4>,
+        session_max_lifetime_seconds -> Nullable<Int4>,
+        max_concurrent_sessions -> Nullable<Int4>,
+    }
+}
+
 diesel::table! {
     service_contexts (id) {
         id -> Int4,
@@ -7,14 +258,130 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sessions (id) {
+        id -> Varchar,
+        user_id -> Int4,
+        organization_id -> Int4,
+        user_agent -> Nullable<Varchar>,
+        ip_address -> Nullable<Varchar>,
+        device_name -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        last_seen_at -> Timestamptz,
+        expires_at -> Timestamptz,
+        revoked_at -> Nullable<Timestamptz>,
+        idle_timeout_seconds -> Int4,
+    }
+}
+
+diesel::table! {
+    user_roles (user_id, role_id) {
+        user_id -> Int4,
+        role_id -> Int4,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Int4,
         username -> Varchar,
-        email -> Varchar,
-        password -> Varchar,
+        email -> Nullable<Varchar>,
+        password -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        organization_id -> Int4,
+        email_verified -> Bool,
+        principal_type -> Varchar,
+        owner_user_id -> Nullable<Int4>,
+        disabled_at -> Nullable<Timestamptz>,
+        password_reset_required -> Bool,
+        failed_login_attempts -> Int4,
+        locked_until -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int8,
+        webhook_id -> Int4,
+        outbox_event_id -> Int8,
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        last_error -> Nullable<Varchar>,
+        delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        url -> Varchar,
+        secret -> Varchar,
+        event_types -> Array<Text>,
         created_at -> Timestamptz,
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(service_contexts, users,);
+diesel::joinable!(authorization_codes -> oauth_clients (oauth_client_id));
+diesel::joinable!(authorization_codes -> users (user_id));
+diesel::joinable!(consents -> oauth_clients (oauth_client_id));
+diesel::joinable!(consents -> users (user_id));
+diesel::joinable!(device_codes -> oauth_clients (oauth_client_id));
+diesel::joinable!(device_codes -> users (user_id));
+diesel::joinable!(group_members -> groups (group_id));
+diesel::joinable!(group_members -> users (user_id));
+diesel::joinable!(group_roles -> groups (group_id));
+diesel::joinable!(group_roles -> roles (role_id));
+diesel::joinable!(groups -> organizations (organization_id));
+diesel::joinable!(initial_access_tokens -> organizations (organization_id));
+diesel::joinable!(oauth_clients -> organizations (organization_id));
+diesel::joinable!(outbox -> organizations (organization_id));
+diesel::joinable!(personal_access_tokens -> organizations (organization_id));
+diesel::joinable!(personal_access_tokens -> users (user_id));
+diesel::joinable!(pushed_authorization_requests -> oauth_clients (oauth_client_id));
+diesel::joinable!(refresh_tokens -> oauth_clients (oauth_client_id));
+diesel::joinable!(refresh_tokens -> users (user_id));
+diesel::joinable!(role_permissions -> permissions (permission_id));
+diesel::joinable!(role_permissions -> roles (role_id));
+diesel::joinable!(roles -> organizations (organization_id));
+diesel::joinable!(sessions -> organizations (organization_id));
+diesel::joinable!(sessions -> users (user_id));
+diesel::joinable!(user_roles -> roles (role_id));
+diesel::joinable!(user_roles -> users (user_id));
+diesel::joinable!(users -> organizations (organization_id));
+diesel::joinable!(webhook_deliveries -> outbox (outbox_event_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> organizations (organization_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    audit_checkpoints,
+    audit_events,
+    authorization_codes,
+    consents,
+    device_codes,
+    dpop_proofs,
+    failed_attempts,
+    group_members,
+    group_roles,
+    group_subgroups,
+    groups,
+    initial_access_tokens,
+    oauth_clients,
+    organizations,
+    outbox,
+    permissions,
+    personal_access_tokens,
+    pushed_authorization_requests,
+    refresh_tokens,
+    revoked_tokens,
+    role_permissions,
+    roles,
+    service_contexts,
+    sessions,
+    user_roles,
+    users,
+    webhook_deliveries,
+    webhooks,
+);
@@ -0,0 +1,578 @@
+use std::collections::VecDeque;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::SecondsFormat;
+
+use crate::domain::constants::{
+    AUDIT_EXPORT_FILE, AUDIT_EXPORT_FILE_MAX_BYTES, AUDIT_EXPORT_FILE_MAX_FILES, AUDIT_EXPORT_FORMAT,
+    AUDIT_EXPORT_QUEUE_SIZE, AUDIT_EXPORT_SINK, AUDIT_EXPORT_SPOOL, AUDIT_EXPORT_SYSLOG_ADDRESS,
+    AUDIT_EXPORT_SYSLOG_PROTOCOL,
+};
+use crate::domain::models::audit::{AuditEvent, AuditOutcome};
+use crate::domain::services::audit_export::AuditExportService;
+
+const APP_NAME: &str = "auth_service";
+/// Facility `authpriv` do syslog.
+const SYSLOG_FACILITY: u8 = 10;
+/// Tempo máximo de conexão e de escrita no syslog por TCP.
+const SYSLOG_TCP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Intervalo entre as tentativas de reenviar o spool com o destino fora do ar.
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Formato de cada evento exportado.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditFormat {
+    /// O evento em JSON, numa linha.
+    Json,
+    /// ArcSight Common Event Format.
+    Cef,
+}
+
+impl AuditFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(AuditFormat::Json),
+            "cef" => Some(AuditFormat::Cef),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, event: &AuditEvent) -> String {
+        match self {
+            AuditFormat::Json => serde_json::to_string(event).unwrap_or_default(),
+            AuditFormat::Cef => cef_message(event),
+        }
+    }
+}
+
+/// Campos `csN` do CEF, com o nome de cada um em `csNLabel`.
+const CEF_CUSTOM_STRINGS: [(&str, &str); 3] = [("cs1Label", "cs1"), ("cs2Label", "cs2"), ("cs3Label", "cs3")];
+
+fn cef_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn cef_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// `CEF:0|Vendor|Product|Version|Signature ID|Name|Severity|Extension`, com a ação como
+/// Signature ID e as falhas com severidade maior.
+pub fn cef_message(event: &AuditEvent) -> String {
+    let severity = match event.outcome {
+        AuditOutcome::Success => 3,
+        AuditOutcome::Failure => 6,
+    };
+    let mut extension = vec![
+        ("rt", event.created_at.timestamp_millis().to_string()),
+        ("externalId", event.id.to_string()),
+        ("outcome", event.outcome.as_str().to_string()),
+    ];
+    let mut optional = |key: &'static str, value: Option<String>| {
+        if let Some(value) = value {
+            extension.push((key, value));
+        }
+    };
+    optional("suid", event.actor_id.clone());
+    optional("duid", event.target_id.clone());
+    optional("src", event.ip_address.clone());
+    optional("requestClientApplication", event.user_agent.clone());
+    let custom = [
+        ("actorType", event.actor_type.clone()),
+        ("targetType", event.target_type.clone()),
+        ("organizationId", event.organization_id.map(|id| id.to_string())),
+    ];
+    for (index, (label, value)) in custom.into_iter().enumerate() {
+        if let Some(value) = value {
+            extension.push((CEF_CUSTOM_STRINGS[index].0, label.to_string()));
+            extension.push((CEF_CUSTOM_STRINGS[index].1, value));
+        }
+    }
+    extension.push(("msg", event.details.to_string()));
+    let extension: Vec<String> = extension
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, cef_value(value)))
+        .collect();
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        APP_NAME,
+        APP_NAME,
+        cef_header(env!("CARGO_PKG_VERSION")),
+        cef_header(event.action.as_str()),
+        cef_header(&format!("{} {}", event.action.as_str(), event.outcome.as_str())),
+        severity,
+        extension.join(" ")
+    )
+}
+
+/// Mensagem RFC 5424: `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID - MSG`, com a ação como
+/// MSGID e o evento formatado como MSG.
+pub fn syslog_message(event: &AuditEvent, format: AuditFormat, hostname: &str) -> String {
+    let severity = match event.outcome {
+        AuditOutcome::Success => 6,
+        AuditOutcome::Failure => 4,
+    };
+    format!(
+        "<{}>1 {} {} {} {} {} - {}",
+        SYSLOG_FACILITY * 8 + severity,
+        event.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        hostname,
+        APP_NAME,
+        std::process::id(),
+        event.action.as_str(),
+        format.render(event)
+    )
+}
+
+/// Destino dos eventos exportados. As escritas acontecem numa thread própria, fora das
+/// requisições.
+pub trait AuditSink: Send {
+    fn write(&mut self, event: &AuditEvent) -> io::Result<()>;
+}
+
+/// Arquivo com um evento por linha. Ao passar de `max_bytes`, o arquivo vira `<path>.1`, os
+/// anteriores andam uma posição e só os `max_files` mais recentes são mantidos.
+pub struct JsonLinesSink {
+    path: PathBuf,
+    format: AuditFormat,
+    max_bytes: u64,
+    max_files: usize,
+    file: Option<File>,
+    written: u64,
+}
+
+impl JsonLinesSink {
+    pub fn new(path: impl Into<PathBuf>, format: AuditFormat, max_bytes: u64, max_files: usize) -> Self {
+        JsonLinesSink {
+            path: path.into(),
+            format,
+            max_bytes,
+            max_files,
+            file: None,
+            written: 0,
+        }
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        path.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(from, self.rotated(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+}
+
+impl AuditSink for JsonLinesSink {
+    fn write(&mut self, event: &AuditEvent) -> io::Result<()> {
+        let line = format!("{}\n", self.format.render(event));
+        if self.file.is_none() {
+            self.written = fs::metadata(&self.path).map_or(0, |metadata| metadata.len());
+        }
+        if self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+            self.written = 0;
+        }
+        if self.file.is_none() {
+            self.file = Some(OpenOptions::new().create(true).append(true).open(&self.path)?);
+        }
+        let file = self.file.as_mut().unwrap();
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyslogProtocol {
+    Udp,
+    /// Mensagens com o tamanho na frente (octet counting, RFC 6587).
+    Tcp,
+}
+
+impl SyslogProtocol {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "udp" => Some(SyslogProtocol::Udp),
+            "tcp" => Some(SyslogProtocol::Tcp),
+            _ => None,
+        }
+    }
+}
+
+/// Syslog RFC 5424 por UDP ou TCP. A conexão TCP é reaberta na próxima escrita depois de uma
+/// falha.
+pub struct SyslogSink {
+    address: String,
+    protocol: SyslogProtocol,
+    format: AuditFormat,
+    hostname: String,
+    stream: Option<TcpStream>,
+}
+
+impl SyslogSink {
+    pub fn new(address: impl Into<String>, protocol: SyslogProtocol, format: AuditFormat) -> Self {
+        SyslogSink {
+            address: address.into(),
+            protocol,
+            format,
+            hostname: env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+            stream: None,
+        }
+    }
+
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "syslog address did not resolve");
+        for address in self.address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&address, SYSLOG_TCP_TIMEOUT) {
+                Ok(stream) => {
+                    stream.set_write_timeout(Some(SYSLOG_TCP_TIMEOUT))?;
+                    return Ok(stream);
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn write(&mut self, event: &AuditEvent) -> io::Result<()> {
+        let message = syslog_message(event, self.format, &self.hostname);
+        match self.protocol {
+            SyslogProtocol::Udp => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.send_to(message.as_bytes(), &self.address)?;
+                Ok(())
+            }
+            SyslogProtocol::Tcp => {
+                if self.stream.is_none() {
+                    self.stream = Some(self.connect()?);
+                }
+                let framed = format!("{} {}", message.len(), message);
+                let result = self.stream.as_mut().unwrap().write_all(framed.as_bytes());
+                if result.is_err() {
+                    self.stream = None;
+                }
+                result
+            }
+        }
+    }
+}
+
+/// Eventos que ainda não chegaram ao destino, um JSON por linha, na ordem em que chegaram.
+pub struct AuditSpool {
+    path: PathBuf,
+}
+
+impl AuditSpool {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        AuditSpool { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_empty(&self) -> bool {
+        fs::metadata(&self.path).map_or(true, |metadata| metadata.len() == 0)
+    }
+
+    pub fn append(&self, event: &AuditEvent) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(event)?)
+    }
+
+    /// Retira do spool todos os eventos, em ordem, deixando-o vazio.
+    pub fn take(&self) -> io::Result<Vec<AuditEvent>> {
+        let events = self.read()?;
+        // as linhas ilegíveis, descartadas por `read`, também saem
+        if !self.is_empty() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(events)
+    }
+
+    /// Devolve ao início do spool eventos retirados por `take` que não chegaram ao destino,
+    /// antes dos que foram gravados nesse meio-tempo.
+    pub fn restore(&self, events: &[AuditEvent]) -> io::Result<()> {
+        let appended = self.read()?;
+        let mut file = File::create(&self.path)?;
+        for event in events.iter().chain(&appended) {
+            writeln!(file, "{}", serde_json::to_string(event)?)?;
+        }
+        Ok(())
+    }
+
+    fn read(&self) -> io::Result<Vec<AuditEvent>> {
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut events = Vec::new();
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let line = line?;
+            match serde_json::from_str::<AuditEvent>(&line) {
+                Ok(event) => events.push(event),
+                Err(e) => log::error!("Discarding unreadable audit spool entry: {}", e),
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Fila e spool da exportação, sob o mesmo lock. Enquanto o spool tiver eventos, ele guarda os
+/// mais antigos: os novos vão para o fim dele, depois dos que estavam na fila.
+struct ExportState {
+    queue: VecDeque<AuditEvent>,
+    queue_size: usize,
+    spool: AuditSpool,
+    closed: bool,
+}
+
+impl ExportState {
+    /// Passa para o spool, em ordem, os eventos que estavam na fila.
+    fn spool_queue(&mut self) -> io::Result<()> {
+        while let Some(event) = self.queue.front() {
+            self.spool.append(event)?;
+            self.queue.pop_front();
+        }
+        Ok(())
+    }
+}
+
+struct ExportQueue {
+    state: Mutex<ExportState>,
+    ready: Condvar,
+}
+
+enum ExportStep {
+    Send(Box<AuditEvent>),
+    Flush,
+    Close,
+}
+
+/// Thread que esvazia a fila no destino. Com o destino fora do ar, os eventos vão para o spool e
+/// só há nova tentativa depois de `retry_interval`; o spool é enviado antes dos eventos novos.
+struct ExportWorker {
+    sink: Box<dyn AuditSink>,
+    queue: Arc<ExportQueue>,
+    retry_interval: Duration,
+    retry_at: Option<Instant>,
+}
+
+impl ExportWorker {
+    fn sink_available(&self) -> bool {
+        self.retry_at.is_none_or(|retry_at| Instant::now() >= retry_at)
+    }
+
+    fn failed(&mut self, e: io::Error) {
+        if self.retry_at.is_none() {
+            log::warn!("Audit export sink is unavailable, spooling events to disk: {}", e);
+        }
+        self.retry_at = Some(Instant::now() + self.retry_interval);
+    }
+
+    /// Envia o spool em ordem. O lock só é segurado para ler e regravar o arquivo, e não durante
+    /// o envio, para que `export` não fique esperando por um destino lento.
+    fn flush_spool(&mut self) {
+        let events = match self.queue.state.lock().unwrap().spool.take() {
+            Ok(events) => events,
+            Err(e) => {
+                log::error!("Could not read the audit spool: {}", e);
+                self.retry_at = Some(Instant::now() + self.retry_interval);
+                return;
+            }
+        };
+        for (index, event) in events.iter().enumerate() {
+            if let Err(e) = self.sink.write(event) {
+                self.return_to_spool(&events[index..]);
+                self.failed(e);
+                return;
+            }
+        }
+        if self.retry_at.take().is_some() {
+            log::info!("Audit export sink is available again");
+        }
+    }
+
+    /// Devolve ao início do spool eventos que não chegaram ao destino; os que chegaram ao spool
+    /// enquanto eles eram enviados são mais novos.
+    fn return_to_spool(&self, events: &[AuditEvent]) {
+        if let Err(e) = self.queue.state.lock().unwrap().spool.restore(events) {
+            log::error!("Could not return {} events to the audit spool: {}", events.len(), e);
+        }
+    }
+
+    /// Envia um evento da fila, retirado com o spool vazio.
+    fn send(&mut self, event: AuditEvent) {
+        if !self.sink_available() {
+            self.return_to_spool(&[event]);
+            return;
+        }
+        if let Err(e) = self.sink.write(&event) {
+            self.return_to_spool(&[event]);
+            self.failed(e);
+        }
+    }
+
+    /// Próximo passo: com eventos no spool, a fila vai para o fim dele e o spool é enviado assim
+    /// que o destino puder ser tentado de novo; com o spool vazio, sai o primeiro da fila.
+    fn next_step(&self) -> ExportStep {
+        let mut state = self.queue.state.lock().unwrap();
+        loop {
+            if !state.spool.is_empty() {
+                if let Err(e) = state.spool_queue() {
+                    log::error!("Could not spool queued audit events: {}", e);
+                }
+                if self.sink_available() {
+                    return ExportStep::Flush;
+                }
+            } else if let Some(event) = state.queue.pop_front() {
+                return ExportStep::Send(Box::new(event));
+            }
+            if state.closed {
+                return ExportStep::Close;
+            }
+            let timeout = self
+                .retry_at
+                .map_or(self.retry_interval, |retry_at| retry_at.saturating_duration_since(Instant::now()));
+            state = self.queue.ready.wait_timeout(state, timeout).unwrap().0;
+        }
+    }
+
+    fn run(mut self) {
+        loop {
+            match self.next_step() {
+                ExportStep::Send(event) => self.send(*event),
+                ExportStep::Flush => self.flush_spool(),
+                ExportStep::Close => return,
+            }
+        }
+    }
+}
+
+/// Exportação assíncrona: os eventos entram numa fila limitada consumida por uma thread. Com a
+/// fila cheia, quem registra não espera: a fila e o evento vão para o spool em disco, nessa ordem.
+pub struct AuditExportServiceImpl {
+    queue: Arc<ExportQueue>,
+}
+
+impl AuditExportServiceImpl {
+    pub fn new(sink: Box<dyn AuditSink>, spool: AuditSpool, queue_size: usize, retry_interval: Duration) -> Self {
+        let queue = Arc::new(ExportQueue {
+            state: Mutex::new(ExportState {
+                queue: VecDeque::new(),
+                queue_size,
+                spool,
+                closed: false,
+            }),
+            ready: Condvar::new(),
+        });
+        let worker = ExportWorker {
+            sink,
+            queue: queue.clone(),
+            retry_interval,
+            retry_at: None,
+        };
+        thread::Builder::new()
+            .name("audit-export".to_string())
+            .spawn(move || worker.run())
+            .expect("Could not start the audit export thread");
+        AuditExportServiceImpl { queue }
+    }
+
+    /// Lê a configuração de `AUDIT_EXPORT_SINK` (`file` ou `syslog`; sem a variável não há
+    /// exportação) e `AUDIT_EXPORT_FORMAT` (`json` ou `cef`, padrão `json`).
+    ///
+    /// O arquivo usa `AUDIT_EXPORT_FILE` (padrão `audit.jsonl`), `AUDIT_EXPORT_FILE_MAX_BYTES`
+    /// (padrão 10 MiB) e `AUDIT_EXPORT_FILE_MAX_FILES` (padrão 5); o syslog usa
+    /// `AUDIT_EXPORT_SYSLOG_ADDRESS` (`host:porta`) e `AUDIT_EXPORT_SYSLOG_PROTOCOL` (`udp` ou
+    /// `tcp`, padrão `udp`). O spool fica em `AUDIT_EXPORT_SPOOL` (padrão `audit-spool.jsonl`)
+    /// e a fila guarda até `AUDIT_EXPORT_QUEUE_SIZE` eventos (padrão 1024).
+    ///
+    /// Uma configuração inválida interrompe a inicialização, para que a exportação não fique
+    /// desligada sem ninguém perceber.
+    pub fn from_env() -> Option<Self> {
+        let sink = env::var(AUDIT_EXPORT_SINK).ok()?;
+        let number = |name: &str, default: u64| {
+            env::var(name).map_or(default, |value| {
+                value.parse().unwrap_or_else(|_| panic!("{} must be a number", name))
+            })
+        };
+        let format = env::var(AUDIT_EXPORT_FORMAT).map_or(AuditFormat::Json, |value| {
+            AuditFormat::parse(&value).expect("AUDIT_EXPORT_FORMAT must be json or cef")
+        });
+        let sink: Box<dyn AuditSink> = match sink.as_str() {
+            "file" => Box::new(JsonLinesSink::new(
+                env::var(AUDIT_EXPORT_FILE).unwrap_or_else(|_| "audit.jsonl".to_string()),
+                format,
+                number(AUDIT_EXPORT_FILE_MAX_BYTES, 10 * 1024 * 1024),
+                number(AUDIT_EXPORT_FILE_MAX_FILES, 5) as usize,
+            )),
+            "syslog" => Box::new(SyslogSink::new(
+                env::var(AUDIT_EXPORT_SYSLOG_ADDRESS).expect("AUDIT_EXPORT_SYSLOG_ADDRESS must be set"),
+                env::var(AUDIT_EXPORT_SYSLOG_PROTOCOL).map_or(SyslogProtocol::Udp, |value| {
+                    SyslogProtocol::parse(&value).expect("AUDIT_EXPORT_SYSLOG_PROTOCOL must be udp or tcp")
+                }),
+                format,
+            )),
+            _ => panic!("AUDIT_EXPORT_SINK must be file or syslog"),
+        };
+        let spool = AuditSpool::new(env::var(AUDIT_EXPORT_SPOOL).unwrap_or_else(|_| "audit-spool.jsonl".to_string()));
+        let queue_size = number(AUDIT_EXPORT_QUEUE_SIZE, 1024).max(1) as usize;
+        Some(AuditExportServiceImpl::new(sink, spool, queue_size, RETRY_INTERVAL))
+    }
+
+    /// A exportação do processo, criada por `from_env` na primeira chamada. Cada worker do
+    /// servidor monta seu próprio `Container`, mas todos precisam da mesma thread e do mesmo
+    /// spool: duas exportações no mesmo arquivo se atropelariam na rotação e no envio do spool.
+    pub fn shared() -> Option<Arc<Self>> {
+        static SHARED: OnceLock<Option<Arc<AuditExportServiceImpl>>> = OnceLock::new();
+        SHARED.get_or_init(|| Self::from_env().map(Arc::new)).clone()
+    }
+}
+
+impl AuditExportService for AuditExportServiceImpl {
+    fn export(&self, event: &AuditEvent) {
+        let mut state = self.queue.state.lock().unwrap();
+        // com eventos no spool ou a fila cheia, o evento entra no spool depois dos da fila
+        if state.spool.is_empty() && state.queue.len() < state.queue_size {
+            state.queue.push_back(event.clone());
+        } else if let Err(e) = state.spool_queue().and_then(|()| state.spool.append(event)) {
+            log::error!("Could not spool audit event {}: {}", event.id, e);
+        }
+        self.queue.ready.notify_one();
+    }
+}
+
+impl Drop for AuditExportServiceImpl {
+    /// Encerra a thread depois de enviar o que estiver na fila e, se o destino estiver no ar, no spool.
+    fn drop(&mut self) {
+        self.queue.state.lock().unwrap().closed = true;
+        self.queue.ready.notify_one();
+    }
+}
@@ -1,2 +1,4 @@
+pub mod audit_export;
 pub mod service_context;
+pub mod webhook_sender;
 // me parece ser o serviço de conexão com  obanco
\ No newline at end of file
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+
+use crate::domain::constants::WEBHOOK_TIMEOUT_SECONDS;
+use crate::domain::error::CommonError;
+use crate::domain::models::outbox::WebhookRequest;
+use crate::domain::services::webhook_sender::WebhookSender;
+
+/// Envia as entregas com `ureq`, no pool de threads bloqueantes. Redirecionamentos não são
+/// seguidos, para que a entrega não vá parar numa URL que não foi registrada.
+#[derive(Clone)]
+pub struct HttpWebhookSender {
+    agent: ureq::Agent,
+}
+
+impl HttpWebhookSender {
+    pub fn new() -> Self {
+        HttpWebhookSender {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+                .redirects(0)
+                .build(),
+        }
+    }
+}
+
+impl Default for HttpWebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), CommonError> {
+        let agent = self.agent.clone();
+        let request = request.clone();
+        run(move || -> Result<(), CommonError> {
+            let mut call = agent.post(&request.url);
+            for (name, value) in &request.headers {
+                call = call.set(name, value);
+            }
+            match call.send_string(&request.body) {
+                Ok(response) if (200..300).contains(&response.status()) => Ok(()),
+                Ok(response) => Err(CommonError {
+                    message: format!("Webhook answered with status {}", response.status()),
+                    code: 500,
+                }),
+                Err(ureq::Error::Status(status, _)) => Err(CommonError {
+                    message: format!("Webhook answered with status {}", status),
+                    code: 500,
+                }),
+                Err(e) => Err(CommonError {
+                    message: format!("Webhook request failed: {}", e),
+                    code: 500,
+                }),
+            }
+        })
+        .await
+        .map_err(|e| match e {
+            actix_threadpool::BlockingError::Error(e) => e,
+            actix_threadpool::BlockingError::Canceled => CommonError {
+                message: "Webhook request canceled".to_string(),
+                code: 500,
+            },
+        })
+    }
+}
@@ -6,3 +6,4 @@ pub mod services;
 pub mod infrastructure;
 pub mod api;
 pub mod create_app;
+pub mod testing;
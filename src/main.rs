@@ -1,7 +1,12 @@
-use actix_web::HttpServer;
+use std::time::Duration;
+
+use actix_web::{rt, HttpServer};
+use chrono::NaiveDate;
 use dotenv::dotenv;
 
+use auth_service::container::Container;
 use auth_service::create_app::create_app;
+use auth_service::domain::constants::{EXPIRED_CODES_PURGE_INTERVAL_SECONDS, WEBHOOK_DISPATCH_INTERVAL_SECONDS};
 
 #[cfg(test)]
 mod tests;
@@ -13,6 +18,70 @@ async fn main() -> std::io::Result<()> {
     dotenv().ok();
     env_logger::init();
 
-    let server = HttpServer::new(move || create_app()).bind(("127.0.0.1", 15423))?;
+    let container = Container::new();
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("verify-audit") {
+        return verify_audit(&container, args.get(2)).await;
+    }
+    container
+        .organization_service
+        .seed_defaults()
+        .await
+        .expect("Could not seed the default organization");
+
+    let oauth_service = container.oauth_service.clone();
+    let dpop_service = container.dpop_service.clone();
+    let session_service = container.session_service.clone();
+    let audit_service = container.audit_service.clone();
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(EXPIRED_CODES_PURGE_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = oauth_service.purge_expired().await {
+                log::warn!("Could not purge expired OAuth codes and tokens: {}", e.message);
+            }
+            if let Err(e) = dpop_service.purge_expired().await {
+                log::warn!("Could not purge expired DPoP proofs: {}", e.message);
+            }
+            if let Err(e) = session_service.purge_expired().await {
+                log::warn!("Could not purge expired sessions: {}", e.message);
+            }
+            if let Err(e) = audit_service.create_checkpoints().await {
+                log::warn!("Could not sign the audit log checkpoints: {}", e.message);
+            }
+        }
+    });
+
+    let webhook_service = container.webhook_service.clone();
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(WEBHOOK_DISPATCH_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = webhook_service.dispatch().await {
+                log::warn!("Could not dispatch webhook deliveries: {}", e.message);
+            }
+        }
+    });
+
+    let server = HttpServer::new(create_app).bind(("127.0.0.1", 15423))?;
     server.run().await
 }
+
+/// `auth_service verify-audit [YYYY-MM-DD]`: confere o log de auditoria, a partir do dia
+/// informado, imprime o resultado em JSON e termina com status 1 na primeira quebra.
+async fn verify_audit(container: &Container, from: Option<&String>) -> std::io::Result<()> {
+    let from = from
+        .map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d"))
+        .transpose()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("invalid day, expected YYYY-MM-DD: {}", e)))?;
+    let report = container
+        .audit_service
+        .verify(from)
+        .await
+        .map_err(|e| std::io::Error::other(e.message))?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.is_intact() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
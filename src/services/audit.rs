@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+
+use crate::domain::constants::SECRET_KEY_TOKEN;
+use crate::domain::error::CommonError;
+use crate::domain::models::audit::{
+    AuditChainBreak, AuditCheckpoint, AuditCheckpointClaims, AuditEvent, AuditEventFilter,
+    AuditVerification, CreateAuditEvent, AUDIT_CHAIN_GENESIS,
+};
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::repository::{validate_page, QueryParams, ResultPaging};
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::audit_export::AuditExportService;
+use crate::services::oidc::SigningKey;
+
+/// Registra o evento sem interromper quem o gerou: uma falha ao gravar a auditoria vira um aviso
+/// no log em vez de derrubar o login ou a ação de administração.
+pub async fn record_or_warn(audit_service: &dyn AuditService, event: CreateAuditEvent) {
+    let action = event.action;
+    if let Err(e) = audit_service.record(event).await {
+        log::warn!("Could not record audit event {}: {}", action.as_str(), e.message);
+    }
+}
+
+/// Entradas lidas por vez na verificação.
+const VERIFY_BATCH_SIZE: i64 = 1000;
+
+/// Assina os checkpoints com a mesma chave dos access tokens: RS256 com a `SigningKey` ou HS256
+/// com `SECRET_KEY`.
+#[derive(Clone, Default)]
+pub struct CheckpointSigner {
+    secret_key: Option<String>,
+    signing_key: Option<SigningKey>,
+}
+
+impl CheckpointSigner {
+    /// Usa a `SigningKey` quando há uma; sem ela, lê `SECRET_KEY` do ambiente.
+    pub fn new(signing_key: Option<SigningKey>) -> Self {
+        CheckpointSigner {
+            secret_key: None,
+            signing_key,
+        }
+    }
+
+    /// Usa uma chave fixa em vez de ler `SECRET_KEY` do ambiente.
+    pub fn with_secret(secret_key: impl Into<String>) -> Self {
+        CheckpointSigner {
+            secret_key: Some(secret_key.into()),
+            signing_key: None,
+        }
+    }
+
+    fn secret_key(&self) -> String {
+        self.secret_key
+            .clone()
+            .unwrap_or_else(|| env::var(SECRET_KEY_TOKEN).expect("SECRET_KEY must be set"))
+    }
+
+    pub fn sign(&self, claims: &AuditCheckpointClaims) -> Result<String, CommonError> {
+        match &self.signing_key {
+            Some(signing_key) => Ok(encode(
+                &signing_key.header(),
+                claims,
+                &signing_key.encoding_key,
+            )?),
+            None => Ok(encode(
+                &Header::default(),
+                claims,
+                &EncodingKey::from_secret(self.secret_key().as_ref()),
+            )?),
+        }
+    }
+
+    pub fn verify(&self, signature: &str) -> Result<AuditCheckpointClaims, CommonError> {
+        let (decoding_key, mut validation) = match &self.signing_key {
+            Some(signing_key) => (
+                DecodingKey::from_jwk(&signing_key.jwk)?,
+                Validation::new(Algorithm::RS256),
+            ),
+            None => (
+                DecodingKey::from_secret(self.secret_key().as_ref()),
+                Validation::default(),
+            ),
+        };
+        // checkpoints não expiram
+        validation.required_spec_claims.clear();
+        validation.validate_exp = false;
+        Ok(decode::<AuditCheckpointClaims>(signature, &decoding_key, &validation)?.claims)
+    }
+}
+
+/// Cadeia de um dia, conferida entrada a entrada.
+struct DayChain {
+    day: NaiveDate,
+    prev_hash: String,
+    event_count: i64,
+    last_event_id: i64,
+}
+
+impl DayChain {
+    fn new(day: NaiveDate) -> Self {
+        DayChain {
+            day,
+            prev_hash: AUDIT_CHAIN_GENESIS.to_string(),
+            event_count: 0,
+            last_event_id: 0,
+        }
+    }
+
+    fn push(&mut self, event: &AuditEvent) -> Result<(), AuditChainBreak> {
+        let broken = |reason: &str| AuditChainBreak {
+            day: self.day,
+            event_id: Some(event.id),
+            reason: reason.to_string(),
+        };
+        if event.hash.is_empty() {
+            return Err(broken("entry has no hash"));
+        }
+        if event.prev_hash != self.prev_hash {
+            return Err(broken(
+                "prev_hash does not match the previous entry of the day",
+            ));
+        }
+        if event.expected_hash() != event.hash {
+            return Err(broken("hash does not match the entry contents"));
+        }
+        self.prev_hash = event.hash.clone();
+        self.event_count += 1;
+        self.last_event_id = event.id;
+        Ok(())
+    }
+
+    fn claims(&self) -> AuditCheckpointClaims {
+        AuditCheckpointClaims {
+            day: self.day,
+            event_count: self.event_count,
+            last_event_id: self.last_event_id,
+            last_hash: self.prev_hash.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuditServiceImpl {
+    pub repository: Arc<dyn AuditRepository>,
+    pub signer: CheckpointSigner,
+    /// Recebe cada evento depois de gravado, para envio a um SIEM.
+    pub exporter: Option<Arc<dyn AuditExportService>>,
+}
+
+impl AuditServiceImpl {
+    pub fn new(repository: Arc<dyn AuditRepository>, signer: CheckpointSigner) -> Self {
+        AuditServiceImpl {
+            repository,
+            signer,
+            exporter: None,
+        }
+    }
+
+    pub fn with_exporter(mut self, exporter: Arc<dyn AuditExportService>) -> Self {
+        self.exporter = Some(exporter);
+        self
+    }
+
+    /// Confere o checkpoint com a cadeia do dia: a assinatura e as claims assinadas precisam
+    /// bater com as colunas e com o que foi percorrido.
+    fn check_checkpoint(
+        &self,
+        chain: &DayChain,
+        checkpoint: &AuditCheckpoint,
+    ) -> Result<(), AuditChainBreak> {
+        let broken = |reason: &str| AuditChainBreak {
+            day: chain.day,
+            event_id: None,
+            reason: reason.to_string(),
+        };
+        let signed = self
+            .signer
+            .verify(&checkpoint.signature)
+            .map_err(|_| broken("checkpoint signature is invalid"))?;
+        let stored = AuditCheckpointClaims {
+            day: checkpoint.day,
+            event_count: checkpoint.event_count,
+            last_event_id: checkpoint.last_event_id,
+            last_hash: checkpoint.last_hash.clone(),
+        };
+        if signed != stored {
+            return Err(broken("checkpoint does not match its signature"));
+        }
+        if signed != chain.claims() {
+            return Err(broken("entries do not match the signed checkpoint"));
+        }
+        Ok(())
+    }
+
+    /// Encerra a cadeia de um dia: com checkpoint, confere-o; sem ele, um dia já terminado fica
+    /// entre os não assinados.
+    fn close_day(
+        &self,
+        chain: &DayChain,
+        checkpoints: &mut BTreeMap<NaiveDate, AuditCheckpoint>,
+        report: &mut AuditVerification,
+    ) -> Result<(), AuditChainBreak> {
+        match checkpoints.remove(&chain.day) {
+            Some(checkpoint) => self.check_checkpoint(chain, &checkpoint),
+            None => {
+                if chain.day < Utc::now().date_naive() {
+                    report.unsigned_days.push(chain.day);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AuditService for AuditServiceImpl {
+    async fn record(&self, event: CreateAuditEvent) -> Result<AuditEvent, CommonError> {
+        let event = self
+            .repository
+            .create(&event)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if let Some(exporter) = &self.exporter {
+            exporter.export(&event);
+        }
+        Ok(event)
+    }
+
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &AuditEventFilter,
+        params: &dyn QueryParams,
+    ) -> Result<ResultPaging<AuditEvent>, CommonError> {
+        validate_page(params)?;
+        self.repository
+            .list(organization_id, filter, params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn create_checkpoints(&self) -> Result<usize, CommonError> {
+        let today = Utc::now().date_naive();
+        let days = self
+            .repository
+            .days_without_checkpoint(today)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let mut created = 0;
+        for day in days {
+            let mut chain = DayChain::new(day);
+            let entries = self
+                .repository
+                .list_day(day)
+                .await
+                .map_err(|e| -> CommonError { e.into() })?;
+            let verified = entries
+                .iter()
+                .skip_while(|event| event.hash.is_empty())
+                .try_for_each(|event| chain.push(event));
+            // um dia adulterado não é assinado, para que a verificação continue acusando a quebra
+            if let Err(broken) = verified {
+                log::error!(
+                    "Audit log for {} is broken at entry {:?}: {}; the day was not signed",
+                    day,
+                    broken.event_id,
+                    broken.reason
+                );
+                continue;
+            }
+            let claims = chain.claims();
+            let signature = self.signer.sign(&claims)?;
+            self.repository
+                .create_checkpoint(&claims, &signature)
+                .await
+                .map_err(|e| -> CommonError { e.into() })?;
+            created += 1;
+        }
+        Ok(created)
+    }
+
+    async fn verify(&self, from: Option<NaiveDate>) -> Result<AuditVerification, CommonError> {
+        let mut checkpoints: BTreeMap<NaiveDate, AuditCheckpoint> = self
+            .repository
+            .list_checkpoints(from)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .into_iter()
+            .map(|checkpoint| (checkpoint.day, checkpoint))
+            .collect();
+        let mut report = AuditVerification::default();
+        let mut chain: Option<DayChain> = None;
+        let mut after_id = 0;
+        loop {
+            let entries = self
+                .repository
+                .list_chain(from, after_id, VERIFY_BATCH_SIZE)
+                .await
+                .map_err(|e| -> CommonError { e.into() })?;
+            if entries.is_empty() {
+                break;
+            }
+            for event in entries {
+                after_id = event.id;
+                // entradas gravadas antes da cadeia só são aceitas antes da primeira encadeada
+                if event.hash.is_empty() && chain.is_none() {
+                    report.unchained_events += 1;
+                    continue;
+                }
+                if chain.as_ref().is_none_or(|current| current.day != event.day()) {
+                    if let Some(finished) = chain.take() {
+                        let closed = if event.day() < finished.day {
+                            Err(AuditChainBreak {
+                                day: finished.day,
+                                event_id: Some(event.id),
+                                reason: "entry is older than the entry before it".to_string(),
+                            })
+                        } else {
+                            self.close_day(&finished, &mut checkpoints, &mut report)
+                        };
+                        if let Err(broken) = closed {
+                            report.first_broken_link = Some(broken);
+                            return Ok(report);
+                        }
+                    }
+                    chain = Some(DayChain::new(event.day()));
+                    report.days_checked += 1;
+                }
+                let current = chain.as_mut().unwrap();
+                if let Err(broken) = current.push(&event) {
+                    report.first_broken_link = Some(broken);
+                    return Ok(report);
+                }
+                report.events_checked += 1;
+            }
+        }
+        if let Some(finished) = chain {
+            if let Err(broken) = self.close_day(&finished, &mut checkpoints, &mut report) {
+                report.first_broken_link = Some(broken);
+                return Ok(report);
+            }
+        }
+        // um checkpoint sem nenhuma entrada no dia quer dizer que o dia inteiro foi apagado
+        if let Some((day, _)) = checkpoints.into_iter().next() {
+            report.first_broken_link = Some(AuditChainBreak {
+                day,
+                event_id: None,
+                reason: "day has a checkpoint but no entries".to_string(),
+            });
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::info;
+
+use crate::domain::constants::ADMIN_ROLE;
+use crate::domain::error::CommonError;
+use crate::domain::models::group::{GroupGraph, PermissionGrant};
+use crate::domain::models::role::{CreatePermission, CreateRole, Permission, Role, RoleSessionPolicy};
+use crate::domain::repositories::group::GroupRepository;
+use crate::domain::repositories::role::RoleRepository;
+use crate::domain::services::authorization::AuthorizationService;
+
+#[derive(Clone)]
+pub struct AuthorizationServiceImpl {
+    pub repository: Arc<dyn RoleRepository>,
+    pub group_repository: Arc<dyn GroupRepository>,
+}
+
+impl AuthorizationServiceImpl {
+    pub fn new(repository: Arc<dyn RoleRepository>, group_repository: Arc<dyn GroupRepository>) -> Self {
+        AuthorizationServiceImpl {
+            repository,
+            group_repository,
+        }
+    }
+
+    // Papéis efetivos do usuário, cada um com o caminho de grupos que o concedeu
+    // (vazio para papéis atribuídos diretamente).
+    async fn role_grants(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<(Role, Vec<String>)>, CommonError> {
+        let mut grants: Vec<(Role, Vec<String>)> = self
+            .repository
+            .get_user_roles(organization_id, user_id)
+            .await?
+            .into_iter()
+            .map(|role| (role, Vec::new()))
+            .collect();
+
+        let direct_groups = self
+            .group_repository
+            .get_user_group_ids(organization_id, user_id)
+            .await?;
+        if direct_groups.is_empty() {
+            return Ok(grants);
+        }
+        let edges = self.group_repository.list_subgroup_edges(organization_id).await?;
+        let paths = GroupGraph::new(&edges).ancestor_paths(&direct_groups);
+        let names: HashMap<i32, String> = self
+            .group_repository
+            .list_groups(organization_id)
+            .await?
+            .into_iter()
+            .map(|group| (group.id, group.name))
+            .collect();
+        let group_roles = self
+            .group_repository
+            .get_groups_roles(organization_id, paths.keys().copied().collect())
+            .await?;
+        for (group_id, role) in group_roles {
+            let path = paths[&group_id]
+                .iter()
+                .filter_map(|id| names.get(id).cloned())
+                .collect();
+            grants.push((role, path));
+        }
+        Ok(grants)
+    }
+
+    async fn role_permissions(
+        &self,
+        organization_id: i32,
+        roles: &[Role],
+    ) -> Result<HashMap<i32, Vec<Permission>>, CommonError> {
+        let mut permissions = HashMap::new();
+        for role in roles {
+            if let Entry::Vacant(entry) = permissions.entry(role.id) {
+                entry.insert(
+                    self.repository
+                        .get_role_permissions(organization_id, role.id)
+                        .await?,
+                );
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+#[async_trait]
+impl AuthorizationService for AuthorizationServiceImpl {
+    async fn create_role(&self, organization_id: i32, role: CreateRole) -> Result<Role, CommonError> {
+        self.repository
+            .create_role(organization_id, &role)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn list_roles(&self, organization_id: i32) -> Result<Vec<Role>, CommonError> {
+        self.repository
+            .list_roles(organization_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn delete_role(&self, organization_id: i32, role_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .delete_role(organization_id, role_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn set_role_session_policy(
+        &self,
+        organization_id: i32,
+        role_id: i32,
+        policy: RoleSessionPolicy,
+    ) -> Result<Role, CommonError> {
+        let limits = [
+            policy.idle_timeout_seconds,
+            policy.max_lifetime_seconds,
+            policy.max_concurrent_sessions,
+        ];
+        if limits.iter().flatten().any(|limit| *limit <= 0) {
+            return Err(CommonError {
+                message: "Session limits must be positive".to_string(),
+                code: 400,
+            });
+        }
+        self.repository
+            .set_role_session_policy(organization_id, role_id, &policy)
+            .await
+            .map_err(|_| CommonError {
+                message: "Role not found".to_string(),
+                code: 404,
+            })
+    }
+    async fn create_permission(&self, permission: CreatePermission) -> Result<Permission, CommonError> {
+        self.repository
+            .create_permission(&permission)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn list_permissions(&self) -> Result<Vec<Permission>, CommonError> {
+        self.repository
+            .list_permissions()
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn get_role_permissions(&self, organization_id: i32, role_id: i32) -> Result<Vec<Permission>, CommonError> {
+        self.repository
+            .get_role_permissions(organization_id, role_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn grant_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .grant_permission(organization_id, role_id, permission_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn revoke_permission(&self, organization_id: i32, role_id: i32, permission_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .revoke_permission(organization_id, role_id, permission_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn assign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .assign_role(organization_id, user_id, role_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn unassign_role(&self, organization_id: i32, user_id: i32, role_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .unassign_role(organization_id, user_id, role_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn get_user_roles(&self, organization_id: i32, user_id: i32) -> Result<Vec<Role>, CommonError> {
+        let mut roles: Vec<Role> = Vec::new();
+        for (role, _) in self.role_grants(organization_id, user_id).await? {
+            if !roles.iter().any(|r| r.id == role.id) {
+                roles.push(role);
+            }
+        }
+        roles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(roles)
+    }
+    async fn get_user_permissions(&self, organization_id: i32, user_id: i32) -> Result<Vec<Permission>, CommonError> {
+        let roles = self.get_user_roles(organization_id, user_id).await?;
+        let mut permissions: Vec<Permission> = Vec::new();
+        for (_, role_permissions) in self.role_permissions(organization_id, &roles).await? {
+            for permission in role_permissions {
+                if !permissions.iter().any(|p| p.id == permission.id) {
+                    permissions.push(permission);
+                }
+            }
+        }
+        permissions.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(permissions)
+    }
+    async fn explain_permission(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        permission: String,
+    ) -> Result<Vec<PermissionGrant>, CommonError> {
+        let grants = self.role_grants(organization_id, user_id).await?;
+        let roles: Vec<Role> = grants.iter().map(|(role, _)| role.clone()).collect();
+        let role_permissions = self.role_permissions(organization_id, &roles).await?;
+        Ok(grants
+            .into_iter()
+            .filter(|(role, _)| role_permissions[&role.id].iter().any(|p| p.name == permission))
+            .map(|(role, groups)| PermissionGrant {
+                permission: permission.clone(),
+                role: role.name,
+                groups,
+            })
+            .collect())
+    }
+    async fn ensure_admin_role(&self, organization_id: i32, permissions: &[&str]) -> Result<Role, CommonError> {
+        let admin_role = match self.repository.get_role_by_name(organization_id, ADMIN_ROLE).await {
+            Ok(role) => role,
+            Err(_) => {
+                info!("Admin role does not exist for organization {}, creating it...", organization_id);
+                self.create_role(
+                    organization_id,
+                    CreateRole {
+                        name: ADMIN_ROLE.to_string(),
+                        description: Some("Full access to the administration API".to_string()),
+                    },
+                )
+                .await?
+            }
+        };
+
+        let existing = self.list_permissions().await?;
+        for name in permissions {
+            let permission = match existing.iter().find(|p| p.name == *name) {
+                Some(permission) => permission.clone(),
+                None => {
+                    self.create_permission(CreatePermission {
+                        name: name.to_string(),
+                        description: None,
+                    })
+                    .await?
+                }
+            };
+            self.grant_permission(organization_id, admin_role.id, permission.id)
+                .await?;
+        }
+        Ok(admin_role)
+    }
+}
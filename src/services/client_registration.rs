@@ -0,0 +1,225 @@
+use std::env;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::domain::constants::{INITIAL_ACCESS_TOKEN_TTL_SECONDS, REGISTRATION_ALLOWED_SCOPES};
+use crate::domain::error::{CommonError, OAuthError};
+use crate::domain::models::client_registration::{ClientInformation, ClientMetadata, InitialAccessToken};
+use crate::domain::models::oauth::{ClientType, CreateOAuthClient, OAuthClient, TOKEN_EXCHANGE_GRANT_TYPE};
+use crate::domain::models::oidc::{EMAIL_SCOPE, OPENID_SCOPE, PROFILE_SCOPE};
+use crate::domain::repositories::oauth::OAuthRepository;
+use crate::domain::services::client_registration::ClientRegistrationService;
+use crate::services::oauth::validate_redirect_uri;
+use crate::services::secret::{generate_secret, hash_secret};
+
+#[derive(Clone)]
+pub struct ClientRegistrationServiceImpl {
+    pub repository: Arc<dyn OAuthRepository>,
+    /// Escopos que um cliente pode pedir no registro dinâmico.
+    pub allowed_scopes: Vec<String>,
+}
+
+impl ClientRegistrationServiceImpl {
+    /// Lê os escopos permitidos no registro de `REGISTRATION_ALLOWED_SCOPES`, separados por
+    /// espaço; sem a variável, só `openid profile email`.
+    pub fn new(repository: Arc<dyn OAuthRepository>) -> Self {
+        let allowed_scopes = match env::var(REGISTRATION_ALLOWED_SCOPES) {
+            Ok(scopes) => scopes.split_whitespace().map(String::from).collect(),
+            Err(_) => [OPENID_SCOPE, PROFILE_SCOPE, EMAIL_SCOPE].map(String::from).to_vec(),
+        };
+        Self::with_allowed_scopes(repository, allowed_scopes)
+    }
+
+    /// Usa uma lista fixa de escopos permitidos em vez de ler o ambiente.
+    pub fn with_allowed_scopes(repository: Arc<dyn OAuthRepository>, allowed_scopes: Vec<String>) -> Self {
+        ClientRegistrationServiceImpl {
+            repository,
+            allowed_scopes,
+        }
+    }
+
+    /// Resolve o cliente pelo `client_id` e confere o `registration_access_token`. Clientes
+    /// criados pelo painel administrativo não têm token e nunca passam.
+    async fn authenticate(&self, client_id: &str, registration_access_token: &str) -> Result<OAuthClient, OAuthError> {
+        let invalid_token = || OAuthError::new("invalid_token", "Invalid registration access token");
+        let client = self
+            .repository
+            .get_client(client_id)
+            .await
+            .map_err(|_| invalid_token())?;
+        if client.registration_access_token_hash.as_deref() != Some(hash_secret(registration_access_token).as_str()) {
+            return Err(invalid_token());
+        }
+        Ok(client)
+    }
+}
+
+/// Valida os metadados do RFC 7591 e os converte no cliente a ser gravado. Sem `scope`, o
+/// cliente recebe todos os `allowed_scopes`.
+fn client_from_metadata(
+    metadata: ClientMetadata,
+    default_name: &str,
+    allowed_scopes: &[String],
+) -> Result<CreateOAuthClient, OAuthError> {
+    let invalid_metadata = |message: String| OAuthError::new("invalid_client_metadata", message);
+    let client_type = match metadata.token_endpoint_auth_method.as_deref() {
+        None | Some("client_secret_basic") | Some("client_secret_post") => ClientType::Confidential,
+        Some("none") => ClientType::Public,
+        Some(method) => {
+            return Err(invalid_metadata(format!(
+                "Unsupported token_endpoint_auth_method {}",
+                method
+            )))
+        }
+    };
+
+    let grant_types = if metadata.grant_types.is_empty() {
+        vec!["authorization_code".to_string()]
+    } else {
+        metadata.grant_types
+    };
+    // o token exchange depende de audiências, que só um administrador concede
+    for grant_type in &grant_types {
+        if grant_type == TOKEN_EXCHANGE_GRANT_TYPE || !client_type.supports_grant_type(grant_type) {
+            return Err(invalid_metadata(format!("Unsupported grant type {}", grant_type)));
+        }
+    }
+    if let Some(response_type) = metadata.response_types.iter().find(|r| *r != "code") {
+        return Err(invalid_metadata(format!("Unsupported response type {}", response_type)));
+    }
+
+    if grant_types.iter().any(|g| g == "authorization_code") && metadata.redirect_uris.is_empty() {
+        return Err(OAuthError::new(
+            "invalid_redirect_uri",
+            "The authorization_code grant needs at least one redirect URI",
+        ));
+    }
+    for uri in &metadata.redirect_uris {
+        validate_redirect_uri(uri).map_err(|message| OAuthError::new("invalid_redirect_uri", message))?;
+    }
+
+    let allowed_scopes = match metadata.scope.as_deref() {
+        Some(scope) => {
+            let mut scopes: Vec<String> = Vec::new();
+            for scope in scope.split_whitespace() {
+                if !allowed_scopes.iter().any(|s| s == scope) {
+                    return Err(invalid_metadata(format!("Scope {} cannot be registered", scope)));
+                }
+                if !scopes.iter().any(|s| s == scope) {
+                    scopes.push(scope.to_string());
+                }
+            }
+            scopes
+        }
+        None => allowed_scopes.to_vec(),
+    };
+    Ok(CreateOAuthClient {
+        name: metadata
+            .client_name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| default_name.to_string()),
+        client_type,
+        redirect_uris: metadata.redirect_uris,
+        allowed_scopes,
+        allowed_audiences: Vec::new(),
+        grant_types,
+        require_pushed_authorization_requests: metadata.require_pushed_authorization_requests,
+    })
+}
+
+#[async_trait]
+impl ClientRegistrationService for ClientRegistrationServiceImpl {
+    async fn create_initial_access_token(&self, organization_id: i32) -> Result<InitialAccessToken, CommonError> {
+        let token = generate_secret(32);
+        let expires_at = Utc::now() + Duration::seconds(INITIAL_ACCESS_TOKEN_TTL_SECONDS);
+        self.repository
+            .create_initial_access_token(organization_id, &hash_secret(&token), expires_at)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(InitialAccessToken { token, expires_at })
+    }
+    async fn register_client(
+        &self,
+        initial_access_token: &str,
+        metadata: ClientMetadata,
+    ) -> Result<ClientInformation, OAuthError> {
+        let invalid_token = || OAuthError::new("invalid_token", "Invalid initial access token");
+        let token_hash = hash_secret(initial_access_token);
+        self.repository
+            .get_initial_access_token_organization(&token_hash)
+            .await
+            .map_err(|_| invalid_token())?;
+
+        let client_id = generate_secret(16);
+        let new_client = client_from_metadata(metadata, &client_id, &self.allowed_scopes)?;
+        // só metadados válidos gastam o token; se dois registros o usarem ao mesmo tempo, um falha
+        let organization_id = self
+            .repository
+            .consume_initial_access_token(&token_hash)
+            .await
+            .map_err(|_| invalid_token())?;
+        let client_secret = match new_client.client_type {
+            ClientType::Confidential => Some(generate_secret(32)),
+            ClientType::Public => None,
+        };
+        let registration_access_token = generate_secret(32);
+        let client = self
+            .repository
+            .create_client(
+                organization_id,
+                &client_id,
+                client_secret.as_deref().map(hash_secret).as_deref(),
+                Some(&hash_secret(&registration_access_token)),
+                &new_client,
+            )
+            .await
+            .map_err(OAuthError::from)?;
+        Ok(ClientInformation {
+            client,
+            client_secret,
+            registration_access_token: Some(registration_access_token),
+        })
+    }
+    async fn get_client(&self, client_id: &str, registration_access_token: &str) -> Result<ClientInformation, OAuthError> {
+        let client = self.authenticate(client_id, registration_access_token).await?;
+        Ok(ClientInformation {
+            client,
+            client_secret: None,
+            registration_access_token: None,
+        })
+    }
+    async fn update_client(
+        &self,
+        client_id: &str,
+        registration_access_token: &str,
+        metadata: ClientMetadata,
+    ) -> Result<ClientInformation, OAuthError> {
+        let client = self.authenticate(client_id, registration_access_token).await?;
+        let changes = client_from_metadata(metadata, &client.client_id, &self.allowed_scopes)?;
+        if changes.client_type != client.client_type {
+            return Err(OAuthError::new(
+                "invalid_client_metadata",
+                "token_endpoint_auth_method cannot change the client type",
+            ));
+        }
+        let client = self
+            .repository
+            .update_client(client.id, &changes)
+            .await
+            .map_err(OAuthError::from)?;
+        Ok(ClientInformation {
+            client,
+            client_secret: None,
+            registration_access_token: None,
+        })
+    }
+    async fn delete_client(&self, client_id: &str, registration_access_token: &str) -> Result<(), OAuthError> {
+        let client = self.authenticate(client_id, registration_access_token).await?;
+        self.repository
+            .delete_client(client.organization_id, client.id)
+            .await
+            .map_err(OAuthError::from)
+    }
+}
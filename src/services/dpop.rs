@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{TimeZone, Utc};
+use jsonwebtoken::jwk::{AlgorithmParameters, Jwk};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::domain::constants::DPOP_PROOF_MAX_AGE_SECONDS;
+use crate::domain::error::CommonError;
+use crate::domain::models::dpop::DpopProofClaims;
+use crate::domain::repositories::dpop::DpopRepository;
+use crate::domain::services::dpop::DpopService;
+use crate::services::secret::hash_secret;
+
+#[derive(Clone)]
+pub struct DpopServiceImpl {
+    pub repository: Arc<dyn DpopRepository>,
+}
+
+impl DpopServiceImpl {
+    pub fn new(repository: Arc<dyn DpopRepository>) -> Self {
+        DpopServiceImpl { repository }
+    }
+}
+
+/// Membros de chave privada ou simétrica (RFC 7518, seção 6) que o `jwk` de uma prova não pode ter.
+const PRIVATE_JWK_MEMBERS: [&str; 8] = ["d", "p", "q", "dp", "dq", "qi", "oth", "k"];
+
+fn invalid_proof(message: impl Into<String>) -> CommonError {
+    CommonError {
+        message: message.into(),
+        code: 401,
+    }
+}
+
+/// Thumbprint SHA-256 de uma chave pública (RFC 7638), em base64url sem padding. Só os membros
+/// obrigatórios entram, em ordem lexicográfica e sem espaços.
+pub fn jwk_thumbprint(jwk: &Jwk) -> Result<String, CommonError> {
+    let members = match &jwk.algorithm {
+        AlgorithmParameters::EllipticCurve(params) => {
+            let curve = serde_json::to_value(&params.curve).map_err(|e| invalid_proof(e.to_string()))?;
+            serde_json::json!({ "crv": curve, "kty": "EC", "x": params.x, "y": params.y })
+        }
+        AlgorithmParameters::RSA(params) => {
+            serde_json::json!({ "e": params.e, "kty": "RSA", "n": params.n })
+        }
+        _ => return Err(invalid_proof("Unsupported DPoP key type")),
+    };
+    // serde_json ordena as chaves dos objetos, o que já dá a forma canônica
+    Ok(URL_SAFE_NO_PAD.encode(Sha256::digest(members.to_string().as_bytes())))
+}
+
+/// Se o `jwk` do header da prova traz membros de chave privada. O `Jwk` do jsonwebtoken descarta
+/// os membros que não conhece, então o header é lido de novo como JSON.
+fn has_private_key_members(proof: &str) -> bool {
+    let header = proof.split('.').next().unwrap_or_default();
+    URL_SAFE_NO_PAD
+        .decode(header)
+        .ok()
+        .and_then(|header| serde_json::from_slice::<serde_json::Value>(&header).ok())
+        .and_then(|header| {
+            let jwk = header.get("jwk")?.as_object()?;
+            Some(PRIVATE_JWK_MEMBERS.iter().any(|member| jwk.contains_key(*member)))
+        })
+        .unwrap_or(false)
+}
+
+/// URI sem query nem fragmento, com esquema e host normalizados pelo parser.
+fn normalize_htu(uri: &str) -> Option<String> {
+    let mut url = Url::parse(uri).ok()?;
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url.to_string())
+}
+
+#[async_trait]
+impl DpopService for DpopServiceImpl {
+    async fn verify_proof(
+        &self,
+        proof: &str,
+        htm: &str,
+        htu: &str,
+        access_token: Option<&str>,
+    ) -> Result<String, CommonError> {
+        let header = decode_header(proof).map_err(|_| invalid_proof("Malformed DPoP proof"))?;
+        if header.typ.as_deref() != Some("dpop+jwt") {
+            return Err(invalid_proof("DPoP proof must have typ dpop+jwt"));
+        }
+        if !matches!(
+            header.alg,
+            Algorithm::ES256 | Algorithm::ES384 | Algorithm::RS256 | Algorithm::PS256
+        ) {
+            return Err(invalid_proof("Unsupported DPoP proof algorithm"));
+        }
+        let jwk = header.jwk.ok_or_else(|| invalid_proof("DPoP proof has no jwk"))?;
+        // RFC 9449, seção 4.3: a chave da prova precisa ser pública
+        if has_private_key_members(proof) {
+            return Err(invalid_proof("DPoP proof jwk must not contain a private key"));
+        }
+        let jkt = jwk_thumbprint(&jwk)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.validate_exp = false;
+        validation.required_spec_claims.clear();
+        let key = DecodingKey::from_jwk(&jwk).map_err(|_| invalid_proof("Invalid DPoP key"))?;
+        let claims = decode::<DpopProofClaims>(proof, &key, &validation)
+            .map_err(|_| invalid_proof("Invalid DPoP proof signature"))?
+            .claims;
+
+        if !claims.htm.eq_ignore_ascii_case(htm) || normalize_htu(&claims.htu) != normalize_htu(htu) {
+            return Err(invalid_proof("DPoP proof does not match the request"));
+        }
+        let now = Utc::now().timestamp();
+        if (now - claims.iat).abs() > DPOP_PROOF_MAX_AGE_SECONDS {
+            return Err(invalid_proof("DPoP proof is too old or from the future"));
+        }
+        if let Some(access_token) = access_token {
+            if claims.ath.as_deref() != Some(hash_secret(access_token).as_str()) {
+                return Err(invalid_proof("DPoP proof does not match the access token"));
+            }
+        }
+
+        // o jti só precisa ser único por chave; depois da janela a prova seria recusada pelo iat
+        let expires_at = Utc
+            .timestamp_opt(claims.iat + DPOP_PROOF_MAX_AGE_SECONDS, 0)
+            .single()
+            .ok_or_else(|| invalid_proof("Invalid DPoP proof iat"))?;
+        let first_use = self
+            .repository
+            .record_proof(&hash_secret(&format!("{}:{}", jkt, claims.jti)), expires_at)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if !first_use {
+            return Err(invalid_proof("DPoP proof has already been used"));
+        }
+        Ok(jkt)
+    }
+    async fn purge_expired(&self) -> Result<usize, CommonError> {
+        self.repository
+            .delete_expired_proofs()
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
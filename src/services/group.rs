@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::group::{CreateGroup, Group};
+use crate::domain::repositories::group::GroupRepository;
+use crate::domain::services::group::GroupService;
+
+#[derive(Clone)]
+pub struct GroupServiceImpl {
+    pub repository: Arc<dyn GroupRepository>,
+}
+
+impl GroupServiceImpl {
+    pub fn new(repository: Arc<dyn GroupRepository>) -> Self {
+        GroupServiceImpl { repository }
+    }
+}
+
+#[async_trait]
+impl GroupService for GroupServiceImpl {
+    async fn create_group(&self, organization_id: i32, group: CreateGroup) -> Result<Group, CommonError> {
+        self.repository
+            .create_group(organization_id, &group)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn list_groups(&self, organization_id: i32) -> Result<Vec<Group>, CommonError> {
+        self.repository
+            .list_groups(organization_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn delete_group(&self, organization_id: i32, group_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .delete_group(organization_id, group_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn add_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .add_member(organization_id, group_id, user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn remove_member(&self, organization_id: i32, group_id: i32, user_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .remove_member(organization_id, group_id, user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn add_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> Result<(), CommonError> {
+        let added = self
+            .repository
+            .add_subgroup(organization_id, parent_group_id, child_group_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if !added {
+            return Err(CommonError {
+                message: "Adding this subgroup would create a cycle".to_string(),
+                code: 400,
+            });
+        }
+        Ok(())
+    }
+    async fn remove_subgroup(&self, organization_id: i32, parent_group_id: i32, child_group_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .remove_subgroup(organization_id, parent_group_id, child_group_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn assign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .assign_role(organization_id, group_id, role_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn unassign_role(&self, organization_id: i32, group_id: i32, role_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .unassign_role(organization_id, group_id, role_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
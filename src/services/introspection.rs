@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use actix_threadpool::run;
+use async_trait::async_trait;
+use url::Url;
+
+use crate::domain::constants::{INTROSPECTION_CONNECT_TIMEOUT_SECONDS, INTROSPECTION_READ_TIMEOUT_SECONDS};
+use crate::domain::error::CommonError;
+use crate::domain::models::token::Claim;
+use crate::domain::services::token::TokenService;
+
+/// Valida tokens chamando o endpoint `/auth/validate` de uma instância remota do serviço.
+///
+/// O bearer token segue no corpo da chamada, então a URL precisa ser `https://`; `http://` só é
+/// aceito em loopback (`localhost`, `127.0.0.1` e `[::1]`). A chamada roda no pool de threads
+/// bloqueantes, com tempo máximo de conexão e de leitura. Tokens vinculados voltam com `cnf` sem
+/// prova DPoP (`caller_verifies_dpop`): quem confere a prova é o extractor `AuthenticatedUser`,
+/// com a requisição original em mãos.
+#[derive(Clone)]
+pub struct IntrospectionTokenService {
+    pub validate_url: String,
+    agent: ureq::Agent,
+}
+
+impl IntrospectionTokenService {
+    pub fn new(validate_url: impl Into<String>) -> Result<Self, CommonError> {
+        let validate_url = validate_url.into();
+        let invalid_url = |reason: String| CommonError {
+            message: format!("Invalid introspection URL {}: {}", validate_url, reason),
+            code: 500,
+        };
+        let url = Url::parse(&validate_url).map_err(|e| invalid_url(e.to_string()))?;
+        let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        match url.scheme() {
+            "https" => {}
+            "http" if loopback => {}
+            _ => return Err(invalid_url("https is required except for localhost".to_string())),
+        }
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(Duration::from_secs(INTROSPECTION_CONNECT_TIMEOUT_SECONDS))
+            .timeout_read(Duration::from_secs(INTROSPECTION_READ_TIMEOUT_SECONDS))
+            .build();
+        Ok(IntrospectionTokenService { validate_url, agent })
+    }
+}
+
+#[async_trait]
+impl TokenService for IntrospectionTokenService {
+    async fn create(&self, _user_id: i32) -> Result<String, CommonError> {
+        Err(CommonError {
+            message: "Introspection token service can only validate tokens".to_string(),
+            code: 500,
+        })
+    }
+    async fn create_with_claim(&self, _claim: Claim) -> Result<String, CommonError> {
+        self.create(0).await
+    }
+    async fn validate(&self, token: String) -> Result<Claim, CommonError> {
+        let url = self.validate_url.clone();
+        let agent = self.agent.clone();
+        run(move || -> Result<Claim, CommonError> {
+            let response = agent
+                .post(&url)
+                .send_json(serde_json::json!({ "token": token, "caller_verifies_dpop": true }))
+                .map_err(|e| match e {
+                    ureq::Error::Status(_, _) => CommonError {
+                        message: "Token rejected by introspection".to_string(),
+                        code: 401,
+                    },
+                    e => CommonError {
+                        message: format!("Introspection request failed: {}", e),
+                        code: 500,
+                    },
+                })?;
+            response.into_json::<Claim>().map_err(|e| CommonError {
+                message: format!("Invalid introspection response: {}", e),
+                code: 500,
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            actix_threadpool::BlockingError::Error(e) => e,
+            actix_threadpool::BlockingError::Canceled => CommonError {
+                message: "Introspection request canceled".to_string(),
+                code: 500,
+            },
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use std::fs;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+
+use crate::domain::error::CommonError;
+use crate::domain::models::token::Claim;
+use crate::domain::services::token::TokenService;
+
+/// Valida tokens localmente a partir de um JWKS, sem chamar o serviço de autenticação.
+#[derive(Clone)]
+pub struct JwksTokenService {
+    pub jwks: JwkSet,
+}
+
+impl JwksTokenService {
+    pub fn new(jwks: JwkSet) -> Self {
+        JwksTokenService { jwks }
+    }
+
+    pub fn from_json(jwks: &str) -> Result<Self, CommonError> {
+        let jwks: JwkSet = serde_json::from_str(jwks).map_err(|e| CommonError {
+            message: format!("Invalid JWKS: {}", e),
+            code: 500,
+        })?;
+        Ok(JwksTokenService::new(jwks))
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, CommonError> {
+        let jwks = fs::read_to_string(path).map_err(|e| CommonError {
+            message: format!("Could not read JWKS file {}: {}", path, e),
+            code: 500,
+        })?;
+        JwksTokenService::from_json(&jwks)
+    }
+}
+
+#[async_trait]
+impl TokenService for JwksTokenService {
+    async fn create(&self, _user_id: i32) -> Result<String, CommonError> {
+        Err(CommonError {
+            message: "JWKS token service can only validate tokens".to_string(),
+            code: 500,
+        })
+    }
+    async fn create_with_claim(&self, _claim: Claim) -> Result<String, CommonError> {
+        self.create(0).await
+    }
+    async fn validate(&self, token: String) -> Result<Claim, CommonError> {
+        let header = decode_header(&token)?;
+        let kid = header.kid.ok_or(CommonError {
+            message: "Token has no kid".to_string(),
+            code: 401,
+        })?;
+        let jwk = self.jwks.find(&kid).ok_or(CommonError {
+            message: format!("Unknown kid: {}", kid),
+            code: 401,
+        })?;
+        // O algoritmo vem da JWK publicada; o `alg` do header não é confiável.
+        let algorithm = jwk
+            .common
+            .key_algorithm
+            .and_then(|alg| Algorithm::from_str(&alg.to_string()).ok())
+            .ok_or(CommonError {
+                message: format!("JWK {} has no signing algorithm", kid),
+                code: 401,
+            })?;
+        if header.alg != algorithm {
+            return Err(CommonError {
+                message: format!("Token algorithm does not match JWK {}", kid),
+                code: 401,
+            });
+        }
+        let mut validation = Validation::new(algorithm);
+        // O `aud` é conferido por `authenticate`, com a audiência configurada na rota ou no app.
+        validation.validate_aud = false;
+        let token_data = decode::<Claim>(&token, &DecodingKey::from_jwk(jwk)?, &validation)?;
+        Ok(token_data.claims)
+    }
+}
@@ -1,2 +1,18 @@
-pub(crate) mod token;
+pub mod audit;
+pub mod authorization;
+pub mod client_registration;
+pub mod dpop;
+pub mod group;
+pub mod introspection;
+pub mod jwks;
+pub mod oauth;
+pub mod oidc;
+pub mod organization;
+pub mod personal_access_token;
+pub mod secret;
+pub mod service_account;
+pub mod session;
+pub mod token;
 pub mod user;
+pub mod user_admin;
+pub mod webhook;
@@ -0,0 +1,1018 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use url::Url;
+
+use crate::domain::constants::{
+    ACCESS_TOKEN_TTL_SECONDS, AUTHORIZATION_CODE_TTL_SECONDS, DEVICE_CODE_INTERVAL_SECONDS,
+    DEVICE_CODE_TTL_SECONDS, PENDING_CONSENT_TTL_SECONDS, PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS,
+    REFRESH_TOKEN_TTL_SECONDS,
+    USER_CODE_FAILED_ATTEMPTS_TTL_SECONDS, USER_CODE_MAX_FAILED_ATTEMPTS,
+};
+use crate::domain::error::{CommonError, OAuthError};
+use crate::domain::models::oauth::{
+    AuthorizationRequest, ClientType, Consent, CreateAuthorizationCode, CreateDeviceCode,
+    CreateOAuthClient, DeviceAuthorization, DeviceAuthorizationRequest, DeviceCode, DeviceCodeStatus,
+    IssuedAuthorizationCode, OAuthClient, PushedAuthorization, PushedAuthorizationRequest, RegisteredOAuthClient,
+    RevocationRequest, TokenRequest, TokenResponse,
+    ACCESS_TOKEN_TYPE, DEVICE_CODE_GRANT_TYPE, GRANT_TYPES, JWT_TOKEN_TYPE, TOKEN_EXCHANGE_GRANT_TYPE,
+};
+use crate::domain::models::oidc::OPENID_SCOPE;
+use crate::domain::models::token::{Actor, Claim, Confirmation, CreateRefreshToken};
+use crate::domain::repositories::oauth::OAuthRepository;
+use crate::domain::repositories::token::TokenRepository;
+use crate::domain::services::oauth::OAuthService;
+use crate::domain::services::oidc::OidcService;
+use crate::domain::services::token::TokenService;
+use crate::domain::services::user::UserService;
+use crate::services::secret::{
+    format_user_code, generate_secret, generate_user_code, hash_secret, normalize_user_code,
+};
+
+const PKCE_METHOD: &str = "S256";
+/// Prefixo dos `request_uri` emitidos por `/oauth/par` (RFC 9126, seção 2.2).
+const REQUEST_URI_PREFIX: &str = "urn:ietf:params:oauth:request_uri:";
+/// Acréscimo ao intervalo a cada `slow_down` (RFC 8628, seção 3.5).
+const SLOW_DOWN_INCREMENT_SECONDS: i32 = 5;
+
+/// Confere um redirect URI registrado: URL absoluta sem fragmento nem curingas, com `https`
+/// exceto em loopback (`localhost`, `127.0.0.1` e `[::1]`), onde `http` é aceito.
+pub fn validate_redirect_uri(uri: &str) -> Result<(), String> {
+    let invalid = |reason: &str| format!("Invalid redirect URI {}: {}", uri, reason);
+    if uri.contains('*') {
+        return Err(invalid("wildcards are not allowed"));
+    }
+    let url = Url::parse(uri).map_err(|e| invalid(&e.to_string()))?;
+    if url.fragment().is_some() {
+        return Err(invalid("fragments are not allowed"));
+    }
+    let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if loopback => Ok(()),
+        _ => Err(invalid("https is required except for localhost")),
+    }
+}
+
+#[derive(Clone)]
+pub struct OAuthServiceImpl {
+    pub repository: Arc<dyn OAuthRepository>,
+    pub user_service: Arc<dyn UserService>,
+    pub token_service: Arc<dyn TokenService>,
+    pub oidc_service: Arc<dyn OidcService>,
+    pub token_repository: Arc<dyn TokenRepository>,
+}
+
+impl OAuthServiceImpl {
+    pub fn new(
+        repository: Arc<dyn OAuthRepository>,
+        user_service: Arc<dyn UserService>,
+        token_service: Arc<dyn TokenService>,
+        oidc_service: Arc<dyn OidcService>,
+        token_repository: Arc<dyn TokenRepository>,
+    ) -> Self {
+        OAuthServiceImpl {
+            repository,
+            user_service,
+            token_service,
+            oidc_service,
+            token_repository,
+        }
+    }
+
+    async fn authenticate_client(
+        &self,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<OAuthClient, OAuthError> {
+        let client_id = client_id
+            .ok_or_else(|| OAuthError::new("invalid_client", "Client authentication is required"))?;
+        let client = self.get_client(client_id).await?;
+        if client.client_type == ClientType::Confidential {
+            let secret_matches = client_secret
+                .is_some_and(|secret| client.has_secret_hash(&hash_secret(secret)));
+            if !secret_matches {
+                return Err(OAuthError::new("invalid_client", "Invalid client credentials"));
+            }
+        }
+        Ok(client)
+    }
+
+    /// Escopo concedido: os pedidos, sem repetição, ou todos os permitidos ao cliente.
+    fn resolve_scope(client: &OAuthClient, requested: Option<&str>) -> Result<String, OAuthError> {
+        let requested: Vec<&str> = match requested {
+            Some(scope) => scope.split_whitespace().collect(),
+            None => client.allowed_scopes.iter().map(String::as_str).collect(),
+        };
+        let mut scopes: Vec<&str> = Vec::new();
+        for scope in requested {
+            if !client.allows_scope(scope) {
+                return Err(OAuthError::new(
+                    "invalid_scope",
+                    format!("Scope {} is not allowed for this client", scope),
+                ));
+            }
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        Ok(scopes.join(" "))
+    }
+
+    async fn authorization_code_grant(
+        &self,
+        client: OAuthClient,
+        request: TokenRequest,
+    ) -> Result<TokenResponse, OAuthError> {
+        let code = request
+            .code
+            .as_deref()
+            .ok_or_else(|| OAuthError::new("invalid_request", "code is required"))?;
+        let invalid_grant = || OAuthError::new("invalid_grant", "Invalid authorization code");
+        let authorization_code = self
+            .repository
+            .consume_authorization_code(&hash_secret(code))
+            .await
+            .map_err(|_| invalid_grant())?;
+        if authorization_code.oauth_client_id != client.id
+            || authorization_code.expires_at < Utc::now()
+            || request.redirect_uri.as_deref() != Some(authorization_code.redirect_uri.as_str())
+        {
+            return Err(invalid_grant());
+        }
+        if let Some(challenge) = &authorization_code.code_challenge {
+            let verifier = request.code_verifier.as_deref().unwrap_or_default();
+            if !(43..=128).contains(&verifier.len()) || hash_secret(verifier) != *challenge {
+                return Err(OAuthError::new("invalid_grant", "Invalid code_verifier"));
+            }
+        }
+
+        let id_token = if authorization_code.scope.split_whitespace().any(|s| s == OPENID_SCOPE) {
+            Some(
+                self.oidc_service
+                    .create_id_token(&client, &authorization_code)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        let scope = authorization_code.scope;
+        let mut response = self
+            .issue_user_tokens(
+                client,
+                authorization_code.user_id,
+                scope.clone(),
+                scope,
+                None,
+                request.dpop_jkt,
+            )
+            .await?;
+        response.id_token = id_token;
+        Ok(response)
+    }
+
+    async fn client_credentials_grant(
+        &self,
+        client: OAuthClient,
+        request: TokenRequest,
+    ) -> Result<TokenResponse, OAuthError> {
+        if client.client_type != ClientType::Confidential {
+            return Err(OAuthError::new(
+                "unauthorized_client",
+                "Only confidential clients can use the client_credentials grant",
+            ));
+        }
+        let scope = Self::resolve_scope(&client, request.scope.as_deref())?;
+        let mut claim = Claim::new(client.client_id.clone(), 0);
+        claim.tenant = Some(client.organization_id);
+        claim.cnf = request.dpop_jkt.map(|jkt| Confirmation { jkt });
+        self.issue_token(claim, client, scope).await
+    }
+
+    async fn device_code_grant(
+        &self,
+        client: OAuthClient,
+        request: TokenRequest,
+    ) -> Result<TokenResponse, OAuthError> {
+        let device_code_hash = request
+            .device_code
+            .as_deref()
+            .map(hash_secret)
+            .ok_or_else(|| OAuthError::new("invalid_request", "device_code is required"))?;
+        let invalid_grant = || OAuthError::new("invalid_grant", "Invalid device code");
+        let device_code = self
+            .repository
+            .poll_device_code(&device_code_hash)
+            .await
+            .map_err(|_| invalid_grant())?;
+        if device_code.oauth_client_id != client.id {
+            return Err(invalid_grant());
+        }
+        let now = Utc::now();
+        if device_code.expires_at < now {
+            return Err(OAuthError::new("expired_token", "The device code has expired"));
+        }
+        let polled_too_soon = device_code
+            .last_polled_at
+            .is_some_and(|last| now < last + Duration::seconds(device_code.interval.into()));
+        if polled_too_soon {
+            self.repository
+                .slow_down_device_code(&device_code_hash, SLOW_DOWN_INCREMENT_SECONDS)
+                .await
+                .map_err(OAuthError::from)?;
+            return Err(OAuthError::new("slow_down", "Polling too frequently"));
+        }
+
+        match device_code.status {
+            DeviceCodeStatus::Pending => Err(OAuthError::new(
+                "authorization_pending",
+                "The user has not approved the request yet",
+            )),
+            DeviceCodeStatus::Denied => {
+                Err(OAuthError::new("access_denied", "The user denied the request"))
+            }
+            DeviceCodeStatus::Approved => {
+                let device_code = self
+                    .repository
+                    .consume_device_code(&device_code_hash)
+                    .await
+                    .map_err(|_| invalid_grant())?;
+                let user_id = device_code.user_id.ok_or_else(invalid_grant)?;
+                let scope = device_code.scope;
+                self.issue_user_tokens(client, user_id, scope.clone(), scope, None, request.dpop_jkt)
+                    .await
+            }
+        }
+    }
+
+    /// Troca um refresh token por um novo par de tokens. O token usado não vale mais; usá-lo
+    /// de novo indica vazamento e revoga toda a família.
+    async fn refresh_token_grant(
+        &self,
+        client: OAuthClient,
+        request: TokenRequest,
+    ) -> Result<TokenResponse, OAuthError> {
+        let token_hash = request
+            .refresh_token
+            .as_deref()
+            .map(hash_secret)
+            .ok_or_else(|| OAuthError::new("invalid_request", "refresh_token is required"))?;
+        let invalid_grant = || OAuthError::new("invalid_grant", "Invalid refresh token");
+        let refresh_token = self
+            .token_repository
+            .get_refresh_token(&token_hash)
+            .await
+            .map_err(|_| invalid_grant())?;
+        if refresh_token.oauth_client_id != client.id
+            || refresh_token.revoked_at.is_some()
+            || refresh_token.expires_at < Utc::now()
+        {
+            return Err(invalid_grant());
+        }
+        // refresh token vinculado a uma chave DPoP só vale com prova da mesma chave
+        if refresh_token.dpop_jkt.is_some() && refresh_token.dpop_jkt != request.dpop_jkt {
+            return Err(OAuthError::new(
+                "invalid_grant",
+                "The refresh token is bound to a different DPoP key",
+            ));
+        }
+        // o escopo pedido pode reduzir o do access token, mas a família mantém o original
+        let scope = match request.scope.as_deref() {
+            Some(requested) => {
+                let mut scopes: Vec<&str> = Vec::new();
+                for scope in requested.split_whitespace() {
+                    if !refresh_token.scope.split_whitespace().any(|s| s == scope) {
+                        return Err(OAuthError::new(
+                            "invalid_scope",
+                            format!("Scope {} was not granted", scope),
+                        ));
+                    }
+                    if !scopes.contains(&scope) {
+                        scopes.push(scope);
+                    }
+                }
+                scopes.join(" ")
+            }
+            None => refresh_token.scope.clone(),
+        };
+        if self.token_repository.use_refresh_token(&token_hash).await.is_err() {
+            self.token_repository
+                .revoke_refresh_token_family(&refresh_token.family_id)
+                .await
+                .map_err(OAuthError::from)?;
+            return Err(invalid_grant());
+        }
+        self.issue_user_tokens(
+            client,
+            refresh_token.user_id,
+            refresh_token.scope,
+            scope,
+            Some(refresh_token.family_id),
+            request.dpop_jkt,
+        )
+        .await
+    }
+
+    /// Token exchange (RFC 8693): troca um access token emitido por este serviço por outro,
+    /// restrito a uma audiência e a escopos do token original, registrando o cliente (ou o
+    /// `actor_token`) em `act`.
+    async fn token_exchange_grant(
+        &self,
+        client: OAuthClient,
+        request: TokenRequest,
+    ) -> Result<TokenResponse, OAuthError> {
+        if client.client_type != ClientType::Confidential {
+            return Err(OAuthError::new(
+                "unauthorized_client",
+                "Only confidential clients can use the token exchange grant",
+            ));
+        }
+        let subject_token = request
+            .subject_token
+            .as_deref()
+            .ok_or_else(|| OAuthError::new("invalid_request", "subject_token is required"))?;
+        Self::check_token_type(request.subject_token_type.as_deref(), "subject_token_type")?;
+        if let Some(token_type) = request.requested_token_type.as_deref() {
+            if token_type != ACCESS_TOKEN_TYPE {
+                return Err(OAuthError::new(
+                    "invalid_request",
+                    format!("Token type {} cannot be issued", token_type),
+                ));
+            }
+        }
+        let subject = self
+            .token_service
+            .validate(subject_token.to_string())
+            .await
+            .map_err(|_| OAuthError::new("invalid_grant", "Invalid subject_token"))?;
+        if subject.tenant != Some(client.organization_id) {
+            return Err(OAuthError::new("invalid_grant", "Invalid subject_token"));
+        }
+        Self::check_proof_of_possession(&subject, request.dpop_jkt.as_deref(), "subject_token")?;
+
+        let audience = request
+            .audience
+            .as_deref()
+            .ok_or_else(|| OAuthError::new("invalid_request", "audience is required"))?;
+        if !client.allows_audience(audience) {
+            return Err(OAuthError::new(
+                "invalid_target",
+                format!("Audience {} is not allowed for this client", audience),
+            ));
+        }
+
+        let subject_scopes: Vec<&str> = subject
+            .scope
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
+        let scope = match request.scope.as_deref() {
+            Some(requested) => {
+                if let Some(scope) = requested.split_whitespace().find(|s| !subject_scopes.contains(s)) {
+                    return Err(OAuthError::new(
+                        "invalid_scope",
+                        format!("Scope {} was not granted to the subject_token", scope),
+                    ));
+                }
+                Self::resolve_scope(&client, Some(requested))?
+            }
+            None => {
+                let scopes: Vec<&str> = subject_scopes
+                    .iter()
+                    .copied()
+                    .filter(|s| client.allows_scope(s))
+                    .collect();
+                Self::resolve_scope(&client, Some(&scopes.join(" ")))?
+            }
+        };
+
+        let actor = match request.actor_token.as_deref() {
+            Some(actor_token) => {
+                Self::check_token_type(request.actor_token_type.as_deref(), "actor_token_type")?;
+                let actor = self
+                    .token_service
+                    .validate(actor_token.to_string())
+                    .await
+                    .map_err(|_| OAuthError::new("invalid_grant", "Invalid actor_token"))?;
+                // o ator precisa ser da mesma organização que o sujeito
+                if actor.tenant != subject.tenant {
+                    return Err(OAuthError::new("invalid_grant", "Invalid actor_token"));
+                }
+                Self::check_proof_of_possession(&actor, request.dpop_jkt.as_deref(), "actor_token")?;
+                actor.sub
+            }
+            None => client.client_id.clone(),
+        };
+
+        // o token trocado vale só pelo `scope`; papéis e permissões do sujeito não vão junto
+        let mut claim = Claim::new(subject.sub, subject.exp);
+        claim.tenant = subject.tenant;
+        // a sessão ou o personal access token do sujeito seguem valendo para o token trocado:
+        // revogá-los também o invalida
+        claim.sid = subject.sid;
+        claim.pat = subject.pat;
+        claim.aud = Some(audience.to_string());
+        claim.act = Some(Actor {
+            sub: actor,
+            act: subject.act.map(Box::new),
+        });
+        // um sujeito vinculado continua vinculado à mesma chave, já provada acima
+        claim.cnf = subject.cnf.or(request.dpop_jkt.map(|jkt| Confirmation { jkt }));
+        let mut response = self.issue_token(claim, client, scope).await?;
+        response.expires_in = response.expires_in.min(subject.exp - Utc::now().timestamp());
+        response.issued_token_type = Some(ACCESS_TOKEN_TYPE.to_string());
+        Ok(response)
+    }
+
+    /// Um token vinculado a uma chave DPoP (`cnf.jkt`) só é aceito como entrada com uma prova
+    /// dessa mesma chave no pedido; sem isso, quem o roubou o trocaria por um token ao portador.
+    fn check_proof_of_possession(token: &Claim, dpop_jkt: Option<&str>, parameter: &str) -> Result<(), OAuthError> {
+        match &token.cnf {
+            Some(cnf) if dpop_jkt != Some(cnf.jkt.as_str()) => Err(OAuthError::new(
+                "invalid_grant",
+                format!("{} is bound to a DPoP key that was not proven", parameter),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Só aceitamos como entrada access tokens emitidos por este serviço.
+    fn check_token_type(token_type: Option<&str>, parameter: &str) -> Result<(), OAuthError> {
+        match token_type {
+            Some(ACCESS_TOKEN_TYPE) | Some(JWT_TOKEN_TYPE) => Ok(()),
+            Some(token_type) => Err(OAuthError::new(
+                "invalid_request",
+                format!("Unsupported {} {}", parameter, token_type),
+            )),
+            None => Err(OAuthError::new("invalid_request", format!("{} is required", parameter))),
+        }
+    }
+
+    /// Emite um access token com `scope` e um refresh token com `granted_scope`, na família
+    /// informada ou em uma nova. Com `dpop_jkt`, os dois ficam vinculados à chave DPoP.
+    async fn issue_user_tokens(
+        &self,
+        client: OAuthClient,
+        user_id: i32,
+        granted_scope: String,
+        scope: String,
+        family_id: Option<String>,
+        dpop_jkt: Option<String>,
+    ) -> Result<TokenResponse, OAuthError> {
+        // conta desativada ou com troca de senha pendente: recusa antes de gravar o refresh token
+        let mut claim = self
+            .user_service
+            .get_claim(client.organization_id, user_id)
+            .await
+            .map_err(|e| match e.code {
+                403 => OAuthError::new("invalid_grant", e.message),
+                _ => e.into(),
+            })?;
+        let refresh_token = if client.allows_grant_type("refresh_token") {
+            let refresh_token = generate_secret(32);
+            let new_token = CreateRefreshToken {
+                token_hash: hash_secret(&refresh_token),
+                family_id: family_id.unwrap_or_else(|| generate_secret(16)),
+                oauth_client_id: client.id,
+                user_id,
+                scope: granted_scope,
+                expires_at: Utc::now() + Duration::seconds(REFRESH_TOKEN_TTL_SECONDS),
+                dpop_jkt: dpop_jkt.clone(),
+            };
+            self.token_repository
+                .create_refresh_token(&new_token)
+                .await
+                .map_err(OAuthError::from)?;
+            Some(refresh_token)
+        } else {
+            None
+        };
+
+        claim.cnf = dpop_jkt.map(|jkt| Confirmation { jkt });
+        let mut response = self.issue_token(claim, client, scope).await?;
+        response.refresh_token = refresh_token;
+        Ok(response)
+    }
+
+    async fn decide_device_code(
+        &self,
+        user_code: &str,
+        user_id: Option<i32>,
+        status: DeviceCodeStatus,
+    ) -> Result<DeviceCode, CommonError> {
+        self.repository
+            .decide_device_code(&normalize_user_code(user_code), user_id, status)
+            .await
+            .map_err(|_| CommonError {
+                message: "Invalid or expired code".to_string(),
+                code: 404,
+            })
+    }
+
+    /// Acrescenta os escopos aprovados ao consentimento do usuário para o cliente.
+    async fn grant_consent(&self, user_id: i32, oauth_client_id: i32, scope: &str) -> Result<(), CommonError> {
+        let mut scopes = self
+            .repository
+            .get_consent(user_id, oauth_client_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .map(|consent| consent.scopes)
+            .unwrap_or_default();
+        for scope in scope.split_whitespace() {
+            if !scopes.iter().any(|s| s == scope) {
+                scopes.push(scope.to_string());
+            }
+        }
+        self.repository
+            .save_consent(user_id, oauth_client_id, &scopes)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(())
+    }
+
+    /// Revoga a família do refresh token, se ele for do cliente. Retorna se o token foi reconhecido.
+    async fn revoke_refresh_token(&self, client: &OAuthClient, token: &str) -> Result<bool, OAuthError> {
+        let refresh_token = match self.token_repository.get_refresh_token(&hash_secret(token)).await {
+            Ok(refresh_token) if refresh_token.oauth_client_id == client.id => refresh_token,
+            _ => return Ok(false),
+        };
+        self.token_repository
+            .revoke_refresh_token_family(&refresh_token.family_id)
+            .await
+            .map_err(OAuthError::from)?;
+        Ok(true)
+    }
+
+    /// Revoga o `jti` do access token até o `exp`, se o token for do cliente. Retorna se o token foi reconhecido.
+    async fn revoke_access_token(&self, client: &OAuthClient, token: &str) -> Result<bool, OAuthError> {
+        let claim = match self.token_service.validate(token.to_string()).await {
+            Ok(claim) if claim.client_id.as_deref() == Some(client.client_id.as_str()) => claim,
+            _ => return Ok(false),
+        };
+        let Some(jti) = claim.jti else {
+            return Ok(false);
+        };
+        let expires_at = DateTime::from_timestamp(claim.exp, 0).unwrap_or_else(Utc::now);
+        self.token_repository
+            .revoke_jti(&jti, expires_at)
+            .await
+            .map_err(OAuthError::from)?;
+        Ok(true)
+    }
+
+    /// Tokens de clientes OAuth valem pelo `scope`: papéis e permissões do usuário ficam de fora,
+    /// senão qualquer cliente autorizado por um admin acessaria `/admin`.
+    async fn issue_token(
+        &self,
+        mut claim: Claim,
+        client: OAuthClient,
+        scope: String,
+    ) -> Result<TokenResponse, OAuthError> {
+        let scope = Some(scope).filter(|scope| !scope.is_empty());
+        claim.roles.clear();
+        claim.permissions.clear();
+        claim.scope = scope.clone();
+        claim.client_id = Some(client.client_id);
+        let token_type = if claim.cnf.is_some() { "DPoP" } else { "Bearer" };
+        let access_token = self.token_service.create_with_claim(claim).await?;
+        Ok(TokenResponse {
+            access_token,
+            token_type: token_type.to_string(),
+            expires_in: ACCESS_TOKEN_TTL_SECONDS,
+            scope,
+            refresh_token: None,
+            id_token: None,
+            issued_token_type: None,
+        })
+    }
+}
+
+#[async_trait]
+impl OAuthService for OAuthServiceImpl {
+    async fn register_client(
+        &self,
+        organization_id: i32,
+        client: CreateOAuthClient,
+    ) -> Result<RegisteredOAuthClient, CommonError> {
+        if client.client_type == ClientType::Public && client.redirect_uris.is_empty() {
+            return Err(CommonError {
+                message: "Public clients need at least one redirect URI".to_string(),
+                code: 400,
+            });
+        }
+        for uri in &client.redirect_uris {
+            validate_redirect_uri(uri).map_err(|message| CommonError { message, code: 400 })?;
+        }
+        if let Some(grant_type) = client
+            .grant_types
+            .iter()
+            .find(|grant_type| !client.client_type.supports_grant_type(grant_type))
+        {
+            return Err(CommonError {
+                message: format!("Grant type {} is not supported for this client type", grant_type),
+                code: 400,
+            });
+        }
+
+        let client_id = generate_secret(16);
+        let client_secret = match client.client_type {
+            ClientType::Confidential => Some(generate_secret(32)),
+            ClientType::Public => None,
+        };
+        let client_secret_hash = client_secret.as_deref().map(hash_secret);
+        let client = self
+            .repository
+            .create_client(organization_id, &client_id, client_secret_hash.as_deref(), None, &client)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(RegisteredOAuthClient {
+            client,
+            client_secret,
+        })
+    }
+    async fn list_clients(&self, organization_id: i32) -> Result<Vec<OAuthClient>, CommonError> {
+        self.repository
+            .list_clients(organization_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn delete_client(&self, organization_id: i32, id: i32) -> Result<(), CommonError> {
+        self.repository
+            .delete_client(organization_id, id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn rotate_client_secret(&self, organization_id: i32, id: i32) -> Result<RegisteredOAuthClient, CommonError> {
+        let client_secret = generate_secret(32);
+        let client = self
+            .repository
+            .rotate_client_secret(organization_id, id, &hash_secret(&client_secret))
+            .await
+            .map_err(|_| CommonError {
+                message: format!("Confidential client {} not found", id),
+                code: 404,
+            })?;
+        Ok(RegisteredOAuthClient {
+            client,
+            client_secret: Some(client_secret),
+        })
+    }
+    async fn revoke_previous_client_secret(&self, organization_id: i32, id: i32) -> Result<(), CommonError> {
+        self.repository
+            .revoke_previous_client_secret(organization_id, id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn get_client(&self, client_id: &str) -> Result<OAuthClient, OAuthError> {
+        self.repository
+            .get_client(client_id)
+            .await
+            .map_err(|_| OAuthError::new("invalid_client", "Unknown client"))
+    }
+    async fn push_authorization_request(
+        &self,
+        request: PushedAuthorizationRequest,
+    ) -> Result<PushedAuthorization, OAuthError> {
+        let client = self
+            .authenticate_client(request.client_id.as_deref(), request.client_secret.as_deref())
+            .await?;
+        let request = request.request;
+        if request.client_id != client.client_id {
+            return Err(OAuthError::new(
+                "invalid_request",
+                "client_id does not match the authenticated client",
+            ));
+        }
+        if request.request_uri.is_some() {
+            return Err(OAuthError::new("invalid_request", "request_uri cannot be pushed"));
+        }
+        if client.redirect_uri_for(request.redirect_uri.as_deref()).is_none() {
+            return Err(OAuthError::new("invalid_request", "Invalid redirect_uri"));
+        }
+        self.validate_authorization_request(&client, &request).await?;
+
+        let request_uri = format!("{}{}", REQUEST_URI_PREFIX, generate_secret(32));
+        let expires_at = Utc::now() + Duration::seconds(PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS);
+        self.repository
+            .create_pushed_authorization_request(&hash_secret(&request_uri), client.id, &request, expires_at)
+            .await
+            .map_err(OAuthError::from)?;
+        Ok(PushedAuthorization {
+            request_uri,
+            expires_in: PUSHED_AUTHORIZATION_REQUEST_TTL_SECONDS,
+        })
+    }
+    async fn resolve_authorization_request(
+        &self,
+        client: &OAuthClient,
+        request: AuthorizationRequest,
+    ) -> Result<AuthorizationRequest, OAuthError> {
+        let Some(request_uri) = request.request_uri else {
+            if client.require_pushed_authorization_requests {
+                return Err(OAuthError::new(
+                    "invalid_request",
+                    "This client must use pushed authorization requests",
+                ));
+            }
+            return Ok(request);
+        };
+        let mut pushed = self
+            .repository
+            .get_pushed_authorization_request(&hash_secret(&request_uri), client.id)
+            .await
+            .map_err(|_| OAuthError::new("invalid_request_uri", "Invalid or expired request_uri"))?;
+        pushed.request_uri = Some(request_uri);
+        Ok(pushed)
+    }
+    async fn validate_authorization_request(
+        &self,
+        client: &OAuthClient,
+        request: &AuthorizationRequest,
+    ) -> Result<String, OAuthError> {
+        if request.response_type != "code" {
+            return Err(OAuthError::new(
+                "unsupported_response_type",
+                "Only the code response type is supported",
+            ));
+        }
+        if !client.allows_grant_type("authorization_code") {
+            return Err(OAuthError::new(
+                "unauthorized_client",
+                "The client is not registered for the authorization code grant",
+            ));
+        }
+
+        let scope = Self::resolve_scope(client, request.scope.as_deref())?;
+
+        match (&request.code_challenge, request.code_challenge_method.as_deref()) {
+            (Some(challenge), Some(PKCE_METHOD)) if challenge.len() == 43 => {}
+            (Some(_), Some(PKCE_METHOD)) => {
+                return Err(OAuthError::new("invalid_request", "Malformed code_challenge"))
+            }
+            (Some(_), _) => {
+                return Err(OAuthError::new(
+                    "invalid_request",
+                    "Only the S256 code_challenge_method is supported",
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(OAuthError::new("invalid_request", "code_challenge is required"))
+            }
+            (None, None) if client.client_type == ClientType::Public => {
+                return Err(OAuthError::new(
+                    "invalid_request",
+                    "PKCE with S256 is required for public clients",
+                ))
+            }
+            (None, None) => {}
+        }
+
+        Ok(scope)
+    }
+    async fn create_authorization_code(
+        &self,
+        client: &OAuthClient,
+        request: &AuthorizationRequest,
+        user_id: i32,
+    ) -> Result<IssuedAuthorizationCode, OAuthError> {
+        let scope = self.validate_authorization_request(client, request).await?;
+        if let Some(request_uri) = &request.request_uri {
+            let deleted = self
+                .repository
+                .delete_pushed_authorization_request(&hash_secret(request_uri), client.id)
+                .await
+                .map_err(OAuthError::from)?;
+            if deleted == 0 {
+                return Err(OAuthError::new("invalid_request_uri", "Invalid or expired request_uri"));
+            }
+        }
+        let consent = self
+            .repository
+            .get_consent(user_id, client.id)
+            .await
+            .map_err(OAuthError::from)?;
+        let consent_required = !consent.is_some_and(|consent| consent.covers(&scope));
+        let ttl = if consent_required {
+            PENDING_CONSENT_TTL_SECONDS
+        } else {
+            AUTHORIZATION_CODE_TTL_SECONDS
+        };
+        let redirect_uri = client
+            .redirect_uri_for(request.redirect_uri.as_deref())
+            .ok_or_else(|| OAuthError::new("invalid_request", "Invalid redirect_uri"))?;
+
+        let code = generate_secret(32);
+        let new_code = CreateAuthorizationCode {
+            code_hash: hash_secret(&code),
+            oauth_client_id: client.id,
+            user_id,
+            redirect_uri,
+            scope,
+            code_challenge: request.code_challenge.clone(),
+            code_challenge_method: request.code_challenge_method.clone(),
+            expires_at: Utc::now() + Duration::seconds(ttl),
+            nonce: request.nonce.clone(),
+            auth_time: Utc::now(),
+            consent_pending: consent_required,
+        };
+        self.repository
+            .create_authorization_code(&new_code)
+            .await
+            .map_err(OAuthError::from)?;
+        Ok(IssuedAuthorizationCode { code, consent_required })
+    }
+    async fn decide_consent(&self, client: &OAuthClient, code: &str, allow: bool) -> Result<(), OAuthError> {
+        let code_hash = hash_secret(code);
+        if !allow {
+            self.repository
+                .delete_pending_authorization_code(&code_hash, client.id)
+                .await
+                .map_err(OAuthError::from)?;
+            return Err(OAuthError::new("access_denied", "The user denied the request"));
+        }
+        let expires_at = Utc::now() + Duration::seconds(AUTHORIZATION_CODE_TTL_SECONDS);
+        let authorization_code = self
+            .repository
+            .approve_authorization_code(&code_hash, client.id, expires_at)
+            .await
+            .map_err(|_| OAuthError::new("invalid_request", "The consent request is invalid or has expired"))?;
+        self.grant_consent(authorization_code.user_id, client.id, &authorization_code.scope)
+            .await
+            .map_err(OAuthError::from)
+    }
+    async fn list_consents(&self, user_id: i32) -> Result<Vec<(Consent, OAuthClient)>, CommonError> {
+        self.repository
+            .list_consents(user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn revoke_consent(&self, user_id: i32, client_id: &str) -> Result<(), CommonError> {
+        let not_found = || CommonError {
+            message: format!("No consent found for client {}", client_id),
+            code: 404,
+        };
+        let client = self
+            .repository
+            .get_client(client_id)
+            .await
+            .map_err(|_| not_found())?;
+        let deleted = self
+            .repository
+            .delete_consent(user_id, client.id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if deleted == 0 {
+            return Err(not_found());
+        }
+        self.token_repository
+            .revoke_user_refresh_tokens(user_id, client.id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn create_device_authorization(
+        &self,
+        request: DeviceAuthorizationRequest,
+    ) -> Result<DeviceAuthorization, OAuthError> {
+        let client = self
+            .authenticate_client(request.client_id.as_deref(), request.client_secret.as_deref())
+            .await?;
+        if !client.allows_grant_type(DEVICE_CODE_GRANT_TYPE) {
+            return Err(OAuthError::new(
+                "unauthorized_client",
+                "The client is not registered for the device code grant",
+            ));
+        }
+        let scope = Self::resolve_scope(&client, request.scope.as_deref())?;
+
+        let device_code = generate_secret(32);
+        let mut new_code = CreateDeviceCode {
+            device_code_hash: hash_secret(&device_code),
+            user_code: generate_user_code(),
+            oauth_client_id: client.id,
+            scope,
+            interval: DEVICE_CODE_INTERVAL_SECONDS,
+            expires_at: Utc::now() + Duration::seconds(DEVICE_CODE_TTL_SECONDS),
+        };
+        // user_code é único; uma colisão só pede outro código
+        let mut attempts = 0;
+        while let Err(e) = self.repository.create_device_code(&new_code).await {
+            attempts += 1;
+            if attempts == 3 {
+                return Err(e.into());
+            }
+            new_code.user_code = generate_user_code();
+        }
+        Ok(DeviceAuthorization {
+            device_code,
+            user_code: format_user_code(&new_code.user_code),
+            expires_in: DEVICE_CODE_TTL_SECONDS,
+            interval: DEVICE_CODE_INTERVAL_SECONDS.into(),
+        })
+    }
+    async fn get_pending_device_code(
+        &self,
+        user_code: &str,
+        remote_addr: &str,
+    ) -> Result<(DeviceCode, OAuthClient), CommonError> {
+        let subject = format!("user_code:{}", remote_addr);
+        let failures = self
+            .repository
+            .count_failed_attempts(&subject)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if failures >= USER_CODE_MAX_FAILED_ATTEMPTS {
+            return Err(CommonError {
+                message: "Too many invalid codes, try again later".to_string(),
+                code: 429,
+            });
+        }
+        match self
+            .repository
+            .get_pending_device_code(&normalize_user_code(user_code))
+            .await
+        {
+            Ok(pending) => Ok(pending),
+            Err(_) => {
+                let expires_at = Utc::now() + Duration::seconds(USER_CODE_FAILED_ATTEMPTS_TTL_SECONDS);
+                self.repository
+                    .record_failed_attempt(&subject, expires_at)
+                    .await
+                    .map_err(|e| -> CommonError { e.into() })?;
+                Err(CommonError {
+                    message: "Invalid or expired code".to_string(),
+                    code: 404,
+                })
+            }
+        }
+    }
+    async fn approve_device_code(&self, user_code: &str, user_id: i32) -> Result<(), CommonError> {
+        let device_code = self
+            .decide_device_code(user_code, Some(user_id), DeviceCodeStatus::Approved)
+            .await?;
+        self.grant_consent(user_id, device_code.oauth_client_id, &device_code.scope)
+            .await
+    }
+    async fn deny_device_code(&self, user_code: &str) -> Result<(), CommonError> {
+        self.decide_device_code(user_code, None, DeviceCodeStatus::Denied)
+            .await?;
+        Ok(())
+    }
+    async fn purge_expired(&self) -> Result<usize, CommonError> {
+        let codes = self
+            .repository
+            .delete_expired_codes()
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let tokens = self
+            .token_repository
+            .delete_expired()
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(codes + tokens)
+    }
+    async fn revoke_token(&self, request: RevocationRequest) -> Result<(), OAuthError> {
+        let client = self
+            .authenticate_client(request.client_id.as_deref(), request.client_secret.as_deref())
+            .await?;
+        // RFC 7009, seção 2.2: tokens inválidos, expirados ou de outro cliente não são erro
+        let revoked = match request.token_type_hint.as_deref() {
+            Some("access_token") => {
+                self.revoke_access_token(&client, &request.token).await?
+                    || self.revoke_refresh_token(&client, &request.token).await?
+            }
+            _ => {
+                self.revoke_refresh_token(&client, &request.token).await?
+                    || self.revoke_access_token(&client, &request.token).await?
+            }
+        };
+        if !revoked {
+            log::debug!("Ignoring revocation of an unknown token for client {}", client.client_id);
+        }
+        Ok(())
+    }
+    async fn exchange_token(&self, request: TokenRequest) -> Result<TokenResponse, OAuthError> {
+        let client = self
+            .authenticate_client(request.client_id.as_deref(), request.client_secret.as_deref())
+            .await?;
+        if GRANT_TYPES.contains(&request.grant_type.as_str()) && !client.allows_grant_type(&request.grant_type)
+        {
+            return Err(OAuthError::new(
+                "unauthorized_client",
+                format!("The client is not registered for grant type {}", request.grant_type),
+            ));
+        }
+        match request.grant_type.as_str() {
+            "authorization_code" => self.authorization_code_grant(client, request).await,
+            "client_credentials" => self.client_credentials_grant(client, request).await,
+            "refresh_token" => self.refresh_token_grant(client, request).await,
+            DEVICE_CODE_GRANT_TYPE => self.device_code_grant(client, request).await,
+            TOKEN_EXCHANGE_GRANT_TYPE => self.token_exchange_grant(client, request).await,
+            grant_type => Err(OAuthError::new(
+                "unsupported_grant_type",
+                format!("Grant type {} is not supported", grant_type),
+            )),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::env;
+use std::fs;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use ring::rsa::{KeyPair, PublicKeyComponents};
+
+use crate::domain::constants::{ACCESS_TOKEN_TTL_SECONDS, OIDC_ISSUER, OIDC_SIGNING_KEY};
+use crate::domain::error::CommonError;
+use crate::domain::models::dpop::DPOP_SIGNING_ALGORITHMS;
+use crate::domain::models::oauth::{AuthorizationCode, OAuthClient, DEVICE_CODE_GRANT_TYPE, TOKEN_EXCHANGE_GRANT_TYPE};
+use crate::domain::models::oidc::{
+    IdTokenClaims, ProviderMetadata, StandardClaims, UserInfo, EMAIL_SCOPE, OPENID_SCOPE,
+    PROFILE_SCOPE,
+};
+use crate::domain::models::token::Claim;
+use crate::domain::services::oidc::OidcService;
+use crate::domain::services::user::UserService;
+use crate::services::secret::hash_secret;
+
+/// Chave RSA usada para assinar os ID tokens (e, se configurada, os access tokens) e a JWK
+/// pública correspondente, publicada em `/.well-known/jwks.json`.
+#[derive(Clone)]
+pub struct SigningKey {
+    pub encoding_key: EncodingKey,
+    pub jwk: Jwk,
+}
+
+impl SigningKey {
+    /// Aceita uma chave RSA privada em PEM, PKCS#8 (`PRIVATE KEY`) ou PKCS#1 (`RSA PRIVATE KEY`).
+    pub fn from_pem(pem: &[u8]) -> Result<Self, CommonError> {
+        let invalid_key = |e: String| CommonError {
+            message: format!("Invalid OIDC signing key: {}", e),
+            code: 500,
+        };
+        let parsed = pem::parse(pem).map_err(|e| invalid_key(e.to_string()))?;
+        let key_pair = match parsed.tag() {
+            "PRIVATE KEY" => KeyPair::from_pkcs8(parsed.contents()),
+            "RSA PRIVATE KEY" => KeyPair::from_der(parsed.contents()),
+            tag => return Err(invalid_key(format!("unsupported PEM block {}", tag))),
+        }
+        .map_err(|e| invalid_key(e.to_string()))?;
+        let encoding_key = EncodingKey::from_rsa_pem(pem)?;
+
+        let public = PublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+        let n = URL_SAFE_NO_PAD.encode(public.n);
+        let e = URL_SAFE_NO_PAD.encode(public.e);
+        // kid é o thumbprint da chave (RFC 7638)
+        let kid = hash_secret(&format!(r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#, e, n));
+        let jwk = serde_json::from_value(serde_json::json!({
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": kid,
+            "n": n,
+            "e": e,
+        }))
+        .map_err(|e| invalid_key(e.to_string()))?;
+        Ok(SigningKey { encoding_key, jwk })
+    }
+
+    /// Lê a chave do caminho em `OIDC_SIGNING_KEY`, se a variável estiver definida.
+    pub fn from_env() -> Option<Self> {
+        env::var(OIDC_SIGNING_KEY).ok().map(|path| {
+            let pem = fs::read(&path).expect("OIDC_SIGNING_KEY must point to a readable PEM file");
+            SigningKey::from_pem(&pem).expect("OIDC_SIGNING_KEY must be an RSA private key")
+        })
+    }
+
+    /// Header RS256 com o `kid` da JWK publicada.
+    pub fn header(&self) -> Header {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = self.jwk.common.key_id.clone();
+        header
+    }
+}
+
+#[derive(Clone)]
+pub struct OidcServiceImpl {
+    pub user_service: Arc<dyn UserService>,
+    pub issuer: Option<String>,
+    pub signing_key: Option<SigningKey>,
+}
+
+impl OidcServiceImpl {
+    /// Lê `OIDC_ISSUER` quando há chave de assinatura (veja `SigningKey::from_env`). Sem a
+    /// chave, o OpenID Connect fica desabilitado.
+    pub fn new(user_service: Arc<dyn UserService>, signing_key: Option<SigningKey>) -> Self {
+        let issuer = signing_key.as_ref().map(|_| {
+            let issuer = env::var(OIDC_ISSUER).expect("OIDC_ISSUER must be set");
+            issuer.trim_end_matches('/').to_string()
+        });
+        OidcServiceImpl {
+            user_service,
+            issuer,
+            signing_key,
+        }
+    }
+
+    pub fn with_signing_key(
+        user_service: Arc<dyn UserService>,
+        issuer: impl Into<String>,
+        signing_key: SigningKey,
+    ) -> Self {
+        OidcServiceImpl {
+            user_service,
+            issuer: Some(issuer.into().trim_end_matches('/').to_string()),
+            signing_key: Some(signing_key),
+        }
+    }
+
+    fn config(&self) -> Result<(&str, &SigningKey), CommonError> {
+        match (&self.issuer, &self.signing_key) {
+            (Some(issuer), Some(signing_key)) => Ok((issuer, signing_key)),
+            _ => Err(CommonError {
+                message: "OpenID Connect is not configured".to_string(),
+                code: 404,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl OidcService for OidcServiceImpl {
+    async fn provider_metadata(&self) -> Result<ProviderMetadata, CommonError> {
+        let (issuer, _) = self.config()?;
+        Ok(ProviderMetadata {
+            issuer: issuer.to_string(),
+            authorization_endpoint: format!("{}/oauth/authorize", issuer),
+            token_endpoint: format!("{}/oauth/token", issuer),
+            device_authorization_endpoint: format!("{}/oauth/device_authorization", issuer),
+            pushed_authorization_request_endpoint: format!("{}/oauth/par", issuer),
+            revocation_endpoint: format!("{}/oauth/revoke", issuer),
+            registration_endpoint: format!("{}/oauth/register", issuer),
+            userinfo_endpoint: format!("{}/userinfo", issuer),
+            jwks_uri: format!("{}/.well-known/jwks.json", issuer),
+            scopes_supported: vec![OPENID_SCOPE, PROFILE_SCOPE, EMAIL_SCOPE],
+            response_types_supported: vec!["code"],
+            grant_types_supported: vec![
+                "authorization_code",
+                "client_credentials",
+                "refresh_token",
+                DEVICE_CODE_GRANT_TYPE,
+                TOKEN_EXCHANGE_GRANT_TYPE,
+            ],
+            subject_types_supported: vec!["public"],
+            id_token_signing_alg_values_supported: vec!["RS256"],
+            token_endpoint_auth_methods_supported: vec![
+                "client_secret_basic",
+                "client_secret_post",
+                "none",
+            ],
+            revocation_endpoint_auth_methods_supported: vec![
+                "client_secret_basic",
+                "client_secret_post",
+                "none",
+            ],
+            code_challenge_methods_supported: vec!["S256"],
+            dpop_signing_alg_values_supported: DPOP_SIGNING_ALGORITHMS.to_vec(),
+            claims_supported: vec![
+                "iss",
+                "sub",
+                "aud",
+                "exp",
+                "iat",
+                "auth_time",
+                "nonce",
+                "preferred_username",
+                "email",
+                "email_verified",
+            ],
+        })
+    }
+    async fn jwks(&self) -> Result<JwkSet, CommonError> {
+        let (_, signing_key) = self.config()?;
+        Ok(JwkSet {
+            keys: vec![signing_key.jwk.clone()],
+        })
+    }
+    async fn create_id_token(&self, client: &OAuthClient, code: &AuthorizationCode) -> Result<String, CommonError> {
+        let (issuer, signing_key) = self.config()?;
+        let user = self
+            .user_service
+            .get_user(client.organization_id, code.user_id)
+            .await?;
+        let scopes: Vec<&str> = code.scope.split_whitespace().collect();
+        let now = Utc::now().timestamp();
+        let claims = IdTokenClaims {
+            iss: issuer.to_string(),
+            sub: user.id.to_string(),
+            aud: client.client_id.clone(),
+            exp: now + ACCESS_TOKEN_TTL_SECONDS,
+            iat: now,
+            auth_time: code.auth_time.timestamp(),
+            nonce: code.nonce.clone(),
+            claims: StandardClaims::for_scopes(&user, &scopes),
+        };
+        Ok(encode(&signing_key.header(), &claims, &signing_key.encoding_key)?)
+    }
+    async fn userinfo(&self, claim: &Claim) -> Result<UserInfo, CommonError> {
+        if !claim.has_scope(OPENID_SCOPE) {
+            return Err(CommonError {
+                message: "Token does not have the openid scope".to_string(),
+                code: 403,
+            });
+        }
+        let invalid_subject = || CommonError {
+            message: "Token does not belong to a user".to_string(),
+            code: 401,
+        };
+        let user_id = claim.sub.parse().map_err(|_| invalid_subject())?;
+        let organization_id = claim.tenant.ok_or_else(invalid_subject)?;
+        let user = self
+            .user_service
+            .get_user(organization_id, user_id)
+            .await
+            .map_err(|_| invalid_subject())?;
+        Ok(UserInfo {
+            sub: user.id.to_string(),
+            claims: StandardClaims::for_scopes(&user, &claim.scopes()),
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use std::env;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::{info, warn};
+
+use crate::domain::constants::{
+    ADMIN_EMAIL, ADMIN_PASSWORD, ADMIN_USERNAME, DEFAULT_ADMIN_PERMISSIONS, DEFAULT_ORGANIZATION,
+    ORGANIZATION_ADMIN_PERMISSIONS,
+};
+use crate::domain::error::CommonError;
+use crate::domain::models::organization::{CreateOrganization, Organization};
+use crate::domain::models::user::CreateUser;
+use crate::domain::repositories::organization::OrganizationRepository;
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::authorization::AuthorizationService;
+use crate::domain::services::organization::OrganizationService;
+use crate::domain::services::user::UserService;
+
+#[derive(Clone)]
+pub struct OrganizationServiceImpl {
+    pub repository: Arc<dyn OrganizationRepository>,
+    pub user_repository: Arc<dyn UserRepository>,
+    pub user_service: Arc<dyn UserService>,
+    pub authorization_service: Arc<dyn AuthorizationService>,
+}
+
+impl OrganizationServiceImpl {
+    pub fn new(
+        repository: Arc<dyn OrganizationRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        user_service: Arc<dyn UserService>,
+        authorization_service: Arc<dyn AuthorizationService>,
+    ) -> Self {
+        OrganizationServiceImpl {
+            repository,
+            user_repository,
+            user_service,
+            authorization_service,
+        }
+    }
+}
+
+#[async_trait]
+impl OrganizationService for OrganizationServiceImpl {
+    async fn create_organization(
+        &self,
+        organization: CreateOrganization,
+        owner: CreateUser,
+    ) -> Result<Organization, CommonError> {
+        let organization = self
+            .repository
+            .create(&organization)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let admin_role = self
+            .authorization_service
+            .ensure_admin_role(organization.id, &DEFAULT_ADMIN_PERMISSIONS)
+            .await?;
+        let owner = self
+            .user_service
+            .create(CreateUser {
+                organization: Some(organization.slug.clone()),
+                ..owner
+            })
+            .await?;
+        self.authorization_service
+            .assign_role(organization.id, owner.id, admin_role.id)
+            .await?;
+        Ok(organization)
+    }
+    async fn list_organizations(&self) -> Result<Vec<Organization>, CommonError> {
+        self.repository
+            .list()
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn seed_defaults(&self) -> Result<(), CommonError> {
+        let organization = match self.repository.get_by_slug(DEFAULT_ORGANIZATION).await {
+            Ok(organization) => organization,
+            Err(_) => {
+                info!("Default organization does not exist, creating the default organization...");
+                self.repository
+                    .create(&CreateOrganization {
+                        slug: DEFAULT_ORGANIZATION.to_string(),
+                        name: "Default".to_string(),
+                    })
+                    .await
+                    .map_err(|e| -> CommonError { e.into() })?
+            }
+        };
+        let permissions: Vec<&str> = DEFAULT_ADMIN_PERMISSIONS
+            .into_iter()
+            .chain(ORGANIZATION_ADMIN_PERMISSIONS)
+            .collect();
+        let admin_role = self
+            .authorization_service
+            .ensure_admin_role(organization.id, &permissions)
+            .await?;
+
+        let Ok(admin_username) = env::var(ADMIN_USERNAME) else {
+            return Ok(());
+        };
+        // Só o usuário criado aqui recebe o papel: uma conta que alguém registrou com o mesmo
+        // nome em /auth/register nunca é promovida.
+        if self
+            .user_repository
+            .get_by_username(organization.id, &admin_username)
+            .await
+            .is_ok()
+        {
+            info!("Admin user {} already exists, skipping bootstrap", admin_username);
+            return Ok(());
+        }
+        let Ok(admin_password) = env::var(ADMIN_PASSWORD) else {
+            warn!("{} is not set, admin user {} was not created", ADMIN_PASSWORD, admin_username);
+            return Ok(());
+        };
+        let admin = self
+            .user_service
+            .create(CreateUser {
+                email: env::var(ADMIN_EMAIL).unwrap_or_else(|_| format!("{}@localhost", admin_username)),
+                username: admin_username,
+                password: admin_password,
+                organization: Some(organization.slug.clone()),
+            })
+            .await?;
+        self.authorization_service
+            .assign_role(organization.id, admin.id, admin_role.id)
+            .await?;
+        info!("Created admin user {}", admin.username);
+        Ok(())
+    }
+}
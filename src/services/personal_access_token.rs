@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::domain::constants::{ACCESS_TOKEN_TTL_SECONDS, PERSONAL_ACCESS_TOKEN_PREFIX};
+use crate::domain::error::CommonError;
+use crate::domain::models::personal_access_token::{
+    CreatePersonalAccessToken, IssuedPersonalAccessToken, PersonalAccessToken,
+};
+use crate::domain::models::token::Claim;
+use crate::domain::models::user::UserStatus;
+use crate::domain::repositories::personal_access_token::PersonalAccessTokenRepository;
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::authorization::AuthorizationService;
+use crate::domain::services::personal_access_token::PersonalAccessTokenService;
+use crate::domain::services::token::TokenService;
+use crate::services::secret::{generate_secret, hash_secret};
+
+/// Caracteres do segredo que ficam visíveis em `token_hint`, além do prefixo.
+const TOKEN_HINT_LENGTH: usize = 4;
+
+#[derive(Clone)]
+pub struct PersonalAccessTokenServiceImpl {
+    pub repository: Arc<dyn PersonalAccessTokenRepository>,
+    pub user_repository: Arc<dyn UserRepository>,
+    pub authorization_service: Arc<dyn AuthorizationService>,
+}
+
+impl PersonalAccessTokenServiceImpl {
+    pub fn new(
+        repository: Arc<dyn PersonalAccessTokenRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        authorization_service: Arc<dyn AuthorizationService>,
+    ) -> Self {
+        PersonalAccessTokenServiceImpl {
+            repository,
+            user_repository,
+            authorization_service,
+        }
+    }
+
+    async fn user_permissions(&self, organization_id: i32, user_id: i32) -> Result<Vec<String>, CommonError> {
+        Ok(self
+            .authorization_service
+            .get_user_permissions(organization_id, user_id)
+            .await?
+            .into_iter()
+            .map(|permission| permission.name)
+            .collect())
+    }
+}
+
+fn invalid_token() -> CommonError {
+    CommonError {
+        message: "Invalid personal access token".to_string(),
+        code: 401,
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenService for PersonalAccessTokenServiceImpl {
+    async fn create(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        mut new_token: CreatePersonalAccessToken,
+    ) -> Result<IssuedPersonalAccessToken, CommonError> {
+        let invalid = |message: &str| CommonError {
+            message: message.to_string(),
+            code: 400,
+        };
+        new_token.name = new_token.name.trim().to_string();
+        if new_token.name.is_empty() {
+            return Err(invalid("Token name is required"));
+        }
+        let mut scopes: Vec<&str> = new_token.scope.split_whitespace().collect();
+        scopes.sort_unstable();
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err(invalid("Token scope is required"));
+        }
+        let permissions = self.user_permissions(organization_id, user_id).await?;
+        if let Some(scope) = scopes.iter().find(|scope| !permissions.iter().any(|p| p == *scope)) {
+            return Err(invalid(&format!("User does not have the permission {}", scope)));
+        }
+        if new_token.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(invalid("expires_at must be in the future"));
+        }
+        new_token.scope = scopes.join(" ");
+
+        let secret = generate_secret(32);
+        let token = format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, secret);
+        let token_hint = format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, &secret[..TOKEN_HINT_LENGTH]);
+        let personal_access_token = self
+            .repository
+            .create(organization_id, user_id, &hash_secret(&token), &token_hint, &new_token)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(IssuedPersonalAccessToken {
+            token,
+            personal_access_token,
+        })
+    }
+    async fn list(&self, user_id: i32) -> Result<Vec<PersonalAccessToken>, CommonError> {
+        self.repository
+            .list(user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn revoke(&self, user_id: i32, id: i32) -> Result<(), CommonError> {
+        let deleted = self
+            .repository
+            .delete(user_id, id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if deleted == 0 {
+            return Err(CommonError {
+                message: "Personal access token not found".to_string(),
+                code: 404,
+            });
+        }
+        Ok(())
+    }
+    async fn validate(&self, token: String) -> Result<Claim, CommonError> {
+        let personal_access_token = self
+            .repository
+            .get_by_hash(&hash_secret(&token))
+            .await
+            .map_err(|_| invalid_token())?;
+        if personal_access_token.is_expired() {
+            return Err(CommonError {
+                message: "Token has expired".to_string(),
+                code: 401,
+            });
+        }
+        self.repository
+            .touch(personal_access_token.id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let user = self
+            .user_repository
+            .get_by_id(personal_access_token.organization_id, personal_access_token.user_id)
+            .await
+            .map_err(|_| invalid_token())?;
+        if user.status() == UserStatus::Disabled || user.password_reset_required {
+            return Err(invalid_token());
+        }
+
+        // As permissões são recalculadas a cada uso: o token perde as que o usuário perder.
+        let permissions = self
+            .user_permissions(personal_access_token.organization_id, personal_access_token.user_id)
+            .await?;
+        let mut claim = Claim::new(user.id.to_string(), 0);
+        claim.tenant = Some(user.organization_id);
+        claim.principal_type = Some(user.principal_type);
+        claim.permissions = personal_access_token
+            .scope
+            .split_whitespace()
+            .filter(|scope| permissions.iter().any(|p| p == scope))
+            .map(String::from)
+            .collect();
+        claim.scope = Some(personal_access_token.scope);
+        // quem guarda o resultado da validação não deve guardá-lo por mais que um access token
+        let expiration = (Utc::now() + Duration::seconds(ACCESS_TOKEN_TTL_SECONDS)).timestamp();
+        claim.exp = personal_access_token
+            .expires_at
+            .map_or(expiration, |expires_at| expires_at.timestamp().min(expiration));
+        claim.pat = Some(personal_access_token.id);
+        Ok(claim)
+    }
+    async fn check_active(&self, id: i32) -> Result<(), CommonError> {
+        match self.repository.get(id).await {
+            Ok(personal_access_token) if !personal_access_token.is_expired() => Ok(()),
+            _ => Err(invalid_token()),
+        }
+    }
+}
+
+/// Envolve outro `TokenService` e valida os tokens com `PERSONAL_ACCESS_TOKEN_PREFIX` pelo
+/// `PersonalAccessTokenService`, para que `/auth/validate` e `authenticate` os aceitem.
+#[derive(Clone)]
+pub struct PersonalAccessTokenAwareTokenService {
+    pub inner: Arc<dyn TokenService>,
+    pub personal_access_token_service: Arc<dyn PersonalAccessTokenService>,
+}
+
+impl PersonalAccessTokenAwareTokenService {
+    pub fn new(
+        inner: Arc<dyn TokenService>,
+        personal_access_token_service: Arc<dyn PersonalAccessTokenService>,
+    ) -> Self {
+        PersonalAccessTokenAwareTokenService {
+            inner,
+            personal_access_token_service,
+        }
+    }
+}
+
+#[async_trait]
+impl TokenService for PersonalAccessTokenAwareTokenService {
+    async fn create(&self, user_id: i32) -> Result<String, CommonError> {
+        self.inner.create(user_id).await
+    }
+    async fn create_with_claim(&self, claim: Claim) -> Result<String, CommonError> {
+        self.inner.create_with_claim(claim).await
+    }
+    async fn validate(&self, token: String) -> Result<Claim, CommonError> {
+        if token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX) {
+            return self.personal_access_token_service.validate(token).await;
+        }
+        let claim = self.inner.validate(token).await?;
+        // tokens trocados a partir de um personal access token caem junto com ele
+        if let Some(id) = claim.pat {
+            self.personal_access_token_service.check_active(id).await?;
+        }
+        Ok(claim)
+    }
+}
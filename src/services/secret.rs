@@ -0,0 +1,45 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::rngs::OsRng;
+use rand::{Rng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// Gera um valor aleatório com `bytes` bytes de entropia, em base64url sem padding.
+pub fn generate_secret(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut buffer);
+    URL_SAFE_NO_PAD.encode(buffer)
+}
+
+/// SHA-256 em base64url sem padding. Usado para guardar segredos de alta entropia
+/// (segredos de clientes, códigos de autorização) e para o `S256` do PKCE.
+pub fn hash_secret(value: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(value.as_bytes()))
+}
+
+/// Alfabeto dos códigos de usuário do device grant: só consoantes, sem caracteres
+/// ambíguos e sem formar palavras (RFC 8628, seção 6.1).
+const USER_CODE_ALPHABET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+const USER_CODE_LENGTH: usize = 8;
+
+/// Gera um código de usuário já normalizado, com 8 caracteres de `USER_CODE_ALPHABET`.
+pub fn generate_user_code() -> String {
+    (0..USER_CODE_LENGTH)
+        .map(|_| USER_CODE_ALPHABET[OsRng.gen_range(0..USER_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Normaliza o código digitado: maiúsculas e apenas caracteres do alfabeto (descarta hífens e espaços).
+pub fn normalize_user_code(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| c.is_ascii() && USER_CODE_ALPHABET.contains(&(*c as u8)))
+        .collect()
+}
+
+/// Formata o código para exibição, como `BDFG-HJKL`.
+pub fn format_user_code(user_code: &str) -> String {
+    let (first, second) = user_code.split_at(user_code.len() / 2);
+    format!("{}-{}", first, second)
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::personal_access_token::{
+    CreatePersonalAccessToken, IssuedPersonalAccessToken, PersonalAccessToken,
+};
+use crate::domain::models::user::{CreateServiceAccount, PrincipalType, User};
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::personal_access_token::PersonalAccessTokenService;
+use crate::domain::services::service_account::ServiceAccountService;
+
+#[derive(Clone)]
+pub struct ServiceAccountServiceImpl {
+    pub repository: Arc<dyn UserRepository>,
+    pub personal_access_token_service: Arc<dyn PersonalAccessTokenService>,
+}
+
+impl ServiceAccountServiceImpl {
+    pub fn new(
+        repository: Arc<dyn UserRepository>,
+        personal_access_token_service: Arc<dyn PersonalAccessTokenService>,
+    ) -> Self {
+        ServiceAccountServiceImpl {
+            repository,
+            personal_access_token_service,
+        }
+    }
+
+    async fn get_service_account(&self, organization_id: i32, id: i32) -> Result<User, CommonError> {
+        match self.repository.get_by_id(organization_id, id).await {
+            Ok(user) if user.principal_type == PrincipalType::Service => Ok(user),
+            _ => Err(CommonError {
+                message: "Service account not found".to_string(),
+                code: 404,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl ServiceAccountService for ServiceAccountServiceImpl {
+    async fn create(&self, organization_id: i32, mut new_account: CreateServiceAccount) -> Result<User, CommonError> {
+        new_account.name = new_account.name.trim().to_string();
+        if new_account.name.is_empty() {
+            return Err(CommonError {
+                message: "Service account name is required".to_string(),
+                code: 400,
+            });
+        }
+        if let Some(owner_user_id) = new_account.owner_user_id {
+            let owner = self.repository.get_by_id(organization_id, owner_user_id).await;
+            if !owner.is_ok_and(|owner| owner.principal_type == PrincipalType::User) {
+                return Err(CommonError {
+                    message: "Owner must be a user of the organization".to_string(),
+                    code: 400,
+                });
+            }
+        }
+        self.repository
+            .create_service_account(organization_id, &new_account)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn list(&self, organization_id: i32) -> Result<Vec<User>, CommonError> {
+        self.repository
+            .list_service_accounts(organization_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn delete(&self, organization_id: i32, id: i32) -> Result<(), CommonError> {
+        let deleted = self
+            .repository
+            .delete_service_account(organization_id, id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if deleted == 0 {
+            return Err(CommonError {
+                message: "Service account not found".to_string(),
+                code: 404,
+            });
+        }
+        Ok(())
+    }
+    async fn create_token(
+        &self,
+        organization_id: i32,
+        id: i32,
+        new_token: CreatePersonalAccessToken,
+    ) -> Result<IssuedPersonalAccessToken, CommonError> {
+        let account = self.get_service_account(organization_id, id).await?;
+        self.personal_access_token_service
+            .create(organization_id, account.id, new_token)
+            .await
+    }
+    async fn list_tokens(&self, organization_id: i32, id: i32) -> Result<Vec<PersonalAccessToken>, CommonError> {
+        let account = self.get_service_account(organization_id, id).await?;
+        self.personal_access_token_service.list(account.id).await
+    }
+    async fn revoke_token(&self, organization_id: i32, id: i32, token_id: i32) -> Result<(), CommonError> {
+        let account = self.get_service_account(organization_id, id).await?;
+        self.personal_access_token_service.revoke(account.id, token_id).await
+    }
+}
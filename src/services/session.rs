@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::domain::error::CommonError;
+use crate::domain::models::session::{CreateSession, Session, SessionClient, SessionPolicy};
+use crate::domain::models::token::Claim;
+use crate::domain::repositories::session::SessionRepository;
+use crate::domain::services::authorization::AuthorizationService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::token::TokenService;
+use crate::services::secret::generate_secret;
+
+/// Abre as sessões conforme a `SessionPolicy` do serviço, restringida pelos papéis do usuário.
+#[derive(Clone)]
+pub struct SessionServiceImpl {
+    pub repository: Arc<dyn SessionRepository>,
+    pub authorization_service: Arc<dyn AuthorizationService>,
+    pub policy: SessionPolicy,
+}
+
+impl SessionServiceImpl {
+    pub fn new(
+        repository: Arc<dyn SessionRepository>,
+        authorization_service: Arc<dyn AuthorizationService>,
+        policy: SessionPolicy,
+    ) -> Self {
+        SessionServiceImpl {
+            repository,
+            authorization_service,
+            policy,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionService for SessionServiceImpl {
+    async fn create(&self, organization_id: i32, user_id: i32, client: SessionClient) -> Result<Session, CommonError> {
+        let roles = self
+            .authorization_service
+            .get_user_roles(organization_id, user_id)
+            .await?;
+        let policy = self.policy.for_roles(&roles);
+        let new_session = CreateSession {
+            id: generate_secret(16),
+            user_id,
+            organization_id,
+            client,
+            expires_at: Utc::now() + Duration::seconds(policy.max_lifetime_seconds.into()),
+            idle_timeout_seconds: policy.idle_timeout_seconds,
+        };
+        let Some(max) = policy.max_concurrent_sessions else {
+            return self
+                .repository
+                .create(&new_session)
+                .await
+                .map_err(|e| -> CommonError { e.into() });
+        };
+        self.repository
+            .create_within_limit(&new_session, max, policy.limit_action)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .ok_or_else(|| CommonError {
+                message: "Maximum number of active sessions reached".to_string(),
+                code: 409,
+            })
+    }
+    async fn list(&self, user_id: i32) -> Result<Vec<Session>, CommonError> {
+        self.repository
+            .list_active(user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn revoke(&self, user_id: i32, id: &str) -> Result<(), CommonError> {
+        let revoked = self
+            .repository
+            .revoke(user_id, id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if revoked == 0 {
+            return Err(CommonError {
+                message: "Session not found".to_string(),
+                code: 404,
+            });
+        }
+        Ok(())
+    }
+    async fn revoke_all(&self, user_id: i32) -> Result<usize, CommonError> {
+        self.repository
+            .revoke_all(user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn validate(&self, id: &str) -> Result<Session, CommonError> {
+        self.repository.touch(id).await.map_err(|_| CommonError {
+            message: "Session has ended".to_string(),
+            code: 401,
+        })
+    }
+    async fn purge_expired(&self) -> Result<usize, CommonError> {
+        self.repository
+            .delete_expired()
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
+
+/// Envolve outro `TokenService` e recusa os tokens cuja sessão (`sid`) foi revogada ou expirou.
+#[derive(Clone)]
+pub struct SessionTokenService {
+    pub inner: Arc<dyn TokenService>,
+    pub session_service: Arc<dyn SessionService>,
+}
+
+impl SessionTokenService {
+    pub fn new(inner: Arc<dyn TokenService>, session_service: Arc<dyn SessionService>) -> Self {
+        SessionTokenService { inner, session_service }
+    }
+}
+
+#[async_trait]
+impl TokenService for SessionTokenService {
+    async fn create(&self, user_id: i32) -> Result<String, CommonError> {
+        self.inner.create(user_id).await
+    }
+    async fn create_with_claim(&self, claim: Claim) -> Result<String, CommonError> {
+        self.inner.create_with_claim(claim).await
+    }
+    async fn validate(&self, token: String) -> Result<Claim, CommonError> {
+        let claim = self.inner.validate(token).await?;
+        if let Some(sid) = &claim.sid {
+            let session = self.session_service.validate(sid).await?;
+            if session.user_id.to_string() != claim.sub {
+                return Err(CommonError {
+                    message: "Session has ended".to_string(),
+                    code: 401,
+                });
+            }
+        }
+        Ok(claim)
+    }
+}
@@ -1,39 +1,95 @@
 use std::env;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 
-use crate::domain::constants::SECRET_KEY_TOKEN;
+use crate::domain::constants::{ACCESS_TOKEN_TTL_SECONDS, SECRET_KEY_TOKEN};
 use crate::domain::error::CommonError;
 use crate::domain::models::token::Claim;
+use crate::domain::repositories::token::TokenRepository;
 use crate::domain::services::token::TokenService;
+use crate::services::oidc::SigningKey;
+use crate::services::secret::generate_secret;
 
-#[derive(Clone)]
-pub struct TokenServiceImpl {}
+/// Assina access tokens com HS256 e `SECRET_KEY` ou, com uma `SigningKey`, com RS256 e o `kid`
+/// publicado em `/.well-known/jwks.json`, o que permite validá-los com `JwksTokenService`.
+#[derive(Clone, Default)]
+pub struct TokenServiceImpl {
+    secret_key: Option<String>,
+    signing_key: Option<SigningKey>,
+}
+
+impl TokenServiceImpl {
+    pub fn new() -> Self {
+        TokenServiceImpl::default()
+    }
+
+    /// Usa uma chave fixa em vez de ler `SECRET_KEY` do ambiente.
+    pub fn with_secret(secret_key: impl Into<String>) -> Self {
+        TokenServiceImpl {
+            secret_key: Some(secret_key.into()),
+            signing_key: None,
+        }
+    }
+
+    /// Assina com a chave RSA em vez de `SECRET_KEY`; só tokens RS256 dessa chave são aceitos.
+    pub fn with_signing_key(signing_key: SigningKey) -> Self {
+        TokenServiceImpl {
+            secret_key: None,
+            signing_key: Some(signing_key),
+        }
+    }
+
+    pub fn encode(&self, claim: &Claim) -> Result<String, CommonError> {
+        match &self.signing_key {
+            Some(signing_key) => Ok(encode(&signing_key.header(), claim, &signing_key.encoding_key)?),
+            None => Ok(encode(
+                &Header::default(),
+                claim,
+                &EncodingKey::from_secret(self.secret_key().as_ref()),
+            )?),
+        }
+    }
+
+    fn secret_key(&self) -> String {
+        self.secret_key
+            .clone()
+            .unwrap_or_else(|| env::var(SECRET_KEY_TOKEN).expect("SECRET_KEY must be set"))
+    }
+}
 
 #[async_trait]
 impl TokenService for TokenServiceImpl {
     async fn create(&self, user_id: i32) -> Result<String, CommonError> {
-        let expiration = Utc::now() + Duration::seconds(3600);
-        let claim = Claim {
-            sub: user_id.to_string(),
-            exp: expiration.timestamp(),
+        self.create_with_claim(Claim::new(user_id.to_string(), 0)).await
+    }
+    async fn create_with_claim(&self, mut claim: Claim) -> Result<String, CommonError> {
+        let expiration = (Utc::now() + Duration::seconds(ACCESS_TOKEN_TTL_SECONDS)).timestamp();
+        // Um `exp` já preenchido só encurta a validade, como no fim de uma sessão de login ou no
+        // token exchange; nenhum access token passa de `ACCESS_TOKEN_TTL_SECONDS`.
+        claim.exp = match claim.exp {
+            exp if exp > 0 => exp.min(expiration),
+            _ => expiration,
         };
-        let secret_key = env::var(SECRET_KEY_TOKEN).expect("SECRET_KEY must be set");
-        Ok(encode(
-            &Header::default(),
-            &claim,
-            &EncodingKey::from_secret(secret_key.as_ref()),
-        )?)
+        claim.jti = Some(generate_secret(16));
+        self.encode(&claim)
     }
     async fn validate(&self, token: String) -> Result<Claim, CommonError> {
-        let secret_key = env::var(SECRET_KEY_TOKEN).expect("SECRET_KEY must be set");
-        let token_data = decode::<Claim>(
-            &*token,
-            &DecodingKey::from_secret(secret_key.as_ref()),
-            &Validation::default(),
-        )?;
+        let (decoding_key, mut validation) = match &self.signing_key {
+            Some(signing_key) => (
+                DecodingKey::from_jwk(&signing_key.jwk)?,
+                Validation::new(Algorithm::RS256),
+            ),
+            None => (
+                DecodingKey::from_secret(self.secret_key().as_ref()),
+                Validation::default(),
+            ),
+        };
+        // O `aud` é conferido por `authenticate`, com a audiência configurada na rota ou no app.
+        validation.validate_aud = false;
+        let token_data = decode::<Claim>(&token, &decoding_key, &validation)?;
 
         let now = Utc::now().timestamp();
         if token_data.claims.exp < now {
@@ -45,3 +101,43 @@ impl TokenService for TokenServiceImpl {
         Ok(token_data.claims)
     }
 }
+
+/// Envolve outro `TokenService` e recusa os tokens cujo `jti` foi revogado em `/oauth/revoke`.
+#[derive(Clone)]
+pub struct RevocableTokenService {
+    pub inner: Arc<dyn TokenService>,
+    pub repository: Arc<dyn TokenRepository>,
+}
+
+impl RevocableTokenService {
+    pub fn new(inner: Arc<dyn TokenService>, repository: Arc<dyn TokenRepository>) -> Self {
+        RevocableTokenService { inner, repository }
+    }
+}
+
+#[async_trait]
+impl TokenService for RevocableTokenService {
+    async fn create(&self, user_id: i32) -> Result<String, CommonError> {
+        self.inner.create(user_id).await
+    }
+    async fn create_with_claim(&self, claim: Claim) -> Result<String, CommonError> {
+        self.inner.create_with_claim(claim).await
+    }
+    async fn validate(&self, token: String) -> Result<Claim, CommonError> {
+        let claim = self.inner.validate(token).await?;
+        if let Some(jti) = &claim.jti {
+            let revoked = self
+                .repository
+                .is_jti_revoked(jti)
+                .await
+                .map_err(|e| -> CommonError { e.into() })?;
+            if revoked {
+                return Err(CommonError {
+                    message: "Token has been revoked".to_string(),
+                    code: 401,
+                });
+            }
+        }
+        Ok(claim)
+    }
+}
@@ -4,51 +4,316 @@ use std::sync::Arc;
 use argon2::password_hash::SaltString;
 use argon2::{Argon2, PasswordHash, PasswordHasher};
 use async_trait::async_trait;
+use chrono::Duration;
 
-use crate::domain::constants::SALT_KEY;
+use crate::domain::constants::{DEFAULT_ORGANIZATION, LOGIN_LOCKOUT_SECONDS, LOGIN_MAX_FAILED_ATTEMPTS, SALT_KEY};
 use crate::domain::error::CommonError;
+use crate::domain::models::audit::{AuditAction, AuditOutcome, CreateAuditEvent};
+use crate::domain::models::organization::Organization;
+use crate::domain::models::session::SessionClient;
 use crate::domain::models::token::Claim;
-use crate::domain::models::user::{CreateUser, LoginUser, User};
+use crate::domain::models::user::{CreateUser, LoginUser, User, UserStatus};
+use crate::domain::repositories::organization::OrganizationRepository;
 use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::authorization::AuthorizationService;
+use crate::domain::services::session::SessionService;
 use crate::domain::services::token::TokenService;
 use crate::domain::services::user::UserService;
+use crate::services::audit::record_or_warn;
 
 #[derive(Clone)]
 pub struct UserServiceImpl {
     pub repository: Arc<dyn UserRepository>,
     pub token_service: Arc<dyn TokenService>,
+    pub authorization_service: Arc<dyn AuthorizationService>,
+    pub organization_repository: Arc<dyn OrganizationRepository>,
+    pub session_service: Arc<dyn SessionService>,
+    pub audit_service: Arc<dyn AuditService>,
 }
 
 impl UserServiceImpl {
-    pub fn new(repository: Arc<dyn UserRepository>, token_service: Arc<dyn TokenService>) -> Self {
+    pub fn new(
+        repository: Arc<dyn UserRepository>,
+        token_service: Arc<dyn TokenService>,
+        authorization_service: Arc<dyn AuthorizationService>,
+        organization_repository: Arc<dyn OrganizationRepository>,
+        session_service: Arc<dyn SessionService>,
+        audit_service: Arc<dyn AuditService>,
+    ) -> Self {
         UserServiceImpl {
             repository,
             token_service,
+            authorization_service,
+            organization_repository,
+            session_service,
+            audit_service,
         }
     }
+
+    async fn issue_session_token(&self, user: &User, client: SessionClient) -> Result<String, CommonError> {
+        let mut claim = self.get_claim(user.organization_id, user.id).await?;
+        claim.csrf = client.csrf_token_hash.clone();
+        let session = self
+            .session_service
+            .create(user.organization_id, user.id, client)
+            .await?;
+        // o token nunca passa do fim da sessão; `create_with_claim` ainda o limita ao TTL do access token
+        claim.exp = session.expires_at.timestamp();
+        claim.sid = Some(session.id);
+        self.token_service.create_with_claim(claim).await
+    }
+
+    /// Registra uma tentativa de login ou de troca de senha (`action`). O motivo de uma falha
+    /// fica na auditoria.
+    async fn audit_login(
+        &self,
+        action: AuditAction,
+        organization_id: Option<i32>,
+        user_id: Option<i32>,
+        login_user: &LoginUser,
+        client: &SessionClient,
+        error: Option<&CommonError>,
+    ) {
+        let outcome = match error {
+            None => AuditOutcome::Success,
+            Some(_) => AuditOutcome::Failure,
+        };
+        let mut details = serde_json::json!({
+            "username": login_user.username,
+            "organization": login_user.organization,
+        });
+        if let Some(e) = error {
+            details["reason"] = e.message.clone().into();
+        }
+        let mut event = CreateAuditEvent::new(action, outcome)
+            .organization(organization_id)
+            .client(client.ip_address.clone(), client.user_agent.clone())
+            .details(details);
+        if let Some(user_id) = user_id {
+            event = event.actor("user", user_id).target("user", user_id);
+        }
+        record_or_warn(self.audit_service.as_ref(), event).await;
+    }
+
+    /// Confere as credenciais, contando os logins errados, e recusa contas desativadas ou
+    /// bloqueadas. A situação da conta é conferida antes da senha: com a conta bloqueada, a
+    /// resposta é a mesma com a senha certa ou errada, e o bloqueio não vira um oráculo de senhas.
+    async fn verify_credentials(&self, organization_id: i32, login_user: &LoginUser) -> Result<User, CommonError> {
+        if let Ok(user) = self
+            .repository
+            .get_by_username(organization_id, &login_user.username)
+            .await
+        {
+            Self::check_status(&user)?;
+        }
+        let mut hashed = login_user.clone();
+        hashed.password = get_hashed_password(hashed.password).await?;
+        let user = match self.repository.get(organization_id, &hashed).await {
+            Ok(user) => user,
+            Err(e) => {
+                if let Err(failure) = self
+                    .repository
+                    .record_failed_login(
+                        organization_id,
+                        &login_user.username,
+                        LOGIN_MAX_FAILED_ATTEMPTS,
+                        Duration::seconds(LOGIN_LOCKOUT_SECONDS),
+                    )
+                    .await
+                {
+                    log::warn!("Could not record failed login: {}", failure.message);
+                }
+                return Err(e.into());
+            }
+        };
+        // a conta pode ter sido bloqueada entre a consulta acima e a conferência da senha
+        Self::check_status(&user)?;
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            return self
+                .repository
+                .unlock(organization_id, user.id)
+                .await
+                .map_err(|e| -> CommonError { e.into() });
+        }
+        Ok(user)
+    }
+
+    fn check_status(user: &User) -> Result<(), CommonError> {
+        match user.status() {
+            UserStatus::Disabled => Err(CommonError {
+                message: "Account is disabled".to_string(),
+                code: 403,
+            }),
+            UserStatus::Locked => Err(CommonError {
+                message: "Account is temporarily locked after too many failed logins".to_string(),
+                code: 403,
+            }),
+            UserStatus::Active => Ok(()),
+        }
+    }
+
+    /// Troca a senha de quem acertou a atual e encerra as sessões abertas com ela. Retorna o ID
+    /// do usuário.
+    async fn replace_password(
+        &self,
+        organization_id: i32,
+        login_user: &LoginUser,
+        new_password: String,
+    ) -> Result<i32, CommonError> {
+        if new_password.is_empty() || new_password == login_user.password {
+            return Err(CommonError {
+                message: "New password must be different from the current one".to_string(),
+                code: 400,
+            });
+        }
+        let user = self.verify_credentials(organization_id, login_user).await?;
+        self.repository
+            .update_password(organization_id, user.id, &get_hashed_password(new_password).await?)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.session_service.revoke_all(user.id).await?;
+        Ok(user.id)
+    }
+
+    async fn get_organization(&self, slug: Option<&str>) -> Result<Organization, CommonError> {
+        let slug = slug.unwrap_or(DEFAULT_ORGANIZATION);
+        self.organization_repository
+            .get_by_slug(slug)
+            .await
+            .map_err(|_| CommonError {
+                message: format!("Organization {} not found", slug),
+                code: 404,
+            })
+    }
 }
 
 #[async_trait]
 impl UserService for UserServiceImpl {
     async fn create(&self, user: CreateUser) -> Result<User, CommonError> {
-        let mut cloned_user = CreateUser {
+        let organization = self.get_organization(user.organization.as_deref()).await?;
+        let cloned_user = CreateUser {
             username: user.username,
             email: user.email,
             password: get_hashed_password(user.password).await?,
+            organization: user.organization,
         };
         self.repository
-            .create(&mut cloned_user)
+            .create(organization.id, &cloned_user)
             .await
             .map_err(|e| -> CommonError { e.into() })
     }
-    async fn get_token(&self, mut login_user: LoginUser) -> Result<String, CommonError> {
-        login_user.password = get_hashed_password(login_user.password).await?;
-        let user = self
-            .repository
-            .get(&login_user)
+    async fn get_token(&self, login_user: LoginUser, client: SessionClient) -> Result<String, CommonError> {
+        let organization = match self.get_organization(login_user.organization.as_deref()).await {
+            Ok(organization) => organization,
+            Err(e) => {
+                self.audit_login(AuditAction::UserLogin, None, None, &login_user, &client, Some(&e)).await;
+                return Err(e);
+            }
+        };
+        let user = match self.authenticate(organization.id, login_user.clone()).await {
+            Ok(user) => user,
+            Err(e) => {
+                self.audit_login(AuditAction::UserLogin, Some(organization.id), None, &login_user, &client, Some(&e)).await;
+                return Err(e);
+            }
+        };
+        let token = self.issue_session_token(&user, client.clone()).await;
+        self.audit_login(AuditAction::UserLogin, Some(organization.id), Some(user.id), &login_user, &client, token.as_ref().err())
+            .await;
+        token
+    }
+    async fn renew_session_token(&self, claim: &Claim) -> Result<String, CommonError> {
+        let (Some(sid), Some(organization_id), None, None) = (&claim.sid, claim.tenant, &claim.client_id, claim.pat)
+        else {
+            return Err(CommonError {
+                message: "Only user session tokens can be renewed".to_string(),
+                code: 403,
+            });
+        };
+        let session = self.session_service.validate(sid).await?;
+        if session.user_id.to_string() != claim.sub {
+            return Err(CommonError {
+                message: "Session not found".to_string(),
+                code: 401,
+            });
+        }
+        // roles e permissões são lidas de novo, como num login
+        let mut renewed = self.get_claim(organization_id, session.user_id).await?;
+        renewed.csrf = claim.csrf.clone();
+        renewed.exp = session.expires_at.timestamp();
+        renewed.sid = Some(session.id);
+        self.token_service.create_with_claim(renewed).await
+    }
+    async fn authenticate(&self, organization_id: i32, login_user: LoginUser) -> Result<User, CommonError> {
+        let user = self.verify_credentials(organization_id, &login_user).await?;
+        if user.password_reset_required {
+            return Err(CommonError {
+                message: "Password change required".to_string(),
+                code: 403,
+            });
+        }
+        Ok(user)
+    }
+    async fn change_password(
+        &self,
+        login_user: LoginUser,
+        new_password: String,
+        client: SessionClient,
+    ) -> Result<(), CommonError> {
+        let organization = self.get_organization(login_user.organization.as_deref()).await?;
+        let result = self.replace_password(organization.id, &login_user, new_password).await;
+        let user_id = result.as_ref().ok().copied();
+        self.audit_login(
+            AuditAction::PasswordChange,
+            Some(organization.id),
+            user_id,
+            &login_user,
+            &client,
+            result.as_ref().err(),
+        )
+        .await;
+        result.map(|_| ())
+    }
+    async fn get_claim(&self, organization_id: i32, user_id: i32) -> Result<Claim, CommonError> {
+        let user = self.get_user(organization_id, user_id).await?;
+        if user.status() == UserStatus::Disabled {
+            return Err(CommonError {
+                message: "Account is disabled".to_string(),
+                code: 403,
+            });
+        }
+        // sem a troca de senha exigida, nenhum token novo é emitido, nem por refresh token
+        if user.password_reset_required {
+            return Err(CommonError {
+                message: "Password change required".to_string(),
+                code: 403,
+            });
+        }
+        let mut claim = Claim::new(user.id.to_string(), 0);
+        claim.tenant = Some(organization_id);
+        claim.principal_type = Some(user.principal_type);
+        claim.roles = self
+            .authorization_service
+            .get_user_roles(organization_id, user_id)
+            .await?
+            .into_iter()
+            .map(|role| role.name)
+            .collect();
+        claim.permissions = self
+            .authorization_service
+            .get_user_permissions(organization_id, user_id)
+            .await?
+            .into_iter()
+            .map(|permission| permission.name)
+            .collect();
+        Ok(claim)
+    }
+    async fn get_user(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError> {
+        self.repository
+            .get_by_id(organization_id, user_id)
             .await
-            .map_err(|e| -> CommonError { e.into() })?;
-        self.token_service.create(user.id).await
+            .map_err(|e| -> CommonError { e.into() })
     }
     async fn validate_token(&self, token: String) -> Result<Claim, CommonError> {
         let claim = self.token_service.validate(token).await?;
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::user::{User, UserFilter};
+use crate::domain::repositories::repository::{validate_page, QueryParams, ResultPaging};
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::user_admin::UserAdminService;
+
+#[derive(Clone)]
+pub struct UserAdminServiceImpl {
+    pub repository: Arc<dyn UserRepository>,
+    pub session_service: Arc<dyn SessionService>,
+}
+
+impl UserAdminServiceImpl {
+    pub fn new(repository: Arc<dyn UserRepository>, session_service: Arc<dyn SessionService>) -> Self {
+        UserAdminServiceImpl {
+            repository,
+            session_service,
+        }
+    }
+}
+
+fn not_found() -> CommonError {
+    CommonError {
+        message: "User not found".to_string(),
+        code: 404,
+    }
+}
+
+#[async_trait]
+impl UserAdminService for UserAdminServiceImpl {
+    async fn list(
+        &self,
+        organization_id: i32,
+        filter: &UserFilter,
+        params: &dyn QueryParams,
+    ) -> Result<ResultPaging<User>, CommonError> {
+        validate_page(params)?;
+        self.repository
+            .list(organization_id, filter, params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn get(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError> {
+        self.repository
+            .get_by_id(organization_id, user_id)
+            .await
+            .map_err(|_| not_found())
+    }
+    async fn update_email(&self, organization_id: i32, user_id: i32, email: &str) -> Result<User, CommonError> {
+        let email = email.trim();
+        if email.is_empty() || !email.contains('@') {
+            return Err(CommonError {
+                message: "A valid email is required".to_string(),
+                code: 400,
+            });
+        }
+        self.get(organization_id, user_id).await?;
+        self.repository
+            .update_email(organization_id, user_id, email)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn delete(&self, organization_id: i32, user_id: i32) -> Result<(), CommonError> {
+        let deleted = self
+            .repository
+            .delete(organization_id, user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if deleted == 0 {
+            return Err(not_found());
+        }
+        Ok(())
+    }
+    async fn disable(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError> {
+        self.get(organization_id, user_id).await?;
+        let user = self
+            .repository
+            .set_disabled(organization_id, user_id, true)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.session_service.revoke_all(user_id).await?;
+        Ok(user)
+    }
+    async fn enable(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError> {
+        self.get(organization_id, user_id).await?;
+        self.repository
+            .set_disabled(organization_id, user_id, false)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn require_password_reset(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError> {
+        let user = self.get(organization_id, user_id).await?;
+        if user.password.is_none() {
+            return Err(CommonError {
+                message: "Service accounts have no password".to_string(),
+                code: 400,
+            });
+        }
+        let user = self
+            .repository
+            .require_password_reset(organization_id, user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.session_service.revoke_all(user_id).await?;
+        Ok(user)
+    }
+    async fn unlock(&self, organization_id: i32, user_id: i32) -> Result<User, CommonError> {
+        self.get(organization_id, user_id).await?;
+        self.repository
+            .unlock(organization_id, user_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn revoke_sessions(&self, organization_id: i32, user_id: i32) -> Result<usize, CommonError> {
+        // as sessões não são separadas por organização; o usuário precisa ser desta
+        self.get(organization_id, user_id).await?;
+        self.session_service.revoke_all(user_id).await
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ring::hmac;
+use url::Url;
+
+use crate::domain::constants::{WEBHOOK_MAX_ATTEMPTS, WEBHOOK_RETRY_BASE_SECONDS, WEBHOOK_RETRY_MAX_SECONDS};
+use crate::domain::error::CommonError;
+use crate::domain::models::outbox::{
+    CreateWebhook, OutboxEventType, PendingDelivery, Webhook, WebhookDelivery, WebhookDeliveryFilter,
+    WebhookRequest,
+};
+use crate::domain::repositories::outbox::OutboxRepository;
+use crate::domain::repositories::repository::{validate_page, QueryParams, ResultPaging};
+use crate::domain::services::webhook::WebhookService;
+use crate::domain::services::webhook_sender::WebhookSender;
+use crate::services::secret::generate_secret;
+
+/// Eventos distribuídos e entregas enviadas por rodada do despachante.
+const DISPATCH_BATCH_SIZE: i64 = 20;
+/// Por quanto tempo uma entrega reservada fica fora da fila; cobre uma rodada inteira de envios
+/// com timeout, e só importa se a instância cair no meio dela.
+const DELIVERY_LEASE_SECONDS: i64 = 600;
+
+/// `sha256=` seguido do HMAC-SHA256, em hexadecimal, de `timestamp.body` com o segredo do webhook.
+pub fn webhook_signature(secret: &str, timestamp: i64, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, format!("{}.{}", timestamp, body).as_bytes());
+    let hex: String = tag.as_ref().iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Espera antes da próxima tentativa depois de `attempts` falhas: dobra a cada falha, a partir
+/// de `WEBHOOK_RETRY_BASE_SECONDS`, até `WEBHOOK_RETRY_MAX_SECONDS`.
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+    let seconds = WEBHOOK_RETRY_BASE_SECONDS.saturating_mul(1 << exponent);
+    Duration::seconds(seconds.min(WEBHOOK_RETRY_MAX_SECONDS))
+}
+
+/// Requisição da entrega: o evento em JSON, com o ID dele em `X-Webhook-Id` para que o
+/// destinatário descarte repetições.
+pub fn webhook_request(pending: &PendingDelivery, now: DateTime<Utc>) -> WebhookRequest {
+    let event = &pending.event;
+    let body = serde_json::json!({
+        "id": event.id,
+        "type": event.event_type,
+        "organization_id": event.organization_id,
+        "created_at": event.created_at,
+        "data": event.payload,
+    })
+    .to_string();
+    let timestamp = now.timestamp();
+    WebhookRequest {
+        url: pending.webhook.url.clone(),
+        headers: vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("X-Webhook-Id".to_string(), event.id.to_string()),
+            ("X-Webhook-Event".to_string(), event.event_type.as_str().to_string()),
+            ("X-Webhook-Timestamp".to_string(), timestamp.to_string()),
+            (
+                "X-Webhook-Signature".to_string(),
+                webhook_signature(&pending.webhook.secret, timestamp, &body),
+            ),
+        ],
+        body,
+    }
+}
+
+/// Exige `https://`, exceto em loopback, porque o corpo leva dados pessoais dos usuários.
+fn validate_url(value: &str) -> Result<(), CommonError> {
+    let invalid_url = |reason: String| CommonError {
+        message: format!("Invalid webhook URL: {}", reason),
+        code: 400,
+    };
+    let url = Url::parse(value).map_err(|e| invalid_url(e.to_string()))?;
+    let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if loopback => Ok(()),
+        _ => Err(invalid_url("https is required except for localhost".to_string())),
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookServiceImpl {
+    pub repository: Arc<dyn OutboxRepository>,
+    pub sender: Arc<dyn WebhookSender>,
+}
+
+impl WebhookServiceImpl {
+    pub fn new(repository: Arc<dyn OutboxRepository>, sender: Arc<dyn WebhookSender>) -> Self {
+        WebhookServiceImpl { repository, sender }
+    }
+
+    /// Envia uma entrega e registra o resultado; a falha ao registrar só vai para o log, e a
+    /// entrega é tentada de novo quando a reserva vencer.
+    async fn deliver(&self, pending: &PendingDelivery) {
+        let id = pending.delivery.id;
+        let recorded = match self.sender.send(&webhook_request(pending, Utc::now())).await {
+            Ok(()) => self.repository.mark_delivered(id).await,
+            Err(e) => {
+                let attempts = pending.delivery.attempts + 1;
+                let next_attempt_at = (attempts < WEBHOOK_MAX_ATTEMPTS).then(|| Utc::now() + retry_delay(attempts));
+                if next_attempt_at.is_none() {
+                    log::warn!(
+                        "Webhook delivery {} to {} is dead after {} attempts: {}",
+                        id,
+                        pending.webhook.url,
+                        attempts,
+                        e.message
+                    );
+                }
+                self.repository.mark_failed(id, &e.message, next_attempt_at).await
+            }
+        };
+        if let Err(e) = recorded {
+            log::warn!("Could not record the result of webhook delivery {}: {}", id, e.message);
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookService for WebhookServiceImpl {
+    async fn create(&self, organization_id: i32, mut new_webhook: CreateWebhook) -> Result<Webhook, CommonError> {
+        validate_url(&new_webhook.url)?;
+        if new_webhook.event_types.is_empty() {
+            new_webhook.event_types = OutboxEventType::ALL.to_vec();
+        }
+        new_webhook.event_types.sort_unstable();
+        new_webhook.event_types.dedup();
+        self.repository
+            .create_webhook(organization_id, &new_webhook, &generate_secret(32))
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn list(&self, organization_id: i32) -> Result<Vec<Webhook>, CommonError> {
+        self.repository
+            .list_webhooks(organization_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn delete(&self, organization_id: i32, id: i32) -> Result<(), CommonError> {
+        let deleted = self
+            .repository
+            .delete_webhook(organization_id, id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if deleted == 0 {
+            return Err(CommonError {
+                message: "Webhook not found".to_string(),
+                code: 404,
+            });
+        }
+        Ok(())
+    }
+    async fn list_deliveries(
+        &self,
+        organization_id: i32,
+        filter: &WebhookDeliveryFilter,
+        params: &dyn QueryParams,
+    ) -> Result<ResultPaging<WebhookDelivery>, CommonError> {
+        validate_page(params)?;
+        self.repository
+            .list_deliveries(organization_id, filter, params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn replay(&self, organization_id: i32, outbox_event_id: i64) -> Result<usize, CommonError> {
+        self.repository
+            .get_event(organization_id, outbox_event_id)
+            .await
+            .map_err(|_| CommonError {
+                message: "Event not found".to_string(),
+                code: 404,
+            })?;
+        self.repository
+            .replay(organization_id, outbox_event_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+    async fn dispatch(&self) -> Result<usize, CommonError> {
+        self.repository
+            .fan_out(DISPATCH_BATCH_SIZE)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let due = self
+            .repository
+            .claim_due(DISPATCH_BATCH_SIZE, Duration::seconds(DELIVERY_LEASE_SECONDS))
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        for pending in &due {
+            self.deliver(pending).await;
+        }
+        Ok(due.len())
+    }
+}
@@ -0,0 +1,37 @@
+//! Utilitários para testes de apps que usam `AuthenticatedUser` e `RequireAuth`.
+//!
+//! ```rust
+//! use actix_web::web;
+//! use auth_service::testing::{mint_token, test_claim, test_token_service};
+//!
+//! let token_service = web::Data::from(test_token_service());
+//! let mut claim = test_claim("42");
+//! claim.roles.push("admin".to_string());
+//! let header = ("Authorization", format!("Bearer {}", mint_token(&claim)));
+//! ```
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::domain::models::token::Claim;
+use crate::domain::services::token::TokenService;
+use crate::services::token::TokenServiceImpl;
+
+pub const TEST_SECRET_KEY: &str = "auth-service-test-secret";
+
+/// Claims válidas por uma hora para o `sub` informado, sem papéis nem escopos.
+pub fn test_claim(sub: &str) -> Claim {
+    Claim::new(sub.to_string(), (Utc::now() + Duration::seconds(3600)).timestamp())
+}
+
+/// Assina as claims com `TEST_SECRET_KEY`.
+pub fn mint_token(claim: &Claim) -> String {
+    TokenServiceImpl::with_secret(TEST_SECRET_KEY)
+        .encode(claim)
+        .expect("test token should be encodable")
+}
+
+/// `TokenService` que aceita os tokens gerados por `mint_token`.
+pub fn test_token_service() -> Arc<dyn TokenService> {
+    Arc::new(TokenServiceImpl::with_secret(TEST_SECRET_KEY))
+}
@@ -1 +1,3 @@
-pub mod test_todo_controllers;
\ No newline at end of file
+pub mod test_auth_middleware;
+pub mod test_client_registration;
+pub mod test_service_context_controller;
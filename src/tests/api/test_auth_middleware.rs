@@ -0,0 +1,729 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use actix_web::cookie::Cookie;
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App, HttpResponse};
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, Utc};
+use jsonwebtoken::crypto::sign;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+
+use auth_service::api::csrf::{CSRF_HEADER, SESSION_COOKIE};
+use auth_service::api::controllers::user_handler::forward_auth_handler;
+use auth_service::api::extractors::{AuthenticatedUser, PublicOrigin, ServiceAudience};
+use auth_service::api::middleware::{AuditTrail, RequireAuth};
+use auth_service::domain::error::{ApiError, CommonError};
+use auth_service::domain::models::audit::{
+    AuditAction, AuditEvent, AuditEventFilter, AuditOutcome, AuditVerification, CreateAuditEvent,
+};
+use auth_service::domain::models::dpop::DpopProofClaims;
+use auth_service::domain::models::role::{CreatePermission, CreateRole};
+use auth_service::domain::models::session::SessionPolicy;
+use auth_service::domain::models::token::{Actor, Claim, Confirmation};
+use auth_service::domain::repositories::dpop::DpopRepository;
+use auth_service::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+use auth_service::domain::services::audit::AuditService;
+use auth_service::domain::services::authorization::AuthorizationService;
+use auth_service::domain::services::dpop::DpopService;
+use auth_service::domain::services::token::TokenService;
+use auth_service::domain::services::user::UserService;
+use auth_service::services::dpop::{jwk_thumbprint, DpopServiceImpl};
+use auth_service::services::introspection::IntrospectionTokenService;
+use auth_service::services::jwks::JwksTokenService;
+use auth_service::services::oidc::SigningKey;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::token::TokenServiceImpl;
+use auth_service::testing::{mint_token, test_claim, test_token_service, TEST_SECRET_KEY};
+
+use crate::tests::support::database::{tenant_with_user, unique};
+use crate::tests::support::services::{authorization_service, session_service, user_service};
+
+async fn whoami(user: AuthenticatedUser) -> HttpResponse {
+    HttpResponse::Ok().body(user.sub.clone())
+}
+
+async fn tenant(user: AuthenticatedUser) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().body(user.tenant()?.to_string()))
+}
+
+fn bearer(token: String) -> (&'static str, String) {
+    ("Authorization", format!("Bearer {}", token))
+}
+
+#[actix_web::test]
+async fn extractor_rejects_request_without_token() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .route("/me", web::get().to(whoami)),
+    )
+    .await;
+
+    let response = test::call_service(&app, test::TestRequest::get().uri("/me").to_request()).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn extractor_accepts_minted_token() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .route("/me", web::get().to(whoami)),
+    )
+    .await;
+
+    let request = test::TestRequest::get()
+        .uri("/me")
+        .insert_header(bearer(mint_token(&test_claim("42"))))
+        .to_request();
+    let body = test::call_and_read_body(&app, request).await;
+
+    assert_eq!(body, "42");
+}
+
+#[actix_web::test]
+async fn require_auth_checks_roles_and_scopes() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .service(
+                web::scope("/admin")
+                    .wrap(RequireAuth::new().role("admin").scope("users:read"))
+                    .route("/me", web::get().to(whoami)),
+            ),
+    )
+    .await;
+
+    let mut claim = test_claim("7");
+    let request = test::TestRequest::get()
+        .uri("/admin/me")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::FORBIDDEN);
+
+    claim.roles.push("admin".to_string());
+    claim.scope = Some("users:read users:write".to_string());
+    let request = test::TestRequest::get()
+        .uri("/admin/me")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn require_auth_checks_permissions_and_tenant() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .service(
+                web::scope("/organizations")
+                    .wrap(RequireAuth::new().permission("organizations:write"))
+                    .route("/tenant", web::get().to(tenant)),
+            ),
+    )
+    .await;
+
+    let mut claim = test_claim("7");
+    claim.permissions.push("organizations:read".to_string());
+    let request = test::TestRequest::get()
+        .uri("/organizations/tenant")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::FORBIDDEN);
+
+    claim.permissions.push("organizations:write".to_string());
+    let request = test::TestRequest::get()
+        .uri("/organizations/tenant")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::FORBIDDEN);
+
+    claim.tenant = Some(3);
+    let request = test::TestRequest::get()
+        .uri("/organizations/tenant")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, "3");
+}
+
+#[actix_web::test]
+async fn admin_routes_refuse_users_without_the_permission() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .service(
+                web::scope("/admin")
+                    .wrap(RequireAuth::new().role("admin").permission("users:write"))
+                    .route("/me", web::get().to(whoami)),
+            ),
+    )
+    .await;
+
+    // O papel sozinho não basta
+    let mut claim = test_claim("7");
+    claim.roles.push("admin".to_string());
+    claim.permissions.push("users:read".to_string());
+    let request = test::TestRequest::get()
+        .uri("/admin/me")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::FORBIDDEN);
+
+    claim.permissions.push("users:write".to_string());
+    let request = test::TestRequest::get()
+        .uri("/admin/me")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn permissions_granted_through_a_role_reach_the_claim() {
+    let (organization, user_id) = tenant_with_user("rbac");
+    let organization_id = organization.organization_id;
+    let authorization_service = authorization_service(&organization.pool);
+    let user_service = user_service(
+        &organization.pool,
+        test_token_service(),
+        session_service(&organization.pool, SessionPolicy::default()),
+    );
+    let permission_name = format!("reports:read:{}", unique("rbac"));
+    let permission = authorization_service
+        .create_permission(CreatePermission {
+            name: permission_name.clone(),
+            description: None,
+        })
+        .await
+        .unwrap();
+    let role = authorization_service
+        .create_role(
+            organization_id,
+            CreateRole {
+                name: "analyst".to_string(),
+                description: None,
+            },
+        )
+        .await
+        .unwrap();
+    authorization_service
+        .grant_permission(organization_id, role.id, permission.id)
+        .await
+        .unwrap();
+
+    let claim = user_service.get_claim(organization_id, user_id).await.unwrap();
+    assert!(!claim.has_permission(&permission_name));
+
+    authorization_service
+        .assign_role(organization_id, user_id, role.id)
+        .await
+        .unwrap();
+    let mut claim = user_service.get_claim(organization_id, user_id).await.unwrap();
+    assert_eq!(claim.roles, vec!["analyst".to_string()]);
+    assert!(claim.has_permission(&permission_name));
+
+    // E o RequireAuth aceita o token com a permissão herdada do papel
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .service(
+                web::scope("/reports")
+                    .wrap(RequireAuth::new().permission(permission_name))
+                    .route("/tenant", web::get().to(tenant)),
+            ),
+    )
+    .await;
+    claim.exp = test_claim("").exp;
+    let request = test::TestRequest::get()
+        .uri("/reports/tenant")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, organization_id.to_string());
+}
+
+#[actix_web::test]
+async fn session_cookies_require_the_csrf_token_on_state_changing_requests() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .route("/me", web::get().to(whoami))
+            .route("/me", web::post().to(whoami)),
+    )
+    .await;
+    let mut claim = test_claim("42");
+    claim.csrf = Some(hash_secret("csrf-token"));
+    let cookie = Cookie::new(SESSION_COOKIE, mint_token(&claim));
+
+    let request = test::TestRequest::get().uri("/me").cookie(cookie.clone()).to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, "42");
+
+    let request = test::TestRequest::post().uri("/me").cookie(cookie.clone()).to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::FORBIDDEN);
+
+    let request = test::TestRequest::post()
+        .uri("/me")
+        .cookie(cookie.clone())
+        .insert_header((CSRF_HEADER, "forged"))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::FORBIDDEN);
+
+    let request = test::TestRequest::post()
+        .uri("/me")
+        .cookie(cookie)
+        .insert_header((CSRF_HEADER, "csrf-token"))
+        .to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, "42");
+
+    // um token de bearer comum posto no cookie não vale como sessão de navegador
+    let request = test::TestRequest::get()
+        .uri("/me")
+        .cookie(Cookie::new(SESSION_COOKIE, mint_token(&test_claim("42"))))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn exchanged_tokens_are_accepted_only_for_their_audience() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .app_data(ServiceAudience::new("billing"))
+            .route("/me", web::get().to(whoami))
+            .service(
+                web::scope("/reports")
+                    .wrap(RequireAuth::new().audience("reports"))
+                    .route("/me", web::get().to(whoami)),
+            ),
+    )
+    .await;
+    let other_app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .route("/me", web::get().to(whoami)),
+    )
+    .await;
+
+    // Mesmo caminho do token exchange: `aud` e `act` definidos e o `exp` do token original
+    let mut claim = test_claim("42");
+    claim.aud = Some("billing".to_string());
+    claim.act = Some(Actor {
+        sub: "gateway".to_string(),
+        act: None,
+    });
+    let exchanged = TokenServiceImpl::with_secret(TEST_SECRET_KEY)
+        .create_with_claim(claim)
+        .await
+        .unwrap();
+
+    let request = test::TestRequest::get()
+        .uri("/me")
+        .insert_header(bearer(exchanged.clone()))
+        .to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, "42");
+
+    let request = test::TestRequest::get()
+        .uri("/reports/me")
+        .insert_header(bearer(exchanged.clone()))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::UNAUTHORIZED);
+
+    let request = test::TestRequest::get()
+        .uri("/me")
+        .insert_header(bearer(exchanged))
+        .to_request();
+    assert_eq!(test::call_service(&other_app, request).await.status(), StatusCode::UNAUTHORIZED);
+
+    // Tokens sem `aud` continuam aceitos
+    let request = test::TestRequest::get()
+        .uri("/reports/me")
+        .insert_header(bearer(mint_token(&test_claim("7"))))
+        .to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, "7");
+}
+
+#[actix_web::test]
+async fn dpop_proofs_with_a_private_key_in_the_jwk_are_rejected() {
+    let dpop_service = DpopServiceImpl::new(Arc::new(InMemoryDpopRepository::default()));
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let htu = "https://auth.example.com/oauth/token";
+    let proof = |jti: &str, private_member: Option<&str>| {
+        let mut jwk = serde_json::to_value(&signing_key.jwk).unwrap();
+        if let Some(member) = private_member {
+            jwk[member] = serde_json::json!("AQAB");
+        }
+        let header = serde_json::json!({ "typ": "dpop+jwt", "alg": "RS256", "jwk": jwk });
+        let claims = DpopProofClaims {
+            jti: jti.to_string(),
+            htm: "POST".to_string(),
+            htu: htu.to_string(),
+            iat: Utc::now().timestamp(),
+            ath: None,
+        };
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(serde_json::to_string(&claims).unwrap())
+        );
+        let signature = sign(message.as_bytes(), &signing_key.encoding_key, Algorithm::RS256).unwrap();
+        format!("{}.{}", message, signature)
+    };
+
+    for (jti, member) in [("1", "d"), ("2", "p"), ("3", "qi")] {
+        let error = dpop_service
+            .verify_proof(&proof(jti, Some(member)), "POST", htu, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, 401);
+    }
+    let jkt = dpop_service.verify_proof(&proof("4", None), "POST", htu, None).await.unwrap();
+    assert_eq!(jkt, jwk_thumbprint(&signing_key.jwk).unwrap());
+}
+
+#[actix_web::test]
+async fn forward_auth_checks_dpop_proofs_against_the_original_uri() {
+    let dpop_service: Arc<dyn DpopService> =
+        Arc::new(DpopServiceImpl::new(Arc::new(InMemoryDpopRepository::default())));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .app_data(web::Data::from(dpop_service))
+            .app_data(PublicOrigin::default().trust_proxy("10.0.0.2".parse().unwrap()))
+            .route("/auth/forward", web::get().to(forward_auth_handler)),
+    )
+    .await;
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let mut claim = test_claim("42");
+    claim.cnf = Some(Confirmation {
+        jkt: jwk_thumbprint(&signing_key.jwk).unwrap(),
+    });
+    let token = mint_token(&claim);
+    let call_from = |peer: &str, proof: String| {
+        test::TestRequest::get()
+            .uri("/auth/forward")
+            .peer_addr(peer.parse().unwrap())
+            .insert_header(("Authorization", format!("DPoP {}", token)))
+            .insert_header(("DPoP", proof))
+            .insert_header(("X-Forwarded-Method", "GET"))
+            .insert_header(("X-Forwarded-Proto", "https"))
+            .insert_header(("X-Forwarded-Host", "app.example.com"))
+            .insert_header(("X-Forwarded-Uri", "/orders?page=2"))
+            .to_request()
+    };
+
+    let proof = dpop_proof(&signing_key, "1", "https://app.example.com/orders", &token);
+    let response = test::call_service(&app, call_from("10.0.0.2:41000", proof)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get("X-Auth-Subject").unwrap(), "42");
+
+    // Uma prova feita para o próprio endpoint de forward auth não vale pela requisição original
+    let proof = dpop_proof(&signing_key, "2", "http://localhost:8080/auth/forward", &token);
+    let response = test::call_service(&app, call_from("10.0.0.2:41000", proof)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Fora do proxy confiável, os headers não trocam a URI conferida
+    let proof = dpop_proof(&signing_key, "3", "https://app.example.com/orders", &token);
+    let response = test::call_service(&app, call_from("198.51.100.9:41000", proof)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn dpop_proofs_ignore_forwarded_headers_from_untrusted_clients() {
+    let dpop_service: Arc<dyn DpopService> =
+        Arc::new(DpopServiceImpl::new(Arc::new(InMemoryDpopRepository::default())));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .app_data(web::Data::from(dpop_service))
+            .route("/me", web::get().to(whoami)),
+    )
+    .await;
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let mut claim = test_claim("42");
+    claim.cnf = Some(Confirmation {
+        jkt: jwk_thumbprint(&signing_key.jwk).unwrap(),
+    });
+    let token = mint_token(&claim);
+    let call = |proof: String| {
+        test::TestRequest::get()
+            .uri("/me")
+            .peer_addr("198.51.100.9:41000".parse().unwrap())
+            .insert_header(("Authorization", format!("DPoP {}", token)))
+            .insert_header(("DPoP", proof))
+            .insert_header(("X-Forwarded-Proto", "https"))
+            .insert_header(("X-Forwarded-Host", "api.example.com"))
+            .insert_header(("Forwarded", "proto=https;host=api.example.com"))
+            .to_request()
+    };
+
+    // Uma prova capturada em outro host não passa a valer com headers de proxy forjados
+    let proof = dpop_proof(&signing_key, "1", "https://api.example.com/me", &token);
+    let response = test::call_service(&app, call(proof)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let proof = dpop_proof(&signing_key, "2", "http://localhost:8080/me", &token);
+    let response = test::call_service(&app, call(proof)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn the_public_base_url_defines_the_dpop_htu() {
+    let dpop_service: Arc<dyn DpopService> =
+        Arc::new(DpopServiceImpl::new(Arc::new(InMemoryDpopRepository::default())));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .app_data(web::Data::from(dpop_service))
+            .app_data(PublicOrigin::new("https://auth.example.com/"))
+            .route("/me", web::get().to(whoami)),
+    )
+    .await;
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let mut claim = test_claim("42");
+    claim.cnf = Some(Confirmation {
+        jkt: jwk_thumbprint(&signing_key.jwk).unwrap(),
+    });
+    let token = mint_token(&claim);
+    let call = |proof: String| {
+        test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Host", "internal:8080"))
+            .insert_header(("Authorization", format!("DPoP {}", token)))
+            .insert_header(("DPoP", proof))
+            .to_request()
+    };
+
+    let proof = dpop_proof(&signing_key, "1", "http://internal:8080/me", &token);
+    let response = test::call_service(&app, call(proof)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let proof = dpop_proof(&signing_key, "2", "https://auth.example.com/me", &token);
+    let response = test::call_service(&app, call(proof)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Guarda as provas em memória, no lugar da tabela `dpop_proofs`.
+#[derive(Default)]
+struct InMemoryDpopRepository(Mutex<HashSet<String>>);
+
+#[async_trait]
+impl DpopRepository for InMemoryDpopRepository {
+    async fn record_proof(&self, proof_hash: &str, _expires_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        Ok(self.0.lock().unwrap().insert(proof_hash.to_string()))
+    }
+    async fn delete_expired_proofs(&self) -> RepositoryResult<usize> {
+        Ok(0)
+    }
+}
+
+fn dpop_proof(signing_key: &SigningKey, jti: &str, htu: &str, access_token: &str) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.typ = Some("dpop+jwt".to_string());
+    header.jwk = Some(signing_key.jwk.clone());
+    let claims = DpopProofClaims {
+        jti: jti.to_string(),
+        htm: "GET".to_string(),
+        htu: htu.to_string(),
+        iat: Utc::now().timestamp(),
+        ath: Some(hash_secret(access_token)),
+    };
+    encode(&header, &claims, &signing_key.encoding_key).unwrap()
+}
+
+#[actix_web::test]
+async fn bound_tokens_require_a_fresh_dpop_proof_of_the_same_key() {
+    let dpop_service: Arc<dyn DpopService> =
+        Arc::new(DpopServiceImpl::new(Arc::new(InMemoryDpopRepository::default())));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .app_data(web::Data::from(dpop_service))
+            .route("/me", web::get().to(whoami)),
+    )
+    .await;
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let mut claim = test_claim("42");
+    claim.cnf = Some(Confirmation {
+        jkt: jwk_thumbprint(&signing_key.jwk).unwrap(),
+    });
+    let token = mint_token(&claim);
+    let htu = "http://localhost:8080/me";
+    let call = |scheme: &'static str, proof: Option<String>| {
+        let mut request = test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Authorization", format!("{} {}", scheme, token)));
+        if let Some(proof) = proof {
+            request = request.insert_header(("DPoP", proof));
+        }
+        request.to_request()
+    };
+
+    let proof = dpop_proof(&signing_key, "1", htu, &token);
+    let response = test::call_service(&app, call("DPoP", Some(proof.clone()))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // A mesma prova não vale duas vezes
+    let response = test::call_service(&app, call("DPoP", Some(proof))).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Nem sem prova, nem como bearer, nem com prova de outra URI
+    let response = test::call_service(&app, call("DPoP", None)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let proof = dpop_proof(&signing_key, "2", htu, &token);
+    let response = test::call_service(&app, call("Bearer", Some(proof))).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let proof = dpop_proof(&signing_key, "3", "http://localhost:8080/other", &token);
+    let response = test::call_service(&app, call("DPoP", Some(proof))).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Tokens sem `cnf` continuam aceitos como bearer
+    let request = test::TestRequest::get()
+        .uri("/me")
+        .insert_header(bearer(mint_token(&test_claim("7"))))
+        .to_request();
+    assert_eq!(test::call_and_read_body(&app, request).await, "7");
+}
+
+#[actix_web::test]
+async fn jwks_token_service_validates_access_tokens_by_kid() {
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let jwks = JwksTokenService::new(JwkSet {
+        keys: vec![signing_key.jwk.clone()],
+    });
+    let token_service = TokenServiceImpl::with_signing_key(signing_key.clone());
+
+    let token = token_service.create_with_claim(test_claim("9")).await.unwrap();
+    assert_eq!(jwks.validate(token).await.unwrap().sub, "9");
+
+    let mut header = signing_key.header();
+    header.kid = Some("unknown".to_string());
+    let token = encode(&header, &test_claim("9"), &signing_key.encoding_key).unwrap();
+    assert!(jwks.validate(token).await.is_err());
+}
+
+#[actix_web::test]
+async fn jwks_token_service_uses_the_algorithm_of_the_jwk() {
+    let signing_key = SigningKey::from_pem(include_bytes!("../fixtures/signing_key.pem")).unwrap();
+    let jwks = JwksTokenService::new(JwkSet {
+        keys: vec![signing_key.jwk.clone()],
+    });
+
+    // HS256 assinado com a chave pública como segredo, com o kid da JWK RSA
+    let header = Header {
+        kid: signing_key.jwk.common.key_id.clone(),
+        ..Default::default()
+    };
+    let public_key = serde_json::to_string(&signing_key.jwk).unwrap();
+    let token = encode(&header, &test_claim("9"), &EncodingKey::from_secret(public_key.as_bytes())).unwrap();
+    assert!(jwks.validate(token).await.is_err());
+}
+
+#[actix_web::test]
+async fn introspection_requires_https_outside_loopback() {
+    assert!(IntrospectionTokenService::new("https://auth.example.com/auth/validate").is_ok());
+    assert!(IntrospectionTokenService::new("http://127.0.0.1:15423/auth/validate").is_ok());
+    assert!(IntrospectionTokenService::new("http://auth.example.com/auth/validate").is_err());
+}
+
+#[derive(Default)]
+struct RecordedAuditEvents(Mutex<Vec<CreateAuditEvent>>);
+
+#[async_trait]
+impl AuditService for RecordedAuditEvents {
+    async fn record(&self, event: CreateAuditEvent) -> Result<AuditEvent, CommonError> {
+        self.0.lock().unwrap().push(event.clone());
+        Ok(AuditEvent {
+            id: 1,
+            organization_id: event.organization_id,
+            action: event.action,
+            outcome: event.outcome,
+            actor_type: event.actor_type,
+            actor_id: event.actor_id,
+            target_type: event.target_type,
+            target_id: event.target_id,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            details: event.details,
+            created_at: Utc::now(),
+            prev_hash: String::new(),
+            hash: String::new(),
+        })
+    }
+
+    async fn list(
+        &self,
+        _organization_id: i32,
+        _filter: &AuditEventFilter,
+        _params: &dyn QueryParams,
+    ) -> Result<ResultPaging<AuditEvent>, CommonError> {
+        unimplemented!()
+    }
+
+    async fn create_checkpoints(&self) -> Result<usize, CommonError> {
+        unimplemented!()
+    }
+
+    async fn verify(&self, _from: Option<NaiveDate>) -> Result<AuditVerification, CommonError> {
+        unimplemented!()
+    }
+}
+
+#[actix_web::test]
+async fn audit_trail_records_state_changing_admin_requests() {
+    let audit_service = Arc::new(RecordedAuditEvents::default());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(test_token_service()))
+            .app_data(web::Data::from(audit_service.clone() as Arc<dyn AuditService>))
+            .service(
+                web::scope("/admin")
+                    .wrap(RequireAuth::new().role("admin"))
+                    .wrap(AuditTrail)
+                    .route("/me", web::get().to(whoami))
+                    .route(
+                        "/users/{user_id}/roles/{role_id}",
+                        web::put().to(|| async { HttpResponse::NoContent().finish() }),
+                    ),
+            ),
+    )
+    .await;
+    let assign = |claim: &Claim| {
+        test::TestRequest::put()
+            .uri("/admin/users/5/roles/2")
+            .insert_header(bearer(mint_token(claim)))
+            .insert_header(("User-Agent", "admin-cli"))
+            .to_request()
+    };
+
+    // Leituras não são registradas
+    let mut claim = test_claim("7");
+    claim.tenant = Some(3);
+    claim.roles.push("admin".to_string());
+    let request = test::TestRequest::get()
+        .uri("/admin/me")
+        .insert_header(bearer(mint_token(&claim)))
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::OK);
+    assert!(audit_service.0.lock().unwrap().is_empty());
+
+    assert_eq!(test::call_service(&app, assign(&claim)).await.status(), StatusCode::NO_CONTENT);
+    // Sem o papel, a tentativa recusada também fica registrada, com quem a fez
+    claim.roles.clear();
+    assert_eq!(test::call_service(&app, assign(&claim)).await.status(), StatusCode::FORBIDDEN);
+
+    let events = audit_service.0.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.action == AuditAction::AdminAction));
+    assert_eq!(events[0].outcome, AuditOutcome::Success);
+    assert_eq!(events[1].outcome, AuditOutcome::Failure);
+    for event in events.iter() {
+        assert_eq!(event.organization_id, Some(3));
+        assert_eq!(event.actor_id.as_deref(), Some("7"));
+        assert_eq!(event.target_type.as_deref(), Some("users"));
+        assert_eq!(event.target_id.as_deref(), Some("5"));
+        assert_eq!(event.user_agent.as_deref(), Some("admin-cli"));
+    }
+    assert_eq!(events[0].details["route"], "/admin/users/{user_id}/roles/{role_id}");
+}
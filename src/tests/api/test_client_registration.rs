@@ -0,0 +1,219 @@
+//! Testes de integração do registro dinâmico de clientes (RFC 7591) e da gestão do registro
+//! (RFC 7592), pelas rotas de `/oauth/register` e contra o banco de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use serde_json::{json, Value};
+
+use auth_service::api::controllers::client_registration_handler::{
+    delete_registration_handler, get_registration_handler, register_handler, update_registration_handler,
+};
+use auth_service::domain::models::oauth::TOKEN_EXCHANGE_GRANT_TYPE;
+use auth_service::domain::services::client_registration::ClientRegistrationService;
+
+use crate::tests::support::database::tenant;
+use crate::tests::support::services::client_registration_service;
+
+/// Cria uma organização e devolve o serviço de registro e um initial access token dela.
+async fn setup() -> (web::Data<dyn ClientRegistrationService>, String) {
+    let tenant = tenant("registration");
+    let service = client_registration_service(&tenant.pool, &["openid", "profile"]);
+    let initial_access_token = service
+        .create_initial_access_token(tenant.organization_id)
+        .await
+        .unwrap()
+        .token;
+    (web::Data::from(service as Arc<dyn ClientRegistrationService>), initial_access_token)
+}
+
+/// As rotas de registro, como em `create_app`.
+fn app(
+    service: web::Data<dyn ClientRegistrationService>,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new().app_data(service).service(
+        web::scope("/oauth")
+            .route("/register", web::post().to(register_handler))
+            .route("/register/{client_id}", web::get().to(get_registration_handler))
+            .route("/register/{client_id}", web::put().to(update_registration_handler))
+            .route("/register/{client_id}", web::delete().to(delete_registration_handler)),
+    )
+}
+
+fn metadata() -> Value {
+    json!({
+        "client_name": "Registration tests",
+        "redirect_uris": ["https://app.example.com/callback"],
+        "scope": "openid",
+    })
+}
+
+fn bearer(token: &str) -> (&'static str, String) {
+    ("Authorization", format!("Bearer {}", token))
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn the_initial_access_token_registers_a_single_client() {
+    let (service, initial_access_token) = setup().await;
+    let app = test::init_service(app(service)).await;
+    let register = || {
+        test::TestRequest::post()
+            .uri("/oauth/register")
+            .insert_header(bearer(&initial_access_token))
+            .set_json(metadata())
+            .to_request()
+    };
+
+    let response = test::call_service(&app, register()).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let registered: Value = test::read_body_json(response).await;
+    let client_id = registered["client_id"].as_str().unwrap();
+    assert!(registered["client_secret"].is_string());
+    assert!(registered["registration_access_token"].is_string());
+    assert!(registered["registration_client_uri"]
+        .as_str()
+        .unwrap()
+        .ends_with(&format!("/oauth/register/{}", client_id)));
+
+    let response = test::call_service(&app, register()).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let error: Value = test::read_body_json(response).await;
+    assert_eq!(error["error"], "invalid_token");
+
+    // sem token, nada é registrado
+    let request = test::TestRequest::post()
+        .uri("/oauth/register")
+        .set_json(metadata())
+        .to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn the_registration_access_token_guards_read_update_and_delete() {
+    let (service, initial_access_token) = setup().await;
+    let app = test::init_service(app(service)).await;
+    let request = test::TestRequest::post()
+        .uri("/oauth/register")
+        .insert_header(bearer(&initial_access_token))
+        .set_json(metadata())
+        .to_request();
+    let registered: Value = test::call_and_read_body_json(&app, request).await;
+    let uri = format!("/oauth/register/{}", registered["client_id"].as_str().unwrap());
+    let token = registered["registration_access_token"].as_str().unwrap();
+
+    // o token de registro de outro cliente não serve
+    let (_, other_initial_access_token) = setup().await;
+    let request = test::TestRequest::post()
+        .uri("/oauth/register")
+        .insert_header(bearer(&other_initial_access_token))
+        .set_json(metadata())
+        .to_request();
+    let other: Value = test::call_and_read_body_json(&app, request).await;
+    let other_token = other["registration_access_token"].as_str().unwrap();
+
+    for wrong in [None, Some("wrong-token"), Some(other_token), Some(initial_access_token.as_str())] {
+        let requests = [
+            test::TestRequest::get().uri(&uri),
+            test::TestRequest::put().uri(&uri).set_json(metadata()),
+            test::TestRequest::delete().uri(&uri),
+        ];
+        for request in requests {
+            let request = match wrong {
+                Some(wrong) => request.insert_header(bearer(wrong)),
+                None => request,
+            };
+            let response = test::call_service(&app, request.to_request()).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    let request = test::TestRequest::get().uri(&uri).insert_header(bearer(token)).to_request();
+    let response = test::call_service(&app, request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let read: Value = test::read_body_json(response).await;
+    assert_eq!(read["client_name"], "Registration tests");
+    assert!(read.get("client_secret").is_none());
+    assert!(read.get("registration_access_token").is_none());
+
+    let mut changes = metadata();
+    changes["client_name"] = json!("Renamed");
+    let request = test::TestRequest::put()
+        .uri(&uri)
+        .insert_header(bearer(token))
+        .set_json(changes)
+        .to_request();
+    let updated: Value = test::call_and_read_body_json(&app, request).await;
+    assert_eq!(updated["client_name"], "Renamed");
+
+    let request = test::TestRequest::delete().uri(&uri).insert_header(bearer(token)).to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::NO_CONTENT);
+    let request = test::TestRequest::get().uri(&uri).insert_header(bearer(token)).to_request();
+    assert_eq!(test::call_service(&app, request).await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn invalid_metadata_is_rejected_without_spending_the_initial_access_token() {
+    let (service, initial_access_token) = setup().await;
+    let app = test::init_service(app(service)).await;
+    let invalid = [
+        (json!({ "redirect_uris": ["https://*.example.com/callback"] }), "invalid_redirect_uri"),
+        (json!({ "redirect_uris": ["http://app.example.com/callback"] }), "invalid_redirect_uri"),
+        (
+            json!({ "grant_types": [TOKEN_EXCHANGE_GRANT_TYPE], "redirect_uris": [] }),
+            "invalid_client_metadata",
+        ),
+        (
+            json!({ "redirect_uris": ["https://app.example.com/callback"], "scope": "users:write" }),
+            "invalid_client_metadata",
+        ),
+    ];
+    for (metadata, expected) in &invalid {
+        let request = test::TestRequest::post()
+            .uri("/oauth/register")
+            .insert_header(bearer(&initial_access_token))
+            .set_json(metadata)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error: Value = test::read_body_json(response).await;
+        assert_eq!(&error["error"], expected, "{}", metadata);
+    }
+
+    let request = test::TestRequest::post()
+        .uri("/oauth/register")
+        .insert_header(bearer(&initial_access_token))
+        .set_json(metadata())
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let registered: Value = test::read_body_json(response).await;
+
+    // a atualização passa pelas mesmas regras
+    let uri = format!("/oauth/register/{}", registered["client_id"].as_str().unwrap());
+    let token = registered["registration_access_token"].as_str().unwrap();
+    for (metadata, expected) in invalid {
+        let request = test::TestRequest::put()
+            .uri(&uri)
+            .insert_header(bearer(token))
+            .set_json(&metadata)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error: Value = test::read_body_json(response).await;
+        assert_eq!(error["error"], expected, "{}", metadata);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod test_audit_chain;
+pub mod test_group_graph;
+pub mod test_oauth;
+pub mod test_oidc;
+pub mod test_webhook_dispatch;
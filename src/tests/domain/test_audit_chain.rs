@@ -0,0 +1,222 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use auth_service::domain::models::audit::{
+    AuditAction, AuditCheckpoint, AuditCheckpointClaims, AuditEvent, AuditEventFilter,
+    AuditOutcome, CreateAuditEvent, AUDIT_CHAIN_GENESIS,
+};
+use auth_service::domain::repositories::audit::AuditRepository;
+use auth_service::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+use auth_service::domain::services::audit::AuditService;
+use auth_service::services::audit::{AuditServiceImpl, CheckpointSigner};
+
+/// Log em memória que encadeia as entradas como o repositório Diesel, com o relógio controlado
+/// pelo teste.
+#[derive(Default)]
+struct InMemoryAuditLog {
+    now: Mutex<Option<DateTime<Utc>>>,
+    events: Mutex<Vec<AuditEvent>>,
+    checkpoints: Mutex<Vec<AuditCheckpoint>>,
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryAuditLog {
+    async fn create(&self, new_event: &CreateAuditEvent) -> RepositoryResult<AuditEvent> {
+        let created_at = self.now.lock().unwrap().unwrap();
+        let mut events = self.events.lock().unwrap();
+        let prev_hash = events
+            .iter()
+            .rev()
+            .find(|event| event.day() == created_at.date_naive())
+            .map_or(AUDIT_CHAIN_GENESIS.to_string(), |event| event.hash.clone());
+        let event = AuditEvent {
+            id: events.len() as i64 + 1,
+            organization_id: new_event.organization_id,
+            action: new_event.action,
+            outcome: new_event.outcome,
+            actor_type: new_event.actor_type.clone(),
+            actor_id: new_event.actor_id.clone(),
+            target_type: new_event.target_type.clone(),
+            target_id: new_event.target_id.clone(),
+            ip_address: new_event.ip_address.clone(),
+            user_agent: new_event.user_agent.clone(),
+            details: new_event.details.clone(),
+            created_at,
+            hash: new_event.chain_hash(&prev_hash, created_at),
+            prev_hash,
+        };
+        events.push(event.clone());
+        Ok(event)
+    }
+    async fn list(
+        &self,
+        _organization_id: i32,
+        _filter: &AuditEventFilter,
+        _params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<AuditEvent>> {
+        unimplemented!()
+    }
+    async fn list_chain(
+        &self,
+        from: Option<NaiveDate>,
+        after_id: i64,
+        limit: i64,
+    ) -> RepositoryResult<Vec<AuditEvent>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .filter(|event| event.id > after_id && from.is_none_or(|from| event.day() >= from))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+    async fn list_day(&self, day: NaiveDate) -> RepositoryResult<Vec<AuditEvent>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .filter(|event| event.day() == day)
+            .cloned()
+            .collect())
+    }
+    async fn days_without_checkpoint(&self, before: NaiveDate) -> RepositoryResult<Vec<NaiveDate>> {
+        let checkpoints = self.checkpoints.lock().unwrap();
+        let mut days: Vec<NaiveDate> = self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(AuditEvent::day)
+            .filter(|day| {
+                *day < before && !checkpoints.iter().any(|checkpoint| checkpoint.day == *day)
+            })
+            .collect();
+        days.dedup();
+        Ok(days)
+    }
+    async fn create_checkpoint(
+        &self,
+        claims: &AuditCheckpointClaims,
+        signature: &str,
+    ) -> RepositoryResult<AuditCheckpoint> {
+        let checkpoint = AuditCheckpoint {
+            day: claims.day,
+            event_count: claims.event_count,
+            last_event_id: claims.last_event_id,
+            last_hash: claims.last_hash.clone(),
+            signature: signature.to_string(),
+            created_at: Utc::now(),
+        };
+        self.checkpoints.lock().unwrap().push(checkpoint.clone());
+        Ok(checkpoint)
+    }
+    async fn list_checkpoints(
+        &self,
+        from: Option<NaiveDate>,
+    ) -> RepositoryResult<Vec<AuditCheckpoint>> {
+        let checkpoints = self.checkpoints.lock().unwrap();
+        Ok(checkpoints
+            .iter()
+            .filter(|checkpoint| from.is_none_or(|from| checkpoint.day >= from))
+            .cloned()
+            .collect())
+    }
+}
+
+/// Três logins em cada um dos dois últimos dias, já com os checkpoints assinados.
+async fn signed_log() -> (Arc<InMemoryAuditLog>, AuditServiceImpl) {
+    let log = Arc::new(InMemoryAuditLog::default());
+    let audit_service = AuditServiceImpl::new(
+        log.clone(),
+        CheckpointSigner::with_secret("audit-chain-tests"),
+    );
+    for days_ago in [2, 1] {
+        for username in ["alice", "bob", "carol"] {
+            *log.now.lock().unwrap() = Some(Utc::now() - Duration::days(days_ago));
+            let event = CreateAuditEvent::new(AuditAction::UserLogin, AuditOutcome::Success)
+                .organization(Some(1))
+                .details(serde_json::json!({ "username": username }));
+            audit_service.record(event).await.unwrap();
+        }
+    }
+    assert_eq!(audit_service.create_checkpoints().await.unwrap(), 2);
+    (log, audit_service)
+}
+
+#[actix_web::test]
+async fn an_untouched_log_verifies() {
+    let (log, audit_service) = signed_log().await;
+
+    let report = audit_service.verify(None).await.unwrap();
+    assert!(report.is_intact());
+    assert_eq!(report.events_checked, 6);
+    assert_eq!(report.days_checked, 2);
+    assert!(report.unsigned_days.is_empty());
+    // cada dia começa uma cadeia nova
+    let events = log.events.lock().unwrap().clone();
+    assert_eq!(events[0].prev_hash, AUDIT_CHAIN_GENESIS);
+    assert_eq!(events[1].prev_hash, events[0].hash);
+    assert_eq!(events[3].prev_hash, AUDIT_CHAIN_GENESIS);
+    // os checkpoints já existentes não são assinados de novo
+    assert_eq!(audit_service.create_checkpoints().await.unwrap(), 0);
+}
+
+#[actix_web::test]
+async fn verification_reports_the_first_broken_link() {
+    // Conteúdo editado
+    let (log, audit_service) = signed_log().await;
+    log.events.lock().unwrap()[1].details = serde_json::json!({ "username": "mallory" });
+    let broken = audit_service
+        .verify(None)
+        .await
+        .unwrap()
+        .first_broken_link
+        .unwrap();
+    assert_eq!(broken.event_id, Some(2));
+    assert_eq!(broken.reason, "hash does not match the entry contents");
+
+    // Entrada apagada do meio do dia
+    let (log, audit_service) = signed_log().await;
+    log.events.lock().unwrap().remove(3);
+    let broken = audit_service
+        .verify(None)
+        .await
+        .unwrap()
+        .first_broken_link
+        .unwrap();
+    assert_eq!(broken.event_id, Some(5));
+    assert_eq!(
+        broken.reason,
+        "prev_hash does not match the previous entry of the day"
+    );
+
+    // Última entrada do dia apagada: só o checkpoint acusa
+    let (log, audit_service) = signed_log().await;
+    log.events.lock().unwrap().remove(2);
+    let broken = audit_service
+        .verify(None)
+        .await
+        .unwrap()
+        .first_broken_link
+        .unwrap();
+    assert_eq!(broken.event_id, None);
+    assert_eq!(broken.day, (Utc::now() - Duration::days(2)).date_naive());
+    assert_eq!(broken.reason, "entries do not match the signed checkpoint");
+
+    // Dia inteiro apagado, junto com um checkpoint refeito por quem não tem a chave
+    let (log, audit_service) = signed_log().await;
+    log.events.lock().unwrap().retain(|event| event.id > 3);
+    let forged = CheckpointSigner::with_secret("another-key")
+        .sign(&AuditCheckpointClaims {
+            day: (Utc::now() - Duration::days(1)).date_naive(),
+            event_count: 3,
+            last_event_id: 6,
+            last_hash: log.events.lock().unwrap()[2].hash.clone(),
+        })
+        .unwrap();
+    log.checkpoints.lock().unwrap()[1].signature = forged;
+    let report = audit_service.verify(None).await.unwrap();
+    let broken = report.first_broken_link.unwrap();
+    assert_eq!(broken.reason, "checkpoint signature is invalid");
+}
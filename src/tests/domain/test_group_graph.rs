@@ -0,0 +1,26 @@
+use auth_service::domain::models::group::GroupGraph;
+
+// (pai, filho): engineering(1) > backend(2) > payments(3); ops(4) > payments(3)
+const EDGES: [(i32, i32); 3] = [(1, 2), (2, 3), (4, 3)];
+
+#[test]
+fn ancestor_paths_walk_every_parent() {
+    let paths = GroupGraph::new(&EDGES).ancestor_paths(&[3]);
+
+    assert_eq!(paths[&3], vec![3]);
+    assert_eq!(paths[&2], vec![3, 2]);
+    assert_eq!(paths[&1], vec![3, 2, 1]);
+    assert_eq!(paths[&4], vec![3, 4]);
+    assert_eq!(paths.len(), 4);
+}
+
+#[test]
+fn would_create_cycle_detects_direct_and_indirect_loops() {
+    let graph = GroupGraph::new(&EDGES);
+
+    assert!(graph.would_create_cycle(1, 1));
+    assert!(graph.would_create_cycle(3, 1));
+    assert!(graph.would_create_cycle(2, 1));
+    assert!(!graph.would_create_cycle(4, 1));
+    assert!(!graph.would_create_cycle(1, 4));
+}
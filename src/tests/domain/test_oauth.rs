@@ -0,0 +1,158 @@
+use chrono::Utc;
+
+use auth_service::domain::models::oauth::{
+    ClientType, Consent, OAuthClient, DEVICE_CODE_GRANT_TYPE, GRANT_TYPES, TOKEN_EXCHANGE_GRANT_TYPE,
+};
+use auth_service::domain::models::token::{Actor, Claim};
+use auth_service::domain::services::token::TokenService;
+use auth_service::services::secret::{
+    format_user_code, generate_secret, generate_user_code, hash_secret, normalize_user_code,
+};
+use auth_service::services::oauth::validate_redirect_uri;
+use auth_service::services::token::TokenServiceImpl;
+
+fn client(redirect_uris: &[&str]) -> OAuthClient {
+    OAuthClient {
+        id: 1,
+        client_id: "client".to_string(),
+        client_secret_hash: None,
+        previous_client_secret_hash: None,
+        registration_access_token_hash: None,
+        name: "SPA".to_string(),
+        client_type: ClientType::Public,
+        redirect_uris: redirect_uris.iter().map(|uri| uri.to_string()).collect(),
+        allowed_scopes: vec!["users:read".to_string()],
+        allowed_audiences: Vec::new(),
+        grant_types: ClientType::Public.default_grant_types(),
+        require_pushed_authorization_requests: false,
+        organization_id: 1,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn redirect_uri_must_match_exactly() {
+    let single = client(&["https://app.example.com/cb"]);
+    assert_eq!(single.redirect_uri_for(None).as_deref(), Some("https://app.example.com/cb"));
+    assert_eq!(
+        single.redirect_uri_for(Some("https://app.example.com/cb")).as_deref(),
+        Some("https://app.example.com/cb")
+    );
+    assert_eq!(single.redirect_uri_for(Some("https://app.example.com/cb/")), None);
+    assert_eq!(single.redirect_uri_for(Some("https://evil.example.com/cb")), None);
+
+    let several = client(&["https://a.example.com/cb", "https://b.example.com/cb"]);
+    assert_eq!(several.redirect_uri_for(None), None);
+}
+
+#[test]
+fn hash_secret_matches_pkce_s256() {
+    // BASE64URL(SHA256(code_verifier)) sem padding
+    assert_eq!(
+        hash_secret("dBjftJeZ4CVP-mJ92K9XbGuU9kDrQMyYb6uUjp9khbQ"),
+        "DKmM9-1Va8cDvjB2BSpjPJzmjJ9eaK7es-6ra66bA3I"
+    );
+    assert_eq!(generate_secret(32).len(), 43);
+    assert_ne!(generate_secret(32), generate_secret(32));
+}
+
+#[test]
+fn rotated_client_accepts_current_and_previous_secret() {
+    let mut confidential = client(&[]);
+    confidential.client_type = ClientType::Confidential;
+    confidential.client_secret_hash = Some(hash_secret("new"));
+    confidential.previous_client_secret_hash = Some(hash_secret("old"));
+    assert!(confidential.has_secret_hash(&hash_secret("new")));
+    assert!(confidential.has_secret_hash(&hash_secret("old")));
+    assert!(!confidential.has_secret_hash(&hash_secret("other")));
+
+    confidential.previous_client_secret_hash = None;
+    assert!(!confidential.has_secret_hash(&hash_secret("old")));
+}
+
+#[test]
+fn user_codes_are_normalized_for_lookup() {
+    let user_code = generate_user_code();
+    assert_eq!(user_code.len(), 8);
+    assert_eq!(normalize_user_code(&user_code), user_code);
+
+    let display = format_user_code(&user_code);
+    assert_eq!(display.len(), 9);
+    assert_eq!(normalize_user_code(&display), user_code);
+    assert_eq!(normalize_user_code(&format!(" {} ", display.to_lowercase())), user_code);
+    assert_eq!(normalize_user_code("BCDF-ghjk"), "BCDFGHJK");
+}
+
+#[actix_web::test]
+async fn access_tokens_carry_a_unique_jti() {
+    let service = TokenServiceImpl::with_secret("secret");
+    let first = service.create_with_claim(Claim::new("1".to_string(), 0)).await.unwrap();
+    let second = service.create_with_claim(Claim::new("1".to_string(), 0)).await.unwrap();
+
+    let first = service.validate(first).await.unwrap().jti.unwrap();
+    let second = service.validate(second).await.unwrap().jti.unwrap();
+    assert_ne!(first, second);
+}
+
+#[actix_web::test]
+async fn exchanged_tokens_keep_the_subject_expiry_and_nest_actors() {
+    let service = TokenServiceImpl::with_secret("secret");
+    let subject_exp = Utc::now().timestamp() + 60;
+    let mut claim = Claim::new("1".to_string(), subject_exp);
+    claim.aud = Some("billing".to_string());
+    claim.act = Some(Actor {
+        sub: "gateway".to_string(),
+        act: Some(Box::new(Actor {
+            sub: "frontend".to_string(),
+            act: None,
+        })),
+    });
+
+    let token = service.create_with_claim(claim.clone()).await.unwrap();
+    let exchanged = service.validate(token).await.unwrap();
+    assert_eq!(exchanged.exp, subject_exp);
+    assert_eq!(exchanged.aud, claim.aud);
+    assert_eq!(exchanged.act, claim.act);
+}
+
+#[test]
+fn redirect_uris_need_https_except_on_loopback() {
+    assert!(validate_redirect_uri("https://app.example.com/callback").is_ok());
+    assert!(validate_redirect_uri("http://localhost:8080/callback").is_ok());
+    assert!(validate_redirect_uri("http://127.0.0.1/callback").is_ok());
+    assert!(validate_redirect_uri("http://[::1]:3000/callback").is_ok());
+
+    assert!(validate_redirect_uri("http://app.example.com/callback").is_err());
+    assert!(validate_redirect_uri("https://*.example.com/callback").is_err());
+    assert!(validate_redirect_uri("https://app.example.com/*").is_err());
+    assert!(validate_redirect_uri("https://app.example.com/callback#token").is_err());
+    assert!(validate_redirect_uri("com.example.app:/callback").is_err());
+    assert!(validate_redirect_uri("/callback").is_err());
+}
+
+#[test]
+fn consent_covers_only_approved_scopes() {
+    let consent = Consent {
+        user_id: 1,
+        oauth_client_id: 1,
+        scopes: vec!["openid".to_string(), "profile".to_string()],
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    assert!(consent.covers("openid"));
+    assert!(consent.covers("profile openid"));
+    assert!(!consent.covers("openid email"));
+}
+
+#[test]
+fn only_confidential_clients_get_client_grants() {
+    assert!(ClientType::Public.supports_grant_type("authorization_code"));
+    assert!(ClientType::Public.supports_grant_type(DEVICE_CODE_GRANT_TYPE));
+    assert!(!ClientType::Public.supports_grant_type("client_credentials"));
+    assert!(!ClientType::Public.supports_grant_type(TOKEN_EXCHANGE_GRANT_TYPE));
+    assert!(ClientType::Confidential.supports_grant_type(TOKEN_EXCHANGE_GRANT_TYPE));
+    assert!(!ClientType::Confidential.supports_grant_type("password"));
+
+    assert_eq!(ClientType::Public.default_grant_types().len(), 3);
+    assert_eq!(ClientType::Confidential.default_grant_types().len(), GRANT_TYPES.len());
+}
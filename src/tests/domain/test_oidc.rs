@@ -0,0 +1,54 @@
+use chrono::Utc;
+
+use auth_service::domain::models::oidc::{StandardClaims, UserInfo};
+use auth_service::domain::models::user::{PrincipalType, User};
+use auth_service::services::oidc::SigningKey;
+
+fn user() -> User {
+    User {
+        id: 7,
+        username: "alice".to_string(),
+        password: Some("hash".to_string()),
+        email: Some("alice@example.com".to_string()),
+        created_at: Utc::now(),
+        organization_id: 1,
+        email_verified: true,
+        principal_type: PrincipalType::User,
+        owner_user_id: None,
+        disabled_at: None,
+        password_reset_required: false,
+        failed_login_attempts: 0,
+        locked_until: None,
+    }
+}
+
+#[test]
+fn scopes_select_standard_claims() {
+    let user = user();
+    assert_eq!(StandardClaims::for_scopes(&user, &["openid"]), StandardClaims::default());
+
+    let profile = StandardClaims::for_scopes(&user, &["openid", "profile"]);
+    assert_eq!(profile.preferred_username.as_deref(), Some("alice"));
+    assert_eq!(profile.email, None);
+
+    let email = StandardClaims::for_scopes(&user, &["openid", "email"]);
+    assert_eq!(email.preferred_username, None);
+    assert_eq!(email.email.as_deref(), Some("alice@example.com"));
+    assert_eq!(email.email_verified, Some(true));
+
+    let info = UserInfo {
+        sub: user.id.to_string(),
+        claims: email,
+    };
+    assert_eq!(
+        serde_json::to_value(info).unwrap(),
+        serde_json::json!({"sub": "7", "email": "alice@example.com", "email_verified": true})
+    );
+}
+
+#[test]
+fn signing_key_rejects_non_rsa_pem() {
+    assert!(SigningKey::from_pem(b"not a key").is_err());
+    let certificate = "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n";
+    assert!(SigningKey::from_pem(certificate.as_bytes()).is_err());
+}
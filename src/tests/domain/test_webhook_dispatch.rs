@@ -0,0 +1,291 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ring::hmac;
+
+use auth_service::domain::constants::WEBHOOK_MAX_ATTEMPTS;
+use auth_service::domain::error::CommonError;
+use auth_service::domain::models::outbox::{
+    CreateWebhook, DeliveryStatus, OutboxEvent, OutboxEventType, PendingDelivery, Webhook,
+    WebhookDelivery, WebhookDeliveryFilter, WebhookRequest,
+};
+use auth_service::domain::repositories::outbox::OutboxRepository;
+use auth_service::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+use auth_service::domain::services::webhook::WebhookService;
+use auth_service::domain::services::webhook_sender::WebhookSender;
+use auth_service::services::webhook::{retry_delay, webhook_signature, WebhookServiceImpl};
+
+const SECRET: &str = "webhook-tests";
+
+/// Um webhook com uma entrega de `user.created` pendente.
+struct InMemoryOutbox {
+    webhook: Webhook,
+    event: OutboxEvent,
+    delivery: Mutex<WebhookDelivery>,
+}
+
+impl InMemoryOutbox {
+    fn new() -> Self {
+        let now = Utc::now();
+        InMemoryOutbox {
+            webhook: Webhook {
+                id: 1,
+                organization_id: 1,
+                url: "https://hooks.example.com/users".to_string(),
+                secret: SECRET.to_string(),
+                event_types: vec![OutboxEventType::UserCreated],
+                created_at: now,
+            },
+            event: OutboxEvent {
+                id: 10,
+                organization_id: 1,
+                event_type: OutboxEventType::UserCreated,
+                aggregate_type: "user".to_string(),
+                aggregate_id: "7".to_string(),
+                payload: serde_json::json!({ "id": 7, "username": "alice" }),
+                created_at: now,
+                dispatched_at: Some(now),
+            },
+            delivery: Mutex::new(WebhookDelivery {
+                id: 100,
+                webhook_id: 1,
+                outbox_event_id: 10,
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_error: None,
+                delivered_at: None,
+                created_at: now,
+            }),
+        }
+    }
+
+    fn delivery(&self) -> WebhookDelivery {
+        self.delivery.lock().unwrap().clone()
+    }
+
+    /// Adianta o relógio até a próxima tentativa.
+    fn make_due(&self) {
+        self.delivery.lock().unwrap().next_attempt_at = Utc::now() - Duration::seconds(1);
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for InMemoryOutbox {
+    async fn get_event(&self, _organization_id: i32, _id: i64) -> RepositoryResult<OutboxEvent> {
+        Ok(self.event.clone())
+    }
+    async fn create_webhook(
+        &self,
+        organization_id: i32,
+        new_webhook: &CreateWebhook,
+        secret: &str,
+    ) -> RepositoryResult<Webhook> {
+        Ok(Webhook {
+            id: 2,
+            organization_id,
+            url: new_webhook.url.clone(),
+            secret: secret.to_string(),
+            event_types: new_webhook.event_types.clone(),
+            created_at: Utc::now(),
+        })
+    }
+    async fn list_webhooks(&self, _organization_id: i32) -> RepositoryResult<Vec<Webhook>> {
+        unimplemented!()
+    }
+    async fn delete_webhook(&self, _organization_id: i32, _id: i32) -> RepositoryResult<usize> {
+        unimplemented!()
+    }
+    async fn fan_out(&self, _limit: i64) -> RepositoryResult<usize> {
+        Ok(0)
+    }
+    async fn claim_due(&self, _limit: i64, lease: Duration) -> RepositoryResult<Vec<PendingDelivery>> {
+        let mut delivery = self.delivery.lock().unwrap();
+        if delivery.status != DeliveryStatus::Pending || delivery.next_attempt_at > Utc::now() {
+            return Ok(vec![]);
+        }
+        delivery.next_attempt_at = Utc::now() + lease;
+        Ok(vec![PendingDelivery {
+            delivery: delivery.clone(),
+            webhook: self.webhook.clone(),
+            event: self.event.clone(),
+        }])
+    }
+    async fn mark_delivered(&self, _id: i64) -> RepositoryResult<usize> {
+        let mut delivery = self.delivery.lock().unwrap();
+        delivery.status = DeliveryStatus::Delivered;
+        delivery.attempts += 1;
+        delivery.delivered_at = Some(Utc::now());
+        Ok(1)
+    }
+    async fn mark_failed(
+        &self,
+        _id: i64,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> RepositoryResult<usize> {
+        let mut delivery = self.delivery.lock().unwrap();
+        delivery.attempts += 1;
+        delivery.last_error = Some(error.to_string());
+        match next_attempt_at {
+            Some(next_attempt_at) => delivery.next_attempt_at = next_attempt_at,
+            None => delivery.status = DeliveryStatus::Dead,
+        }
+        Ok(1)
+    }
+    async fn list_deliveries(
+        &self,
+        _organization_id: i32,
+        _filter: &WebhookDeliveryFilter,
+        _params: &dyn QueryParams,
+    ) -> RepositoryResult<ResultPaging<WebhookDelivery>> {
+        unimplemented!()
+    }
+    async fn replay(&self, _organization_id: i32, _outbox_event_id: i64) -> RepositoryResult<usize> {
+        let mut delivery = self.delivery.lock().unwrap();
+        delivery.status = DeliveryStatus::Pending;
+        delivery.attempts = 0;
+        delivery.next_attempt_at = Utc::now();
+        delivery.last_error = None;
+        delivery.delivered_at = None;
+        Ok(1)
+    }
+}
+
+/// Guarda as requisições e responde conforme `up`.
+#[derive(Default)]
+struct RecordingSender {
+    up: Mutex<bool>,
+    requests: Mutex<Vec<WebhookRequest>>,
+}
+
+#[async_trait]
+impl WebhookSender for RecordingSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), CommonError> {
+        self.requests.lock().unwrap().push(request.clone());
+        if *self.up.lock().unwrap() {
+            Ok(())
+        } else {
+            Err(CommonError {
+                message: "Webhook answered with status 503".to_string(),
+                code: 500,
+            })
+        }
+    }
+}
+
+fn header<'a>(request: &'a WebhookRequest, name: &str) -> &'a str {
+    request
+        .headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+        .unwrap()
+}
+
+fn setup(up: bool) -> (Arc<InMemoryOutbox>, Arc<RecordingSender>, WebhookServiceImpl) {
+    let outbox = Arc::new(InMemoryOutbox::new());
+    let sender = Arc::new(RecordingSender::default());
+    *sender.up.lock().unwrap() = up;
+    let webhook_service = WebhookServiceImpl::new(outbox.clone(), sender.clone());
+    (outbox, sender, webhook_service)
+}
+
+#[actix_web::test]
+async fn deliveries_are_signed_with_the_webhook_secret() {
+    let (outbox, sender, webhook_service) = setup(true);
+
+    assert_eq!(webhook_service.dispatch().await.unwrap(), 1);
+    assert_eq!(outbox.delivery().status, DeliveryStatus::Delivered);
+    let request = sender.requests.lock().unwrap()[0].clone();
+    assert_eq!(request.url, "https://hooks.example.com/users");
+    assert_eq!(header(&request, "X-Webhook-Id"), "10");
+    assert_eq!(header(&request, "X-Webhook-Event"), "user.created");
+    let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(body["type"], "user.created");
+    assert_eq!(body["data"]["username"], "alice");
+
+    // o destinatário confere o HMAC de `timestamp.body` com o segredo
+    let timestamp = header(&request, "X-Webhook-Timestamp");
+    let signature = header(&request, "X-Webhook-Signature");
+    assert_eq!(signature, webhook_signature(SECRET, timestamp.parse().unwrap(), &request.body));
+    let expected = hex_to_bytes(signature.strip_prefix("sha256=").unwrap());
+    let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+    hmac::verify(&key, format!("{}.{}", timestamp, request.body).as_bytes(), &expected).unwrap();
+    assert!(hmac::verify(&key, b"tampered", &expected).is_err());
+}
+
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn retries_back_off_exponentially_up_to_the_cap() {
+    assert_eq!(retry_delay(1), Duration::seconds(30));
+    assert_eq!(retry_delay(2), Duration::seconds(60));
+    assert_eq!(retry_delay(5), Duration::seconds(480));
+    assert_eq!(retry_delay(20), Duration::hours(6));
+    assert_eq!(retry_delay(i32::MAX), Duration::hours(6));
+}
+
+#[actix_web::test]
+async fn failed_deliveries_are_retried_until_they_are_dead() {
+    let (outbox, sender, webhook_service) = setup(false);
+
+    webhook_service.dispatch().await.unwrap();
+    let delivery = outbox.delivery();
+    assert_eq!(delivery.status, DeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.last_error.as_deref(), Some("Webhook answered with status 503"));
+    assert!(delivery.next_attempt_at > Utc::now() + Duration::seconds(25));
+    // nada é enviado antes da hora
+    assert_eq!(webhook_service.dispatch().await.unwrap(), 0);
+
+    for _ in 1..WEBHOOK_MAX_ATTEMPTS {
+        outbox.make_due();
+        assert_eq!(webhook_service.dispatch().await.unwrap(), 1);
+    }
+    let delivery = outbox.delivery();
+    assert_eq!(delivery.status, DeliveryStatus::Dead);
+    assert_eq!(delivery.attempts, WEBHOOK_MAX_ATTEMPTS);
+    outbox.make_due();
+    assert_eq!(webhook_service.dispatch().await.unwrap(), 0);
+    assert_eq!(sender.requests.lock().unwrap().len(), WEBHOOK_MAX_ATTEMPTS as usize);
+
+    // o reenvio tira a entrega do estado `dead`
+    *sender.up.lock().unwrap() = true;
+    assert_eq!(webhook_service.replay(1, 10).await.unwrap(), 1);
+    assert_eq!(webhook_service.dispatch().await.unwrap(), 1);
+    let delivery = outbox.delivery();
+    assert_eq!(delivery.status, DeliveryStatus::Delivered);
+    assert_eq!(delivery.attempts, 1);
+}
+
+#[actix_web::test]
+async fn repeated_event_types_are_subscribed_once() {
+    let (_, _, webhook_service) = setup(true);
+
+    let webhook = webhook_service
+        .create(
+            1,
+            CreateWebhook {
+                url: "https://hooks.example.com/users".to_string(),
+                event_types: vec![
+                    OutboxEventType::UserDeleted,
+                    OutboxEventType::UserCreated,
+                    OutboxEventType::UserDeleted,
+                ],
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        webhook.event_types,
+        vec![OutboxEventType::UserCreated, OutboxEventType::UserDeleted]
+    );
+}
@@ -0,0 +1,12 @@
+pub mod test_audit_events;
+pub mod test_audit_export;
+pub mod test_device_code;
+pub mod test_outbox;
+pub mod test_personal_access_tokens;
+pub mod test_pkce;
+pub mod test_pushed_authorization_requests;
+pub mod test_refresh_tokens;
+pub mod test_row_level_security;
+pub mod test_sessions;
+pub mod test_token_exchange;
+pub mod test_user_admin;
@@ -0,0 +1,103 @@
+//! Testes de integração do log de auditoria contra o banco de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use auth_service::domain::models::audit::{AuditAction, AuditEventFilter, AuditOutcome, CreateAuditEvent};
+use auth_service::domain::repositories::repository::QueryParamsImpl;
+use auth_service::domain::services::audit::AuditService;
+use auth_service::services::audit::AuditServiceImpl;
+
+use crate::tests::support::database::tenant;
+use crate::tests::support::services::audit_service;
+
+/// Cria duas organizações e o serviço de auditoria.
+fn setup() -> (Arc<AuditServiceImpl>, i32, i32) {
+    let (first, second) = (tenant("audit-first"), tenant("audit-second"));
+    (audit_service(&first.pool), first.organization_id, second.organization_id)
+}
+
+fn page(limit: i64, offset: i64) -> QueryParamsImpl {
+    QueryParamsImpl {
+        limit: Some(limit),
+        offset: Some(offset),
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn events_are_listed_per_organization_with_filters_and_pages() {
+    let (audit_service, organization_id, other_organization_id) = setup();
+    for username in ["alice", "bob", "carol"] {
+        let event = CreateAuditEvent::new(AuditAction::UserLogin, AuditOutcome::Failure)
+            .organization(Some(organization_id))
+            .client(Some("203.0.113.7".to_string()), Some("curl/8.0".to_string()))
+            .details(serde_json::json!({ "username": username }));
+        audit_service.record(event).await.unwrap();
+    }
+    let success = CreateAuditEvent::new(AuditAction::UserLogin, AuditOutcome::Success)
+        .organization(Some(organization_id))
+        .actor("user", 7)
+        .target("user", 7);
+    audit_service.record(success).await.unwrap();
+    let other = CreateAuditEvent::new(AuditAction::AdminAction, AuditOutcome::Success)
+        .organization(Some(other_organization_id))
+        .actor("user", 7);
+    audit_service.record(other).await.unwrap();
+
+    // Só os eventos da organização, do mais recente para o mais antigo
+    let all = audit_service
+        .list(organization_id, &AuditEventFilter::default(), &page(25, 0))
+        .await
+        .unwrap();
+    assert_eq!(all.total, 4);
+    assert_eq!(all.items[0].outcome, AuditOutcome::Success);
+    assert_eq!(all.items[3].details["username"], "alice");
+    assert_eq!(all.items[3].ip_address.as_deref(), Some("203.0.113.7"));
+    // Cada entrada tem o hash do próprio conteúdo e a cadeia do dia confere
+    for event in &all.items {
+        assert_eq!(event.hash, event.expected_hash());
+    }
+    assert!(audit_service
+        .verify(Some(Utc::now().date_naive()))
+        .await
+        .unwrap()
+        .is_intact());
+
+    let failures = AuditEventFilter {
+        action: Some(AuditAction::UserLogin),
+        outcome: Some(AuditOutcome::Failure),
+        ..Default::default()
+    };
+    let second_page = audit_service
+        .list(organization_id, &failures, &page(2, 2))
+        .await
+        .unwrap();
+    assert_eq!(second_page.total, 3);
+    assert_eq!(second_page.items.len(), 1);
+    assert_eq!(second_page.items[0].details["username"], "alice");
+
+    let by_actor = AuditEventFilter {
+        actor_id: Some("7".to_string()),
+        ..Default::default()
+    };
+    let events = audit_service.list(organization_id, &by_actor, &page(25, 0)).await.unwrap();
+    assert_eq!(events.total, 1);
+    assert_eq!(events.items[0].target_id.as_deref(), Some("7"));
+
+    let in_the_future = AuditEventFilter {
+        since: Some(Utc::now() + Duration::hours(1)),
+        ..Default::default()
+    };
+    let events = audit_service.list(organization_id, &in_the_future, &page(25, 0)).await.unwrap();
+    assert_eq!(events.total, 0);
+
+    // Páginas fora dos limites são recusadas
+    let error = audit_service
+        .list(organization_id, &AuditEventFilter::default(), &page(500, 0))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 400);
+}
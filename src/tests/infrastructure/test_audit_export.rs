@@ -0,0 +1,230 @@
+//! Testes da exportação do log de auditoria, com arquivos temporários e listeners locais.
+use std::fs;
+use std::io::{self, Read};
+use std::net::{TcpListener, UdpSocket};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{TimeZone, Utc};
+
+use auth_service::domain::models::audit::{AuditAction, AuditEvent, AuditOutcome};
+use auth_service::domain::services::audit_export::AuditExportService;
+use auth_service::infrastructure::services::audit_export::{
+    cef_message, syslog_message, AuditExportServiceImpl, AuditFormat, AuditSink, AuditSpool, JsonLinesSink,
+    SyslogProtocol, SyslogSink,
+};
+
+fn event(id: i64) -> AuditEvent {
+    AuditEvent {
+        id,
+        organization_id: Some(3),
+        action: AuditAction::UserLogin,
+        outcome: AuditOutcome::Failure,
+        actor_type: None,
+        actor_id: None,
+        target_type: Some("user".to_string()),
+        target_id: Some("42".to_string()),
+        ip_address: Some("203.0.113.7".to_string()),
+        user_agent: Some("curl/8.0".to_string()),
+        details: serde_json::json!({ "username": "a=b|c", "reason": "invalid\ncredentials" }),
+        created_at: Utc.with_ymd_and_hms(2024, 7, 24, 12, 30, 0).unwrap(),
+        prev_hash: String::new(),
+        hash: String::new(),
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "audit-export-{}-{}",
+        name,
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(Instant::now() < deadline, "condition not reached in time");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn events_are_formatted_as_cef_and_rfc_5424() {
+    let cef = cef_message(&event(9));
+    assert!(cef.starts_with("CEF:0|auth_service|auth_service|"));
+    assert!(cef.contains("|user.login|user.login failure|6|rt=1721824200000 externalId=9 outcome=failure"));
+    assert!(cef.contains(" duid=42 src=203.0.113.7 requestClientApplication=curl/8.0 "));
+    assert!(cef.contains("cs2Label=targetType cs2=user cs3Label=organizationId cs3=3"));
+    assert!(!cef.contains("cs1"));
+    // `=`, `\` e quebras de linha são escapados nos valores; `|` só no cabeçalho
+    assert!(cef.contains(r#"msg={"reason":"invalid\\ncredentials","username":"a\=b|c"}"#));
+
+    let message = syslog_message(&event(9), AuditFormat::Json, "auth-1");
+    let prefix = format!("<84>1 2024-07-24T12:30:00.000000Z auth-1 auth_service {} user.login - ", std::process::id());
+    assert!(message.starts_with(&prefix));
+    let json: serde_json::Value = serde_json::from_str(&message[prefix.len()..]).unwrap();
+    assert_eq!(json["action"], "user.login");
+    assert_eq!(json["details"]["username"], "a=b|c");
+}
+
+#[test]
+fn json_lines_file_is_rotated() {
+    let dir = temp_dir("rotation");
+    let path = dir.join("audit.jsonl");
+    let line_size = AuditFormat::Json.render(&event(1)).len() as u64 + 1;
+    let mut sink = JsonLinesSink::new(&path, AuditFormat::Json, line_size * 2, 2);
+    for id in 1..=7 {
+        sink.write(&event(id)).unwrap();
+    }
+
+    let ids = |file: &str| -> Vec<i64> {
+        fs::read_to_string(dir.join(file))
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<AuditEvent>(line).unwrap().id)
+            .collect()
+    };
+    assert_eq!(ids("audit.jsonl"), vec![7]);
+    assert_eq!(ids("audit.jsonl.1"), vec![5, 6]);
+    assert_eq!(ids("audit.jsonl.2"), vec![3, 4]);
+    assert!(!dir.join("audit.jsonl.3").exists());
+
+    // Um novo processo continua o arquivo atual sem passar do limite
+    let mut sink = JsonLinesSink::new(&path, AuditFormat::Json, line_size * 2, 2);
+    sink.write(&event(8)).unwrap();
+    sink.write(&event(9)).unwrap();
+    assert_eq!(ids("audit.jsonl"), vec![9]);
+    assert_eq!(ids("audit.jsonl.1"), vec![7, 8]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn syslog_reaches_a_local_listener_over_udp_and_tcp() {
+    let udp = UdpSocket::bind("127.0.0.1:0").unwrap();
+    udp.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut sink = SyslogSink::new(udp.local_addr().unwrap().to_string(), SyslogProtocol::Udp, AuditFormat::Cef);
+    sink.write(&event(1)).unwrap();
+    let mut datagram = [0u8; 4096];
+    let size = udp.recv(&mut datagram).unwrap();
+    let message = String::from_utf8_lossy(&datagram[..size]).to_string();
+    assert!(message.starts_with("<84>1 2024-07-24T12:30:00.000000Z "));
+    assert!(message.contains(" user.login - CEF:0|auth_service|"));
+
+    let tcp = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut sink = SyslogSink::new(tcp.local_addr().unwrap().to_string(), SyslogProtocol::Tcp, AuditFormat::Json);
+    sink.write(&event(1)).unwrap();
+    sink.write(&event(2)).unwrap();
+    let (mut stream, _) = tcp.accept().unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut received = String::new();
+    while received.matches("\"id\":").count() < 2 || !received.ends_with('}') {
+        let mut buffer = [0u8; 4096];
+        let size = stream.read(&mut buffer).unwrap();
+        received.push_str(&String::from_utf8_lossy(&buffer[..size]));
+    }
+    // Octet counting: cada mensagem vem precedida do tamanho
+    let (length, rest) = received.split_once(' ').unwrap();
+    let first = &rest[..length.parse::<usize>().unwrap()];
+    assert!(first.starts_with("<84>1 ") && first.ends_with('}'));
+    assert!(first.contains("\"id\":1,"));
+    assert!(rest[first.len()..].contains("\"id\":2,"));
+}
+
+/// Destino que pode ser desligado pelo teste.
+struct SwitchableSink {
+    up: Arc<AtomicBool>,
+    delivered: Arc<Mutex<Vec<i64>>>,
+}
+
+impl AuditSink for SwitchableSink {
+    fn write(&mut self, event: &AuditEvent) -> io::Result<()> {
+        if !self.up.load(Ordering::SeqCst) {
+            return Err(io::Error::new(io::ErrorKind::ConnectionRefused, "sink is down"));
+        }
+        self.delivered.lock().unwrap().push(event.id);
+        Ok(())
+    }
+}
+
+#[test]
+fn events_wait_in_the_spool_while_the_sink_is_down() {
+    let dir = temp_dir("spool");
+    let spool_path = dir.join("spool.jsonl");
+    let up = Arc::new(AtomicBool::new(false));
+    let delivered = Arc::new(Mutex::new(Vec::new()));
+    let sink = SwitchableSink {
+        up: up.clone(),
+        delivered: delivered.clone(),
+    };
+    let export_service =
+        AuditExportServiceImpl::new(Box::new(sink), AuditSpool::new(&spool_path), 16, Duration::from_millis(50));
+
+    for id in 1..=3 {
+        export_service.export(&event(id));
+    }
+    let spooled = || fs::read_to_string(&spool_path).map_or(0, |spool| spool.lines().count());
+    wait_until(|| spooled() == 3);
+    assert!(delivered.lock().unwrap().is_empty());
+
+    // De volta ao ar, o spool sai primeiro e na ordem
+    up.store(true, Ordering::SeqCst);
+    export_service.export(&event(4));
+    wait_until(|| delivered.lock().unwrap().len() == 4);
+    assert_eq!(*delivered.lock().unwrap(), vec![1, 2, 3, 4]);
+    assert!(!spool_path.exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn events_that_overflow_the_queue_keep_their_order() {
+    let dir = temp_dir("overflow");
+    let spool_path = dir.join("spool.jsonl");
+    let up = Arc::new(AtomicBool::new(false));
+    let delivered = Arc::new(Mutex::new(Vec::new()));
+    let sink = SwitchableSink {
+        up: up.clone(),
+        delivered: delivered.clone(),
+    };
+    let export_service =
+        AuditExportServiceImpl::new(Box::new(sink), AuditSpool::new(&spool_path), 1, Duration::from_millis(50));
+
+    // Com a fila de um evento só, os seguintes transbordam para o spool atrás dela
+    for id in 1..=5 {
+        export_service.export(&event(id));
+    }
+    let spooled = || fs::read_to_string(&spool_path).map_or(0, |spool| spool.lines().count());
+    wait_until(|| spooled() == 5);
+
+    up.store(true, Ordering::SeqCst);
+    for id in 6..=8 {
+        export_service.export(&event(id));
+    }
+    wait_until(|| delivered.lock().unwrap().len() == 8);
+    assert_eq!(*delivered.lock().unwrap(), (1..=8).collect::<Vec<i64>>());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn events_returned_to_the_spool_come_before_the_ones_spooled_meanwhile() {
+    let dir = temp_dir("restore");
+    let spool = AuditSpool::new(dir.join("spool.jsonl"));
+    for id in 1..=3 {
+        spool.append(&event(id)).unwrap();
+    }
+
+    let taken = spool.take().unwrap();
+    assert!(spool.is_empty());
+    // O destino caiu depois do primeiro evento e um quarto chegou durante o envio
+    spool.append(&event(4)).unwrap();
+    spool.restore(&taken[1..]).unwrap();
+
+    let ids: Vec<i64> = spool.take().unwrap().iter().map(|event| event.id).collect();
+    assert_eq!(ids, vec![2, 3, 4]);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,217 @@
+//! Testes de integração do device authorization grant (RFC 8628), contra o banco de
+//! `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+
+use auth_service::domain::constants::DEVICE_CODE_INTERVAL_SECONDS;
+use auth_service::domain::error::OAuthError;
+use auth_service::domain::models::oauth::{
+    ClientType, CreateOAuthClient, DeviceAuthorization, DeviceAuthorizationRequest, TokenRequest,
+    TokenResponse, DEVICE_CODE_GRANT_TYPE,
+};
+use auth_service::domain::repositories::oauth::OAuthRepository;
+use auth_service::domain::services::oauth::OAuthService;
+use auth_service::domain::services::token::TokenService;
+use auth_service::infrastructure::databases::postgresql::DBConn;
+use auth_service::infrastructure::repositories::oauth::OAuthDieselRepository;
+use auth_service::infrastructure::schema::device_codes;
+use auth_service::services::oauth::OAuthServiceImpl;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::{tenant_with_user, unique};
+use crate::tests::support::services::oauth_service;
+
+struct Fixture {
+    oauth_service: OAuthServiceImpl,
+    token_service: Arc<TokenServiceImpl>,
+    pool: Arc<DBConn>,
+    client_id: String,
+    user_id: i32,
+}
+
+/// Cria uma organização com um usuário e um cliente público que pode usar o device code grant.
+async fn setup() -> Fixture {
+    let (tenant, user_id) = tenant_with_user("device");
+    let client_id = unique("tv");
+    OAuthDieselRepository::new(tenant.pool.clone())
+        .create_client(
+            tenant.organization_id,
+            &client_id,
+            None,
+            None,
+            &CreateOAuthClient {
+                name: "Device code tests".to_string(),
+                client_type: ClientType::Public,
+                redirect_uris: Vec::new(),
+                allowed_scopes: vec!["users:read".to_string()],
+                allowed_audiences: Vec::new(),
+                grant_types: vec![DEVICE_CODE_GRANT_TYPE.to_string()],
+                require_pushed_authorization_requests: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    let token_service = Arc::new(TokenServiceImpl::with_secret("device-code-tests"));
+    Fixture {
+        oauth_service: oauth_service(&tenant.pool, token_service.clone()),
+        token_service,
+        pool: tenant.pool,
+        client_id,
+        user_id,
+    }
+}
+
+impl Fixture {
+    async fn authorize(&self) -> DeviceAuthorization {
+        self.oauth_service
+            .create_device_authorization(DeviceAuthorizationRequest {
+                scope: Some("users:read".to_string()),
+                client_id: Some(self.client_id.clone()),
+                client_secret: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    /// Consulta de `/oauth/token` feita pelo dispositivo.
+    async fn poll(&self, device_code: &str) -> Result<TokenResponse, OAuthError> {
+        self.oauth_service
+            .exchange_token(TokenRequest {
+                grant_type: DEVICE_CODE_GRANT_TYPE.to_string(),
+                client_id: Some(self.client_id.clone()),
+                device_code: Some(device_code.to_string()),
+                ..Default::default()
+            })
+            .await
+    }
+
+    /// Simula a espera do dispositivo: a última consulta passa a ter sido `seconds` atrás.
+    fn wait(&self, device_code: &str, seconds: i64) {
+        diesel::update(device_codes::table.find(hash_secret(device_code)))
+            .set(device_codes::last_polled_at.eq(Utc::now() - Duration::seconds(seconds)))
+            .execute(&mut self.pool.get().unwrap())
+            .unwrap();
+    }
+
+    fn interval(&self, device_code: &str) -> i32 {
+        device_codes::table
+            .find(hash_secret(device_code))
+            .select(device_codes::interval)
+            .first(&mut self.pool.get().unwrap())
+            .unwrap()
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn a_pending_device_code_answers_authorization_pending() {
+    let fixture = setup().await;
+    let authorization = fixture.authorize().await;
+
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "authorization_pending");
+    // o dispositivo que respeita o intervalo continua recebendo a mesma resposta
+    fixture.wait(&authorization.device_code, DEVICE_CODE_INTERVAL_SECONDS.into());
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "authorization_pending");
+    assert_eq!(fixture.interval(&authorization.device_code), DEVICE_CODE_INTERVAL_SECONDS);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn polling_too_soon_answers_slow_down_and_raises_the_interval() {
+    let fixture = setup().await;
+    let authorization = fixture.authorize().await;
+    fixture.poll(&authorization.device_code).await.unwrap_err();
+
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "slow_down");
+    let interval = fixture.interval(&authorization.device_code);
+    assert!(interval > DEVICE_CODE_INTERVAL_SECONDS);
+
+    // o intervalo original não basta mais
+    fixture.wait(&authorization.device_code, DEVICE_CODE_INTERVAL_SECONDS.into());
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "slow_down");
+    assert!(fixture.interval(&authorization.device_code) > interval);
+
+    fixture.wait(&authorization.device_code, fixture.interval(&authorization.device_code).into());
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "authorization_pending");
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn an_expired_device_code_answers_expired_token() {
+    let fixture = setup().await;
+    let authorization = fixture.authorize().await;
+    diesel::update(device_codes::table.find(hash_secret(&authorization.device_code)))
+        .set(device_codes::expires_at.eq(Utc::now() - Duration::seconds(1)))
+        .execute(&mut fixture.pool.get().unwrap())
+        .unwrap();
+
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "expired_token");
+    // nem a aprovação o recupera
+    assert!(fixture
+        .oauth_service
+        .approve_device_code(&authorization.user_code, fixture.user_id)
+        .await
+        .is_err());
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn a_denied_device_code_answers_access_denied() {
+    let fixture = setup().await;
+    let authorization = fixture.authorize().await;
+    fixture.poll(&authorization.device_code).await.unwrap_err();
+
+    fixture
+        .oauth_service
+        .deny_device_code(&authorization.user_code)
+        .await
+        .unwrap();
+    fixture.wait(&authorization.device_code, DEVICE_CODE_INTERVAL_SECONDS.into());
+
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "access_denied");
+    // a decisão é definitiva
+    let error = fixture
+        .oauth_service
+        .approve_device_code(&authorization.user_code, fixture.user_id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 404);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn an_approved_device_code_is_exchanged_only_once() {
+    let fixture = setup().await;
+    let authorization = fixture.authorize().await;
+    fixture.poll(&authorization.device_code).await.unwrap_err();
+
+    // o usuário digita o código sem o hífen
+    fixture
+        .oauth_service
+        .approve_device_code(&authorization.user_code.replace('-', ""), fixture.user_id)
+        .await
+        .unwrap();
+    fixture.wait(&authorization.device_code, DEVICE_CODE_INTERVAL_SECONDS.into());
+
+    let response = fixture.poll(&authorization.device_code).await.unwrap();
+    assert_eq!(response.scope.as_deref(), Some("users:read"));
+    let claim = fixture.token_service.validate(response.access_token).await.unwrap();
+    assert_eq!(claim.sub, fixture.user_id.to_string());
+
+    fixture.wait(&authorization.device_code, DEVICE_CODE_INTERVAL_SECONDS.into());
+    let error = fixture.poll(&authorization.device_code).await.unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+}
@@ -0,0 +1,225 @@
+//! Testes de integração do outbox e dos webhooks contra o banco de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use chrono::Utc;
+use diesel::prelude::*;
+
+use auth_service::domain::models::outbox::{CreateWebhook, DeliveryStatus, OutboxEventType, WebhookDeliveryFilter};
+use auth_service::domain::models::user::CreateUser;
+use auth_service::domain::repositories::repository::QueryParamsImpl;
+use auth_service::domain::repositories::user::UserRepository;
+use auth_service::domain::services::webhook::WebhookService;
+use auth_service::infrastructure::databases::postgresql::DBConn;
+use auth_service::infrastructure::repositories::outbox::OutboxDieselRepository;
+use auth_service::infrastructure::repositories::user::UserDieselRepository;
+use auth_service::infrastructure::schema::outbox;
+use auth_service::infrastructure::services::webhook_sender::HttpWebhookSender;
+use auth_service::services::webhook::{webhook_signature, WebhookServiceImpl};
+
+use crate::tests::support::database::tenant;
+
+struct Fixture {
+    user_repository: UserDieselRepository,
+    webhook_service: WebhookServiceImpl,
+    pool: Arc<DBConn>,
+    organization_id: i32,
+}
+
+/// Cria uma organização vazia.
+fn setup() -> Fixture {
+    let tenant = tenant("outbox");
+    Fixture {
+        user_repository: UserDieselRepository::new(tenant.pool.clone()),
+        webhook_service: WebhookServiceImpl::new(
+            Arc::new(OutboxDieselRepository::new(tenant.pool.clone())),
+            Arc::new(HttpWebhookSender::new()),
+        ),
+        pool: tenant.pool,
+        organization_id: tenant.organization_id,
+    }
+}
+
+fn new_user(username: &str) -> CreateUser {
+    CreateUser {
+        username: username.to_string(),
+        password: "password".to_string(),
+        email: format!("{}@example.com", username),
+        organization: None,
+    }
+}
+
+/// Tipo e payload dos eventos da organização, em ordem.
+fn events(fixture: &Fixture) -> Vec<(String, serde_json::Value)> {
+    let mut conn = fixture.pool.get().unwrap();
+    outbox::table
+        .filter(outbox::organization_id.eq(fixture.organization_id))
+        .order(outbox::id)
+        .select((outbox::event_type, outbox::payload))
+        .load::<(String, serde_json::Value)>(&mut conn)
+        .unwrap()
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn user_changes_write_events_in_the_same_transaction() {
+    let fixture = setup();
+    let repository = &fixture.user_repository;
+    let alice = repository.create(fixture.organization_id, &new_user("alice")).await.unwrap();
+    // a inserção que falha não deixa evento
+    assert!(repository.create(fixture.organization_id, &new_user("alice")).await.is_err());
+    repository
+        .update_email(fixture.organization_id, alice.id, "alice@example.org")
+        .await
+        .unwrap();
+    // o mesmo e-mail não é uma mudança
+    repository
+        .update_email(fixture.organization_id, alice.id, "alice@example.org")
+        .await
+        .unwrap();
+    assert_eq!(repository.delete(fixture.organization_id, alice.id).await.unwrap(), 1);
+
+    let events = events(&fixture);
+    let types: Vec<&str> = events.iter().map(|(event_type, _)| event_type.as_str()).collect();
+    assert_eq!(types, ["user.created", "user.email_changed", "user.deleted"]);
+    assert_eq!(events[0].1["username"], "alice");
+    assert!(events[0].1.get("password").is_none());
+    assert_eq!(events[1].1["email"], "alice@example.org");
+    assert_eq!(events[1].1["previous_email"], "alice@example.com");
+    assert_eq!(events[1].1["email_verified"], false);
+}
+
+#[derive(Clone)]
+struct ReceivedRequest {
+    timestamp: String,
+    signature: String,
+    body: String,
+}
+
+/// Responde `status` a cada requisição e guarda a assinatura e o corpo recebidos.
+fn webhook_receiver(status: u16) -> (String, Arc<Mutex<Vec<ReceivedRequest>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://127.0.0.1:{}/hooks", listener.local_addr().unwrap().port());
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let requests = received.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let (mut length, mut timestamp, mut signature) = (0, String::new(), String::new());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                let (name, value) = line.split_once(": ").unwrap_or((line, ""));
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => length = value.parse().unwrap(),
+                    "x-webhook-timestamp" => timestamp = value.to_string(),
+                    "x-webhook-signature" => signature = value.to_string(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0u8; length];
+            reader.read_exact(&mut body).unwrap();
+            requests.lock().unwrap().push(ReceivedRequest {
+                timestamp,
+                signature,
+                body: String::from_utf8(body).unwrap(),
+            });
+            write!(stream, "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status).unwrap();
+        }
+    });
+    (url, received)
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn events_are_delivered_retried_and_replayed() {
+    let fixture = setup();
+    let (up_url, received) = webhook_receiver(200);
+    let (down_url, _) = webhook_receiver(503);
+    let webhook_service = &fixture.webhook_service;
+    let up = webhook_service
+        .create(
+            fixture.organization_id,
+            CreateWebhook {
+                url: up_url,
+                event_types: vec![OutboxEventType::UserCreated],
+            },
+        )
+        .await
+        .unwrap();
+    let down = webhook_service
+        .create(
+            fixture.organization_id,
+            CreateWebhook {
+                url: down_url,
+                event_types: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(down.event_types, OutboxEventType::ALL);
+    fixture
+        .user_repository
+        .create(fixture.organization_id, &new_user("bob"))
+        .await
+        .unwrap();
+
+    webhook_service.dispatch().await.unwrap();
+    let request = received.lock().unwrap()[0].clone();
+    assert_eq!(
+        request.signature,
+        webhook_signature(&up.secret, request.timestamp.parse().unwrap(), &request.body)
+    );
+    let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(body["type"], "user.created");
+    assert_eq!(body["data"]["username"], "bob");
+
+    let params = QueryParamsImpl {
+        limit: Some(10),
+        offset: None,
+    };
+    let deliveries = |webhook_id| WebhookDeliveryFilter {
+        webhook_id: Some(webhook_id),
+        ..Default::default()
+    };
+    let delivered = webhook_service
+        .list_deliveries(fixture.organization_id, &deliveries(up.id), &params)
+        .await
+        .unwrap();
+    assert_eq!(delivered.items[0].status, DeliveryStatus::Delivered);
+    let failed = webhook_service
+        .list_deliveries(fixture.organization_id, &deliveries(down.id), &params)
+        .await
+        .unwrap();
+    let failed = &failed.items[0];
+    assert_eq!(failed.status, DeliveryStatus::Pending);
+    assert_eq!(failed.attempts, 1);
+    assert_eq!(failed.last_error.as_deref(), Some("Webhook answered with status 503"));
+    assert!(failed.next_attempt_at > Utc::now());
+
+    // o reenvio volta as duas entregas para a fila
+    assert_eq!(
+        webhook_service
+            .replay(fixture.organization_id, failed.outbox_event_id)
+            .await
+            .unwrap(),
+        2
+    );
+    webhook_service.dispatch().await.unwrap();
+    assert_eq!(received.lock().unwrap().len(), 2);
+    // o evento de outra organização não é encontrado
+    let error = webhook_service
+        .replay(fixture.organization_id + 1_000_000, failed.outbox_event_id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 404);
+}
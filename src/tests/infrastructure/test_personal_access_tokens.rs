@@ -0,0 +1,224 @@
+//! Testes de integração dos personal access tokens contra o banco de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use auth_service::domain::constants::PERSONAL_ACCESS_TOKEN_PREFIX;
+use auth_service::domain::models::personal_access_token::CreatePersonalAccessToken;
+use auth_service::domain::models::role::{CreatePermission, CreateRole};
+use auth_service::domain::models::user::{CreateServiceAccount, PrincipalType};
+use auth_service::domain::services::authorization::AuthorizationService;
+use auth_service::domain::services::personal_access_token::PersonalAccessTokenService;
+use auth_service::domain::services::service_account::ServiceAccountService;
+use auth_service::domain::services::token::TokenService;
+use auth_service::infrastructure::repositories::personal_access_token::PersonalAccessTokenDieselRepository;
+use auth_service::infrastructure::repositories::user::UserDieselRepository;
+use auth_service::services::authorization::AuthorizationServiceImpl;
+use auth_service::services::personal_access_token::{
+    PersonalAccessTokenAwareTokenService, PersonalAccessTokenServiceImpl,
+};
+use auth_service::services::service_account::ServiceAccountServiceImpl;
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::{tenant_with_user, unique};
+use crate::tests::support::services::authorization_service;
+
+struct Fixture {
+    service: Arc<PersonalAccessTokenServiceImpl>,
+    service_accounts: ServiceAccountServiceImpl,
+    authorization_service: Arc<AuthorizationServiceImpl>,
+    role_id: i32,
+    token_service: PersonalAccessTokenAwareTokenService,
+    organization_id: i32,
+    user_id: i32,
+    read_permission: String,
+}
+
+/// Cria uma organização com um usuário que tem uma permissão de leitura, por um papel.
+async fn setup() -> Fixture {
+    let (tenant, user_id) = tenant_with_user("pat");
+    let (pool, organization_id) = (tenant.pool, tenant.organization_id);
+    let authorization_service = authorization_service(&pool);
+    let read_permission = format!("{}:read", unique("reports"));
+    let permission = authorization_service
+        .create_permission(CreatePermission {
+            name: read_permission.clone(),
+            description: None,
+        })
+        .await
+        .unwrap();
+    let role = authorization_service
+        .create_role(
+            organization_id,
+            CreateRole {
+                name: "reader".to_string(),
+                description: None,
+            },
+        )
+        .await
+        .unwrap();
+    authorization_service
+        .grant_permission(organization_id, role.id, permission.id)
+        .await
+        .unwrap();
+    authorization_service
+        .assign_role(organization_id, user_id, role.id)
+        .await
+        .unwrap();
+
+    let user_repository = Arc::new(UserDieselRepository::new(pool.clone()));
+    let service = Arc::new(PersonalAccessTokenServiceImpl::new(
+        Arc::new(PersonalAccessTokenDieselRepository::new(pool)),
+        user_repository.clone(),
+        authorization_service.clone(),
+    ));
+    Fixture {
+        service_accounts: ServiceAccountServiceImpl::new(user_repository, service.clone()),
+        authorization_service,
+        role_id: role.id,
+        token_service: PersonalAccessTokenAwareTokenService::new(
+            Arc::new(TokenServiceImpl::with_secret("pat-tests")),
+            service.clone(),
+        ),
+        service,
+        organization_id,
+        user_id,
+        read_permission,
+    }
+}
+
+fn new_token(scope: &str) -> CreatePersonalAccessToken {
+    CreatePersonalAccessToken {
+        name: "CI".to_string(),
+        scope: scope.to_string(),
+        expires_at: None,
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn personal_access_tokens_validate_as_their_user_until_revoked() {
+    let fixture = setup().await;
+    let issued = fixture
+        .service
+        .create(fixture.organization_id, fixture.user_id, new_token(&fixture.read_permission))
+        .await
+        .unwrap();
+    assert!(issued.token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX));
+    assert!(issued.token.starts_with(&issued.personal_access_token.token_hint));
+
+    let claim = fixture.token_service.validate(issued.token.clone()).await.unwrap();
+    assert_eq!(claim.sub, fixture.user_id.to_string());
+    assert_eq!(claim.tenant, Some(fixture.organization_id));
+    assert_eq!(claim.permissions, vec![fixture.read_permission.clone()]);
+    assert!(claim.roles.is_empty());
+    assert_eq!(claim.pat, Some(issued.personal_access_token.id));
+
+    let listed = fixture.service.list(fixture.user_id).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0].last_used_at.is_some());
+    // um token trocado a partir do personal access token o referencia em `pat`
+    let exchanged = fixture.token_service.create_with_claim(claim).await.unwrap();
+    fixture.token_service.validate(exchanged.clone()).await.unwrap();
+
+    fixture
+        .service
+        .revoke(fixture.user_id, issued.personal_access_token.id)
+        .await
+        .unwrap();
+    let error = fixture.token_service.validate(issued.token).await.unwrap_err();
+    assert_eq!(error.code, 401);
+    let error = fixture.token_service.validate(exchanged).await.unwrap_err();
+    assert_eq!(error.code, 401);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn personal_access_tokens_cannot_exceed_the_user_permissions_or_outlive_expiry() {
+    let fixture = setup().await;
+
+    let error = fixture
+        .service
+        .create(
+            fixture.organization_id,
+            fixture.user_id,
+            new_token(&format!("{} users:write", fixture.read_permission)),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 400);
+
+    let issued = fixture
+        .service
+        .create(
+            fixture.organization_id,
+            fixture.user_id,
+            CreatePersonalAccessToken {
+                expires_at: Some(Utc::now() + Duration::seconds(1)),
+                ..new_token(&fixture.read_permission)
+            },
+        )
+        .await
+        .unwrap();
+    actix_web::rt::time::sleep(std::time::Duration::from_millis(1100)).await;
+    let error = fixture.token_service.validate(issued.token).await.unwrap_err();
+    assert_eq!(error.code, 401);
+
+    let error = fixture
+        .service
+        .revoke(fixture.user_id + 1, issued.personal_access_token.id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 404);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn service_account_api_keys_authenticate_as_service_principals() {
+    let fixture = setup().await;
+    let account = fixture
+        .service_accounts
+        .create(
+            fixture.organization_id,
+            CreateServiceAccount {
+                name: "deploy-bot".to_string(),
+                owner_user_id: Some(fixture.user_id),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(account.principal_type, PrincipalType::Service);
+    assert!(account.password.is_none());
+
+    // sem papéis, a conta não tem permissões para delegar à chave
+    let error = fixture
+        .service_accounts
+        .create_token(fixture.organization_id, account.id, new_token(&fixture.read_permission))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 400);
+
+    fixture
+        .authorization_service
+        .assign_role(fixture.organization_id, account.id, fixture.role_id)
+        .await
+        .unwrap();
+    let issued = fixture
+        .service_accounts
+        .create_token(fixture.organization_id, account.id, new_token(&fixture.read_permission))
+        .await
+        .unwrap();
+    let claim = fixture.token_service.validate(issued.token).await.unwrap();
+    assert_eq!(claim.sub, account.id.to_string());
+    assert_eq!(claim.principal_type, Some(PrincipalType::Service));
+
+    // usuários não são contas de serviço
+    let error = fixture
+        .service_accounts
+        .list_tokens(fixture.organization_id, fixture.user_id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 404);
+}
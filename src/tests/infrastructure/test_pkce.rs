@@ -0,0 +1,163 @@
+//! Testes de integração do PKCE no fluxo de authorization code, contra o banco de
+//! `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use auth_service::domain::models::oauth::{
+    AuthorizationRequest, ClientType, CreateOAuthClient, OAuthClient, TokenRequest,
+};
+use auth_service::domain::repositories::oauth::OAuthRepository;
+use auth_service::domain::services::oauth::OAuthService;
+use auth_service::infrastructure::repositories::oauth::OAuthDieselRepository;
+use auth_service::services::oauth::OAuthServiceImpl;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::{tenant_with_user, unique};
+use crate::tests::support::services::oauth_service;
+
+const REDIRECT_URI: &str = "https://app.example.com/callback";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mJ92K9XbGuU9kDrQMyYb6uUjp9khbQ";
+
+struct Fixture {
+    oauth_service: OAuthServiceImpl,
+    client: OAuthClient,
+    user_id: i32,
+}
+
+/// Cria uma organização com um usuário e um cliente público.
+async fn setup() -> Fixture {
+    let (tenant, user_id) = tenant_with_user("pkce");
+    let client = OAuthDieselRepository::new(tenant.pool.clone())
+        .create_client(
+            tenant.organization_id,
+            &unique("client"),
+            None,
+            None,
+            &CreateOAuthClient {
+                name: "PKCE tests".to_string(),
+                client_type: ClientType::Public,
+                redirect_uris: vec![REDIRECT_URI.to_string()],
+                allowed_scopes: vec!["users:read".to_string()],
+                allowed_audiences: Vec::new(),
+                grant_types: ClientType::Public.default_grant_types(),
+                require_pushed_authorization_requests: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    Fixture {
+        oauth_service: oauth_service(&tenant.pool, Arc::new(TokenServiceImpl::with_secret("pkce-tests"))),
+        client,
+        user_id,
+    }
+}
+
+fn authorization_request(client: &OAuthClient, code_challenge_method: Option<&str>) -> AuthorizationRequest {
+    AuthorizationRequest {
+        response_type: "code".to_string(),
+        client_id: client.client_id.clone(),
+        redirect_uri: Some(REDIRECT_URI.to_string()),
+        scope: Some("users:read".to_string()),
+        code_challenge: code_challenge_method.map(|_| hash_secret(CODE_VERIFIER)),
+        code_challenge_method: code_challenge_method.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+/// Emite um código com desafio S256 e aprova o consentimento, deixando-o pronto para a troca.
+async fn issue_code(fixture: &Fixture) -> String {
+    let issued = fixture
+        .oauth_service
+        .create_authorization_code(
+            &fixture.client,
+            &authorization_request(&fixture.client, Some("S256")),
+            fixture.user_id,
+        )
+        .await
+        .unwrap();
+    if issued.consent_required {
+        fixture
+            .oauth_service
+            .decide_consent(&fixture.client, &issued.code, true)
+            .await
+            .unwrap();
+    }
+    issued.code
+}
+
+fn code_request(client: &OAuthClient, code: &str, code_verifier: Option<&str>) -> TokenRequest {
+    TokenRequest {
+        grant_type: "authorization_code".to_string(),
+        client_id: Some(client.client_id.clone()),
+        code: Some(code.to_string()),
+        redirect_uri: Some(REDIRECT_URI.to_string()),
+        code_verifier: code_verifier.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn the_code_verifier_must_match_the_challenge() {
+    let fixture = setup().await;
+
+    let code = issue_code(&fixture).await;
+    let wrong = "wrong-verifier-wrong-verifier-wrong-verifier";
+    let error = fixture
+        .oauth_service
+        .exchange_token(code_request(&fixture.client, &code, Some(wrong)))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+
+    let code = issue_code(&fixture).await;
+    let error = fixture
+        .oauth_service
+        .exchange_token(code_request(&fixture.client, &code, None))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+
+    let code = issue_code(&fixture).await;
+    let response = fixture
+        .oauth_service
+        .exchange_token(code_request(&fixture.client, &code, Some(CODE_VERIFIER)))
+        .await
+        .unwrap();
+    assert_eq!(response.token_type, "Bearer");
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn public_clients_must_send_an_s256_challenge() {
+    let fixture = setup().await;
+
+    for method in [None, Some("plain")] {
+        let error = fixture
+            .oauth_service
+            .create_authorization_code(
+                &fixture.client,
+                &authorization_request(&fixture.client, method),
+                fixture.user_id,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.error, "invalid_request");
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn an_authorization_code_is_redeemed_only_once() {
+    let fixture = setup().await;
+    let code = issue_code(&fixture).await;
+    let request = code_request(&fixture.client, &code, Some(CODE_VERIFIER));
+
+    fixture.oauth_service.exchange_token(request.clone()).await.unwrap();
+    let error = fixture.oauth_service.exchange_token(request).await.unwrap_err();
+
+    assert_eq!(error.error, "invalid_grant");
+}
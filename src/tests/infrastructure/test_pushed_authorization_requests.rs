@@ -0,0 +1,145 @@
+//! Testes de integração de `/oauth/par` (RFC 9126) contra o banco de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use auth_service::domain::models::oauth::{
+    AuthorizationRequest, ClientType, CreateOAuthClient, OAuthClient, PushedAuthorizationRequest,
+};
+use auth_service::domain::repositories::oauth::OAuthRepository;
+use auth_service::domain::services::oauth::OAuthService;
+use auth_service::infrastructure::repositories::oauth::OAuthDieselRepository;
+use auth_service::services::oauth::OAuthServiceImpl;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::{tenant_with_user, unique};
+use crate::tests::support::services::oauth_service;
+
+const CLIENT_SECRET: &str = "par-secret";
+const REDIRECT_URI: &str = "https://app.example.com/callback";
+
+struct Fixture {
+    oauth_service: OAuthServiceImpl,
+    client: OAuthClient,
+    user_id: i32,
+}
+
+/// Cria uma organização com um usuário e um cliente confidencial que exige PAR.
+async fn setup() -> Fixture {
+    let (tenant, user_id) = tenant_with_user("par");
+    let client = OAuthDieselRepository::new(tenant.pool.clone())
+        .create_client(
+            tenant.organization_id,
+            &unique("client"),
+            Some(&hash_secret(CLIENT_SECRET)),
+            None,
+            &CreateOAuthClient {
+                name: "PAR tests".to_string(),
+                client_type: ClientType::Confidential,
+                redirect_uris: vec![REDIRECT_URI.to_string()],
+                allowed_scopes: vec!["users:read".to_string()],
+                allowed_audiences: Vec::new(),
+                grant_types: ClientType::Confidential.default_grant_types(),
+                require_pushed_authorization_requests: true,
+            },
+        )
+        .await
+        .unwrap();
+
+    Fixture {
+        oauth_service: oauth_service(&tenant.pool, Arc::new(TokenServiceImpl::with_secret("par-tests"))),
+        client,
+        user_id,
+    }
+}
+
+fn authorization_request(client: &OAuthClient) -> AuthorizationRequest {
+    AuthorizationRequest {
+        response_type: "code".to_string(),
+        client_id: client.client_id.clone(),
+        redirect_uri: Some(REDIRECT_URI.to_string()),
+        scope: Some("users:read".to_string()),
+        state: Some("xyz".to_string()),
+        ..Default::default()
+    }
+}
+
+fn pushed_request(client: &OAuthClient, client_secret: &str) -> PushedAuthorizationRequest {
+    PushedAuthorizationRequest {
+        request: authorization_request(client),
+        client_id: Some(client.client_id.clone()),
+        client_secret: Some(client_secret.to_string()),
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn request_uri_resolves_to_the_pushed_parameters_and_issues_one_code() {
+    let fixture = setup().await;
+    let pushed = fixture
+        .oauth_service
+        .push_authorization_request(pushed_request(&fixture.client, CLIENT_SECRET))
+        .await
+        .unwrap();
+    assert!(pushed.request_uri.starts_with("urn:ietf:params:oauth:request_uri:"));
+
+    // Só o client_id e o request_uri vêm na URL; os demais parâmetros são ignorados
+    let from_browser = AuthorizationRequest {
+        client_id: fixture.client.client_id.clone(),
+        request_uri: Some(pushed.request_uri.clone()),
+        state: Some("forged".to_string()),
+        ..Default::default()
+    };
+    let request = fixture
+        .oauth_service
+        .resolve_authorization_request(&fixture.client, from_browser)
+        .await
+        .unwrap();
+    assert_eq!(request.state.as_deref(), Some("xyz"));
+    assert_eq!(request.redirect_uri.as_deref(), Some(REDIRECT_URI));
+
+    fixture
+        .oauth_service
+        .create_authorization_code(&fixture.client, &request, fixture.user_id)
+        .await
+        .unwrap();
+    let error = fixture
+        .oauth_service
+        .create_authorization_code(&fixture.client, &request, fixture.user_id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_request_uri");
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn clients_that_require_par_cannot_send_parameters_in_the_url() {
+    let fixture = setup().await;
+
+    let error = fixture
+        .oauth_service
+        .resolve_authorization_request(&fixture.client, authorization_request(&fixture.client))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_request");
+
+    let error = fixture
+        .oauth_service
+        .push_authorization_request(pushed_request(&fixture.client, "wrong"))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_client");
+
+    let unknown = AuthorizationRequest {
+        client_id: fixture.client.client_id.clone(),
+        request_uri: Some("urn:ietf:params:oauth:request_uri:unknown".to_string()),
+        ..Default::default()
+    };
+    let error = fixture
+        .oauth_service
+        .resolve_authorization_request(&fixture.client, unknown)
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_request_uri");
+}
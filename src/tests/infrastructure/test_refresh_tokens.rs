@@ -0,0 +1,187 @@
+//! Testes de integração da rotação de refresh tokens e da detecção de reuso.
+//!
+//! Assim como os de row-level security, rodam contra o banco de `TEST_DATABASE_URL` e ficam
+//! marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use auth_service::domain::models::oauth::{ClientType, CreateOAuthClient, TokenRequest};
+use auth_service::domain::models::token::CreateRefreshToken;
+use auth_service::domain::repositories::oauth::OAuthRepository;
+use auth_service::domain::repositories::token::TokenRepository;
+use auth_service::domain::repositories::user::UserRepository;
+use auth_service::domain::services::oauth::OAuthService;
+use auth_service::infrastructure::repositories::oauth::OAuthDieselRepository;
+use auth_service::infrastructure::repositories::token::TokenDieselRepository;
+use auth_service::infrastructure::repositories::user::UserDieselRepository;
+use auth_service::services::oauth::OAuthServiceImpl;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::{tenant_with_user, unique};
+use crate::tests::support::services::oauth_service;
+
+struct Fixture {
+    oauth_service: OAuthServiceImpl,
+    token_repository: TokenDieselRepository,
+    user_repository: UserDieselRepository,
+    organization_id: i32,
+    user_id: i32,
+    client_id: String,
+    refresh_token: String,
+}
+
+/// Cria uma organização com um usuário, um cliente público e um refresh token inicial.
+async fn setup() -> Fixture {
+    let (tenant, user_id) = tenant_with_user("refresh");
+    let client_id = unique("client");
+    let client = OAuthDieselRepository::new(tenant.pool.clone())
+        .create_client(
+            tenant.organization_id,
+            &client_id,
+            None,
+            None,
+            &CreateOAuthClient {
+                name: "Refresh tests".to_string(),
+                client_type: ClientType::Public,
+                redirect_uris: vec!["https://app.example.com/callback".to_string()],
+                allowed_scopes: vec!["users:read".to_string()],
+                allowed_audiences: Vec::new(),
+                grant_types: ClientType::Public.default_grant_types(),
+                require_pushed_authorization_requests: false,
+            },
+        )
+        .await
+        .unwrap();
+    let token_repository = TokenDieselRepository::new(tenant.pool.clone());
+    let refresh_token = unique("initial");
+    token_repository
+        .create_refresh_token(&CreateRefreshToken {
+            token_hash: hash_secret(&refresh_token),
+            family_id: unique("family"),
+            oauth_client_id: client.id,
+            user_id,
+            scope: "users:read".to_string(),
+            expires_at: Utc::now() + Duration::hours(1),
+            dpop_jkt: None,
+        })
+        .await
+        .unwrap();
+
+    Fixture {
+        oauth_service: oauth_service(
+            &tenant.pool,
+            Arc::new(TokenServiceImpl::with_secret("refresh-token-tests")),
+        ),
+        token_repository,
+        user_repository: UserDieselRepository::new(tenant.pool),
+        organization_id: tenant.organization_id,
+        user_id,
+        client_id,
+        refresh_token,
+    }
+}
+
+fn refresh_request(client_id: &str, refresh_token: &str) -> TokenRequest {
+    TokenRequest {
+        grant_type: "refresh_token".to_string(),
+        client_id: Some(client_id.to_string()),
+        refresh_token: Some(refresh_token.to_string()),
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn refreshing_rotates_the_token_within_the_family() {
+    let fixture = setup().await;
+
+    let response = fixture
+        .oauth_service
+        .exchange_token(refresh_request(&fixture.client_id, &fixture.refresh_token))
+        .await
+        .unwrap();
+
+    let rotated = response.refresh_token.expect("a new refresh token");
+    assert_ne!(rotated, fixture.refresh_token);
+    let old = fixture
+        .token_repository
+        .get_refresh_token(&hash_secret(&fixture.refresh_token))
+        .await
+        .unwrap();
+    let new = fixture
+        .token_repository
+        .get_refresh_token(&hash_secret(&rotated))
+        .await
+        .unwrap();
+    assert!(old.used_at.is_some());
+    assert_eq!(new.family_id, old.family_id);
+    assert_eq!(new.scope, old.scope);
+    assert!(new.used_at.is_none() && new.revoked_at.is_none());
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn a_used_refresh_token_cannot_be_exchanged_again() {
+    let fixture = setup().await;
+    let request = refresh_request(&fixture.client_id, &fixture.refresh_token);
+
+    fixture.oauth_service.exchange_token(request.clone()).await.unwrap();
+    let error = fixture.oauth_service.exchange_token(request).await.unwrap_err();
+
+    assert_eq!(error.error, "invalid_grant");
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn reusing_a_refresh_token_revokes_the_family() {
+    let fixture = setup().await;
+
+    let rotated = fixture
+        .oauth_service
+        .exchange_token(refresh_request(&fixture.client_id, &fixture.refresh_token))
+        .await
+        .unwrap()
+        .refresh_token
+        .unwrap();
+    // Um atacante reusa o token já trocado...
+    let reuse = fixture
+        .oauth_service
+        .exchange_token(refresh_request(&fixture.client_id, &fixture.refresh_token))
+        .await;
+    assert!(reuse.is_err());
+
+    // ...e o token legítimo da família também deixa de valer.
+    let revoked = fixture
+        .token_repository
+        .get_refresh_token(&hash_secret(&rotated))
+        .await
+        .unwrap();
+    assert!(revoked.revoked_at.is_some());
+    let error = fixture
+        .oauth_service
+        .exchange_token(refresh_request(&fixture.client_id, &rotated))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn refresh_tokens_are_refused_while_a_password_reset_is_pending() {
+    let fixture = setup().await;
+    fixture
+        .user_repository
+        .require_password_reset(fixture.organization_id, fixture.user_id)
+        .await
+        .unwrap();
+
+    let error = fixture
+        .oauth_service
+        .exchange_token(refresh_request(&fixture.client_id, &fixture.refresh_token))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.error, "invalid_grant");
+}
@@ -0,0 +1,100 @@
+//! Testes de integração das políticas de row-level security.
+//!
+//! Rodam contra o banco de `TEST_DATABASE_URL` e ficam marcados com `#[ignore]`; rode-os com
+//! `cargo test -- --ignored`, que falha se a variável não estiver definida. O usuário da URL
+//! não pode ser superusuário nem ter `BYPASSRLS`, pois esses ignoram as políticas.
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+use auth_service::domain::repositories::user::UserRepository;
+use auth_service::infrastructure::databases::postgresql::with_tenant;
+use auth_service::infrastructure::models::user::UserDiesel;
+use auth_service::infrastructure::repositories::user::UserDieselRepository;
+use auth_service::infrastructure::schema::{roles, user_roles, users};
+
+use crate::tests::support::database::{connect, create_organization, new_user, pool, unique};
+
+/// Cria duas organizações, cada uma com um usuário que tem um papel.
+fn seed(conn: &mut PgConnection) -> (i32, i32) {
+    let tenant_a = create_organization(conn, &unique("rls-a"));
+    let tenant_b = create_organization(conn, &unique("rls-b"));
+    for organization_id in [tenant_a, tenant_b] {
+        with_tenant(conn, organization_id, |conn| {
+            let user_id = diesel::insert_into(users::table)
+                .values(new_user(organization_id, "alice"))
+                .returning(users::id)
+                .get_result::<i32>(conn)?;
+            let role_id = diesel::insert_into(roles::table)
+                .values((roles::name.eq("member"), roles::organization_id.eq(organization_id)))
+                .returning(roles::id)
+                .get_result::<i32>(conn)?;
+            diesel::insert_into(user_roles::table)
+                .values((user_roles::user_id.eq(user_id), user_roles::role_id.eq(role_id)))
+                .execute(conn)
+        })
+        .unwrap();
+    }
+    (tenant_a, tenant_b)
+}
+
+#[test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+fn unfiltered_queries_only_see_the_current_tenant() {
+    let mut conn = connect();
+    let (tenant_a, tenant_b) = seed(&mut conn);
+
+    // Consultas "esquecidas" sem filtro de organização
+    let (visible_users, visible_user_roles) = with_tenant(&mut conn, tenant_a, |conn| {
+        let users = users::table.load::<UserDiesel>(conn)?;
+        let user_roles = user_roles::table
+            .select((user_roles::user_id, user_roles::role_id))
+            .load::<(i32, i32)>(conn)?;
+        Ok((users, user_roles))
+    })
+    .unwrap();
+
+    assert!(!visible_users.is_empty());
+    assert!(visible_users.iter().all(|user| user.organization_id == tenant_a));
+    assert!(!visible_users.iter().any(|user| user.organization_id == tenant_b));
+    assert_eq!(visible_user_roles.len(), visible_users.len());
+
+    // Sem tenant definido, nada fica visível
+    assert_eq!(users::table.count().get_result::<i64>(&mut conn).unwrap(), 0);
+    assert_eq!(user_roles::table.count().get_result::<i64>(&mut conn).unwrap(), 0);
+}
+
+#[test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+fn writes_into_another_tenant_are_rejected() {
+    let mut conn = connect();
+    let (tenant_a, tenant_b) = seed(&mut conn);
+
+    let insert = with_tenant(&mut conn, tenant_a, |conn| {
+        diesel::insert_into(users::table)
+            .values(new_user(tenant_b, "mallory"))
+            .execute(conn)
+    });
+    assert!(insert.is_err());
+
+    let updated = with_tenant(&mut conn, tenant_a, |conn| {
+        diesel::update(users::table.filter(users::organization_id.eq(tenant_b)))
+            .set(users::password.eq("hijacked"))
+            .execute(conn)
+    })
+    .unwrap();
+    assert_eq!(updated, 0);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn repository_reads_are_tenant_scoped() {
+    let (tenant_a, tenant_b) = seed(&mut connect());
+    let repository = UserDieselRepository::new(pool());
+
+    let alice_a = repository.get_by_username(tenant_a, "alice").await.unwrap();
+    let alice_b = repository.get_by_username(tenant_b, "alice").await.unwrap();
+
+    assert_eq!(alice_a.organization_id, tenant_a);
+    assert_eq!(alice_b.organization_id, tenant_b);
+    assert_ne!(alice_a.id, alice_b.id);
+}
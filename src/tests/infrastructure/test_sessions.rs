@@ -0,0 +1,249 @@
+//! Testes de integração das sessões de login contra o banco de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use futures_util::future::join;
+
+use auth_service::domain::constants::ACCESS_TOKEN_TTL_SECONDS;
+use auth_service::domain::models::role::{CreateRole, RoleSessionPolicy};
+use auth_service::domain::models::session::{SessionClient, SessionLimitAction, SessionPolicy};
+use auth_service::domain::models::token::Claim;
+use auth_service::domain::services::authorization::AuthorizationService;
+use auth_service::domain::services::session::SessionService;
+use auth_service::domain::services::token::TokenService;
+use auth_service::infrastructure::databases::postgresql::DBConn;
+use auth_service::infrastructure::schema::sessions;
+use auth_service::services::authorization::AuthorizationServiceImpl;
+use auth_service::services::session::{SessionServiceImpl, SessionTokenService};
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::tenant_with_user;
+use crate::tests::support::services::{authorization_service, session_service};
+
+struct Fixture {
+    session_service: Arc<SessionServiceImpl>,
+    token_service: SessionTokenService,
+    authorization_service: Arc<AuthorizationServiceImpl>,
+    pool: Arc<DBConn>,
+    organization_id: i32,
+    user_id: i32,
+}
+
+/// Cria uma organização com um usuário, com a política de sessão padrão.
+async fn setup() -> Fixture {
+    setup_with_policy(SessionPolicy::default()).await
+}
+
+async fn setup_with_policy(policy: SessionPolicy) -> Fixture {
+    let (tenant, user_id) = tenant_with_user("sessions");
+    let session_service = session_service(&tenant.pool, policy);
+    Fixture {
+        token_service: SessionTokenService::new(
+            Arc::new(TokenServiceImpl::with_secret("sessions-tests")),
+            session_service.clone(),
+        ),
+        session_service,
+        authorization_service: authorization_service(&tenant.pool),
+        pool: tenant.pool,
+        organization_id: tenant.organization_id,
+        user_id,
+    }
+}
+
+fn laptop() -> SessionClient {
+    SessionClient {
+        user_agent: Some("Mozilla/5.0".to_string()),
+        ip_address: Some("203.0.113.7".to_string()),
+        device_name: Some("laptop".to_string()),
+        csrf_token_hash: None,
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn revoking_a_session_invalidates_its_tokens_only() {
+    let fixture = setup().await;
+    let mut tokens = Vec::new();
+    for _ in 0..2 {
+        let session = fixture
+            .session_service
+            .create(fixture.organization_id, fixture.user_id, laptop())
+            .await
+            .unwrap();
+        let mut claim = Claim::new(fixture.user_id.to_string(), 0);
+        claim.sid = Some(session.id.clone());
+        tokens.push((session.id, fixture.token_service.create_with_claim(claim).await.unwrap()));
+    }
+    let sessions = fixture.session_service.list(fixture.user_id).await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].device_name.as_deref(), Some("laptop"));
+
+    let (lost_laptop, stolen_token) = &tokens[0];
+    fixture
+        .session_service
+        .revoke(fixture.user_id, lost_laptop)
+        .await
+        .unwrap();
+
+    let error = fixture.token_service.validate(stolen_token.clone()).await.unwrap_err();
+    assert_eq!(error.code, 401);
+    let claim = fixture.token_service.validate(tokens[1].1.clone()).await.unwrap();
+    assert_eq!(claim.sid.as_deref(), Some(tokens[1].0.as_str()));
+    assert_eq!(fixture.session_service.list(fixture.user_id).await.unwrap().len(), 1);
+
+    // outro usuário não revoga a sessão
+    let error = fixture
+        .session_service
+        .revoke(fixture.user_id + 1, &tokens[1].0)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 404);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn session_tokens_expire_with_the_access_token_ttl_even_in_long_sessions() {
+    let fixture = setup_with_policy(SessionPolicy {
+        max_lifetime_seconds: 30 * 24 * 3600,
+        ..SessionPolicy::default()
+    })
+    .await;
+    let session = fixture
+        .session_service
+        .create(fixture.organization_id, fixture.user_id, laptop())
+        .await
+        .unwrap();
+    let mut claim = Claim::new(fixture.user_id.to_string(), session.expires_at.timestamp());
+    claim.sid = Some(session.id);
+
+    let token = fixture.token_service.create_with_claim(claim).await.unwrap();
+
+    let claim = fixture.token_service.validate(token).await.unwrap();
+    assert!(claim.exp <= Utc::now().timestamp() + ACCESS_TOKEN_TTL_SECONDS);
+    assert!(session.expires_at.timestamp() > claim.exp);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn the_concurrent_session_limit_evicts_the_oldest_or_rejects_the_login() {
+    let fixture = setup_with_policy(SessionPolicy {
+        max_concurrent_sessions: Some(2),
+        ..SessionPolicy::default()
+    })
+    .await;
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let session = fixture
+            .session_service
+            .create(fixture.organization_id, fixture.user_id, laptop())
+            .await
+            .unwrap();
+        ids.push(session.id);
+    }
+    let active: Vec<String> = fixture
+        .session_service
+        .list(fixture.user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|session| session.id)
+        .collect();
+    assert_eq!(active.len(), 2);
+    assert!(!active.contains(&ids[0]));
+
+    let fixture = setup_with_policy(SessionPolicy {
+        max_concurrent_sessions: Some(1),
+        limit_action: SessionLimitAction::RejectNew,
+        ..SessionPolicy::default()
+    })
+    .await;
+    fixture
+        .session_service
+        .create(fixture.organization_id, fixture.user_id, laptop())
+        .await
+        .unwrap();
+    let error = fixture
+        .session_service
+        .create(fixture.organization_id, fixture.user_id, laptop())
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 409);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn simultaneous_logins_do_not_exceed_the_session_limit() {
+    let fixture = setup_with_policy(SessionPolicy {
+        max_concurrent_sessions: Some(1),
+        limit_action: SessionLimitAction::RejectNew,
+        ..SessionPolicy::default()
+    })
+    .await;
+    let login = || {
+        fixture
+            .session_service
+            .create(fixture.organization_id, fixture.user_id, laptop())
+    };
+
+    let (first, second) = join(login(), login()).await;
+
+    assert!(first.is_ok() != second.is_ok());
+    assert_eq!(fixture.session_service.list(fixture.user_id).await.unwrap().len(), 1);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn role_overrides_shorten_the_session_and_idle_sessions_end() {
+    let fixture = setup().await;
+    let role = fixture
+        .authorization_service
+        .create_role(
+            fixture.organization_id,
+            CreateRole {
+                name: "admin".to_string(),
+                description: None,
+            },
+        )
+        .await
+        .unwrap();
+    fixture
+        .authorization_service
+        .set_role_session_policy(
+            fixture.organization_id,
+            role.id,
+            RoleSessionPolicy {
+                idle_timeout_seconds: Some(300),
+                max_lifetime_seconds: Some(900),
+                max_concurrent_sessions: None,
+            },
+        )
+        .await
+        .unwrap();
+    fixture
+        .authorization_service
+        .assign_role(fixture.organization_id, fixture.user_id, role.id)
+        .await
+        .unwrap();
+
+    let session = fixture
+        .session_service
+        .create(fixture.organization_id, fixture.user_id, laptop())
+        .await
+        .unwrap();
+    assert_eq!(session.idle_timeout_seconds, 300);
+    assert!(session.expires_at <= Utc::now() + Duration::seconds(900));
+    fixture.session_service.validate(&session.id).await.unwrap();
+
+    // sem uso por mais que o tempo máximo, a sessão termina antes do `expires_at`
+    let mut conn = fixture.pool.get().unwrap();
+    diesel::update(sessions::table.filter(sessions::id.eq(&session.id)))
+        .set(sessions::last_seen_at.eq(Utc::now() - Duration::seconds(301)))
+        .execute(&mut conn)
+        .unwrap();
+    let error = fixture.session_service.validate(&session.id).await.unwrap_err();
+    assert_eq!(error.code, 401);
+    assert!(fixture.session_service.list(fixture.user_id).await.unwrap().is_empty());
+}
@@ -0,0 +1,192 @@
+//! Testes de integração do token exchange com tokens vinculados a chaves DPoP, contra o banco
+//! de `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use auth_service::domain::models::oauth::{
+    ClientType, CreateOAuthClient, TokenRequest, TOKEN_EXCHANGE_GRANT_TYPE,
+};
+use auth_service::domain::models::token::{Claim, Confirmation};
+use auth_service::domain::repositories::oauth::OAuthRepository;
+use auth_service::domain::services::oauth::OAuthService;
+use auth_service::domain::services::token::TokenService;
+use auth_service::infrastructure::repositories::oauth::OAuthDieselRepository;
+use auth_service::services::oauth::OAuthServiceImpl;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::token::TokenServiceImpl;
+
+use crate::tests::support::database::{tenant, unique};
+use crate::tests::support::services::oauth_service;
+
+const CLIENT_SECRET: &str = "token-exchange-secret";
+const AUDIENCE: &str = "https://api.example.com";
+const KEY: &str = "0ZcOCORZNYy-DWpqq30jZyJGHTN0d2HglBV3uiguA4I";
+
+struct Fixture {
+    oauth_service: OAuthServiceImpl,
+    token_service: Arc<TokenServiceImpl>,
+    organization_id: i32,
+    client_id: String,
+}
+
+/// Cria uma organização com um cliente confidencial que pode fazer token exchange para `AUDIENCE`.
+async fn setup() -> Fixture {
+    let tenant = tenant("exchange");
+    let client_id = unique("exchanger");
+    OAuthDieselRepository::new(tenant.pool.clone())
+        .create_client(
+            tenant.organization_id,
+            &client_id,
+            Some(&hash_secret(CLIENT_SECRET)),
+            None,
+            &CreateOAuthClient {
+                name: "Token exchange tests".to_string(),
+                client_type: ClientType::Confidential,
+                redirect_uris: Vec::new(),
+                allowed_scopes: vec!["users:read".to_string()],
+                allowed_audiences: vec![AUDIENCE.to_string()],
+                grant_types: vec![TOKEN_EXCHANGE_GRANT_TYPE.to_string()],
+                require_pushed_authorization_requests: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    let token_service = Arc::new(TokenServiceImpl::with_secret("token-exchange-tests"));
+    Fixture {
+        oauth_service: oauth_service(&tenant.pool, token_service.clone()),
+        token_service,
+        organization_id: tenant.organization_id,
+        client_id,
+    }
+}
+
+impl Fixture {
+    /// Access token de `sub` com escopo `users:read`, vinculado à chave `jkt` quando informada.
+    async fn access_token(&self, sub: &str, jkt: Option<&str>) -> String {
+        let mut claim = Claim::new(sub.to_string(), Utc::now().timestamp() + 600);
+        claim.tenant = Some(self.organization_id);
+        claim.scope = Some("users:read".to_string());
+        claim.cnf = jkt.map(|jkt| Confirmation { jkt: jkt.to_string() });
+        self.token_service.create_with_claim(claim).await.unwrap()
+    }
+
+    fn exchange(&self, subject_token: String, dpop_jkt: Option<&str>) -> TokenRequest {
+        TokenRequest {
+            grant_type: TOKEN_EXCHANGE_GRANT_TYPE.to_string(),
+            client_id: Some(self.client_id.clone()),
+            client_secret: Some(CLIENT_SECRET.to_string()),
+            subject_token: Some(subject_token),
+            subject_token_type: Some("urn:ietf:params:oauth:token-type:access_token".to_string()),
+            audience: Some(AUDIENCE.to_string()),
+            dpop_jkt: dpop_jkt.map(String::from),
+            ..Default::default()
+        }
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn a_bound_subject_token_needs_a_proof_of_the_same_key() {
+    let fixture = setup().await;
+    let subject = fixture.access_token("7", Some(KEY)).await;
+
+    let error = fixture
+        .oauth_service
+        .exchange_token(fixture.exchange(subject.clone(), None))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+    // a prova de outra chave, como a do cliente, também não serve
+    let error = fixture
+        .oauth_service
+        .exchange_token(fixture.exchange(subject.clone(), Some("another-key")))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+
+    let response = fixture
+        .oauth_service
+        .exchange_token(fixture.exchange(subject, Some(KEY)))
+        .await
+        .unwrap();
+    assert_eq!(response.token_type, "DPoP");
+    let exchanged = fixture.token_service.validate(response.access_token).await.unwrap();
+    assert_eq!(exchanged.cnf, Some(Confirmation { jkt: KEY.to_string() }));
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn a_bound_actor_token_needs_a_proof_of_the_same_key() {
+    let fixture = setup().await;
+    let mut request = fixture.exchange(fixture.access_token("7", None).await, None);
+    request.actor_token = Some(fixture.access_token("8", Some(KEY)).await);
+    request.actor_token_type = Some("urn:ietf:params:oauth:token-type:access_token".to_string());
+
+    let error = fixture
+        .oauth_service
+        .exchange_token(request.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+
+    request.dpop_jkt = Some(KEY.to_string());
+    let response = fixture.oauth_service.exchange_token(request).await.unwrap();
+    let exchanged = fixture.token_service.validate(response.access_token).await.unwrap();
+    assert_eq!(exchanged.act.unwrap().sub, "8");
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn an_unbound_subject_token_is_exchanged_for_a_bearer_token() {
+    let fixture = setup().await;
+    let subject = fixture.access_token("7", None).await;
+
+    let response = fixture
+        .oauth_service
+        .exchange_token(fixture.exchange(subject, None))
+        .await
+        .unwrap();
+    assert_eq!(response.token_type, "Bearer");
+    let exchanged = fixture.token_service.validate(response.access_token).await.unwrap();
+    assert_eq!(exchanged.aud.as_deref(), Some(AUDIENCE));
+    assert_eq!(exchanged.cnf, None);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn the_exchanged_token_keeps_the_session_and_personal_access_token_of_the_subject() {
+    let fixture = setup().await;
+    let mut claim = Claim::new("7".to_string(), Utc::now().timestamp() + 600);
+    claim.tenant = Some(fixture.organization_id);
+    claim.scope = Some("users:read".to_string());
+    claim.sid = Some("session-7".to_string());
+    claim.pat = Some(42);
+    let subject = fixture.token_service.create_with_claim(claim).await.unwrap();
+
+    let response = fixture
+        .oauth_service
+        .exchange_token(fixture.exchange(subject, None))
+        .await
+        .unwrap();
+    let exchanged = fixture.token_service.validate(response.access_token).await.unwrap();
+    assert_eq!(exchanged.sid.as_deref(), Some("session-7"));
+    assert_eq!(exchanged.pat, Some(42));
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn an_actor_token_from_another_organization_is_rejected() {
+    let fixture = setup().await;
+    let mut actor = Claim::new("8".to_string(), Utc::now().timestamp() + 600);
+    actor.tenant = Some(fixture.organization_id + 1);
+    let mut request = fixture.exchange(fixture.access_token("7", None).await, None);
+    request.actor_token = Some(fixture.token_service.create_with_claim(actor).await.unwrap());
+    request.actor_token_type = Some("urn:ietf:params:oauth:token-type:access_token".to_string());
+
+    let error = fixture.oauth_service.exchange_token(request).await.unwrap_err();
+    assert_eq!(error.error, "invalid_grant");
+}
@@ -0,0 +1,350 @@
+//! Testes de integração da gestão de usuários e do bloqueio de login contra o banco de
+//! `TEST_DATABASE_URL`.
+//!
+//! Ficam marcados com `#[ignore]`; rode-os com `cargo test -- --ignored`.
+use std::env;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use auth_service::domain::constants::{LOGIN_MAX_FAILED_ATTEMPTS, SALT_KEY};
+use auth_service::domain::models::personal_access_token::CreatePersonalAccessToken;
+use auth_service::domain::models::session::{SessionClient, SessionPolicy};
+use auth_service::domain::models::user::{
+    CreateUser, LoginUser, SortDirection, User, UserFilter, UserSortField, UserStatus,
+};
+use auth_service::domain::repositories::personal_access_token::PersonalAccessTokenRepository;
+use auth_service::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use auth_service::domain::repositories::user::UserRepository;
+use auth_service::domain::services::personal_access_token::PersonalAccessTokenService;
+use auth_service::domain::services::session::SessionService;
+use auth_service::domain::services::user::UserService;
+use auth_service::domain::services::user_admin::UserAdminService;
+use auth_service::infrastructure::repositories::personal_access_token::PersonalAccessTokenDieselRepository;
+use auth_service::infrastructure::repositories::user::UserDieselRepository;
+use auth_service::services::personal_access_token::PersonalAccessTokenServiceImpl;
+use auth_service::services::secret::hash_secret;
+use auth_service::services::session::SessionServiceImpl;
+use auth_service::services::token::TokenServiceImpl;
+use auth_service::services::user::UserServiceImpl;
+use auth_service::services::user_admin::UserAdminServiceImpl;
+
+use crate::tests::support::database::tenant;
+use crate::tests::support::services::{authorization_service, session_service, user_service};
+
+struct Fixture {
+    user_service: Arc<UserServiceImpl>,
+    user_admin_service: UserAdminServiceImpl,
+    session_service: Arc<SessionServiceImpl>,
+    personal_access_token_service: PersonalAccessTokenServiceImpl,
+    personal_access_token_repository: Arc<PersonalAccessTokenDieselRepository>,
+    user_repository: Arc<UserDieselRepository>,
+    organization_id: i32,
+    slug: String,
+}
+
+/// Cria uma organização vazia.
+fn setup() -> Fixture {
+    if env::var(SALT_KEY).is_err() {
+        env::set_var(SALT_KEY, "user-admin-tests");
+    }
+    let tenant = tenant("users");
+    let pool = tenant.pool;
+    let user_repository = Arc::new(UserDieselRepository::new(pool.clone()));
+    let session_service = session_service(&pool, SessionPolicy::default());
+    let personal_access_token_repository = Arc::new(PersonalAccessTokenDieselRepository::new(pool.clone()));
+    Fixture {
+        personal_access_token_service: PersonalAccessTokenServiceImpl::new(
+            personal_access_token_repository.clone(),
+            user_repository.clone(),
+            authorization_service(&pool),
+        ),
+        personal_access_token_repository,
+        user_service: user_service(
+            &pool,
+            Arc::new(TokenServiceImpl::with_secret("user-admin-tests")),
+            session_service.clone(),
+        ),
+        user_admin_service: UserAdminServiceImpl::new(user_repository.clone(), session_service.clone()),
+        session_service,
+        user_repository,
+        organization_id: tenant.organization_id,
+        slug: tenant.slug,
+    }
+}
+
+impl Fixture {
+    async fn create_user(&self, username: &str) -> i32 {
+        self.user_service
+            .create(CreateUser {
+                username: username.to_string(),
+                password: "password".to_string(),
+                email: format!("{}@example.com", username),
+                organization: Some(self.slug.clone()),
+            })
+            .await
+            .unwrap()
+            .id
+    }
+
+    fn credentials(&self, username: &str, password: &str) -> LoginUser {
+        LoginUser {
+            username: username.to_string(),
+            password: password.to_string(),
+            organization: Some(self.slug.clone()),
+        }
+    }
+
+    async fn login(&self, username: &str, password: &str) -> Result<String, u32> {
+        self.user_service
+            .get_token(self.credentials(username, password), SessionClient::default())
+            .await
+            .map_err(|e| e.code)
+    }
+}
+
+fn page(limit: i64, offset: i64) -> QueryParamsImpl {
+    QueryParamsImpl {
+        limit: Some(limit),
+        offset: Some(offset),
+    }
+}
+
+fn usernames(page: ResultPaging<User>) -> Vec<String> {
+    page.items.into_iter().map(|user| user.username).collect()
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn listing_filters_sorts_and_pages() {
+    let fixture = setup();
+    let service = &fixture.user_admin_service;
+    for username in ["alice", "albert", "bob", "al_x"] {
+        fixture.create_user(username).await;
+    }
+    let bob = fixture
+        .user_repository
+        .get_by_username(fixture.organization_id, "bob")
+        .await
+        .unwrap();
+    service.disable(fixture.organization_id, bob.id).await.unwrap();
+
+    let by_username = |prefix: &str| UserFilter {
+        username_prefix: Some(prefix.to_string()),
+        sort: UserSortField::Username,
+        direction: SortDirection::Asc,
+        ..Default::default()
+    };
+    let al = service
+        .list(fixture.organization_id, &by_username("AL"), &page(10, 0))
+        .await
+        .unwrap();
+    assert_eq!(al.total, 3);
+    assert_eq!(usernames(al), ["al_x", "albert", "alice"]);
+    // `_` é literal, não um curinga
+    let underscore = service
+        .list(fixture.organization_id, &by_username("al_"), &page(10, 0))
+        .await
+        .unwrap();
+    assert_eq!(usernames(underscore), ["al_x"]);
+    let second = service
+        .list(fixture.organization_id, &by_username("al"), &page(1, 1))
+        .await
+        .unwrap();
+    assert_eq!(second.total, 3);
+    assert_eq!(usernames(second), ["albert"]);
+
+    let disabled = UserFilter {
+        status: Some(UserStatus::Disabled),
+        ..Default::default()
+    };
+    let disabled = service
+        .list(fixture.organization_id, &disabled, &page(10, 0))
+        .await
+        .unwrap();
+    assert_eq!(usernames(disabled), ["bob"]);
+    let future = UserFilter {
+        created_since: Some(Utc::now() + Duration::hours(1)),
+        ..Default::default()
+    };
+    let future = service
+        .list(fixture.organization_id, &future, &page(10, 0))
+        .await
+        .unwrap();
+    assert_eq!(future.total, 0);
+
+    let error = service
+        .list(fixture.organization_id, &UserFilter::default(), &page(101, 0))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 400);
+    // um usuário de outra organização não é encontrado
+    let error = service
+        .get(fixture.organization_id + 1_000_000, bob.id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 404);
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn failed_logins_lock_the_account_until_an_admin_unlocks_it() {
+    let fixture = setup();
+    let alice = fixture.create_user("alice").await;
+    for _ in 1..LOGIN_MAX_FAILED_ATTEMPTS {
+        assert!(fixture.login("alice", "wrong").await.is_err());
+    }
+    // um login certo antes do limite zera a contagem
+    fixture.login("alice", "password").await.unwrap();
+    let user = fixture.user_admin_service.get(fixture.organization_id, alice).await.unwrap();
+    assert_eq!(user.failed_login_attempts, 0);
+
+    for _ in 0..LOGIN_MAX_FAILED_ATTEMPTS {
+        assert!(fixture.login("alice", "wrong").await.is_err());
+    }
+    let user = fixture.user_admin_service.get(fixture.organization_id, alice).await.unwrap();
+    assert_eq!(user.status(), UserStatus::Locked);
+    // bloqueada, a conta responde igual à senha certa e à errada
+    let login = |password: &str| {
+        fixture
+            .user_service
+            .get_token(fixture.credentials("alice", password), SessionClient::default())
+    };
+    let wrong = login("wrong").await.unwrap_err();
+    let right = login("password").await.unwrap_err();
+    assert_eq!((wrong.code, &wrong.message), (403, &right.message));
+    assert_eq!(right.code, 403);
+
+    let user = fixture
+        .user_admin_service
+        .unlock(fixture.organization_id, alice)
+        .await
+        .unwrap();
+    assert_eq!(user.status(), UserStatus::Active);
+    fixture.login("alice", "password").await.unwrap();
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn disabling_and_forcing_a_password_reset_end_the_sessions() {
+    let fixture = setup();
+    let admin = &fixture.user_admin_service;
+    let alice = fixture.create_user("alice").await;
+    fixture.login("alice", "password").await.unwrap();
+    fixture.login("alice", "password").await.unwrap();
+    assert_eq!(fixture.session_service.list(alice).await.unwrap().len(), 2);
+
+    admin.disable(fixture.organization_id, alice).await.unwrap();
+    assert!(fixture.session_service.list(alice).await.unwrap().is_empty());
+    assert_eq!(fixture.login("alice", "password").await, Err(403));
+    let user = admin.enable(fixture.organization_id, alice).await.unwrap();
+    assert_eq!(user.status(), UserStatus::Active);
+
+    fixture.login("alice", "password").await.unwrap();
+    assert_eq!(admin.revoke_sessions(fixture.organization_id, alice).await.unwrap(), 1);
+
+    fixture.login("alice", "password").await.unwrap();
+    let user = admin
+        .require_password_reset(fixture.organization_id, alice)
+        .await
+        .unwrap();
+    assert!(user.password_reset_required);
+    assert!(fixture.session_service.list(alice).await.unwrap().is_empty());
+    assert_eq!(fixture.login("alice", "password").await, Err(403));
+
+    let error = fixture
+        .user_service
+        .change_password(
+            fixture.credentials("alice", "password"),
+            "password".to_string(),
+            SessionClient::default(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 400);
+    fixture
+        .user_service
+        .change_password(
+            fixture.credentials("alice", "password"),
+            "new-password".to_string(),
+            SessionClient::default(),
+        )
+        .await
+        .unwrap();
+    assert!(fixture.login("alice", "password").await.is_err());
+    fixture.login("alice", "new-password").await.unwrap();
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn a_pending_password_reset_suspends_personal_access_tokens_and_claims() {
+    let fixture = setup();
+    let alice = fixture.create_user("alice").await;
+    let token = format!("pat_{}", fixture.slug);
+    fixture
+        .personal_access_token_repository
+        .create(
+            fixture.organization_id,
+            alice,
+            &hash_secret(&token),
+            "pat_passw",
+            &CreatePersonalAccessToken {
+                name: "ci".to_string(),
+                scope: "users:read".to_string(),
+                expires_at: None,
+            },
+        )
+        .await
+        .unwrap();
+    let pat = &fixture.personal_access_token_service;
+    pat.validate(token.clone()).await.unwrap();
+
+    fixture
+        .user_admin_service
+        .require_password_reset(fixture.organization_id, alice)
+        .await
+        .unwrap();
+    assert_eq!(pat.validate(token.clone()).await.unwrap_err().code, 401);
+    // as claims alimentam também a troca de refresh tokens e os demais grants OAuth
+    let error = fixture
+        .user_service
+        .get_claim(fixture.organization_id, alice)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, 403);
+
+    fixture
+        .user_service
+        .change_password(
+            fixture.credentials("alice", "password"),
+            "new-password".to_string(),
+            SessionClient::default(),
+        )
+        .await
+        .unwrap();
+    pat.validate(token.clone()).await.unwrap();
+    fixture
+        .user_service
+        .get_claim(fixture.organization_id, alice)
+        .await
+        .unwrap();
+}
+
+#[actix_web::test]
+#[ignore = "needs Postgres at TEST_DATABASE_URL"]
+async fn session_tokens_are_renewed_only_while_the_session_is_active() {
+    let fixture = setup();
+    let alice = fixture.create_user("alice").await;
+    let token = fixture.login("alice", "password").await.unwrap();
+    let claim = fixture.user_service.validate_token(token).await.unwrap();
+
+    let renewed = fixture.user_service.renew_session_token(&claim).await.unwrap();
+    let renewed = fixture.user_service.validate_token(renewed).await.unwrap();
+    assert_eq!(renewed.sid, claim.sid);
+    assert_eq!(renewed.sub, claim.sub);
+    assert_ne!(renewed.jti, claim.jti);
+
+    fixture.session_service.revoke_all(alice).await.unwrap();
+    let error = fixture.user_service.renew_session_token(&claim).await.unwrap_err();
+    assert_eq!(error.code, 401);
+}
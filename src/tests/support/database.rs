@@ -0,0 +1,105 @@
+//! Banco dos testes de integração. As migrations rodam uma vez por processo, sob um único lock,
+//! e cada teste cria a sua organização com um slug único, já que o banco é reaproveitado.
+use std::env;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+use auth_service::domain::models::user::CreateUser;
+use auth_service::infrastructure::databases::postgresql::{with_tenant, DBConn};
+use auth_service::infrastructure::models::user::CreateUserDiesel;
+use auth_service::infrastructure::schema::{organizations, users};
+
+const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+/// Se as migrations já rodaram neste processo.
+static MIGRATED: Mutex<bool> = Mutex::new(false);
+
+pub fn database_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must point to a Postgres database")
+}
+
+/// Conexão com o banco de testes, com as migrations aplicadas.
+pub fn connect() -> PgConnection {
+    let mut conn = PgConnection::establish(&database_url()).unwrap();
+    let mut migrated = MIGRATED.lock().unwrap();
+    if !*migrated {
+        conn.run_pending_migrations(MIGRATIONS).unwrap();
+        *migrated = true;
+    }
+    conn
+}
+
+/// Pool pequeno para os repositórios de um teste.
+pub fn pool() -> Arc<DBConn> {
+    Arc::new(
+        Pool::builder()
+            .max_size(2)
+            .build(ConnectionManager::<PgConnection>::new(database_url()))
+            .unwrap(),
+    )
+}
+
+/// `prefix` seguido de um sufixo único por chamada, para slugs, client IDs e afins.
+pub fn unique(prefix: &str) -> String {
+    format!("{}-{}", prefix, Utc::now().timestamp_nanos_opt().unwrap())
+}
+
+/// Cria uma organização com o slug (e o nome) `slug`.
+pub fn create_organization(conn: &mut PgConnection, slug: &str) -> i32 {
+    diesel::insert_into(organizations::table)
+        .values((organizations::slug.eq(slug), organizations::name.eq(slug)))
+        .returning(organizations::id)
+        .get_result(conn)
+        .unwrap()
+}
+
+pub fn new_user(organization_id: i32, username: &str) -> CreateUserDiesel {
+    CreateUserDiesel::new(
+        organization_id,
+        CreateUser {
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            password: "password".to_string(),
+            organization: None,
+        },
+    )
+}
+
+/// Cria um usuário na organização, direto no banco, e devolve o ID.
+pub fn create_user(conn: &mut PgConnection, organization_id: i32, username: &str) -> i32 {
+    with_tenant(conn, organization_id, |conn| {
+        diesel::insert_into(users::table)
+            .values(new_user(organization_id, username))
+            .returning(users::id)
+            .get_result(conn)
+    })
+    .unwrap()
+}
+
+/// Organização de teste, com o pool para os repositórios.
+pub struct Tenant {
+    pub pool: Arc<DBConn>,
+    pub organization_id: i32,
+    pub slug: String,
+}
+
+/// Cria uma organização vazia com slug único a partir de `prefix`.
+pub fn tenant(prefix: &str) -> Tenant {
+    let slug = unique(prefix);
+    Tenant {
+        organization_id: create_organization(&mut connect(), &slug),
+        pool: pool(),
+        slug,
+    }
+}
+
+/// Cria uma organização como `tenant`, com o usuário `alice`, e devolve também o ID dele.
+pub fn tenant_with_user(prefix: &str) -> (Tenant, i32) {
+    let tenant = tenant(prefix);
+    let user_id = create_user(&mut connect(), tenant.organization_id, "alice");
+    (tenant, user_id)
+}
@@ -0,0 +1,4 @@
+//! Apoio aos testes de integração que rodam contra o banco de `TEST_DATABASE_URL`: o pool, as
+//! migrations, organizações e usuários de teste e a montagem dos serviços.
+pub mod database;
+pub mod services;
@@ -0,0 +1,82 @@
+//! Montagem dos serviços sobre o pool de um teste, com as mesmas dependências do `Container`.
+use std::sync::Arc;
+
+use auth_service::domain::models::session::SessionPolicy;
+use auth_service::domain::services::token::TokenService;
+use auth_service::infrastructure::databases::postgresql::DBConn;
+use auth_service::infrastructure::repositories::audit::AuditDieselRepository;
+use auth_service::infrastructure::repositories::group::GroupDieselRepository;
+use auth_service::infrastructure::repositories::oauth::OAuthDieselRepository;
+use auth_service::infrastructure::repositories::organization::OrganizationDieselRepository;
+use auth_service::infrastructure::repositories::role::RoleDieselRepository;
+use auth_service::infrastructure::repositories::session::SessionDieselRepository;
+use auth_service::infrastructure::repositories::token::TokenDieselRepository;
+use auth_service::infrastructure::repositories::user::UserDieselRepository;
+use auth_service::services::audit::{AuditServiceImpl, CheckpointSigner};
+use auth_service::services::authorization::AuthorizationServiceImpl;
+use auth_service::services::client_registration::ClientRegistrationServiceImpl;
+use auth_service::services::oauth::OAuthServiceImpl;
+use auth_service::services::oidc::OidcServiceImpl;
+use auth_service::services::session::SessionServiceImpl;
+use auth_service::services::user::UserServiceImpl;
+
+/// Segredo do `CheckpointSigner` dos testes.
+const TEST_CHECKPOINT_SECRET: &str = "integration-tests";
+
+pub fn authorization_service(pool: &Arc<DBConn>) -> Arc<AuthorizationServiceImpl> {
+    Arc::new(AuthorizationServiceImpl::new(
+        Arc::new(RoleDieselRepository::new(pool.clone())),
+        Arc::new(GroupDieselRepository::new(pool.clone())),
+    ))
+}
+
+pub fn session_service(pool: &Arc<DBConn>, policy: SessionPolicy) -> Arc<SessionServiceImpl> {
+    Arc::new(SessionServiceImpl::new(
+        Arc::new(SessionDieselRepository::new(pool.clone())),
+        authorization_service(pool),
+        policy,
+    ))
+}
+
+pub fn audit_service(pool: &Arc<DBConn>) -> Arc<AuditServiceImpl> {
+    Arc::new(AuditServiceImpl::new(
+        Arc::new(AuditDieselRepository::new(pool.clone())),
+        CheckpointSigner::with_secret(TEST_CHECKPOINT_SECRET),
+    ))
+}
+
+/// `UserService` que emite tokens por `token_service` e abre as sessões em `session_service`.
+pub fn user_service(
+    pool: &Arc<DBConn>,
+    token_service: Arc<dyn TokenService>,
+    session_service: Arc<SessionServiceImpl>,
+) -> Arc<UserServiceImpl> {
+    Arc::new(UserServiceImpl::new(
+        Arc::new(UserDieselRepository::new(pool.clone())),
+        token_service,
+        authorization_service(pool),
+        Arc::new(OrganizationDieselRepository::new(pool.clone())),
+        session_service,
+        audit_service(pool),
+    ))
+}
+
+/// `OAuthService` sem OpenID Connect, com a política de sessão padrão.
+pub fn oauth_service(pool: &Arc<DBConn>, token_service: Arc<dyn TokenService>) -> OAuthServiceImpl {
+    let user_service = user_service(pool, token_service.clone(), session_service(pool, SessionPolicy::default()));
+    OAuthServiceImpl::new(
+        Arc::new(OAuthDieselRepository::new(pool.clone())),
+        user_service.clone(),
+        token_service,
+        Arc::new(OidcServiceImpl::new(user_service, None)),
+        Arc::new(TokenDieselRepository::new(pool.clone())),
+    )
+}
+
+/// Registro dinâmico de clientes que aceita os escopos `allowed_scopes`.
+pub fn client_registration_service(pool: &Arc<DBConn>, allowed_scopes: &[&str]) -> Arc<ClientRegistrationServiceImpl> {
+    Arc::new(ClientRegistrationServiceImpl::with_allowed_scopes(
+        Arc::new(OAuthDieselRepository::new(pool.clone())),
+        allowed_scopes.iter().map(|scope| scope.to_string()).collect(),
+    ))
+}